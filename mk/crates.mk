@@ -52,7 +52,11 @@
 TARGET_CRATES := libc std flate arena term \
                  serialize getopts collections test rand \
                  log graphviz core rbml alloc \
-                 unicode rustc_bitflags
+                 unicode rustc_bitflags panic_abort panic_unwind \
+                 alloc_system rustc_demangle
+ifeq ($(CFG_DISABLE_JEMALLOC),)
+TARGET_CRATES += alloc_jemalloc
+endif
 RUSTC_CRATES := rustc rustc_typeck rustc_borrowck rustc_resolve rustc_driver \
                 rustc_trans rustc_back rustc_llvm rustc_privacy rustc_lint
 HOST_CRATES := syntax $(RUSTC_CRATES) rustdoc fmt_macros
@@ -62,10 +66,17 @@ TOOLS := compiletest rustdoc rustc rustbook
 DEPS_core :=
 DEPS_libc := core
 DEPS_unicode := core
-DEPS_alloc := core libc native:jemalloc
+DEPS_alloc := core
+DEPS_alloc_system := core libc
+DEPS_alloc_jemalloc := core libc native:jemalloc
 DEPS_std := core libc rand alloc collections unicode \
-	native:rust_builtin native:backtrace native:rustrt_native \
-	rustc_bitflags
+	native:rust_builtin native:backtrace \
+	rustc_bitflags panic_abort panic_unwind alloc_system rustc_demangle
+ifeq ($(CFG_DISABLE_JEMALLOC),)
+DEPS_std += alloc_jemalloc
+endif
+DEPS_panic_abort := core
+DEPS_panic_unwind := core alloc native:rustrt_native
 DEPS_graphviz := std
 DEPS_syntax := std term serialize log fmt_macros arena libc
 DEPS_rustc_driver := arena flate getopts graphviz libc rustc rustc_back rustc_borrowck \
@@ -73,7 +84,7 @@ DEPS_rustc_driver := arena flate getopts graphviz libc rustc rustc_back rustc_bo
 		     rustc_trans rustc_privacy rustc_lint
 
 DEPS_rustc_trans := arena flate getopts graphviz libc rustc rustc_back \
-	                log syntax serialize rustc_llvm
+	                log syntax serialize rustc_llvm rustc_demangle
 DEPS_rustc_typeck := rustc syntax
 DEPS_rustc_borrowck := rustc log graphviz syntax
 DEPS_rustc_resolve := rustc log syntax
@@ -86,6 +97,7 @@ DEPS_rustc_back := std syntax rustc_llvm flate log libc
 DEPS_rustdoc := rustc rustc_driver native:hoedown serialize getopts \
                 test rustc_lint
 DEPS_rustc_bitflags := core
+DEPS_rustc_demangle := core
 DEPS_flate := std native:miniz
 DEPS_arena := std
 DEPS_graphviz := std
@@ -117,6 +129,17 @@ ONLY_RLIB_rand := 1
 ONLY_RLIB_collections := 1
 ONLY_RLIB_unicode := 1
 ONLY_RLIB_rustc_bitflags := 1
+ONLY_RLIB_rustc_demangle := 1
+ONLY_RLIB_panic_abort := 1
+ONLY_RLIB_panic_unwind := 1
+ONLY_RLIB_alloc_system := 1
+ONLY_RLIB_alloc_jemalloc := 1
+
+# The abort runtime has to be built with the panic strategy it implements. The
+# stage0 snapshot compiler doesn't understand `-C panic` yet.
+RUSTFLAGS1_panic_abort := -C panic=abort
+RUSTFLAGS2_panic_abort := -C panic=abort
+RUSTFLAGS3_panic_abort := -C panic=abort
 
 ################################################################################
 # You should not need to edit below this line
@@ -268,10 +268,10 @@ endif
 
 # FIXME: x86-ism
 LLVM_COMPONENTS=x86 arm aarch64 mips powerpc ipo bitreader bitwriter linker asmparser mcjit \
-                interpreter instrumentation
+                interpreter instrumentation profiledata
 
 # Only build these LLVM tools
-LLVM_TOOLS=bugpoint llc llvm-ar llvm-as llvm-dis llvm-mc opt llvm-extract
+LLVM_TOOLS=bugpoint llc llvm-ar llvm-as llvm-dis llvm-mc opt llvm-extract llvm-profdata
 
 define DEF_LLVM_VARS
 # The configure script defines these variables with the target triples
@@ -333,6 +333,11 @@ export CFG_PREFIX
 export CFG_LIBDIR
 export CFG_LIBDIR_RELATIVE
 export CFG_DISABLE_INJECT_STD_VERSION
+ifdef CFG_DISABLE_SANITIZERS
+CFG_INFO := $(info cfg: disabling the sanitizer runtimes (CFG_DISABLE_SANITIZERS))
+# Tells rustc to reject -Z sanitizer and the tests to skip it
+export CFG_DISABLE_SANITIZERS
+endif
 ifdef CFG_DISABLE_UNSTABLE_FEATURES
 CFG_INFO := $(info cfg: disabling unstable features (CFG_DISABLE_UNSTABLE_FEATURES))
 # Turn on feature-staging
@@ -391,7 +396,9 @@ endif
 TSREQ$(1)_T_$(2)_H_$(3) = \
 	$$(HSREQ$(1)_H_$(3)) \
 	$$(TLIB$(1)_T_$(2)_H_$(3))/libmorestack.a \
-	$$(TLIB$(1)_T_$(2)_H_$(3))/libcompiler-rt.a
+	$$(TLIB$(1)_T_$(2)_H_$(3))/libcompiler-rt.a \
+	$$(TLIB$(1)_T_$(2)_H_$(3))/libprofiler-rt.a \
+	$$(foreach san,$$(SANITIZERS),$$(TLIB$(1)_T_$(2)_H_$(3))/lib$$(san)-rt.a)
 
 # Prerequisites for a working stageN compiler and libraries, for a specific
 # target
@@ -141,7 +141,9 @@ prepare-target-$(2)-host-$(3)-$(1)-$(4): prepare-maybe-clean-$(4) \
             $$(foreach crate,$$(HOST_CRATES), \
               $$(call PREPARE_LIB,$$(call CFG_LIB_GLOB_$(2),$$(crate)))),) \
           $$(call PREPARE_LIB,libmorestack.a) \
-          $$(call PREPARE_LIB,libcompiler-rt.a),),),)
+          $$(call PREPARE_LIB,libcompiler-rt.a) \
+          $$(call PREPARE_LIB,libprofiler-rt.a) \
+          $$(foreach san,$$(SANITIZERS),$$(call PREPARE_LIB,lib$$(san)-rt.a)),),),)
 endef
 
 define INSTALL_GDB_DEBUGGER_SCRIPTS_COMMANDS
@@ -184,7 +184,6 @@ $$(JEMALLOC_LOCAL_$(1)): $$(JEMALLOC_DEPS) $$(MKFILE_DEPS)
 	$$(Q)$$(MAKE) -C "$$(JEMALLOC_BUILD_DIR_$(1))" build_lib_static
 
 ifeq ($$(CFG_DISABLE_JEMALLOC),)
-RUSTFLAGS_alloc := --cfg jemalloc
 ifeq ($(1),$$(CFG_BUILD))
 ifneq ($$(CFG_JEMALLOC_ROOT),)
 $$(JEMALLOC_LIB_$(1)): $$(CFG_JEMALLOC_ROOT)/libjemalloc_pic.a
@@ -199,6 +198,8 @@ $$(JEMALLOC_LIB_$(1)): $$(JEMALLOC_LOCAL_$(1))
 	$$(Q)cp $$< $$@
 endif
 else
+# Without jemalloc the compiler has to default to the system allocator.
+RUSTFLAGS_rustc := --cfg disable_jemalloc
 $$(JEMALLOC_LIB_$(1)): $$(MKFILE_DEPS)
 	$$(Q)touch $$@
 endif
@@ -234,6 +235,62 @@ $$(COMPRT_LIB_$(1)): $$(COMPRT_DEPS) $$(MKFILE_DEPS)
 		triple-builtins
 	$$(Q)cp $$(COMPRT_BUILD_DIR_$(1))/triple/builtins/libcompiler_rt.a $$(COMPRT_LIB_$(1))
 
+################################################################################
+# profiler-rt
+#
+# The profiling runtime from compiler-rt, which programs compiled with
+# `-C profile-generate` or `-Z profile` are linked against. It's only a handful
+# of files so we build it directly rather than going through compiler-rt's
+# build system.
+################################################################################
+
+PROFILER_NAME_$(1) := $$(call CFG_STATIC_LIB_NAME_$(1),profiler-rt)
+PROFILER_LIB_$(1) := $$(RT_OUTPUT_DIR_$(1))/$$(PROFILER_NAME_$(1))
+PROFILER_BUILD_DIR_$(1) := $$(RT_OUTPUT_DIR_$(1))/profiler-rt
+PROFILER_SRC_DIR := $(S)src/compiler-rt/lib/profile
+PROFILER_OBJS_$(1) := \
+	$$(patsubst $$(PROFILER_SRC_DIR)/%.c,$$(PROFILER_BUILD_DIR_$(1))/%.o, \
+	  $$(wildcard $$(PROFILER_SRC_DIR)/*.c)) \
+	$$(patsubst $$(PROFILER_SRC_DIR)/%.cc,$$(PROFILER_BUILD_DIR_$(1))/%.o, \
+	  $$(wildcard $$(PROFILER_SRC_DIR)/*.cc))
+
+$$(PROFILER_BUILD_DIR_$(1))/%.o: $$(PROFILER_SRC_DIR)/%.c $$(MKFILE_DEPS)
+	@mkdir -p $$(@D)
+	@$$(call E, compile: $$@)
+	$$(Q)$$(call CFG_COMPILE_C_$(1), $$@, -fno-builtin) $$<
+
+$$(PROFILER_BUILD_DIR_$(1))/%.o: $$(PROFILER_SRC_DIR)/%.cc $$(MKFILE_DEPS)
+	@mkdir -p $$(@D)
+	@$$(call E, compile: $$@)
+	$$(Q)$$(call CFG_COMPILE_CXX_$(1), $$@, -fno-exceptions -fno-rtti) $$<
+
+$$(PROFILER_LIB_$(1)): $$(PROFILER_OBJS_$(1))
+	@$$(call E, link: $$@)
+	$$(Q)$$(AR_$(1)) rcs $$@ $$^
+
+################################################################################
+# Sanitizer runtimes
+#
+# The runtimes for `-Z sanitizer` come from compiler-rt as well, but they can
+# only be built with its CMake build system. They're only supported on x86_64
+# Linux, every other target, or every target when configured with
+# --disable-sanitizers, gets empty placeholders. rustc refuses to use them.
+################################################################################
+
+SANITIZER_BUILD_DIR_$(1) := $$(RT_OUTPUT_DIR_$(1))/sanitizers
+
+$$(SANITIZER_BUILD_DIR_$(1))/Makefile: $$(COMPRT_DEPS) $$(MKFILE_DEPS) \
+	    $$(LLVM_CONFIG_$$(CFG_BUILD))
+	@$$(call E, cmake: sanitizers)
+	$$(Q)rm -rf $$(SANITIZER_BUILD_DIR_$(1))
+	$$(Q)mkdir -p $$(SANITIZER_BUILD_DIR_$(1))
+	$$(Q)cd $$(SANITIZER_BUILD_DIR_$(1)) && $$(CFG_CMAKE) "$(S)src/compiler-rt" \
+		-DCMAKE_BUILD_TYPE=Release \
+		-DCMAKE_C_COMPILER="$$(CC_$(1))" \
+		-DCMAKE_CXX_COMPILER="$$(CXX_$(1))" \
+		-DLLVM_CONFIG_PATH="$$(LLVM_CONFIG_$$(CFG_BUILD))" \
+		-DCOMPILER_RT_INCLUDE_TESTS=OFF
+
 ################################################################################
 # libbacktrace
 #
@@ -313,3 +370,54 @@ endef
 # Instantiate template for all stages/targets
 $(foreach target,$(CFG_TARGET), \
      $(eval $(call DEF_THIRD_PARTY_TARGETS,$(target))))
+
+SANITIZERS := asan lsan msan tsan
+
+# $(1) is the target triple
+# $(2) is the sanitizer
+define DEF_SANITIZER_RUNTIME
+
+SANITIZER_LIB_$(2)_$(1) := \
+	$$(RT_OUTPUT_DIR_$(1))/$$(call CFG_STATIC_LIB_NAME_$(1),$(2)-rt)
+
+ifneq ($$(and $(findstring x86_64-unknown-linux-gnu,$(1)),$$(if $$(CFG_DISABLE_SANITIZERS),,1)),1)
+$$(SANITIZER_LIB_$(2)_$(1)): $$(MKFILE_DEPS)
+	@mkdir -p $$(@D)
+	$$(Q)touch $$@
+else
+$$(SANITIZER_LIB_$(2)_$(1)): $$(SANITIZER_BUILD_DIR_$(1))/Makefile
+	@$$(call E, make: $(2) runtime)
+	$$(Q)$$(MAKE) -C $$(SANITIZER_BUILD_DIR_$(1)) clang_rt.$(2)-x86_64
+	$$(Q)cp $$(SANITIZER_BUILD_DIR_$(1))/lib/linux/libclang_rt.$(2)-x86_64.a $$@
+endif
+
+endef
+
+$(foreach target,$(CFG_TARGET), \
+ $(foreach sanitizer,$(SANITIZERS), \
+  $(eval $(call DEF_SANITIZER_RUNTIME,$(target),$(sanitizer)))))
+
+################################################################################
+# musl
+#
+# Targets linking musl statically take its libc.a and C runtime objects from
+# the installation given to `./configure --musl-root`, next to a libunwind.a
+# built from LLVM's libunwind, as there's no libgcc_s to unwind with.
+################################################################################
+
+# $(1) is the target triple
+define DEF_MUSL_RUNTIME
+
+ifneq ($$(findstring musl,$(1)),)
+$$(foreach dep,$$(sort $$(foreach crate,$$(CRATES),$$(NATIVE_DEPS_$$(crate)_T_$(1)))), \
+    $$(RT_OUTPUT_DIR_$(1))/$$(dep)): \
+	    $$(RT_OUTPUT_DIR_$(1))/%: $$(CFG_MUSL_ROOT)/lib/%
+	@mkdir -p $$(@D)
+	@$$(call E, cp: $$@)
+	$$(Q)cp $$< $$@
+endif
+
+endef
+
+$(foreach target,$(CFG_TARGET), \
+  $(eval $(call DEF_MUSL_RUNTIME,$(target))))
@@ -78,7 +78,13 @@ fn get_output(props: &TestProps, proc_res: &ProcRes) -> String {
 }
 
 fn run_cfail_test(config: &Config, props: &TestProps, testfile: &Path) {
-    let proc_res = compile_test(config, props, testfile);
+    // rustc carries on past syntax errors it recovers from, so stop
+    // parse-fail tests after parsing; they only check the syntax errors.
+    let proc_res = if config.mode == ParseFail {
+        compile_test_(config, props, testfile, &["-Z".to_string(), "parse-only".to_string()])
+    } else {
+        compile_test(config, props, testfile)
+    };
 
     if proc_res.status.success() {
         fatal_proc_rec(&format!("{} test compiled successfully!", config.mode)[..],
@@ -1202,6 +1208,10 @@ fn compose_and_run_compiler(
         let aux_props = header::load_props(&abs_ab);
         let mut crate_type = if aux_props.no_prefer_dynamic {
             Vec::new()
+        } else if config.target.contains("musl") && !aux_props.force_host {
+            // There are no dylibs on targets which link everything
+            // statically, so build a plain library there instead.
+            vec!("--crate-type=lib".to_string())
         } else {
             vec!("--crate-type=dylib".to_string())
         };
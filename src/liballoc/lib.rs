@@ -81,15 +81,17 @@
 #![feature(core)]
 #![feature(unique)]
 #![cfg_attr(test, feature(test, alloc, rustc_private))]
-#![cfg_attr(all(not(feature = "external_funcs"), not(feature = "external_crate")),
-            feature(libc))]
-
+#![cfg_attr(stage0, feature(alloc_system))]
+#![cfg_attr(not(stage0), feature(needs_allocator))]
+#![cfg_attr(not(stage0), needs_allocator)]
 
 #[macro_use]
 extern crate core;
 
-#[cfg(all(not(feature = "external_funcs"), not(feature = "external_crate")))]
-extern crate libc;
+// The snapshot compiler doesn't know how to inject an allocator, so always
+// link the system one when bootstrapping.
+#[cfg(stage0)]
+extern crate alloc_system;
 
 // Allow testing this library
 
@@ -2485,6 +2485,8 @@ step_impl!(usize u8 u16 u32 isize i8 i16 i32);
 step_impl!(u64 i64);
 #[cfg(target_pointer_width = "32")]
 step_impl_no_between!(u64 i64);
+#[cfg(not(stage0))]
+step_impl_no_between!(u128 i128);
 
 /// An adapter for stepping range iterators by a custom amount.
 ///
@@ -0,0 +1,236 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Runtime routines for 128-bit integer arithmetic on 32-bit targets.
+//!
+//! LLVM lowers 128-bit multiplication, division, variable shifts and
+//! conversions to floating point to calls to these compiler-rt routines,
+//! but compiler-rt only builds them when the C compiler has a 128-bit
+//! integer type, which is not the case on 32-bit targets. They are
+//! implemented here on 64-bit halves instead.
+//!
+//! The only operations performed directly on 128-bit values below are
+//! casts, shifts by a constant, comparisons, and bitwise or additive
+//! operations, all of which LLVM expands inline, so none of these
+//! routines can end up calling itself.
+
+#[inline]
+fn hi(a: u128) -> u64 { (a >> 64) as u64 }
+
+#[inline]
+fn lo(a: u128) -> u64 { a as u64 }
+
+#[inline]
+fn from_parts(hi: u64, lo: u64) -> u128 { ((hi as u128) << 64) | lo as u128 }
+
+#[inline]
+fn magnitude(a: i128) -> u128 {
+    if a < 0 { 0u128.wrapping_sub(a as u128) } else { a as u128 }
+}
+
+#[inline]
+fn with_sign(a: u128, negative: bool) -> i128 {
+    (if negative { 0u128.wrapping_sub(a) } else { a }) as i128
+}
+
+/// Returns the full 128-bit product of two 64-bit integers as `(hi, lo)`.
+fn mul_u64(a: u64, b: u64) -> (u64, u64) {
+    let (a1, a0) = (a >> 32, a & 0xffff_ffff);
+    let (b1, b0) = (b >> 32, b & 0xffff_ffff);
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+    let mid = (p00 >> 32) + (p01 & 0xffff_ffff) + (p10 & 0xffff_ffff);
+    let lo = (p00 & 0xffff_ffff) | (mid << 32);
+    let hi = p11 + (p01 >> 32) + (p10 >> 32) + (mid >> 32);
+    (hi, lo)
+}
+
+/// Shift-and-subtract long division, one quotient bit per iteration.
+fn udivmod(n: u128, d: u128) -> (u128, u128) {
+    if hi(n) == 0 && hi(d) == 0 {
+        let (n, d) = (lo(n), lo(d));
+        return ((n / d) as u128, (n % d) as u128);
+    }
+
+    let mut q: u128 = 0;
+    let mut r: u128 = 0;
+    let mut i = 128;
+    while i > 0 {
+        i -= 1;
+        let bit = if i >= 64 { hi(n) >> (i - 64) } else { lo(n) >> i } & 1;
+        r = (r << 1) | bit as u128;
+        q = q << 1;
+        if r >= d {
+            r = r - d;
+            q = q | 1;
+        }
+    }
+    (q, r)
+}
+
+/// `a << b`
+#[no_mangle]
+pub extern "C" fn __ashlti3(a: u128, b: i32) -> u128 {
+    let b = b as u32 & 127;
+    let (h, l) = (hi(a), lo(a));
+    if b >= 64 {
+        from_parts(l << (b - 64), 0)
+    } else if b == 0 {
+        a
+    } else {
+        from_parts((h << b) | (l >> (64 - b)), l << b)
+    }
+}
+
+/// Logical `a >> b`
+#[no_mangle]
+pub extern "C" fn __lshrti3(a: u128, b: i32) -> u128 {
+    let b = b as u32 & 127;
+    let (h, l) = (hi(a), lo(a));
+    if b >= 64 {
+        from_parts(0, h >> (b - 64))
+    } else if b == 0 {
+        a
+    } else {
+        from_parts(h >> b, (l >> b) | (h << (64 - b)))
+    }
+}
+
+/// Arithmetic `a >> b`
+#[no_mangle]
+pub extern "C" fn __ashrti3(a: i128, b: i32) -> i128 {
+    let b = b as u32 & 127;
+    let (h, l) = (hi(a as u128) as i64, lo(a as u128));
+    let r = if b >= 64 {
+        from_parts((h >> 63) as u64, (h >> (b - 64)) as u64)
+    } else if b == 0 {
+        a as u128
+    } else {
+        from_parts((h >> b) as u64, (l >> b) | ((h as u64) << (64 - b)))
+    };
+    r as i128
+}
+
+/// Wrapping `a * b`, for both signed and unsigned operands.
+#[no_mangle]
+pub extern "C" fn __multi3(a: i128, b: i128) -> i128 {
+    let (a, b) = (a as u128, b as u128);
+    let (h, l) = mul_u64(lo(a), lo(b));
+    let h = h.wrapping_add(lo(a).wrapping_mul(hi(b)))
+             .wrapping_add(hi(a).wrapping_mul(lo(b)));
+    from_parts(h, l) as i128
+}
+
+/// `a * b`, setting `*overflow` to 1 if the product does not fit in an `i128`.
+#[no_mangle]
+pub extern "C" fn __muloti4(a: i128, b: i128, overflow: &mut i32) -> i128 {
+    let negative = (a < 0) != (b < 0);
+    let (ma, mb) = (magnitude(a), magnitude(b));
+
+    // At most one operand may have a non-zero high half; call it `x`.
+    let (x, y) = if hi(ma) == 0 { (mb, ma) } else { (ma, mb) };
+    let mut oflo = hi(y) != 0;
+    let (h1, l1) = mul_u64(lo(x), lo(y));
+    let (h2, l2) = mul_u64(hi(x), lo(y));
+    let (mid, carry) = h1.overflowing_add(l2);
+    oflo = oflo || h2 != 0 || carry;
+    let m = from_parts(mid, l1);
+
+    let limit = if negative { 1u128 << 127 } else { (1u128 << 127) - 1 };
+    *overflow = (oflo || m > limit) as i32;
+    with_sign(m, negative)
+}
+
+/// Unsigned `a / b`, storing `a % b` through `rem` if it is not null.
+#[no_mangle]
+pub unsafe extern "C" fn __udivmodti4(a: u128, b: u128, rem: *mut u128) -> u128 {
+    let (q, r) = udivmod(a, b);
+    if !rem.is_null() {
+        *rem = r;
+    }
+    q
+}
+
+/// Unsigned `a / b`
+#[no_mangle]
+pub extern "C" fn __udivti3(a: u128, b: u128) -> u128 {
+    udivmod(a, b).0
+}
+
+/// Unsigned `a % b`
+#[no_mangle]
+pub extern "C" fn __umodti3(a: u128, b: u128) -> u128 {
+    udivmod(a, b).1
+}
+
+/// Signed `a / b`, rounding towards zero.
+#[no_mangle]
+pub extern "C" fn __divti3(a: i128, b: i128) -> i128 {
+    let q = udivmod(magnitude(a), magnitude(b)).0;
+    with_sign(q, (a < 0) != (b < 0))
+}
+
+/// Signed `a % b`, with the sign of `a`.
+#[no_mangle]
+pub extern "C" fn __modti3(a: i128, b: i128) -> i128 {
+    let r = udivmod(magnitude(a), magnitude(b)).1;
+    with_sign(r, a < 0)
+}
+
+/// Converts `a` to the 64 bits that matter when rounding it to a float,
+/// returning them along with the power of two to scale them by.
+///
+/// The bits below the top 64 are folded into a sticky bit, which is below
+/// the rounding position of both `f32` and `f64`, so converting the result
+/// rounds exactly as converting `a` itself would.
+fn float_parts(a: u128) -> (u64, u32) {
+    if hi(a) == 0 {
+        return (lo(a), 0);
+    }
+    let n = hi(a).leading_zeros();
+    let (h, l) = if n == 0 {
+        (hi(a), lo(a))
+    } else {
+        ((hi(a) << n) | (lo(a) >> (64 - n)), lo(a) << n)
+    };
+    (h | (l != 0) as u64, 64 - n)
+}
+
+/// Unsigned `a as f64`
+#[no_mangle]
+pub extern "C" fn __floatuntidf(a: u128) -> f64 {
+    let (m, e) = float_parts(a);
+    let scale = if e == 0 { 1.0 } else { (1u64 << (e - 1)) as f64 * 2.0 };
+    m as f64 * scale
+}
+
+/// Unsigned `a as f32`
+#[no_mangle]
+pub extern "C" fn __floatuntisf(a: u128) -> f32 {
+    let (m, e) = float_parts(a);
+    let scale = if e == 0 { 1.0 } else { (1u64 << (e - 1)) as f32 * 2.0 };
+    m as f32 * scale
+}
+
+/// Signed `a as f64`
+#[no_mangle]
+pub extern "C" fn __floattidf(a: i128) -> f64 {
+    let r = __floatuntidf(magnitude(a));
+    if a < 0 { -r } else { r }
+}
+
+/// Signed `a as f32`
+#[no_mangle]
+pub extern "C" fn __floattisf(a: i128) -> f32 {
+    let r = __floatuntisf(magnitude(a));
+    if a < 0 { -r } else { r }
+}
@@ -50,6 +50,11 @@ pub struct Wrapping<T>(#[stable(feature = "rust1", since = "1.0.0")] pub T);
 #[unstable(feature = "core", reason = "may be removed or relocated")]
 pub mod wrapping;
 
+#[cfg(all(not(stage0), target_pointer_width = "32"))]
+#[doc(hidden)]
+#[unstable(feature = "core", reason = "only called by generated code")]
+pub mod int128_builtins;
+
 /// Types that have a "zero" value.
 ///
 /// This trait is intended for use in conjunction with `Add`, as an identity:
@@ -85,6 +90,8 @@ macro_rules! zero_one_impl {
     )*)
 }
 zero_one_impl! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+zero_one_impl! { u128 i128 }
 
 /// A built-in signed or unsigned integer.
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -618,6 +625,16 @@ uint_impl! { u64 = u64, 64,
     intrinsics::u64_sub_with_overflow,
     intrinsics::u64_mul_with_overflow }
 
+#[cfg(not(stage0))]
+uint_impl! { u128 = u128, 128,
+    intrinsics::ctpop128,
+    intrinsics::ctlz128,
+    intrinsics::cttz128,
+    intrinsics::bswap128,
+    intrinsics::u128_add_with_overflow,
+    intrinsics::u128_sub_with_overflow,
+    intrinsics::u128_mul_with_overflow }
+
 #[cfg(target_pointer_width = "32")]
 uint_impl! { usize = u32, 32,
     intrinsics::ctpop32,
@@ -734,6 +751,12 @@ int_impl! { i64 = i64, u64, 64,
     intrinsics::i64_sub_with_overflow,
     intrinsics::i64_mul_with_overflow }
 
+#[cfg(not(stage0))]
+int_impl! { i128 = i128, u128, 128,
+    intrinsics::i128_add_with_overflow,
+    intrinsics::i128_sub_with_overflow,
+    intrinsics::i128_mul_with_overflow }
+
 #[cfg(target_pointer_width = "32")]
 int_impl! { isize = i32, u32, 32,
     intrinsics::i32_add_with_overflow,
@@ -811,6 +834,8 @@ signed_int_impl! { i8 }
 signed_int_impl! { i16 }
 signed_int_impl! { i32 }
 signed_int_impl! { i64 }
+#[cfg(not(stage0))]
+signed_int_impl! { i128 }
 signed_int_impl! { isize }
 
 // `Int` + `SignedInt` implemented for signed integers
@@ -1313,6 +1338,15 @@ impl i64 {
         intrinsics::i64_mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "i128"]
+impl i128 {
+    int_impl! { i128 = i128, u128, 128,
+        intrinsics::i128_add_with_overflow,
+        intrinsics::i128_sub_with_overflow,
+        intrinsics::i128_mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "isize"]
 impl isize {
@@ -1848,6 +1882,19 @@ impl u64 {
         intrinsics::u64_mul_with_overflow }
 }
 
+#[cfg(not(stage0))]
+#[lang = "u128"]
+impl u128 {
+    uint_impl! { u128 = u128, 128,
+        intrinsics::ctpop128,
+        intrinsics::ctlz128,
+        intrinsics::cttz128,
+        intrinsics::bswap128,
+        intrinsics::u128_add_with_overflow,
+        intrinsics::u128_sub_with_overflow,
+        intrinsics::u128_mul_with_overflow }
+}
+
 #[cfg(target_pointer_width = "32")]
 #[lang = "usize"]
 impl usize {
@@ -1912,6 +1959,13 @@ pub trait ToPrimitive {
     /// Converts the value of `self` to an `i64`.
     fn to_i64(&self) -> Option<i64>;
 
+    /// Converts the value of `self` to an `i128`.
+    #[cfg(not(stage0))]
+    #[inline]
+    fn to_i128(&self) -> Option<i128> {
+        self.to_i64().map(|x| x as i128).or_else(|| self.to_u64().map(|x| x as i128))
+    }
+
     /// Converts the value of `self` to an `usize`.
     #[inline]
     #[unstable(feature = "core")]
@@ -1948,6 +2002,13 @@ pub trait ToPrimitive {
     #[inline]
     fn to_u64(&self) -> Option<u64>;
 
+    /// Converts the value of `self` to an `u128`.
+    #[cfg(not(stage0))]
+    #[inline]
+    fn to_u128(&self) -> Option<u128> {
+        self.to_u64().map(|x| x as u128)
+    }
+
     /// Converts the value of `self` to an `f32`.
     #[inline]
     fn to_f32(&self) -> Option<f32> {
@@ -2113,6 +2174,71 @@ impl_to_primitive_uint! { u16 }
 impl_to_primitive_uint! { u32 }
 impl_to_primitive_uint! { u64 }
 
+// The conversions above go through a 64-bit value to check the range,
+// which would truncate a 128-bit one first. Check the sign and the bounds
+// separately instead, so that neither comparison needs a wider type.
+macro_rules! impl_to_primitive_128_to_prim {
+    ($SrcT:ty, $DstT:ty, $slf:expr) => (
+        {
+            let zero: $SrcT = Int::zero();
+            let min_value: $DstT = Int::min_value();
+            let max_value: $DstT = Int::max_value();
+            if ($slf >= zero || min_value as i128 <= $slf as i128) &&
+               ($slf < zero || $slf as u128 <= max_value as u128) {
+                Some($slf as $DstT)
+            } else {
+                None
+            }
+        }
+    )
+}
+
+macro_rules! impl_to_primitive_128 {
+    ($T:ty) => (
+        impl ToPrimitive for $T {
+            #[inline]
+            fn to_int(&self) -> Option<isize> { impl_to_primitive_128_to_prim!($T, isize, *self) }
+            #[inline]
+            fn to_isize(&self) -> Option<isize> { impl_to_primitive_128_to_prim!($T, isize, *self) }
+            #[inline]
+            fn to_i8(&self) -> Option<i8> { impl_to_primitive_128_to_prim!($T, i8, *self) }
+            #[inline]
+            fn to_i16(&self) -> Option<i16> { impl_to_primitive_128_to_prim!($T, i16, *self) }
+            #[inline]
+            fn to_i32(&self) -> Option<i32> { impl_to_primitive_128_to_prim!($T, i32, *self) }
+            #[inline]
+            fn to_i64(&self) -> Option<i64> { impl_to_primitive_128_to_prim!($T, i64, *self) }
+            #[inline]
+            fn to_i128(&self) -> Option<i128> { impl_to_primitive_128_to_prim!($T, i128, *self) }
+
+            #[inline]
+            fn to_uint(&self) -> Option<usize> { impl_to_primitive_128_to_prim!($T, usize, *self) }
+            #[inline]
+            fn to_usize(&self) -> Option<usize> { impl_to_primitive_128_to_prim!($T, usize, *self) }
+            #[inline]
+            fn to_u8(&self) -> Option<u8> { impl_to_primitive_128_to_prim!($T, u8, *self) }
+            #[inline]
+            fn to_u16(&self) -> Option<u16> { impl_to_primitive_128_to_prim!($T, u16, *self) }
+            #[inline]
+            fn to_u32(&self) -> Option<u32> { impl_to_primitive_128_to_prim!($T, u32, *self) }
+            #[inline]
+            fn to_u64(&self) -> Option<u64> { impl_to_primitive_128_to_prim!($T, u64, *self) }
+            #[inline]
+            fn to_u128(&self) -> Option<u128> { impl_to_primitive_128_to_prim!($T, u128, *self) }
+
+            #[inline]
+            fn to_f32(&self) -> Option<f32> { Some(*self as f32) }
+            #[inline]
+            fn to_f64(&self) -> Option<f64> { Some(*self as f64) }
+        }
+    )
+}
+
+#[cfg(not(stage0))]
+impl_to_primitive_128! { i128 }
+#[cfg(not(stage0))]
+impl_to_primitive_128! { u128 }
+
 macro_rules! impl_to_primitive_float_to_float {
     ($SrcT:ident, $DstT:ident, $slf:expr) => (
         if size_of::<$SrcT>() <= size_of::<$DstT>() {
@@ -2443,6 +2569,11 @@ impl_num_cast! { isize,   to_int }
 impl_num_cast! { f32,   to_f32 }
 impl_num_cast! { f64,   to_f64 }
 
+#[cfg(not(stage0))]
+impl_num_cast! { i128,  to_i128 }
+#[cfg(not(stage0))]
+impl_num_cast! { u128,  to_u128 }
+
 /// Used for representing the classification of floating point numbers
 #[derive(Copy, Clone, PartialEq, Debug)]
 #[stable(feature = "rust1", since = "1.0.0")]
@@ -2953,6 +3084,10 @@ from_str_radix_int_impl! { u8 }
 from_str_radix_int_impl! { u16 }
 from_str_radix_int_impl! { u32 }
 from_str_radix_int_impl! { u64 }
+#[cfg(not(stage0))]
+from_str_radix_int_impl! { i128 }
+#[cfg(not(stage0))]
+from_str_radix_int_impl! { u128 }
 
 /// An error which can be returned when parsing an integer.
 #[derive(Debug, Clone, PartialEq)]
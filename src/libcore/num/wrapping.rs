@@ -29,8 +29,16 @@ use intrinsics::{i8_mul_with_overflow, u8_mul_with_overflow};
 use intrinsics::{i16_mul_with_overflow, u16_mul_with_overflow};
 use intrinsics::{i32_mul_with_overflow, u32_mul_with_overflow};
 use intrinsics::{i64_mul_with_overflow, u64_mul_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_add_with_overflow, u128_add_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_sub_with_overflow, u128_sub_with_overflow};
+#[cfg(not(stage0))]
+use intrinsics::{i128_mul_with_overflow, u128_mul_with_overflow};
 
 use ::{i8,i16,i32,i64};
+#[cfg(not(stage0))]
+use ::i128;
 
 #[unstable(feature = "core", reason = "may be removed, renamed, or relocated")]
 #[deprecated(since = "1.0.0", reason = "moved to inherent methods")]
@@ -95,6 +103,8 @@ macro_rules! sh_impl_all {
 }
 
 sh_impl_all! { u8 u16 u32 u64 usize i8 i16 i32 i64 isize }
+#[cfg(not(stage0))]
+sh_impl_all! { u128 i128 }
 
 macro_rules! wrapping_impl {
     ($($t:ty)*) => ($(
@@ -191,6 +201,8 @@ macro_rules! wrapping_impl {
 }
 
 wrapping_impl! { usize u8 u16 u32 u64 isize i8 i16 i32 i64 }
+#[cfg(not(stage0))]
+wrapping_impl! { u128 i128 }
 
 mod shift_max {
     #![allow(non_upper_case_globals)]
@@ -199,11 +211,13 @@ mod shift_max {
     pub const i16: u32 = (1 << 4) - 1;
     pub const i32: u32 = (1 << 5) - 1;
     pub const i64: u32 = (1 << 6) - 1;
+    pub const i128: u32 = (1 << 7) - 1;
 
     pub const  u8: u32 = i8;
     pub const u16: u32 = i16;
     pub const u32: u32 = i32;
     pub const u64: u32 = i64;
+    pub const u128: u32 = i128;
 }
 
 macro_rules! signed_overflowing_impl {
@@ -306,6 +320,10 @@ macro_rules! unsigned_overflowing_impl {
 
 signed_overflowing_impl! { i8 i16 i32 i64 }
 unsigned_overflowing_impl! { u8 u16 u32 u64 }
+#[cfg(not(stage0))]
+signed_overflowing_impl! { i128 }
+#[cfg(not(stage0))]
+unsigned_overflowing_impl! { u128 }
 
 #[cfg(target_pointer_width = "64")]
 impl OverflowingOps for usize {
@@ -1627,4 +1627,6 @@ impl_int_slices! { u8,   i8  }
 impl_int_slices! { u16,  i16 }
 impl_int_slices! { u32,  i32 }
 impl_int_slices! { u64,  i64 }
+#[cfg(not(stage0))]
+impl_int_slices! { u128, i128 }
 impl_int_slices! { usize, isize }
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Implementation of panics by aborting the process
+//!
+//! This crate is the panic runtime linked in by `-C panic=abort`. Raising a
+//! panic simply aborts, so nothing here ever unwinds the stack and no panic is
+//! ever caught. The panic message has already been printed by the standard
+//! library by the time `__rust_start_panic` is called.
+
+// Do not remove on snapshot creation. Needed for bootstrap. (Issue #22364)
+#![cfg_attr(stage0, feature(custom_attribute))]
+#![crate_name = "panic_abort"]
+#![crate_type = "rlib"]
+#![unstable(feature = "panic_abort")]
+#![feature(staged_api)]
+#![staged_api]
+#![doc(html_logo_url = "http://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
+       html_favicon_url = "http://www.rust-lang.org/favicon.ico",
+       html_root_url = "http://doc.rust-lang.org/nightly/")]
+
+#![feature(core)]
+#![feature(no_std)]
+#![no_std]
+#![cfg_attr(not(stage0), feature(panic_runtime))]
+#![cfg_attr(not(stage0), panic_runtime)]
+
+extern crate core;
+
+use core::intrinsics;
+
+/// Entry point for raising a panic, which aborts the process.
+#[no_mangle]
+pub unsafe extern fn __rust_start_panic(_data: usize, _vtable: usize) -> u32 {
+    intrinsics::abort()
+}
+
+/// Invokes `f(data)`. As panics never unwind with this runtime there is
+/// nothing to catch, so this always reports that `f` returned normally.
+#[no_mangle]
+pub unsafe extern fn __rust_maybe_catch_panic(f: extern fn(*mut u8),
+                                              data: *mut u8,
+                                              _data_ptr: *mut usize,
+                                              _vtable_ptr: *mut usize)
+                                              -> u32 {
+    f(data);
+    0
+}
@@ -0,0 +1,126 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Implementation of panics via stack unwinding
+//!
+//! This crate is the panic runtime linked in by default (`-C panic=unwind`).
+//! A panic raised by the standard library is handed to `__rust_start_panic`
+//! below, which throws it as an exception through the system unwinder (libgcc
+//! here). The exception is caught again by `__rust_maybe_catch_panic`, which
+//! is what `std::rt::unwind::try` is built on.
+//!
+//! The personality routines that drive the unwinder remain in libstd; see
+//! `libstd/rt/unwind.rs` for the details of how unwinding proceeds.
+
+// Do not remove on snapshot creation. Needed for bootstrap. (Issue #22364)
+#![cfg_attr(stage0, feature(custom_attribute))]
+#![crate_name = "panic_unwind"]
+#![crate_type = "rlib"]
+#![unstable(feature = "panic_unwind")]
+#![feature(staged_api)]
+#![staged_api]
+#![doc(html_logo_url = "http://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
+       html_favicon_url = "http://www.rust-lang.org/favicon.ico",
+       html_root_url = "http://doc.rust-lang.org/nightly/")]
+
+#![feature(alloc)]
+#![feature(box_syntax)]
+#![feature(core)]
+#![feature(no_std)]
+#![no_std]
+#![cfg_attr(not(stage0), feature(panic_runtime))]
+#![cfg_attr(not(stage0), panic_runtime)]
+
+extern crate core;
+extern crate alloc;
+
+use alloc::boxed::{self, Box};
+use core::any::Any;
+use core::mem;
+use core::raw;
+
+mod uw;
+
+struct Exception {
+    uwe: uw::_Unwind_Exception,
+    cause: Option<Box<Any + Send + 'static>>,
+}
+
+// Rust's exception class identifier.  This is used by personality routines to
+// determine whether the exception was thrown by their own runtime.
+fn rust_exception_class() -> uw::_Unwind_Exception_Class {
+    // M O Z \0  R U S T -- vendor, language
+    0x4d4f5a_00_52555354
+}
+
+/// Entry point for raising a panic.
+///
+/// The payload is passed as the two halves of a `Box<Any + Send>` trait
+/// object. On success this function never returns; the return value is the
+/// unwinder's error code if the exception could not be raised.
+#[no_mangle]
+pub unsafe extern fn __rust_start_panic(data: usize, vtable: usize) -> u32 {
+    let cause = mem::transmute::<raw::TraitObject, *mut (Any + Send)>(raw::TraitObject {
+        data: data as *mut (),
+        vtable: vtable as *mut (),
+    });
+    let exception: Box<_> = box Exception {
+        uwe: uw::_Unwind_Exception {
+            exception_class: rust_exception_class(),
+            exception_cleanup: exception_cleanup,
+            private: [0; uw::unwinder_private_data_size],
+        },
+        cause: Some(Box::from_raw(cause)),
+    };
+    let exception_param = boxed::into_raw(exception) as *mut uw::_Unwind_Exception;
+    return uw::_Unwind_RaiseException(exception_param) as u32;
+
+    extern fn exception_cleanup(_unwind_code: uw::_Unwind_Reason_Code,
+                                exception: *mut uw::_Unwind_Exception) {
+        unsafe {
+            let _: Box<Exception> = Box::from_raw(exception as *mut Exception);
+        }
+    }
+}
+
+/// Invokes `f(data)`, catching any panic raised by it.
+///
+/// Returns 0 if `f` returned normally. Otherwise returns 1, and the payload
+/// of the panic is written to `data_ptr` and `vtable_ptr` as the two halves of
+/// a `Box<Any + Send>` trait object.
+#[no_mangle]
+pub unsafe extern fn __rust_maybe_catch_panic(f: extern fn(*mut u8),
+                                              data: *mut u8,
+                                              data_ptr: *mut usize,
+                                              vtable_ptr: *mut usize)
+                                              -> u32 {
+    let ep = rust_try(f, data);
+    if ep.is_null() {
+        return 0
+    }
+
+    let my_ep = ep as *mut Exception;
+    let cause = (*my_ep).cause.take().unwrap();
+    uw::_Unwind_DeleteException(ep);
+    let obj = mem::transmute::<*mut (Any + Send), raw::TraitObject>(boxed::into_raw(cause));
+    *data_ptr = obj.data as usize;
+    *vtable_ptr = obj.vtable as usize;
+    1
+}
+
+#[link(name = "rustrt_native", kind = "static")]
+extern {
+    // Rust's try-catch
+    // When f(...) returns normally, the return value is null.
+    // When f(...) throws, the return value is a pointer to the caught
+    // exception object.
+    fn rust_try(f: extern fn(*mut u8),
+                data: *mut u8) -> *mut uw::_Unwind_Exception;
+}
@@ -106,6 +106,7 @@ pub mod middle {
     pub mod graph;
     pub mod intrinsicck;
     pub mod infer;
+    pub mod ir;
     pub mod lang_items;
     pub mod liveness;
     pub mod mem_categorization;
@@ -159,6 +159,8 @@ pub const tag_crate_triple: usize = 0x105; // top-level only
 
 pub const tag_dylib_dependency_formats: usize = 0x106; // top-level only
 
+pub const tag_panic_strategy: usize = 0x10f; // top-level only
+
 // Language items are a top-level directory (for speed). Hierarchy:
 //
 // tag_lang_items
@@ -256,3 +258,5 @@ pub const tag_codemap_filemap: usize = 0xa2;
 pub const tag_item_super_predicates: usize = 0xa3;
 
 pub const tag_defaulted_trait: usize = 0xa4;
+
+pub const tag_items_data_item_constness: usize = 0xa5;
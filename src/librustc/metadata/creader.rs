@@ -157,6 +157,8 @@ impl<'a> CrateReader<'a> {
     pub fn read_crates(&mut self, krate: &ast::Crate) {
         self.process_crate(krate);
         visit::walk_crate(self, krate);
+        self.inject_panic_runtime(krate);
+        self.inject_allocator_crate(krate);
 
         if log_enabled!(log::DEBUG) {
             dump_crates(&self.sess.cstore);
@@ -452,6 +454,134 @@ impl<'a> CrateReader<'a> {
         }).collect()
     }
 
+    // Selects the panic runtime for the final artifact and checks that every
+    // crate in the graph was compiled with a compatible panic strategy.
+    //
+    // A runtime only needs to be linked in if some crate declares
+    // `#![needs_panic_runtime]` (libstd does). If one of our dependencies is
+    // already a `#![panic_runtime]` that one is used, otherwise the runtime
+    // matching `-C panic` is loaded from the sysroot.
+    fn inject_panic_runtime(&mut self, krate: &ast::Crate) {
+        // Rlibs are never linked on their own, so the choice is left to
+        // whoever eventually links them.
+        let any_non_rlib = self.sess.crate_types.borrow().iter().any(|ct| {
+            *ct != config::CrateTypeRlib
+        });
+        if !any_non_rlib {
+            return
+        }
+
+        let desired_strategy = self.sess.panic_strategy();
+        let mut needs_panic_runtime = attr::contains_name(&krate.attrs,
+                                                          "needs_panic_runtime");
+        let mut runtime: Option<(ast::CrateNum, String)> = None;
+        self.sess.cstore.iter_crate_data(|cnum, data| {
+            needs_panic_runtime = needs_panic_runtime || data.needs_panic_runtime();
+            if !data.is_panic_runtime() {
+                // Crates compiled with `-C panic=abort` have no landing pads,
+                // so it's unsound to unwind through them. Crates which do have
+                // landing pads are fine to use when aborting, they just never
+                // run them.
+                let strategy = data.panic_strategy();
+                if strategy != desired_strategy &&
+                   desired_strategy == config::PanicStrategy::Unwind {
+                    self.sess.err(&format!("the crate `{}` is compiled with the \
+                                            panic strategy `{}` which is \
+                                            incompatible with this crate's \
+                                            strategy of `{}`",
+                                           data.name(), strategy.desc(),
+                                           desired_strategy.desc()));
+                }
+                return
+            }
+            match runtime {
+                Some((_, ref prev)) => {
+                    self.sess.err(&format!("cannot link together two panic \
+                                            runtimes: {} and {}",
+                                           prev, data.name()));
+                }
+                None => runtime = Some((cnum, data.name())),
+            }
+        });
+
+        // If we are the runtime ourselves there's nothing to inject.
+        if !needs_panic_runtime || attr::contains_name(&krate.attrs, "panic_runtime") {
+            return
+        }
+
+        let (cnum, name) = match runtime {
+            Some(runtime) => runtime,
+            None => {
+                let name = match desired_strategy {
+                    config::PanicStrategy::Unwind => "panic_unwind",
+                    config::PanicStrategy::Abort => "panic_abort",
+                };
+                info!("injecting panic runtime `{}`", name);
+                let (cnum, data, _) = self.resolve_crate(&None, name, name, None,
+                                                         codemap::DUMMY_SP,
+                                                         PathKind::Crate);
+                if !data.is_panic_runtime() {
+                    self.sess.err(&format!("the crate `{}` is not a panic runtime",
+                                           name));
+                }
+                self.sess.cstore.set_injected_panic_runtime(cnum);
+                (cnum, name.to_string())
+            }
+        };
+
+        let runtime_strategy = self.sess.cstore.get_crate_data(cnum).panic_strategy();
+        if runtime_strategy != desired_strategy {
+            self.sess.err(&format!("the linked panic runtime `{}` is not \
+                                    compiled with this crate's panic strategy \
+                                    `{}`",
+                                   name, desired_strategy.desc()));
+        }
+    }
+
+    // Links in the default allocator if the final artifact needs one and none
+    // of our dependencies already provides it. The program can pick its own
+    // allocator simply by linking to a different `#![allocator]` crate.
+    //
+    // Executables get jemalloc (if the compiler was built with it). Anything
+    // else may well be loaded into a host program which has its own heap, so
+    // it gets the system allocator to avoid mixing two allocators.
+    fn inject_allocator_crate(&mut self, krate: &ast::Crate) {
+        let crate_types = self.sess.crate_types.borrow().clone();
+        if crate_types.iter().all(|ct| *ct == config::CrateTypeRlib) {
+            return
+        }
+        if attr::contains_name(&krate.attrs, "allocator") {
+            return
+        }
+
+        let mut needs_allocator = attr::contains_name(&krate.attrs,
+                                                      "needs_allocator");
+        let mut found_allocator = false;
+        self.sess.cstore.iter_crate_data(|_, data| {
+            needs_allocator = needs_allocator || data.needs_allocator();
+            found_allocator = found_allocator || data.is_allocator();
+        });
+        if !needs_allocator || found_allocator {
+            return
+        }
+
+        let only_exes = crate_types.iter().all(|ct| {
+            *ct == config::CrateTypeExecutable || *ct == config::CrateTypeRlib
+        });
+        let name = if only_exes && !cfg!(disable_jemalloc) {
+            "alloc_jemalloc"
+        } else {
+            "alloc_system"
+        };
+        info!("injecting allocator `{}`", name);
+        let (_, data, _) = self.resolve_crate(&None, name, name, None,
+                                              codemap::DUMMY_SP,
+                                              PathKind::Crate);
+        if !data.is_allocator() {
+            self.sess.err(&format!("the crate `{}` is not an allocator", name));
+        }
+    }
+
     fn read_extension_crate(&mut self, span: Span, info: &CrateInfo) -> ExtensionCrate {
         let target_triple = &self.sess.opts.target_triple[..];
         let is_cross = target_triple != config::host_triple();
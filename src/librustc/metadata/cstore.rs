@@ -20,14 +20,16 @@ pub use self::NativeLibraryKind::*;
 use back::svh::Svh;
 use metadata::decoder;
 use metadata::loader;
+use session::config::PanicStrategy;
 use session::search_paths::PathKind;
 use util::nodemap::{FnvHashMap, NodeMap};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::path::PathBuf;
 use flate::Bytes;
 use syntax::ast;
+use syntax::attr;
 use syntax::codemap;
 use syntax::parse::token::IdentInterner;
 
@@ -91,6 +93,8 @@ pub struct CStore {
     used_crate_sources: RefCell<Vec<CrateSource>>,
     used_libraries: RefCell<Vec<(String, NativeLibraryKind)>>,
     used_link_args: RefCell<Vec<String>>,
+    /// The panic runtime injected by the crate reader, if any
+    injected_panic_runtime: Cell<Option<ast::CrateNum>>,
     pub intr: Rc<IdentInterner>,
 }
 
@@ -102,6 +106,7 @@ impl CStore {
             used_crate_sources: RefCell::new(Vec::new()),
             used_libraries: RefCell::new(Vec::new()),
             used_link_args: RefCell::new(Vec::new()),
+            injected_panic_runtime: Cell::new(None),
             intr: intr
         }
     }
@@ -161,6 +166,7 @@ impl CStore {
         self.used_crate_sources.borrow_mut().clear();
         self.used_libraries.borrow_mut().clear();
         self.used_link_args.borrow_mut().clear();
+        self.injected_panic_runtime.set(None);
     }
 
     // This method is used when generating the command line to pass through to
@@ -174,18 +180,37 @@ impl CStore {
     // positions.
     pub fn get_used_crates(&self, prefer: LinkagePreference)
                            -> Vec<(ast::CrateNum, Option<PathBuf>)> {
+        // Crates which need an allocator implicitly depend on whichever one is
+        // linked in, so it has to be ordered after them as well.
+        let mut allocators = Vec::new();
+        self.iter_crate_data(|cnum, data| {
+            if data.is_allocator() { allocators.push(cnum) }
+        });
+
         let mut ordering = Vec::new();
-        fn visit(cstore: &CStore, cnum: ast::CrateNum,
+        fn visit(cstore: &CStore, cnum: ast::CrateNum, allocators: &[ast::CrateNum],
                  ordering: &mut Vec<ast::CrateNum>) {
             if ordering.contains(&cnum) { return }
             let meta = cstore.get_crate_data(cnum);
             for (_, &dep) in &meta.cnum_map {
-                visit(cstore, dep, ordering);
+                visit(cstore, dep, allocators, ordering);
+            }
+            if meta.needs_allocator() {
+                for &dep in allocators {
+                    visit(cstore, dep, allocators, ordering);
+                }
+            }
+            // Crates which need a panic runtime implicitly depend on the one
+            // that was injected for them, so it must be linked after them.
+            if meta.needs_panic_runtime() {
+                if let Some(runtime) = cstore.injected_panic_runtime() {
+                    visit(cstore, runtime, allocators, ordering);
+                }
             }
             ordering.push(cnum);
         };
         for (&num, _) in &*self.metas.borrow() {
-            visit(self, num, &mut ordering);
+            visit(self, num, &allocators, &mut ordering);
         }
         ordering.reverse();
         let mut libs = self.used_crate_sources.borrow()
@@ -201,6 +226,14 @@ impl CStore {
         libs
     }
 
+    pub fn injected_panic_runtime(&self) -> Option<ast::CrateNum> {
+        self.injected_panic_runtime.get()
+    }
+
+    pub fn set_injected_panic_runtime(&self, cnum: ast::CrateNum) {
+        self.injected_panic_runtime.set(Some(cnum));
+    }
+
     pub fn add_used_library(&self, lib: String, kind: NativeLibraryKind) {
         assert!(!lib.is_empty());
         self.used_libraries.borrow_mut().push((lib, kind));
@@ -238,6 +271,25 @@ impl crate_metadata {
     pub fn data<'a>(&'a self) -> &'a [u8] { self.data.as_slice() }
     pub fn name(&self) -> String { decoder::get_crate_name(self.data()) }
     pub fn hash(&self) -> Svh { decoder::get_crate_hash(self.data()) }
+    pub fn panic_strategy(&self) -> PanicStrategy {
+        decoder::get_panic_strategy(self.data())
+    }
+    pub fn is_panic_runtime(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "panic_runtime")
+    }
+    pub fn needs_panic_runtime(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "needs_panic_runtime")
+    }
+    pub fn is_allocator(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "allocator")
+    }
+    pub fn needs_allocator(&self) -> bool {
+        let attrs = decoder::get_crate_attributes(self.data());
+        attr::contains_name(&attrs, "needs_allocator")
+    }
 }
 
 impl MetadataBlob {
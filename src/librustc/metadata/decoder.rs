@@ -30,6 +30,7 @@ use middle::subst;
 use middle::ty::{ImplContainer, TraitContainer};
 use middle::ty::{self, Ty};
 use middle::astencode::vtable_decoder_helpers;
+use session::config::PanicStrategy;
 
 use std::collections::HashMap;
 use std::hash::{self, Hash, SipHasher};
@@ -134,13 +135,16 @@ enum Family {
     Struct,                // S
     PublicField,           // g
     InheritedField,        // N
+    CrateField,            // r
     Constant,              // C
+    AssociatedConst,       // A
 }
 
 fn item_family(item: rbml::Doc) -> Family {
     let fam = reader::get_doc(item, tag_items_data_item_family);
     match reader::doc_as_u8(fam) as char {
       'C' => Constant,
+      'A' => AssociatedConst,
       'c' => ImmStatic,
       'b' => MutStatic,
       'f' => Fn,
@@ -159,6 +163,7 @@ fn item_family(item: rbml::Doc) -> Family {
       'S' => Struct,
       'g' => PublicField,
       'N' => InheritedField,
+      'r' => CrateField,
        c => panic!("unexpected family char: {}", c)
     }
 }
@@ -170,6 +175,7 @@ fn item_visibility(item: rbml::Doc) -> ast::Visibility {
             match reader::doc_as_u8(visibility_doc) as char {
                 'y' => ast::Public,
                 'i' => ast::Inherited,
+                'r' => ast::PubCrate,
                 _ => panic!("unknown visibility character")
             }
         }
@@ -328,6 +334,19 @@ fn item_to_def_like(item: rbml::Doc, did: ast::DefId, cnum: ast::CrateNum)
             };
             DlDef(def::DefMethod(did, provenance))
         }
+        AssociatedConst => {
+            // Like methods, associated constants record whether their
+            // parent is a trait or an impl through the trait parent sort.
+            let provenance = if reader::maybe_get_doc(
+                  item, tag_item_trait_parent_sort).is_some() {
+                def::FromTrait(item_reqd_and_translated_parent_item(cnum,
+                                                                    item))
+            } else {
+                def::FromImpl(item_reqd_and_translated_parent_item(cnum,
+                                                                   item))
+            };
+            DlDef(def::DefAssociatedConst(did, provenance))
+        }
         Type => {
             if item_sort(item) == Some('t') {
                 let trait_did = item_reqd_and_translated_parent_item(cnum, item);
@@ -349,7 +368,7 @@ fn item_to_def_like(item: rbml::Doc, did: ast::DefId, cnum: ast::CrateNum)
         Trait => DlDef(def::DefTrait(did)),
         Enum => DlDef(def::DefTy(did, true)),
         Impl | DefaultImpl => DlImpl(did),
-        PublicField | InheritedField => DlField,
+        PublicField | InheritedField | CrateField => DlField,
     }
 }
 
@@ -827,6 +846,7 @@ pub fn get_impl_items(cdata: Cmd, impl_id: ast::NodeId)
                         tag_item_impl_item, |doc| {
         let def_id = item_def_id(doc, cdata);
         match item_sort(doc) {
+            Some('C') => impl_items.push(ty::ConstTraitItemId(def_id)),
             Some('r') | Some('p') => {
                 impl_items.push(ty::MethodTraitItemId(def_id))
             }
@@ -878,6 +898,18 @@ pub fn get_impl_or_trait_item<'tcx>(intr: Rc<IdentInterner>,
     let vis = item_visibility(method_doc);
 
     match item_sort(method_doc) {
+        Some('C') => {
+            let ty = doc_type(method_doc, tcx, cdata);
+            let default = get_provided_source(method_doc, cdata);
+            ty::ConstTraitItem(Rc::new(ty::AssociatedConst {
+                name: name,
+                ty: ty,
+                vis: vis,
+                def_id: def_id,
+                container: container,
+                default: default,
+            }))
+        }
         Some('r') | Some('p') => {
             let generics = doc_generics(method_doc, tcx, cdata, tag_method_ty_generics);
             let predicates = doc_predicates(method_doc, tcx, cdata, tag_method_ty_generics);
@@ -915,6 +947,7 @@ pub fn get_trait_item_def_ids(cdata: Cmd, id: ast::NodeId)
     reader::tagged_docs(item, tag_item_trait_item, |mth| {
         let def_id = item_def_id(mth, cdata);
         match item_sort(mth) {
+            Some('C') => result.push(ty::ConstTraitItemId(def_id)),
             Some('r') | Some('p') => {
                 result.push(ty::MethodTraitItemId(def_id));
             }
@@ -956,7 +989,7 @@ pub fn get_provided_trait_methods<'tcx>(intr: Rc<IdentInterner>,
                 ty::MethodTraitItem(ref method) => {
                     result.push((*method).clone())
                 }
-                ty::TypeTraitItem(_) => {}
+                _ => {}
             }
         }
         true
@@ -1066,6 +1099,7 @@ fn struct_field_family_to_visibility(family: Family) -> ast::Visibility {
     match family {
       PublicField => ast::Public,
       InheritedField => ast::Inherited,
+      CrateField => ast::PubCrate,
       _ => panic!()
     }
 }
@@ -1077,7 +1111,7 @@ pub fn get_struct_fields(intr: Rc<IdentInterner>, cdata: Cmd, id: ast::NodeId)
     let mut result = Vec::new();
     reader::tagged_docs(item, tag_item_field, |an_item| {
         let f = item_family(an_item);
-        if f == PublicField || f == InheritedField {
+        if f == PublicField || f == InheritedField || f == CrateField {
             let name = item_name(&*intr, an_item);
             let did = item_def_id(an_item, cdata);
             let tagdoc = reader::get_doc(an_item, tag_item_field_origin);
@@ -1247,6 +1281,16 @@ pub fn get_crate_triple(data: &[u8]) -> Option<String> {
     triple_doc.map(|s| s.as_str().to_string())
 }
 
+/// Returns the panic strategy the crate was compiled with. Metadata which
+/// predates `-C panic` is taken to mean the crate unwinds.
+pub fn get_panic_strategy(data: &[u8]) -> PanicStrategy {
+    let cratedoc = rbml::Doc::new(data);
+    match reader::maybe_get_doc(cratedoc, tag_panic_strategy) {
+        Some(doc) if reader::doc_as_u8(doc) == b'a' => PanicStrategy::Abort,
+        _ => PanicStrategy::Unwind,
+    }
+}
+
 pub fn get_crate_name(data: &[u8]) -> String {
     maybe_get_crate_name(data).expect("no crate name in crate")
 }
@@ -1443,6 +1487,14 @@ pub fn get_reachable_extern_fns(cdata: Cmd) -> Vec<ast::DefId> {
     return ret;
 }
 
+pub fn is_const_fn(cdata: Cmd, id: ast::NodeId) -> bool {
+    let item_doc = lookup_item(id, cdata.data());
+    match reader::maybe_get_doc(item_doc, tag_items_data_item_constness) {
+        None => false,
+        Some(doc) => reader::doc_as_u8(doc) != 0
+    }
+}
+
 pub fn is_typedef(cdata: Cmd, id: ast::NodeId) -> bool {
     let item_doc = lookup_item(id, cdata.data());
     match item_family(item_doc) {
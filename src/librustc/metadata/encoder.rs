@@ -273,7 +273,7 @@ fn encode_struct_fields(rbml_w: &mut Encoder,
             rbml_w.start_tag(tag_item_field);
             encode_name(rbml_w, f.name);
         }
-        encode_struct_field_family(rbml_w, f.vis);
+        encode_struct_field_family(rbml_w, &f.vis);
         encode_def_id(rbml_w, f.id);
         rbml_w.wr_tagged_str(tag_item_field_origin, &def_to_string(origin));
         rbml_w.end_tag();
@@ -305,7 +305,7 @@ fn encode_enum_variant_info(ecx: &EncodeContext,
         }
         encode_name(rbml_w, variant.node.name.name);
         encode_parent_item(rbml_w, local_def(id));
-        encode_visibility(rbml_w, variant.node.vis);
+        encode_visibility(rbml_w, &variant.node.vis);
         encode_attributes(rbml_w, &variant.node.attrs);
         encode_repr_attrs(rbml_w, ecx, &variant.node.attrs);
 
@@ -385,7 +385,7 @@ fn encode_reexported_static_base_methods(ecx: &EncodeContext,
                                                             m.def_id,
                                                             m.name);
                         }
-                        ty::TypeTraitItem(_) => {}
+                        _ => {}
                     }
                 }
             }
@@ -520,7 +520,7 @@ fn encode_info_for_mod(ecx: &EncodeContext,
                        id: NodeId,
                        path: PathElems,
                        name: ast::Ident,
-                       vis: ast::Visibility) {
+                       vis: &ast::Visibility) {
     rbml_w.start_tag(tag_items_data_item);
     encode_def_id(rbml_w, local_def(id));
     encode_family(rbml_w, 'm');
@@ -556,7 +556,7 @@ fn encode_info_for_mod(ecx: &EncodeContext,
     encode_stability(rbml_w, stab);
 
     // Encode the reexports of this module, if this module is public.
-    if vis == ast::Public {
+    if *vis == ast::Public {
         debug!("(encoding info for module) encoding reexports for {}", id);
         encode_reexports(ecx, rbml_w, id, path);
     }
@@ -566,16 +566,20 @@ fn encode_info_for_mod(ecx: &EncodeContext,
 }
 
 fn encode_struct_field_family(rbml_w: &mut Encoder,
-                              visibility: ast::Visibility) {
-    encode_family(rbml_w, match visibility {
+                              visibility: &ast::Visibility) {
+    encode_family(rbml_w, match *visibility {
         ast::Public => 'g',
+        ast::PubCrate | ast::Restricted { .. } => 'r',
         ast::Inherited => 'N'
     });
 }
 
-fn encode_visibility(rbml_w: &mut Encoder, visibility: ast::Visibility) {
-    let ch = match visibility {
+// `pub(crate)` and `pub(in path)` are both recorded as crate-restricted; other
+// crates only need to know that the item is not visible to them.
+fn encode_visibility(rbml_w: &mut Encoder, visibility: &ast::Visibility) {
+    let ch = match *visibility {
         ast::Public => 'y',
+        ast::PubCrate | ast::Restricted { .. } => 'r',
         ast::Inherited => 'i',
     };
     rbml_w.wr_tagged_u8(tag_items_data_item_visibility, ch as u8);
@@ -651,7 +655,7 @@ fn encode_info_for_struct(ecx: &EncodeContext,
         rbml_w.start_tag(tag_items_data_item);
         debug!("encode_info_for_struct: doing {} {}",
                token::get_name(nm), id);
-        encode_struct_field_family(rbml_w, field.vis);
+        encode_struct_field_family(rbml_w, &field.vis);
         encode_name(rbml_w, nm);
         encode_bounds_and_type_for_item(rbml_w, ecx, id);
         encode_def_id(rbml_w, local_def(id));
@@ -791,7 +795,7 @@ fn encode_method_ty_fields<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
     encode_generics(rbml_w, ecx, &method_ty.generics, &method_ty.predicates,
                     tag_method_ty_generics);
     encode_method_fty(ecx, rbml_w, &method_ty.fty);
-    encode_visibility(rbml_w, method_ty.vis);
+    encode_visibility(rbml_w, &method_ty.vis);
     encode_explicit_self(rbml_w, &method_ty.explicit_self);
     match method_ty.explicit_self {
         ty::StaticExplicitSelfCategory => {
@@ -802,6 +806,43 @@ fn encode_method_ty_fields<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
     encode_provided_source(rbml_w, method_ty.provided_source);
 }
 
+fn encode_info_for_associated_const(ecx: &EncodeContext,
+                                    rbml_w: &mut Encoder,
+                                    associated_const: &ty::AssociatedConst,
+                                    impl_path: PathElems,
+                                    parent_id: NodeId,
+                                    impl_item_opt: Option<&ast::ImplItem>) {
+    debug!("encode_info_for_associated_const({:?},{:?})",
+           associated_const.def_id,
+           token::get_name(associated_const.name));
+
+    rbml_w.start_tag(tag_items_data_item);
+
+    encode_def_id(rbml_w, associated_const.def_id);
+    encode_name(rbml_w, associated_const.name);
+    encode_visibility(rbml_w, &associated_const.vis);
+    encode_family(rbml_w, 'A');
+    encode_provided_source(rbml_w, associated_const.default);
+
+    encode_parent_item(rbml_w, local_def(parent_id));
+    encode_item_sort(rbml_w, 'C');
+
+    encode_bounds_and_type_for_item(rbml_w, ecx, associated_const.def_id.local_id());
+
+    let stab = stability::lookup(ecx.tcx, associated_const.def_id);
+    encode_stability(rbml_w, stab);
+
+    let elem = ast_map::PathName(associated_const.name);
+    encode_path(rbml_w, impl_path.chain(Some(elem).into_iter()));
+
+    if let Some(ii) = impl_item_opt {
+        encode_attributes(rbml_w, &ii.attrs);
+        encode_inlined_item(ecx, rbml_w, IIImplItemRef(local_def(parent_id), ii));
+    }
+
+    rbml_w.end_tag();
+}
+
 fn encode_info_for_method<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
                                     rbml_w: &mut Encoder,
                                     m: &ty::Method<'tcx>,
@@ -829,9 +870,11 @@ fn encode_info_for_method<'a, 'tcx>(ecx: &EncodeContext<'a, 'tcx>,
     if let Some(impl_item) = impl_item_opt {
         if let ast::MethodImplItem(ref sig, _) = impl_item.node {
             encode_attributes(rbml_w, &impl_item.attrs);
+            encode_constness(rbml_w, sig.constness);
             let scheme = ty::lookup_item_type(ecx.tcx, m.def_id);
             let any_types = !scheme.generics.types.is_empty();
-            if any_types || is_default_impl || attr::requests_inline(&impl_item.attrs) {
+            if any_types || is_default_impl || attr::requests_inline(&impl_item.attrs) ||
+               sig.constness == ast::Constness::Const {
                 encode_inlined_item(ecx, rbml_w, IIImplItemRef(local_def(parent_id),
                                                                impl_item));
             }
@@ -859,7 +902,7 @@ fn encode_info_for_associated_type(ecx: &EncodeContext,
 
     encode_def_id(rbml_w, associated_type.def_id);
     encode_name(rbml_w, associated_type.name);
-    encode_visibility(rbml_w, associated_type.vis);
+    encode_visibility(rbml_w, &associated_type.vis);
     encode_family(rbml_w, 'y');
     encode_parent_item(rbml_w, local_def(parent_id));
     encode_item_sort(rbml_w, 't');
@@ -965,7 +1008,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
                         item: &ast::Item,
                         index: &mut Vec<entry<i64>>,
                         path: PathElems,
-                        vis: ast::Visibility) {
+                        vis: &ast::Visibility) {
     let tcx = ecx.tcx;
 
     fn add_to_index(item: &ast::Item, rbml_w: &mut Encoder,
@@ -1015,7 +1058,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_stability(rbml_w, stab);
         rbml_w.end_tag();
       }
-      ast::ItemFn(ref decl, _, _, ref generics, _) => {
+      ast::ItemFn(ref decl, _, constness, _, ref generics, _) => {
         add_to_index(item, rbml_w, index);
         rbml_w.start_tag(tag_items_data_item);
         encode_def_id(rbml_w, def_id);
@@ -1025,7 +1068,11 @@ fn encode_info_for_item(ecx: &EncodeContext,
         encode_name(rbml_w, item.ident.name);
         encode_path(rbml_w, path);
         encode_attributes(rbml_w, &item.attrs);
-        if tps_len > 0 || attr::requests_inline(&item.attrs) {
+        encode_constness(rbml_w, constness);
+        // The body of a const fn is needed to evaluate calls to it
+        // from other crates.
+        if tps_len > 0 || attr::requests_inline(&item.attrs) ||
+           constness == ast::Constness::Const {
             encode_inlined_item(ecx, rbml_w, IIItemRef(item));
         }
         if tps_len == 0 {
@@ -1045,7 +1092,7 @@ fn encode_info_for_item(ecx: &EncodeContext,
                             item.id,
                             path,
                             item.ident,
-                            item.vis);
+                            &item.vis);
       }
       ast::ItemForeignMod(ref fm) => {
         add_to_index(item, rbml_w, index);
@@ -1195,6 +1242,10 @@ fn encode_info_for_item(ecx: &EncodeContext,
         for &item_def_id in items {
             rbml_w.start_tag(tag_item_impl_item);
             match item_def_id {
+                ty::ConstTraitItemId(item_def_id) => {
+                    encode_def_id(rbml_w, item_def_id);
+                    encode_item_sort(rbml_w, 'C');
+                }
                 ty::MethodTraitItemId(item_def_id) => {
                     encode_def_id(rbml_w, item_def_id);
                     encode_item_sort(rbml_w, 'r');
@@ -1232,6 +1283,14 @@ fn encode_info_for_item(ecx: &EncodeContext,
             });
 
             match ty::impl_or_trait_item(tcx, trait_item_def_id.def_id()) {
+                ty::ConstTraitItem(ref associated_const) => {
+                    encode_info_for_associated_const(ecx,
+                                                     rbml_w,
+                                                     &*associated_const,
+                                                     path.clone(),
+                                                     item.id,
+                                                     ast_item)
+                }
                 ty::MethodTraitItem(ref method_type) => {
                     encode_info_for_method(ecx,
                                            rbml_w,
@@ -1275,6 +1334,10 @@ fn encode_info_for_item(ecx: &EncodeContext,
         for &method_def_id in &*ty::trait_item_def_ids(tcx, def_id) {
             rbml_w.start_tag(tag_item_trait_item);
             match method_def_id {
+                ty::ConstTraitItemId(const_def_id) => {
+                    encode_def_id(rbml_w, const_def_id);
+                    encode_item_sort(rbml_w, 'C');
+                }
                 ty::MethodTraitItemId(method_def_id) => {
                     encode_def_id(rbml_w, method_def_id);
                     encode_item_sort(rbml_w, 'r');
@@ -1320,6 +1383,25 @@ fn encode_info_for_item(ecx: &EncodeContext,
                 ty::impl_or_trait_item(tcx, item_def_id.def_id());
             let is_nonstatic_method;
             match trait_item_type {
+                ty::ConstTraitItem(associated_const) => {
+                    encode_name(rbml_w, associated_const.name);
+                    encode_def_id(rbml_w, associated_const.def_id);
+                    encode_visibility(rbml_w, &associated_const.vis);
+
+                    encode_provided_source(rbml_w, associated_const.default);
+
+                    let elem = ast_map::PathName(associated_const.name);
+                    encode_path(rbml_w,
+                                path.clone().chain(Some(elem).into_iter()));
+
+                    encode_item_sort(rbml_w, 'C');
+                    encode_family(rbml_w, 'A');
+
+                    encode_bounds_and_type_for_item(rbml_w, ecx,
+                                                    associated_const.def_id.local_id());
+
+                    is_nonstatic_method = false;
+                }
                 ty::MethodTraitItem(method_ty) => {
                     let method_def_id = item_def_id.def_id();
 
@@ -1364,6 +1446,10 @@ fn encode_info_for_item(ecx: &EncodeContext,
             let trait_item = &*ms[i];
             encode_attributes(rbml_w, &trait_item.attrs);
             match trait_item.node {
+                ast::ConstTraitItem(_, _) => {
+                    encode_inlined_item(ecx, rbml_w,
+                                        IITraitItemRef(def_id, trait_item));
+                }
                 ast::MethodTraitItem(ref sig, ref body) => {
                     // If this is a static method, we've already
                     // encoded this.
@@ -1410,7 +1496,7 @@ fn encode_info_for_foreign_item(ecx: &EncodeContext,
 
     rbml_w.start_tag(tag_items_data_item);
     encode_def_id(rbml_w, local_def(nitem.id));
-    encode_visibility(rbml_w, nitem.vis);
+    encode_visibility(rbml_w, &nitem.vis);
     match nitem.node {
       ast::ForeignItemFn(ref fndecl, _) => {
         encode_family(rbml_w, FN_FAMILY);
@@ -1450,7 +1536,7 @@ fn my_visit_item(i: &ast::Item,
                  ecx: &EncodeContext,
                  index: &mut Vec<entry<i64>>) {
     ecx.tcx.map.with_path(i.id, |path| {
-        encode_info_for_item(ecx, rbml_w, i, index, path, i.vis);
+        encode_info_for_item(ecx, rbml_w, i, index, path, &i.vis);
     });
 }
 
@@ -1514,7 +1600,7 @@ fn encode_info_for_items(ecx: &EncodeContext,
                         ast::CRATE_NODE_ID,
                         [].iter().cloned().chain(LinkedPath::empty()),
                         syntax::parse::token::special_idents::invalid,
-                        ast::Public);
+                        &ast::Public);
 
     visit::walk_crate(&mut EncodeVisitor {
         index: &mut index,
@@ -1633,6 +1719,14 @@ fn encode_unsafety(rbml_w: &mut Encoder, unsafety: ast::Unsafety) {
     rbml_w.wr_tagged_u8(tag_unsafety, byte);
 }
 
+fn encode_constness(rbml_w: &mut Encoder, constness: ast::Constness) {
+    let byte: u8 = match constness {
+        ast::Constness::NotConst => 0,
+        ast::Constness::Const => 1,
+    };
+    rbml_w.wr_tagged_u8(tag_items_data_item_constness, byte);
+}
+
 fn encode_paren_sugar(rbml_w: &mut Encoder, paren_sugar: bool) {
     let byte: u8 = if paren_sugar {1} else {0};
     rbml_w.wr_tagged_u8(tag_paren_sugar, byte);
@@ -1759,7 +1853,8 @@ fn encode_codemap(ecx: &EncodeContext, rbml_w: &mut Encoder) {
         }
 
         rbml_w.start_tag(tag_codemap_filemap);
-        filemap.encode(rbml_w);
+        let name = codemap.path_mapping().map_prefix(&filemap.name);
+        filemap.encode_as(&name, rbml_w);
         rbml_w.end_tag();
     }
 
@@ -1882,9 +1977,12 @@ fn encode_misc_info(ecx: &EncodeContext,
 fn encode_reachable_extern_fns(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     rbml_w.start_tag(tag_reachable_extern_fns);
 
-    for id in ecx.reachable {
+    // Sort the ids so the encoding doesn't depend on the set's hash order.
+    let mut reachable: Vec<_> = ecx.reachable.iter().cloned().collect();
+    reachable.sort();
+    for id in &reachable {
         if let Some(ast_map::NodeItem(i)) = ecx.tcx.map.find(*id) {
-            if let ast::ItemFn(_, _, abi, ref generics, _) = i.node {
+            if let ast::ItemFn(_, _, _, abi, ref generics, _) = i.node {
                 if abi != abi::Rust && !generics.is_type_parameterized() {
                     rbml_w.wr_tagged_u32(tag_reachable_extern_fn_id, *id);
                 }
@@ -1915,6 +2013,14 @@ fn encode_crate_triple(rbml_w: &mut Encoder, triple: &str) {
     rbml_w.wr_tagged_str(tag_crate_triple, triple);
 }
 
+fn encode_panic_strategy(rbml_w: &mut Encoder, strategy: config::PanicStrategy) {
+    let byte: u8 = match strategy {
+        config::PanicStrategy::Unwind => b'u',
+        config::PanicStrategy::Abort => b'a',
+    };
+    rbml_w.wr_tagged_u8(tag_panic_strategy, byte);
+}
+
 fn encode_dylib_dependency_formats(rbml_w: &mut Encoder, ecx: &EncodeContext) {
     let tag = tag_dylib_dependency_formats;
     match ecx.tcx.dependency_formats.borrow().get(&config::CrateTypeDylib) {
@@ -2039,6 +2145,7 @@ fn encode_metadata_inner(wr: &mut Cursor<Vec<u8>>,
                            .target_triple
                            );
     encode_hash(&mut rbml_w, &ecx.link_meta.crate_hash);
+    encode_panic_strategy(&mut rbml_w, tcx.sess.panic_strategy());
     encode_dylib_dependency_formats(&mut rbml_w, &ecx);
 
     let mut i = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap();
@@ -68,7 +68,8 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
                 ast::TyI8 => mywrite!(w, "MB"),
                 ast::TyI16 => mywrite!(w, "MW"),
                 ast::TyI32 => mywrite!(w, "ML"),
-                ast::TyI64 => mywrite!(w, "MD")
+                ast::TyI64 => mywrite!(w, "MD"),
+                ast::TyI128 => mywrite!(w, "MQ")
             }
         }
         ty::ty_uint(t) => {
@@ -77,7 +78,8 @@ pub fn enc_ty<'a, 'tcx>(w: &mut Encoder, cx: &ctxt<'a, 'tcx>, t: Ty<'tcx>) {
                 ast::TyU8 => mywrite!(w, "Mb"),
                 ast::TyU16 => mywrite!(w, "Mw"),
                 ast::TyU32 => mywrite!(w, "Ml"),
-                ast::TyU64 => mywrite!(w, "Md")
+                ast::TyU64 => mywrite!(w, "Md"),
+                ast::TyU128 => mywrite!(w, "Mq")
             }
         }
         ty::ty_float(t) => {
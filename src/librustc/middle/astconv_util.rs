@@ -17,6 +17,8 @@
 use middle::def;
 use middle::ty::{self, Ty};
 use syntax::ast;
+use syntax::codemap::Span;
+use syntax::feature_gate;
 use util::ppaux::Repr;
 
 pub const NO_REGIONS: usize = 1;
@@ -43,10 +45,22 @@ pub fn check_path_args(tcx: &ty::ctxt, segments: &[ast::PathSegment], flags: usi
 }
 
 pub fn prim_ty_to_ty<'tcx>(tcx: &ty::ctxt<'tcx>,
+                           span: Span,
                            segments: &[ast::PathSegment],
                            nty: ast::PrimTy)
                            -> Ty<'tcx> {
     check_path_args(tcx, segments, NO_TPS | NO_REGIONS);
+    match nty {
+        ast::TyInt(ast::TyI128) | ast::TyUint(ast::TyU128) => {
+            if !tcx.sess.features.borrow().i128_type {
+                feature_gate::emit_feature_err(&tcx.sess.parse_sess.span_diagnostic,
+                                               "i128_type",
+                                               span,
+                                               "128-bit integer types are experimental");
+            }
+        }
+        _ => {}
+    }
     match nty {
         ast::TyBool => tcx.types.bool,
         ast::TyChar => tcx.types.char,
@@ -68,7 +82,7 @@ pub fn ast_ty_to_prim_ty<'tcx>(tcx: &ty::ctxt<'tcx>, ast_ty: &ast::Ty)
             Some(d) => d.full_def()
         };
         if let def::DefPrimTy(nty) = def {
-            Some(prim_ty_to_ty(tcx, &path.segments, nty))
+            Some(prim_ty_to_ty(tcx, ast_ty.span, &path.segments, nty))
         } else {
             None
         }
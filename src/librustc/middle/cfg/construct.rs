@@ -396,6 +396,7 @@ impl<'a, 'tcx> CFGBuilder<'a, 'tcx> {
             }
 
             ast::ExprMac(..) |
+            ast::ExprError |
             ast::ExprClosure(..) |
             ast::ExprLit(..) |
             ast::ExprPath(..) => {
@@ -16,17 +16,21 @@ pub use self::const_val::*;
 use self::ErrKind::*;
 
 use metadata::csearch;
-use middle::{astencode, def};
+use middle::{astencode, def, infer, subst, traits};
 use middle::pat_util::def_to_path;
 use middle::ty::{self, Ty};
 use middle::astconv_util::ast_ty_to_prim_ty;
+use util::nodemap::NodeMap;
+use util::ppaux::Repr;
 
 use syntax::ast::{self, Expr};
 use syntax::codemap::Span;
 use syntax::feature_gate;
 use syntax::parse::token::InternedString;
 use syntax::ptr::P;
+use syntax::util::int128::{I128, U128};
 use syntax::{ast_map, ast_util, codemap};
+use syntax::ast_map::blocks::{Code, FnLikeCode, FnLikeNode};
 
 use std::borrow::{Cow, IntoCow};
 use std::num::wrapping::OverflowingOps;
@@ -39,8 +43,9 @@ use std::rc::Rc;
 fn lookup_const<'a>(tcx: &'a ty::ctxt, e: &Expr) -> Option<&'a Expr> {
     let opt_def = tcx.def_map.borrow().get(&e.id).map(|d| d.full_def());
     match opt_def {
-        Some(def::DefConst(def_id)) => {
-            lookup_const_by_id(tcx, def_id)
+        Some(def::DefConst(def_id)) |
+        Some(def::DefAssociatedConst(def_id, _)) => {
+            lookup_const_by_id(tcx, def_id, Some(e.id))
         }
         Some(def::DefVariant(enum_def, variant_def, _)) => {
             lookup_variant_by_id(tcx, enum_def, variant_def)
@@ -101,8 +106,13 @@ fn lookup_variant_by_id<'a>(tcx: &'a ty::ctxt,
     }
 }
 
-pub fn lookup_const_by_id<'a>(tcx: &'a ty::ctxt, def_id: ast::DefId)
-                          -> Option<&'a Expr> {
+/// Looks up the initializer of the constant `def_id`. For a constant
+/// declared in a trait, `maybe_ref_id` is the id of the path expression
+/// referring to it, whose substitutions select the impl providing the value.
+pub fn lookup_const_by_id<'a, 'tcx: 'a>(tcx: &'a ty::ctxt<'tcx>,
+                                        def_id: ast::DefId,
+                                        maybe_ref_id: Option<ast::NodeId>)
+                                        -> Option<&'tcx Expr> {
     if ast_util::is_local(def_id) {
         match tcx.map.find(def_id.node) {
             None => None,
@@ -112,6 +122,36 @@ pub fn lookup_const_by_id<'a>(tcx: &'a ty::ctxt, def_id: ast::DefId)
                 }
                 _ => None
             },
+            Some(ast_map::NodeTraitItem(ti)) => match ti.node {
+                ast::ConstTraitItem(_, ref default) => {
+                    match maybe_ref_id {
+                        Some(ref_id) => {
+                            let trait_id = ast_util::local_def(tcx.map.get_parent(def_id.node));
+                            let substs = ty::node_id_item_substs(tcx, ref_id).substs;
+                            match resolve_trait_associated_const(tcx, def_id, trait_id,
+                                                                 substs) {
+                                Some(id) if id == def_id => default.as_ref().map(|e| &**e),
+                                Some(id) => lookup_const_by_id(tcx, id, None),
+                                None => None
+                            }
+                        }
+                        // Technically, without knowing anything about the
+                        // expression that generates the obligation, we could
+                        // still return the default if there is one. However,
+                        // it's safer to return `None` than to return some value
+                        // that may differ from what you would get from
+                        // correctly selecting an impl.
+                        None => None
+                    }
+                }
+                _ => None
+            },
+            Some(ast_map::NodeImplItem(ii)) => match ii.node {
+                ast::ConstImplItem(_, ref expr) => {
+                    Some(&**expr)
+                }
+                _ => None
+            },
             Some(_) => None
         }
     } else {
@@ -122,25 +162,116 @@ pub fn lookup_const_by_id<'a>(tcx: &'a ty::ctxt, def_id: ast::DefId)
             }
             None => {}
         }
+        let mut used_ref_id = false;
         let expr_id = match csearch::maybe_get_item_ast(tcx, def_id,
             Box::new(|a, b, c, d| astencode::decode_inlined_item(a, b, c, d))) {
             csearch::FoundAst::Found(&ast::IIItem(ref item)) => match item.node {
                 ast::ItemConst(_, ref const_expr) => Some(const_expr.id),
                 _ => None
             },
+            csearch::FoundAst::Found(&ast::IITraitItem(_, ref ti)) => match ti.node {
+                ast::ConstTraitItem(_, ref default) => {
+                    used_ref_id = true;
+                    match maybe_ref_id {
+                        // As mentioned in the comments above for in-crate
+                        // constants, we only try to find the expression for
+                        // a trait-associated const if the caller gives us
+                        // the expression that refers to it.
+                        Some(ref_id) => {
+                            let trait_id = ty::trait_of_item(tcx, def_id).unwrap();
+                            let substs = ty::node_id_item_substs(tcx, ref_id).substs;
+                            match resolve_trait_associated_const(tcx, def_id, trait_id,
+                                                                 substs) {
+                                Some(id) if id == def_id => default.as_ref().map(|e| e.id),
+                                Some(id) => lookup_const_by_id(tcx, id, None).map(|e| e.id),
+                                None => None
+                            }
+                        }
+                        None => None
+                    }
+                }
+                _ => None
+            },
+            csearch::FoundAst::Found(&ast::IIImplItem(_, ref ii)) => match ii.node {
+                ast::ConstImplItem(_, ref expr) => Some(expr.id),
+                _ => None
+            },
             _ => None
         };
-        tcx.extern_const_statics.borrow_mut().insert(def_id,
-                                                     expr_id.unwrap_or(ast::DUMMY_NODE_ID));
+        // If we used the reference expression, particularly to choose an impl
+        // of a trait-associated const, don't cache that, because the next
+        // lookup with the same def_id may yield a different result.
+        if !used_ref_id {
+            tcx.extern_const_statics
+               .borrow_mut().insert(def_id, expr_id.unwrap_or(ast::DUMMY_NODE_ID));
+        }
         expr_id.map(|id| tcx.map.expect_expr(id))
     }
 }
 
+fn inline_const_fn_from_external_crate(tcx: &ty::ctxt, def_id: ast::DefId)
+                                       -> Option<ast::NodeId> {
+    match tcx.extern_const_fns.borrow().get(&def_id) {
+        Some(&ast::DUMMY_NODE_ID) => return None,
+        Some(&fn_id) => return Some(fn_id),
+        None => {}
+    }
+
+    if !csearch::is_const_fn(&tcx.sess.cstore, def_id) {
+        tcx.extern_const_fns.borrow_mut().insert(def_id, ast::DUMMY_NODE_ID);
+        return None;
+    }
+
+    let fn_id = match csearch::maybe_get_item_ast(tcx, def_id,
+        Box::new(|a, b, c, d| astencode::decode_inlined_item(a, b, c, d))) {
+        csearch::FoundAst::Found(&ast::IIItem(ref item)) => Some(item.id),
+        csearch::FoundAst::Found(&ast::IIImplItem(_, ref item)) => Some(item.id),
+        _ => None
+    };
+    tcx.extern_const_fns.borrow_mut().insert(def_id,
+                                             fn_id.unwrap_or(ast::DUMMY_NODE_ID));
+    fn_id
+}
+
+/// Returns the fn-like node for `def_id` if it is a `const fn`, inlining
+/// it from its crate's metadata if necessary.
+pub fn lookup_const_fn_by_id<'tcx>(tcx: &ty::ctxt<'tcx>, def_id: ast::DefId)
+                                   -> Option<FnLikeNode<'tcx>> {
+    let fn_id = if !ast_util::is_local(def_id) {
+        match inline_const_fn_from_external_crate(tcx, def_id) {
+            Some(fn_id) => fn_id,
+            None => return None
+        }
+    } else {
+        def_id.node
+    };
+
+    let is_const = match tcx.map.find(fn_id) {
+        Some(ast_map::NodeItem(it)) => match it.node {
+            ast::ItemFn(_, _, constness, _, _, _) => constness == ast::Constness::Const,
+            _ => false
+        },
+        Some(ast_map::NodeImplItem(it)) => match it.node {
+            ast::MethodImplItem(ref sig, _) => sig.constness == ast::Constness::Const,
+            _ => false
+        },
+        _ => false
+    };
+    if !is_const {
+        return None;
+    }
+
+    match Code::from_node(tcx.map.get(fn_id)) {
+        Some(FnLikeCode(fn_like)) => Some(fn_like),
+        _ => None
+    }
+}
+
 #[derive(Clone, PartialEq)]
 pub enum const_val {
     const_float(f64),
-    const_int(i64),
-    const_uint(u64),
+    const_int(I128),
+    const_uint(U128),
     const_str(InternedString),
     const_binary(Rc<Vec<u8>>),
     const_bool(bool),
@@ -238,13 +369,13 @@ pub enum ErrKind {
     NotOnStruct,
     NotOnTuple,
 
-    NegateWithOverflow(i64),
-    AddiWithOverflow(i64, i64),
-    SubiWithOverflow(i64, i64),
-    MuliWithOverflow(i64, i64),
-    AdduWithOverflow(u64, u64),
-    SubuWithOverflow(u64, u64),
-    MuluWithOverflow(u64, u64),
+    NegateWithOverflow(I128),
+    AddiWithOverflow(I128, I128),
+    SubiWithOverflow(I128, I128),
+    MuliWithOverflow(I128, I128),
+    AdduWithOverflow(U128, U128),
+    SubuWithOverflow(U128, U128),
+    MuluWithOverflow(U128, U128),
     DivideByZero,
     DivideWithOverflow,
     ModuloByZero,
@@ -256,6 +387,7 @@ pub enum ErrKind {
     ExpectedConstTuple,
     ExpectedConstStruct,
     TupleIndexOutOfBounds,
+    CallRecursionLimit,
 
     MiscBinaryOp,
     MiscCatchAll,
@@ -301,6 +433,8 @@ impl ConstEvalErr {
             ExpectedConstTuple => "expected constant tuple".into_cow(),
             ExpectedConstStruct => "expected constant struct".into_cow(),
             TupleIndexOutOfBounds => "tuple index out of bounds".into_cow(),
+            CallRecursionLimit => "reached the recursion limit while evaluating \
+                                   a constant function call".into_cow(),
 
             MiscBinaryOp => "bad operands for binary".into_cow(),
             MiscCatchAll => "unsupported constant expr".into_cow(),
@@ -312,9 +446,9 @@ pub type EvalResult = Result<const_val, ConstEvalErr>;
 pub type CastResult = Result<const_val, ErrKind>;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum IntTy { I8, I16, I32, I64 }
+pub enum IntTy { I8, I16, I32, I64, I128 }
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub enum UintTy { U8, U16, U32, U64 }
+pub enum UintTy { U8, U16, U32, U64, U128 }
 
 impl IntTy {
     pub fn from(tcx: &ty::ctxt, t: ast::IntTy) -> IntTy {
@@ -329,6 +463,7 @@ impl IntTy {
             ast::TyI16 => IntTy::I16,
             ast::TyI32 => IntTy::I32,
             ast::TyI64 => IntTy::I64,
+            ast::TyI128 => IntTy::I128,
         }
     }
 }
@@ -346,6 +481,7 @@ impl UintTy {
             ast::TyU16 => UintTy::U16,
             ast::TyU32 => UintTy::U32,
             ast::TyU64 => UintTy::U64,
+            ast::TyU128 => UintTy::U128,
         }
     }
 }
@@ -368,69 +504,93 @@ macro_rules! signal {
 // constant evaluation happening before type check (e.g. in the check
 // to confirm that a pattern range's left-side is not greater than its
 // right-side). We do not do arithmetic modulo the type's bitwidth in
-// such a case; we just do 64-bit arithmetic and assume that later
+// such a case; we just do 128-bit arithmetic and assume that later
 // passes will do it again with the type information, and thus do the
 // overflow checks then.
 
 pub fn const_int_checked_neg<'a>(
-    a: i64, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
+    a: I128, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
 
+    let int = I128::from_i64;
     let (min,max) = match opt_ety {
         // (-i8::MIN is itself not an i8, etc, but this is an easy way
         // to allow literals to pass the check. Of course that does
-        // not work for i64::MIN.)
-        Some(IntTy::I8) =>  (-(i8::MAX as i64), -(i8::MIN as i64)),
-        Some(IntTy::I16) => (-(i16::MAX as i64), -(i16::MIN as i64)),
-        Some(IntTy::I32) => (-(i32::MAX as i64), -(i32::MIN as i64)),
-        None | Some(IntTy::I64) => (-i64::MAX, -(i64::MIN+1)),
+        // not work for i128::MIN.)
+        Some(IntTy::I8) =>  (int(-(i8::MAX as i64)), int(-(i8::MIN as i64))),
+        Some(IntTy::I16) => (int(-(i16::MAX as i64)), int(-(i16::MIN as i64))),
+        Some(IntTy::I32) => (int(-(i32::MAX as i64)), int(-(i32::MIN as i64))),
+        Some(IntTy::I64) => (int(-i64::MAX), int(i64::MIN).wrapping_neg()),
+        None | Some(IntTy::I128) => (I128::max_value().wrapping_neg(), I128::max_value()),
     };
 
     let oflo = a < min || a > max;
     if oflo {
         signal!(e, NegateWithOverflow(a));
     } else {
-        Ok(const_int(-a))
+        Ok(const_int(a.wrapping_neg()))
     }
 }
 
 pub fn const_uint_checked_neg<'a>(
-    a: u64, _e: &'a Expr, _opt_ety: Option<UintTy>) -> EvalResult {
-    // This always succeeds, and by definition, returns `(!a)+1`.
-    Ok(const_uint((!a).wrapping_add(1)))
+    a: U128, _e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
+    // This always succeeds, and by definition, returns `(!a)+1` in the
+    // type's bitwidth.
+    Ok(const_uint(uint_truncate(a.wrapping_neg(), opt_ety)))
+}
+
+/// Wraps `a` to the bitwidth of `opt_ety`, for the operations that
+/// cannot overflow but whose 128-bit result has bits set above it.
+fn uint_truncate(a: U128, opt_ety: Option<UintTy>) -> U128 {
+    let max = match opt_ety {
+        Some(UintTy::U8) => ast_util::uint_ty_max(ast::TyU8),
+        Some(UintTy::U16) => ast_util::uint_ty_max(ast::TyU16),
+        Some(UintTy::U32) => ast_util::uint_ty_max(ast::TyU32),
+        Some(UintTy::U64) => ast_util::uint_ty_max(ast::TyU64),
+        None | Some(UintTy::U128) => ast_util::uint_ty_max(ast::TyU128),
+    };
+    a & max
 }
 
 macro_rules! overflow_checking_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident,
-     lhs: $to_8_lhs:ident $to_16_lhs:ident $to_32_lhs:ident,
-     rhs: $to_8_rhs:ident $to_16_rhs:ident $to_32_rhs:ident $to_64_rhs:ident,
-     $EnumTy:ident $T8: ident $T16: ident $T32: ident $T64: ident,
-     $result_type: ident) => { {
+     lhs: $to_8_lhs:ident $to_16_lhs:ident $to_32_lhs:ident $to_64_lhs:ident,
+     rhs: $to_8_rhs:ident $to_16_rhs:ident $to_32_rhs:ident $to_64_rhs:ident
+          $to_128_rhs:ident,
+     $EnumTy:ident $T8: ident $T16: ident $T32: ident $T64: ident $T128: ident,
+     $result_type: ident $from_64: ident $ty_64: ident) => { {
         let (a,b,opt_ety) = ($a,$b,$ety);
         match opt_ety {
             Some($EnumTy::$T8) => match (a.$to_8_lhs(), b.$to_8_rhs()) {
                 (Some(a), Some(b)) => {
                     let (a, oflo) = a.$overflowing_op(b);
-                    (a as $result_type, oflo)
+                    ($result_type::$from_64(a as $ty_64), oflo)
                 }
-                (None, _) | (_, None) => (0, true)
+                (None, _) | (_, None) => ($result_type::$from_64(0), true)
             },
             Some($EnumTy::$T16) => match (a.$to_16_lhs(), b.$to_16_rhs()) {
                 (Some(a), Some(b)) => {
                     let (a, oflo) = a.$overflowing_op(b);
-                    (a as $result_type, oflo)
+                    ($result_type::$from_64(a as $ty_64), oflo)
                 }
-                (None, _) | (_, None) => (0, true)
+                (None, _) | (_, None) => ($result_type::$from_64(0), true)
             },
             Some($EnumTy::$T32) => match (a.$to_32_lhs(), b.$to_32_rhs()) {
                 (Some(a), Some(b)) => {
                     let (a, oflo) = a.$overflowing_op(b);
-                    (a as $result_type, oflo)
+                    ($result_type::$from_64(a as $ty_64), oflo)
+                }
+                (None, _) | (_, None) => ($result_type::$from_64(0), true)
+            },
+            Some($EnumTy::$T64) => match (a.$to_64_lhs(), b.$to_64_rhs()) {
+                (Some(a), Some(b)) => {
+                    let (a, oflo) = a.$overflowing_op(b);
+                    ($result_type::$from_64(a), oflo)
                 }
-                (None, _) | (_, None) => (0, true)
+                (None, _) | (_, None) => ($result_type::$from_64(0), true)
             },
-            None | Some($EnumTy::$T64) => match b.$to_64_rhs() {
+            None | Some($EnumTy::$T128) => match b.$to_128_rhs() {
                 Some(b) => a.$overflowing_op(b),
-                None => (0, true),
+                None => ($result_type::$from_64(0), true),
             }
         }
     } }
@@ -440,8 +600,9 @@ macro_rules! int_arith_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident) => {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
-            lhs: to_i8 to_i16 to_i32,
-            rhs: to_i8 to_i16 to_i32 to_i64, IntTy I8 I16 I32 I64, i64)
+            lhs: to_i8 to_i16 to_i32 to_i64,
+            rhs: to_i8 to_i16 to_i32 to_i64 to_i128,
+            IntTy I8 I16 I32 I64 I128, I128 from_i64 i64)
     }
 }
 
@@ -449,8 +610,9 @@ macro_rules! uint_arith_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident) => {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
-            lhs: to_u8 to_u16 to_u32,
-            rhs: to_u8 to_u16 to_u32 to_u64, UintTy U8 U16 U32 U64, u64)
+            lhs: to_u8 to_u16 to_u32 to_u64,
+            rhs: to_u8 to_u16 to_u32 to_u64 to_u128,
+            UintTy U8 U16 U32 U64 U128, U128 from_u64 u64)
     }
 }
 
@@ -458,8 +620,9 @@ macro_rules! int_shift_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident) => {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
-            lhs: to_i8 to_i16 to_i32,
-            rhs: to_u32 to_u32 to_u32 to_u32, IntTy I8 I16 I32 I64, i64)
+            lhs: to_i8 to_i16 to_i32 to_i64,
+            rhs: to_u32 to_u32 to_u32 to_u32 to_u32,
+            IntTy I8 I16 I32 I64 I128, I128 from_i64 i64)
     }
 }
 
@@ -467,8 +630,9 @@ macro_rules! uint_shift_body {
     ($a:ident, $b:ident, $ety:ident, $overflowing_op:ident) => {
         overflow_checking_body!(
             $a, $b, $ety, $overflowing_op,
-            lhs: to_u8 to_u16 to_u32,
-            rhs: to_u32 to_u32 to_u32 to_u32, UintTy U8 U16 U32 U64, u64)
+            lhs: to_u8 to_u16 to_u32 to_u64,
+            rhs: to_u32 to_u32 to_u32 to_u32 to_u32,
+            UintTy U8 U16 U32 U64 U128, U128 from_u64 u64)
     }
 }
 
@@ -487,94 +651,112 @@ macro_rules! pub_fn_checked_op {
     }
 }
 
-pub_fn_checked_op!{ const_int_checked_add(a: i64, b: i64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_add(a: I128, b: I128,.. IntTy) {
            int_arith_body overflowing_add const_int AddiWithOverflow(a, b)
 }}
 
-pub_fn_checked_op!{ const_int_checked_sub(a: i64, b: i64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_sub(a: I128, b: I128,.. IntTy) {
            int_arith_body overflowing_sub const_int SubiWithOverflow(a, b)
 }}
 
-pub_fn_checked_op!{ const_int_checked_mul(a: i64, b: i64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_mul(a: I128, b: I128,.. IntTy) {
            int_arith_body overflowing_mul const_int MuliWithOverflow(a, b)
 }}
 
 pub fn const_int_checked_div<'a>(
-    a: i64, b: i64, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
-    if b == 0 { signal!(e, DivideByZero); }
+    a: I128, b: I128, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
+    if b == I128::from_i64(0) { signal!(e, DivideByZero); }
     let (ret, oflo) = int_arith_body!(a, b, opt_ety, overflowing_div);
     if !oflo { Ok(const_int(ret)) } else { signal!(e, DivideWithOverflow) }
 }
 
 pub fn const_int_checked_rem<'a>(
-    a: i64, b: i64, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
-    if b == 0 { signal!(e, ModuloByZero); }
+    a: I128, b: I128, e: &'a Expr, opt_ety: Option<IntTy>) -> EvalResult {
+    if b == I128::from_i64(0) { signal!(e, ModuloByZero); }
     let (ret, oflo) = int_arith_body!(a, b, opt_ety, overflowing_rem);
     if !oflo { Ok(const_int(ret)) } else { signal!(e, ModuloWithOverflow) }
 }
 
-pub_fn_checked_op!{ const_int_checked_shl(a: i64, b: i64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_shl(a: I128, b: I128,.. IntTy) {
            int_shift_body overflowing_shl const_int ShiftLeftWithOverflow
 }}
 
-pub_fn_checked_op!{ const_int_checked_shl_via_uint(a: i64, b: u64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_shl_via_uint(a: I128, b: U128,.. IntTy) {
            int_shift_body overflowing_shl const_int ShiftLeftWithOverflow
 }}
 
-pub_fn_checked_op!{ const_int_checked_shr(a: i64, b: i64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_shr(a: I128, b: I128,.. IntTy) {
            int_shift_body overflowing_shr const_int ShiftRightWithOverflow
 }}
 
-pub_fn_checked_op!{ const_int_checked_shr_via_uint(a: i64, b: u64,.. IntTy) {
+pub_fn_checked_op!{ const_int_checked_shr_via_uint(a: I128, b: U128,.. IntTy) {
            int_shift_body overflowing_shr const_int ShiftRightWithOverflow
 }}
 
-pub_fn_checked_op!{ const_uint_checked_add(a: u64, b: u64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_add(a: U128, b: U128,.. UintTy) {
            uint_arith_body overflowing_add const_uint AdduWithOverflow(a, b)
 }}
 
-pub_fn_checked_op!{ const_uint_checked_sub(a: u64, b: u64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_sub(a: U128, b: U128,.. UintTy) {
            uint_arith_body overflowing_sub const_uint SubuWithOverflow(a, b)
 }}
 
-pub_fn_checked_op!{ const_uint_checked_mul(a: u64, b: u64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_mul(a: U128, b: U128,.. UintTy) {
            uint_arith_body overflowing_mul const_uint MuluWithOverflow(a, b)
 }}
 
 pub fn const_uint_checked_div<'a>(
-    a: u64, b: u64, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
-    if b == 0 { signal!(e, DivideByZero); }
+    a: U128, b: U128, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
+    if b == U128::from_u64(0) { signal!(e, DivideByZero); }
     let (ret, oflo) = uint_arith_body!(a, b, opt_ety, overflowing_div);
     if !oflo { Ok(const_uint(ret)) } else { signal!(e, DivideWithOverflow) }
 }
 
 pub fn const_uint_checked_rem<'a>(
-    a: u64, b: u64, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
-    if b == 0 { signal!(e, ModuloByZero); }
+    a: U128, b: U128, e: &'a Expr, opt_ety: Option<UintTy>) -> EvalResult {
+    if b == U128::from_u64(0) { signal!(e, ModuloByZero); }
     let (ret, oflo) = uint_arith_body!(a, b, opt_ety, overflowing_rem);
     if !oflo { Ok(const_uint(ret)) } else { signal!(e, ModuloWithOverflow) }
 }
 
-pub_fn_checked_op!{ const_uint_checked_shl(a: u64, b: u64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_shl(a: U128, b: U128,.. UintTy) {
            uint_shift_body overflowing_shl const_uint ShiftLeftWithOverflow
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shl_via_int(a: u64, b: i64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_shl_via_int(a: U128, b: I128,.. UintTy) {
            uint_shift_body overflowing_shl const_uint ShiftLeftWithOverflow
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shr(a: u64, b: u64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_shr(a: U128, b: U128,.. UintTy) {
            uint_shift_body overflowing_shr const_uint ShiftRightWithOverflow
 }}
 
-pub_fn_checked_op!{ const_uint_checked_shr_via_int(a: u64, b: i64,.. UintTy) {
+pub_fn_checked_op!{ const_uint_checked_shr_via_int(a: U128, b: I128,.. UintTy) {
            uint_shift_body overflowing_shr const_uint ShiftRightWithOverflow
 }}
 
 pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
                                      e: &Expr,
                                      ty_hint: Option<Ty<'tcx>>) -> EvalResult {
-    fn fromb(b: bool) -> const_val { const_int(b as i64) }
+    eval_const_expr_with_args(tcx, e, ty_hint, None)
+}
+
+/// The call of the `const fn` whose body is being evaluated.
+#[derive(Copy, Clone)]
+struct FnArgs<'a> {
+    /// Values of the arguments, keyed by the id of each argument's binding.
+    values: &'a NodeMap<const_val>,
+    /// How many calls deep the evaluation is, to stop runaway recursion.
+    depth: usize,
+}
+
+type FnArgMap<'a> = Option<FnArgs<'a>>;
+
+fn eval_const_expr_with_args<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                   e: &Expr,
+                                   ty_hint: Option<Ty<'tcx>>,
+                                   fn_args: FnArgMap) -> EvalResult {
+    fn fromb(b: bool) -> const_val { const_int(I128::from_i64(b as i64)) }
 
     let ety = ty_hint.or_else(|| ty::expr_ty_opt(tcx, e));
 
@@ -592,7 +774,7 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
 
     let result = match e.node {
       ast::ExprUnary(ast::UnNeg, ref inner) => {
-        match try!(eval_const_expr_partial(tcx, &**inner, ety)) {
+        match try!(eval_const_expr_with_args(tcx, &**inner, ety, fn_args)) {
           const_float(f) => const_float(-f),
           const_int(n) =>  try!(const_int_checked_neg(n, e, expr_int_type)),
           const_uint(i) => {
@@ -613,9 +795,9 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
         }
       }
       ast::ExprUnary(ast::UnNot, ref inner) => {
-        match try!(eval_const_expr_partial(tcx, &**inner, ety)) {
+        match try!(eval_const_expr_with_args(tcx, &**inner, ety, fn_args)) {
           const_int(i) => const_int(!i),
-          const_uint(i) => const_uint(!i),
+          const_uint(i) => const_uint(uint_truncate(!i, expr_uint_type)),
           const_bool(b) => const_bool(!b),
           const_str(_) => signal!(e, NotOnString),
           const_float(_) => signal!(e, NotOnFloat),
@@ -629,8 +811,8 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
             ast::BiShl | ast::BiShr => Some(tcx.types.usize),
             _ => ety
         };
-        match (try!(eval_const_expr_partial(tcx, &**a, ety)),
-               try!(eval_const_expr_partial(tcx, &**b, b_ty))) {
+        match (try!(eval_const_expr_with_args(tcx, &**a, ety, fn_args)),
+               try!(eval_const_expr_with_args(tcx, &**b, b_ty, fn_args))) {
           (const_float(a), const_float(b)) => {
             match op.node {
               ast::BiAdd => const_float(a + b),
@@ -734,7 +916,7 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
         // e.g. `(i8::MAX + 1_i8) as u32` feeds in `u32` as result
         // type to the sum, and thus no overflow is signaled.
         let base_hint = ty::expr_ty_opt(tcx, &**base).unwrap_or(ety);
-        let val = try!(eval_const_expr_partial(tcx, &**base, Some(base_hint)));
+        let val = try!(eval_const_expr_with_args(tcx, &**base, Some(base_hint), fn_args));
         match cast_const(tcx, val, ety) {
             Ok(val) => val,
             Err(kind) => return Err(ConstEvalErr { span: e.span, kind: kind }),
@@ -755,12 +937,47 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
                           _ => (None, None)
                       }
                   } else {
-                      (lookup_const_by_id(tcx, def_id), None)
+                      (lookup_const_by_id(tcx, def_id, Some(e.id)), None)
+                  }
+              }
+              Some(def::DefAssociatedConst(def_id, provenance)) => {
+                  if ast_util::is_local(def_id) {
+                      match provenance {
+                          def::FromTrait(_) => match tcx.map.find(def_id.node) {
+                              Some(ast_map::NodeTraitItem(ti)) => match ti.node {
+                                  ast::ConstTraitItem(ref ty, _) => {
+                                      (lookup_const_by_id(tcx, def_id, Some(e.id)),
+                                       Some(&**ty))
+                                  }
+                                  _ => (None, None)
+                              },
+                              _ => (None, None)
+                          },
+                          def::FromImpl(_) => match tcx.map.find(def_id.node) {
+                              Some(ast_map::NodeImplItem(ii)) => match ii.node {
+                                  ast::ConstImplItem(ref ty, ref expr) => {
+                                      (Some(&**expr), Some(&**ty))
+                                  }
+                                  _ => (None, None)
+                              },
+                              _ => (None, None)
+                          },
+                      }
+                  } else {
+                      (lookup_const_by_id(tcx, def_id, Some(e.id)), None)
                   }
               }
               Some(def::DefVariant(enum_def, variant_def, _)) => {
                   (lookup_variant_by_id(tcx, enum_def, variant_def), None)
               }
+              Some(def::DefLocal(id)) => {
+                  // Within a const fn, arguments evaluate to the values
+                  // they were called with.
+                  match fn_args.and_then(|args| args.values.get(&id)) {
+                      Some(val) => return Ok(val.clone()),
+                      None => (None, None)
+                  }
+              }
               _ => (None, None)
           };
           let const_expr = match const_expr {
@@ -768,16 +985,48 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
               None => signal!(e, NonConstPath)
           };
           let ety = ety.or_else(|| const_ty.and_then(|ty| ast_ty_to_prim_ty(tcx, ty)));
-          try!(eval_const_expr_partial(tcx, const_expr, ety))
+          try!(eval_const_expr_with_args(tcx, const_expr, ety, None))
+      }
+      ast::ExprCall(ref callee, ref args) => {
+          let opt_def = tcx.def_map.borrow().get(&callee.id).map(|d| d.full_def());
+          let fn_like = match opt_def {
+              Some(def::DefFn(def_id, _)) |
+              Some(def::DefMethod(def_id, _)) => lookup_const_fn_by_id(tcx, def_id),
+              _ => None
+          };
+          let fn_like = match fn_like {
+              Some(fn_like) => fn_like,
+              None => signal!(e, NonConstPath)
+          };
+          let depth = fn_args.map_or(0, |args| args.depth) + 1;
+          if depth > tcx.sess.recursion_limit.get() {
+              signal!(e, CallRecursionLimit)
+          }
+          let mut call_args = NodeMap();
+          for (arg, arg_expr) in fn_like.decl().inputs.iter().zip(args.iter()) {
+              // The call may be evaluated before type checking (e.g. in an
+              // array length), so hint with the declared argument type.
+              let arg_hint = ast_ty_to_prim_ty(tcx, &*arg.ty);
+              let arg_val = try!(eval_const_expr_with_args(tcx, &**arg_expr, arg_hint,
+                                                           fn_args));
+              call_args.insert(arg.pat.id, arg_val);
+          }
+          match fn_like.body().expr {
+              Some(ref expr) => {
+                  let call = FnArgs { values: &call_args, depth: depth };
+                  try!(eval_const_expr_with_args(tcx, &**expr, ety, Some(call)))
+              }
+              None => const_int(I128::from_i64(0))
+          }
       }
       ast::ExprLit(ref lit) => {
           lit_to_const(&**lit, ety)
       }
-      ast::ExprParen(ref e) => try!(eval_const_expr_partial(tcx, &**e, ety)),
+      ast::ExprParen(ref e) => try!(eval_const_expr_with_args(tcx, &**e, ety, fn_args)),
       ast::ExprBlock(ref block) => {
         match block.expr {
-            Some(ref expr) => try!(eval_const_expr_partial(tcx, &**expr, ety)),
-            None => const_int(0)
+            Some(ref expr) => try!(eval_const_expr_with_args(tcx, &**expr, ety, fn_args)),
+            None => const_int(I128::from_i64(0))
         }
       }
       ast::ExprTup(_) => {
@@ -787,7 +1036,7 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
         const_val::Struct(e.id)
       }
       ast::ExprTupField(ref base, index) => {
-        if let Ok(c) = eval_const_expr_partial(tcx, base, None) {
+        if let Ok(c) = eval_const_expr_with_args(tcx, base, None, fn_args) {
             if let const_val::Tuple(tup_id) = c {
                 if let ast::ExprTup(ref fields) = tcx.map.expect_expr(tup_id).node {
                     if index.node < fields.len() {
@@ -807,7 +1056,7 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
       }
       ast::ExprField(ref base, field_name) => {
         // Get the base expression if it is a struct and it is constant
-        if let Ok(c) = eval_const_expr_partial(tcx, base, None) {
+        if let Ok(c) = eval_const_expr_with_args(tcx, base, None, fn_args) {
             if let const_val::Struct(struct_id) = c {
                 if let ast::ExprStruct(_, ref fields, _) = tcx.map.expect_expr(struct_id).node {
                     // Check that the given field exists and evaluate it
@@ -833,27 +1082,119 @@ pub fn eval_const_expr_partial<'tcx>(tcx: &ty::ctxt<'tcx>,
     Ok(result)
 }
 
+/// Selects the impl of `trait_id` matching the receiver substitutions and
+/// returns the id of the constant that gives the trait's associated const
+/// `trait_const_id` its value: the impl's own constant, or `trait_const_id`
+/// itself if the impl relies on the trait's default. Returns `None` if the
+/// impl cannot be chosen yet, e.g. because the reference is still generic.
+pub fn resolve_trait_associated_const<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                            trait_const_id: ast::DefId,
+                                            trait_id: ast::DefId,
+                                            rcvr_substs: subst::Substs<'tcx>)
+                                            -> Option<ast::DefId> {
+    let subst::SeparateVecsPerParamSpace {
+        types: rcvr_type,
+        selfs: rcvr_self,
+        fns: _,
+    } = rcvr_substs.types.split();
+    if rcvr_self.is_empty() {
+        // The reference has not been type-checked yet, so there is no
+        // `Self` type to select an impl with.
+        return None;
+    }
+    let trait_substs =
+        subst::Substs::erased(subst::VecPerParamSpace::new(rcvr_type,
+                                                           rcvr_self,
+                                                           Vec::new()));
+    let trait_substs = tcx.mk_substs(trait_substs);
+    debug!("resolve_trait_associated_const: trait_substs={}",
+           trait_substs.repr(tcx));
+    let trait_ref = ty::Binder(Rc::new(ty::TraitRef { def_id: trait_id,
+                                                      substs: trait_substs }));
+
+    ty::populate_implementations_for_trait_if_necessary(tcx, trait_ref.def_id());
+    let infcx = infer::new_infer_ctxt(tcx);
+
+    let param_env = ty::empty_parameter_environment(tcx);
+    let mut selcx = traits::SelectionContext::new(&infcx, &param_env);
+    let obligation = traits::Obligation::new(traits::ObligationCause::dummy(),
+                                             trait_ref.to_poly_trait_predicate());
+    let selection = match selcx.select(&obligation) {
+        Ok(Some(vtable)) => vtable,
+        // Still ambiguous, so give up and let the caller decide whether this
+        // expression is really needed yet. Some associated constant values
+        // can't be evaluated until monomorphization is done in trans.
+        Ok(None) => return None,
+        Err(e) => {
+            tcx.sess.bug(&format!("encountered error `{}` when trying to select \
+                                   an implementation for constant trait item \
+                                   reference", e.repr(tcx)))
+        }
+    };
+
+    let trait_const = match ty::impl_or_trait_item(tcx, trait_const_id) {
+        ty::ConstTraitItem(ac) => ac,
+        _ => tcx.sess.bug("resolve_trait_associated_const: not an associated const")
+    };
+    match selection {
+        traits::VtableImpl(ref impl_data) => {
+            match ty::associated_consts(tcx, impl_data.impl_def_id)
+                     .iter().find(|ic| ic.name == trait_const.name) {
+                Some(ic) => Some(ic.def_id),
+                None => trait_const.default
+            }
+        }
+        _ => tcx.sess.bug("resolve_trait_associated_const: unexpected vtable type")
+    }
+}
+
 fn cast_const<'tcx>(tcx: &ty::ctxt<'tcx>, val: const_val, ty: Ty) -> CastResult {
+    // None of the integer types handled here is wider than 64 bits, so
+    // 128-bit values are truncated to that first.
     macro_rules! convert_val {
-        ($intermediate_ty:ty, $const_type:ident, $target_ty:ty) => {
+        ($intermediate_ty:ty, $const_type:ident, $target_ty:ty, $widen:path) => {
             match val {
-                const_bool(b) => Ok($const_type(b as $intermediate_ty as $target_ty)),
-                const_uint(u) => Ok($const_type(u as $intermediate_ty as $target_ty)),
-                const_int(i) => Ok($const_type(i as $intermediate_ty as $target_ty)),
-                const_float(f) => Ok($const_type(f as $intermediate_ty as $target_ty)),
+                const_bool(b) => Ok($const_type($widen(b as $intermediate_ty as $target_ty))),
+                const_uint(u) => {
+                    Ok($const_type($widen(u.as_u64() as $intermediate_ty as $target_ty)))
+                }
+                const_int(i) => {
+                    Ok($const_type($widen(i.as_u64() as $intermediate_ty as $target_ty)))
+                }
+                const_float(f) => Ok($const_type($widen(f as $intermediate_ty as $target_ty))),
                 _ => Err(ErrKind::CannotCastTo(stringify!($const_type))),
             }
         }
     }
 
+    macro_rules! convert_float {
+        ($intermediate_ty:ty) => {
+            match val {
+                const_bool(b) => Ok(const_float(b as $intermediate_ty as f64)),
+                const_uint(u) => Ok(const_float(u.as_f64() as $intermediate_ty as f64)),
+                const_int(i) => Ok(const_float(i.as_f64() as $intermediate_ty as f64)),
+                const_float(f) => Ok(const_float(f as $intermediate_ty as f64)),
+                _ => Err(ErrKind::CannotCastTo("const_float")),
+            }
+        }
+    }
+
     // Issue #23890: If isize/usize, then dispatch to appropriate target representation type
     match (&ty.sty, tcx.sess.target.int_type, tcx.sess.target.uint_type) {
-        (&ty::ty_int(ast::TyIs), ast::TyI32, _) => return convert_val!(i32, const_int, i64),
-        (&ty::ty_int(ast::TyIs), ast::TyI64, _) => return convert_val!(i64, const_int, i64),
+        (&ty::ty_int(ast::TyIs), ast::TyI32, _) => {
+            return convert_val!(i32, const_int, i64, I128::from_i64)
+        }
+        (&ty::ty_int(ast::TyIs), ast::TyI64, _) => {
+            return convert_val!(i64, const_int, i64, I128::from_i64)
+        }
         (&ty::ty_int(ast::TyIs), _, _) => panic!("unexpected target.int_type"),
 
-        (&ty::ty_uint(ast::TyUs), _, ast::TyU32) => return convert_val!(u32, const_uint, u64),
-        (&ty::ty_uint(ast::TyUs), _, ast::TyU64) => return convert_val!(u64, const_uint, u64),
+        (&ty::ty_uint(ast::TyUs), _, ast::TyU32) => {
+            return convert_val!(u32, const_uint, u64, U128::from_u64)
+        }
+        (&ty::ty_uint(ast::TyUs), _, ast::TyU64) => {
+            return convert_val!(u64, const_uint, u64, U128::from_u64)
+        }
         (&ty::ty_uint(ast::TyUs), _, _) => panic!("unexpected target.uint_type"),
 
         _ => {}
@@ -863,18 +1204,32 @@ fn cast_const<'tcx>(tcx: &ty::ctxt<'tcx>, val: const_val, ty: Ty) -> CastResult
         ty::ty_int(ast::TyIs) => unreachable!(),
         ty::ty_uint(ast::TyUs) => unreachable!(),
 
-        ty::ty_int(ast::TyI8) => convert_val!(i8, const_int, i64),
-        ty::ty_int(ast::TyI16) => convert_val!(i16, const_int, i64),
-        ty::ty_int(ast::TyI32) => convert_val!(i32, const_int, i64),
-        ty::ty_int(ast::TyI64) => convert_val!(i64, const_int, i64),
-
-        ty::ty_uint(ast::TyU8) => convert_val!(u8, const_uint, u64),
-        ty::ty_uint(ast::TyU16) => convert_val!(u16, const_uint, u64),
-        ty::ty_uint(ast::TyU32) => convert_val!(u32, const_uint, u64),
-        ty::ty_uint(ast::TyU64) => convert_val!(u64, const_uint, u64),
-
-        ty::ty_float(ast::TyF32) => convert_val!(f32, const_float, f64),
-        ty::ty_float(ast::TyF64) => convert_val!(f64, const_float, f64),
+        ty::ty_int(ast::TyI8) => convert_val!(i8, const_int, i64, I128::from_i64),
+        ty::ty_int(ast::TyI16) => convert_val!(i16, const_int, i64, I128::from_i64),
+        ty::ty_int(ast::TyI32) => convert_val!(i32, const_int, i64, I128::from_i64),
+        ty::ty_int(ast::TyI64) => convert_val!(i64, const_int, i64, I128::from_i64),
+        ty::ty_int(ast::TyI128) => match val {
+            const_bool(b) => Ok(const_int(I128::from_i64(b as i64))),
+            const_uint(u) => Ok(const_int(u.as_i128())),
+            const_int(i) => Ok(const_int(i)),
+            const_float(f) => Ok(const_int(I128::from_f64(f))),
+            _ => Err(ErrKind::CannotCastTo("const_int")),
+        },
+
+        ty::ty_uint(ast::TyU8) => convert_val!(u8, const_uint, u64, U128::from_u64),
+        ty::ty_uint(ast::TyU16) => convert_val!(u16, const_uint, u64, U128::from_u64),
+        ty::ty_uint(ast::TyU32) => convert_val!(u32, const_uint, u64, U128::from_u64),
+        ty::ty_uint(ast::TyU64) => convert_val!(u64, const_uint, u64, U128::from_u64),
+        ty::ty_uint(ast::TyU128) => match val {
+            const_bool(b) => Ok(const_uint(U128::from_u64(b as u64))),
+            const_uint(u) => Ok(const_uint(u)),
+            const_int(i) => Ok(const_uint(i.as_u128())),
+            const_float(f) => Ok(const_uint(U128::from_f64(f))),
+            _ => Err(ErrKind::CannotCastTo("const_uint")),
+        },
+
+        ty::ty_float(ast::TyF32) => convert_float!(f32),
+        ty::ty_float(ast::TyF64) => convert_float!(f64),
         _ => Err(ErrKind::CannotCast),
     }
 }
@@ -885,18 +1240,20 @@ fn lit_to_const(lit: &ast::Lit, ty_hint: Option<Ty>) -> const_val {
         ast::LitBinary(ref data) => {
             const_binary(data.clone())
         }
-        ast::LitByte(n) => const_uint(n as u64),
-        ast::LitChar(n) => const_uint(n as u64),
-        ast::LitInt(n, ast::SignedIntLit(_, ast::Plus)) => const_int(n as i64),
+        ast::LitByte(n) => const_uint(U128::from_u64(n as u64)),
+        ast::LitChar(n) => const_uint(U128::from_u64(n as u64)),
+        ast::LitInt(n, ast::SignedIntLit(_, ast::Plus)) => const_int(U128::from_u64(n).as_i128()),
         ast::LitInt(n, ast::UnsuffixedIntLit(ast::Plus)) => {
             match ty_hint.map(|ty| &ty.sty) {
-                Some(&ty::ty_uint(_)) => const_uint(n),
-                _ => const_int(n as i64)
+                Some(&ty::ty_uint(_)) => const_uint(U128::from_u64(n)),
+                _ => const_int(U128::from_u64(n).as_i128())
             }
         }
         ast::LitInt(n, ast::SignedIntLit(_, ast::Minus)) |
-        ast::LitInt(n, ast::UnsuffixedIntLit(ast::Minus)) => const_int(-(n as i64)),
-        ast::LitInt(n, ast::UnsignedIntLit(_)) => const_uint(n),
+        ast::LitInt(n, ast::UnsuffixedIntLit(ast::Minus)) => {
+            const_int(U128::from_u64(n).as_i128().wrapping_neg())
+        }
+        ast::LitInt(n, ast::UnsignedIntLit(_)) => const_uint(U128::from_u64(n)),
         ast::LitFloat(ref n, _) |
         ast::LitFloatUnsuffixed(ref n) => {
             const_float(n.parse::<f64>().unwrap() as f64)
@@ -62,6 +62,7 @@
 //! than finding a number of solutions (there are normally quite a few).
 
 use syntax::ast;
+use syntax::attr;
 
 use session;
 use session::config;
@@ -88,6 +89,7 @@ pub fn calculate(tcx: &ty::ctxt) {
     for &ty in &*tcx.sess.crate_types.borrow() {
         fmts.insert(ty, calculate_type(&tcx.sess, ty));
     }
+    verify_allocator(tcx);
     tcx.sess.abort_if_errors();
 }
 
@@ -205,6 +207,42 @@ fn calculate_type(sess: &session::Session,
     return ret;
 }
 
+// Checks that the artifact being produced contains exactly one allocator if
+// anything in it needs one. Every crate we depend on ends up in the output,
+// either linked directly or through one of our dylibs, and every allocator
+// defines the same set of symbols, so two of them can never coexist.
+fn verify_allocator(tcx: &ty::ctxt) {
+    let sess = &tcx.sess;
+    if sess.crate_types.borrow().iter().all(|ty| *ty == config::CrateTypeRlib) {
+        return
+    }
+
+    let attrs = &tcx.map.krate().attrs;
+    let mut needs_allocator = attr::contains_name(attrs, "needs_allocator");
+    let mut allocators = Vec::new();
+    if attr::contains_name(attrs, "allocator") {
+        allocators.push("this crate".to_string());
+    }
+    sess.cstore.iter_crate_data(|_, data| {
+        needs_allocator = needs_allocator || data.needs_allocator();
+        if data.is_allocator() {
+            allocators.push(format!("`{}`", data.name));
+        }
+    });
+
+    match allocators.len() {
+        0 if needs_allocator => {
+            sess.err("no global allocator found, but one is required; link \
+                      to an `#![allocator]` crate such as `alloc_system`");
+        }
+        0 | 1 => {}
+        _ => {
+            sess.err(&format!("cannot link together two allocators: {} and {}",
+                              allocators[0], allocators[1]));
+        }
+    }
+}
+
 fn add_library(sess: &session::Session,
                cnum: ast::CrateNum,
                link: cstore::LinkagePreference,
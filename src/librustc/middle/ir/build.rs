@@ -0,0 +1,359 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Construction of the basic-block IR from the control-flow graph.
+
+use middle::cfg;
+use middle::cfg::CFGIndex;
+use middle::ir::*;
+use middle::pat_util;
+use middle::region::CodeExtent;
+use middle::ty;
+use std::iter::repeat;
+use syntax::ast;
+use syntax::ast_map;
+use syntax::visit::{self, Visitor};
+use util::nodemap::NodeMap;
+
+struct IrBuilder<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    cfg: &'a cfg::CFG,
+    preds: Vec<usize>,
+    succs: Vec<usize>,
+    /// Basic block started by each leader node of the CFG, indexed by
+    /// cfg node.
+    leaders: Vec<Option<BasicBlock>>,
+    blocks: Vec<BasicBlockData<'tcx>>,
+    bindings: Bindings,
+}
+
+/// A variable bound by a `let`, a match arm (which includes the
+/// desugared `for`, `if let` and `while let`) or a fn argument.
+struct Binding {
+    var_id: ast::NodeId,
+    /// Index of the `let` statement in its block, if any. Control that
+    /// leaves a block from an earlier statement never initialized it.
+    stmt_index: Option<usize>,
+}
+
+/// The bindings of a body, keyed by the node at whose end they go out
+/// of scope: the declaring block for `let`s, the arm body for match
+/// bindings, and the fn body for arguments.
+struct Bindings {
+    scopes: NodeMap<Vec<Binding>>,
+    /// Block and index of each statement of a block in the body.
+    stmts: NodeMap<(ast::NodeId, usize)>,
+    /// Arm body for each match guard; leaving a guard early has to drop
+    /// the arm's bindings too.
+    guards: NodeMap<ast::NodeId>,
+}
+
+struct BindingCollector<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    bindings: Bindings,
+}
+
+impl<'a, 'tcx> BindingCollector<'a, 'tcx> {
+    fn add(&mut self, scope: ast::NodeId, stmt_index: Option<usize>, pat: &ast::Pat) {
+        let scope = self.bindings.scopes.entry(scope).or_insert(Vec::new());
+        pat_util::pat_bindings(&self.tcx.def_map, pat, |_, var_id, _, _| {
+            scope.push(Binding { var_id: var_id, stmt_index: stmt_index });
+        });
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for BindingCollector<'a, 'tcx> {
+    fn visit_block(&mut self, blk: &'v ast::Block) {
+        for (i, stmt) in blk.stmts.iter().enumerate() {
+            let stmt_id = match stmt.node {
+                ast::StmtDecl(ref decl, id) => {
+                    if let ast::DeclLocal(ref local) = decl.node {
+                        self.add(blk.id, Some(i), &*local.pat);
+                    }
+                    id
+                }
+                ast::StmtExpr(_, id) | ast::StmtSemi(_, id) => id,
+                ast::StmtMac(..) => continue,
+            };
+            self.bindings.stmts.insert(stmt_id, (blk.id, i));
+        }
+        visit::walk_block(self, blk);
+    }
+
+    fn visit_arm(&mut self, arm: &'v ast::Arm) {
+        // Every pattern of an arm binds the same names; trans uses the
+        // first one's.
+        self.add(arm.body.id, None, &*arm.pats[0]);
+        if let Some(ref guard) = arm.guard {
+            self.bindings.guards.insert(guard.id, arm.body.id);
+        }
+        visit::walk_arm(self, arm);
+    }
+
+    fn visit_expr(&mut self, expr: &'v ast::Expr) {
+        // Closure bodies get IR of their own.
+        if let ast::ExprClosure(..) = expr.node {
+            return;
+        }
+        visit::walk_expr(self, expr);
+    }
+
+    fn visit_item(&mut self, _: &'v ast::Item) {}
+}
+
+/// Builds the IR for the fn body (or other block) `body`, whose fn
+/// takes the arguments `args`.
+pub fn build<'tcx>(tcx: &ty::ctxt<'tcx>, args: &[ast::Arg], body: &ast::Block) -> Ir<'tcx> {
+    let cfg = cfg::CFG::new(tcx, body);
+
+    let mut collector = BindingCollector {
+        tcx: tcx,
+        bindings: Bindings {
+            scopes: NodeMap(),
+            stmts: NodeMap(),
+            guards: NodeMap(),
+        },
+    };
+    // Arguments outlive every local of the body, so they're recorded
+    // first and dropped last.
+    for arg in args {
+        collector.add(body.id, None, &*arg.pat);
+    }
+    collector.visit_block(body);
+
+    let num_nodes = cfg.graph.all_nodes().len();
+    let mut preds: Vec<usize> = repeat(0).take(num_nodes).collect();
+    let mut succs: Vec<usize> = repeat(0).take(num_nodes).collect();
+    cfg.graph.each_edge(|_, edge| {
+        succs[edge.source().node_id()] += 1;
+        preds[edge.target().node_id()] += 1;
+        true
+    });
+
+    let mut builder = IrBuilder {
+        tcx: tcx,
+        cfg: &cfg,
+        preds: preds,
+        succs: succs,
+        leaders: repeat(None).take(num_nodes).collect(),
+        blocks: Vec::new(),
+        bindings: collector.bindings,
+    };
+
+    // A node starts a basic block unless it is the only successor of
+    // its only predecessor.
+    let mut leaders = Vec::new();
+    cfg.graph.each_node(|idx, _| {
+        if builder.is_leader(idx) {
+            leaders.push(idx);
+        }
+        true
+    });
+    for &idx in &leaders {
+        let bb = builder.new_block();
+        builder.leaders[idx.node_id()] = Some(bb);
+    }
+    for &idx in &leaders {
+        builder.fill_block(idx);
+    }
+
+    let entry = builder.leader_block(cfg.entry);
+    let exit = builder.leader_block(cfg.exit);
+    Ir {
+        body_id: body.id,
+        blocks: builder.blocks,
+        entry: entry,
+        exit: exit,
+    }
+}
+
+impl<'a, 'tcx> IrBuilder<'a, 'tcx> {
+    fn is_leader(&self, idx: CFGIndex) -> bool {
+        if idx == self.cfg.entry || idx == self.cfg.exit {
+            return true;
+        }
+        if self.preds[idx.node_id()] != 1 {
+            return true;
+        }
+        let mut leader = false;
+        self.cfg.graph.each_incoming_edge(idx, |_, edge| {
+            let pred = edge.source();
+            leader = self.succs[pred.node_id()] != 1 ||
+                     !edge.data.exiting_scopes.is_empty();
+            true
+        });
+        leader
+    }
+
+    fn leader_block(&self, idx: CFGIndex) -> BasicBlock {
+        self.leaders[idx.node_id()].unwrap()
+    }
+
+    fn new_block(&mut self) -> BasicBlock {
+        let bb = BasicBlock(self.blocks.len());
+        self.blocks.push(BasicBlockData {
+            statements: Vec::new(),
+            terminator: Terminator::Unreachable,
+        });
+        bb
+    }
+
+    fn fill_block(&mut self, leader: CFGIndex) {
+        let bb = self.leader_block(leader);
+        let mut statements = Vec::new();
+        let mut idx = leader;
+        loop {
+            self.push_node_statements(idx, &mut statements);
+
+            let mut next = None;
+            if self.succs[idx.node_id()] == 1 {
+                self.cfg.graph.each_outgoing_edge(idx, |_, edge| {
+                    next = Some(edge.target());
+                    true
+                });
+            }
+            match next {
+                Some(n) if self.leaders[n.node_id()].is_none() => idx = n,
+                _ => break,
+            }
+        }
+
+        let terminator = self.terminator(idx);
+        let data = &mut self.blocks[bb.index()];
+        data.statements = statements;
+        data.terminator = terminator;
+    }
+
+    fn terminator(&mut self, last: CFGIndex) -> Terminator {
+        if last == self.cfg.exit {
+            return Terminator::Return;
+        }
+
+        let mut edges = Vec::new();
+        self.cfg.graph.each_outgoing_edge(last, |_, edge| {
+            edges.push((edge.target(), self.exited_blocks(last, edge)));
+            true
+        });
+
+        let mut targets = Vec::new();
+        for (target, exited) in edges {
+            let target_bb = self.leader_block(target);
+            let drops = self.drops_for_scopes(&exited);
+            if drops.is_empty() {
+                targets.push(target_bb);
+            } else {
+                // Scopes exited along this edge need their locals
+                // dropped before control reaches the target.
+                let drop_bb = self.new_block();
+                let data = &mut self.blocks[drop_bb.index()];
+                data.statements = drops;
+                data.terminator = Terminator::Goto(target_bb);
+                targets.push(drop_bb);
+            }
+        }
+
+        match targets.len() {
+            0 => Terminator::Unreachable,
+            1 => Terminator::Goto(targets[0]),
+            _ => Terminator::Switch(self.cfg.graph.node_data(last).id(), targets),
+        }
+    }
+
+    fn push_node_statements(&self,
+                            idx: CFGIndex,
+                            statements: &mut Vec<Statement<'tcx>>) {
+        let id = match self.cfg.graph.node_data(idx) {
+            &cfg::CFGNodeData::AST(id) => id,
+            _ => return,
+        };
+        statements.push(Statement::Eval(id, ty::node_id_to_type_opt(self.tcx, id)));
+
+        // Reaching the node for a block or an arm body means its
+        // bindings go out of scope normally.
+        statements.extend(self.drops_for_scopes(&[id]).into_iter());
+    }
+
+    /// Returns the scopes exited when control flows along `edge` out
+    /// of the node `source`, innermost first.
+    fn exited_blocks(&self, source: CFGIndex, edge: &cfg::CFGEdge) -> Vec<ast::NodeId> {
+        let mut exited = Vec::new();
+        let is_return = match self.tcx.map.find(self.cfg.graph.node_data(source).id()) {
+            Some(ast_map::NodeExpr(e)) => match e.node {
+                ast::ExprRet(..) => true,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if is_return {
+            // `return` leaves every enclosing block of the body.
+            let mut scope = CodeExtent::from_node_id(self.cfg.graph.node_data(source).id());
+            while let Some(parent) = self.tcx.region_maps.opt_encl_scope(scope) {
+                if let CodeExtent::Misc(id) = parent {
+                    exited.push(id);
+                }
+                scope = parent;
+            }
+        } else {
+            exited.extend(edge.data.exiting_scopes.iter().cloned());
+        }
+
+        let mut seen = Vec::new();
+        exited.retain(|&id| {
+            if seen.contains(&id) {
+                false
+            } else {
+                seen.push(id);
+                true
+            }
+        });
+        exited
+    }
+
+    /// Drop statements for the bindings that go out of scope when
+    /// control leaves each of `scopes`, innermost first, in reverse
+    /// order of declaration. When leaving a block from one of its
+    /// statements, only the `let`s before that statement are dropped.
+    fn drops_for_scopes(&self, scopes: &[ast::NodeId]) -> Vec<Statement<'tcx>> {
+        let mut drops = Vec::new();
+        for (i, &id) in scopes.iter().enumerate() {
+            let scope = *self.bindings.guards.get(&id).unwrap_or(&id);
+            let bindings = match self.bindings.scopes.get(&scope) {
+                Some(bindings) => bindings,
+                None => continue,
+            };
+
+            // Leaving a guard is like leaving the arm body before its
+            // first statement.
+            let exit_stmt = if scope != id {
+                Some(0)
+            } else {
+                scopes[..i].iter().filter_map(|inner| {
+                    match self.bindings.stmts.get(inner) {
+                        Some(&(blk, index)) if blk == scope => Some(index),
+                        _ => None,
+                    }
+                }).next()
+            };
+
+            for binding in bindings.iter().rev() {
+                match (binding.stmt_index, exit_stmt) {
+                    (Some(decl), Some(exit)) if decl >= exit => continue,
+                    _ => {}
+                }
+                let var_ty = ty::node_id_to_type(self.tcx, binding.var_id);
+                if ty::type_contents(self.tcx, var_ty).needs_drop(self.tcx) {
+                    drops.push(Statement::Drop(binding.var_id, var_ty));
+                }
+            }
+        }
+        drops
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rendering of the basic-block IR with libgraphviz.
+
+use std::borrow::IntoCow;
+
+// For clarity, rename the graphviz crate locally to dot.
+use graphviz as dot;
+
+use middle::ir::*;
+use middle::ir::pretty::{statement_to_string, terminator_to_string};
+use middle::ty;
+
+pub type Node = BasicBlock;
+pub type Edge = (BasicBlock, BasicBlock);
+
+pub struct LabelledIr<'a, 'tcx: 'a> {
+    pub tcx: &'a ty::ctxt<'tcx>,
+    pub ir: &'a Ir<'tcx>,
+    pub name: String,
+}
+
+impl<'a, 'tcx> dot::Labeller<'a, Node, Edge> for LabelledIr<'a, 'tcx> {
+    fn graph_id(&'a self) -> dot::Id<'a> { dot::Id::new(&self.name[..]).unwrap() }
+
+    fn node_id(&'a self, bb: &Node) -> dot::Id<'a> {
+        dot::Id::new(format!("bb{}", bb.index())).unwrap()
+    }
+
+    fn node_label(&'a self, bb: &Node) -> dot::LabelText<'a> {
+        // Each statement on its own left-aligned line.
+        let data = self.ir.block(*bb);
+        let mut s = format!("bb{}\\l", bb.index());
+        for stmt in &data.statements {
+            s.push_str(&statement_to_string(self.tcx, stmt));
+            s.push_str("\\l");
+        }
+        s.push_str(&terminator_to_string(self.tcx, &data.terminator));
+        s.push_str("\\l");
+        dot::LabelText::EscStr(s.into_cow())
+    }
+}
+
+impl<'a, 'tcx> dot::GraphWalk<'a, Node, Edge> for LabelledIr<'a, 'tcx> {
+    fn nodes(&'a self) -> dot::Nodes<'a, Node> {
+        self.ir.basic_blocks().into_cow()
+    }
+    fn edges(&'a self) -> dot::Edges<'a, Edge> {
+        let mut edges = Vec::new();
+        for bb in self.ir.basic_blocks() {
+            for &succ in self.ir.block(bb).terminator.successors() {
+                edges.push((bb, succ));
+            }
+        }
+        edges.into_cow()
+    }
+    fn source(&'a self, edge: &Edge) -> Node { edge.0 }
+    fn target(&'a self, edge: &Edge) -> Node { edge.1 }
+}
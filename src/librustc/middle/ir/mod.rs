@@ -0,0 +1,105 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A typed, basic-block intermediate representation of fn bodies.
+//!
+//! The IR is built after type checking from the expression-level
+//! control-flow graph in `middle::cfg`. Straight-line runs of CFG
+//! nodes are collapsed into basic blocks, each statement carries the
+//! type recorded for its node in the `ty::ctxt` tables, and the
+//! destructors that run when locals go out of scope (including on
+//! `break`, `continue` and `return`) are made explicit as `Drop`
+//! statements.
+//!
+//! The IR is currently only used for debugging output, via
+//! `--xpretty ir=<nodeid>` and `--xpretty ir,dot=<nodeid>`; the
+//! intent is that later analyses can be written against it instead
+//! of re-deriving control flow from the AST.
+
+use middle::ty::Ty;
+use std::slice;
+use syntax::ast;
+
+mod build;
+pub mod graphviz;
+pub mod pretty;
+
+pub use self::build::build;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BasicBlock(pub usize);
+
+impl BasicBlock {
+    pub fn index(&self) -> usize {
+        let BasicBlock(i) = *self;
+        i
+    }
+}
+
+pub struct Ir<'tcx> {
+    /// The id of the block this IR was built from.
+    pub body_id: ast::NodeId,
+    pub blocks: Vec<BasicBlockData<'tcx>>,
+    pub entry: BasicBlock,
+    pub exit: BasicBlock,
+}
+
+impl<'tcx> Ir<'tcx> {
+    pub fn block(&self, bb: BasicBlock) -> &BasicBlockData<'tcx> {
+        &self.blocks[bb.index()]
+    }
+
+    pub fn basic_blocks(&self) -> Vec<BasicBlock> {
+        (0..self.blocks.len()).map(BasicBlock).collect()
+    }
+}
+
+pub struct BasicBlockData<'tcx> {
+    pub statements: Vec<Statement<'tcx>>,
+    pub terminator: Terminator,
+}
+
+#[derive(Clone, Debug)]
+pub enum Statement<'tcx> {
+    /// Evaluates the AST node `id`, whose operands have already been
+    /// evaluated by earlier statements. The type is absent for nodes
+    /// that produce no value, such as `let` statements.
+    Eval(ast::NodeId, Option<Ty<'tcx>>),
+
+    /// Runs the destructor for the local variable bound by the
+    /// pattern `id`, if it is still initialized.
+    Drop(ast::NodeId, Ty<'tcx>),
+}
+
+#[derive(Clone, Debug)]
+pub enum Terminator {
+    Goto(BasicBlock),
+
+    /// Transfers control to one of `targets` depending on the value of
+    /// the node `id` (the condition of an `if`, the scrutinee of a
+    /// `match`, and so on).
+    Switch(ast::NodeId, Vec<BasicBlock>),
+
+    Return,
+
+    /// Control never leaves this block, e.g. after a call to a
+    /// diverging fn.
+    Unreachable,
+}
+
+impl Terminator {
+    pub fn successors(&self) -> &[BasicBlock] {
+        match *self {
+            Terminator::Goto(ref b) => slice::ref_slice(b),
+            Terminator::Switch(_, ref targets) => &targets[..],
+            Terminator::Return | Terminator::Unreachable => &[],
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Textual dump of the basic-block IR.
+
+use middle::ir::*;
+use middle::ty;
+use util::ppaux::UserString;
+use std::io::{self, Write};
+use syntax::ast;
+use syntax::ast_map;
+use syntax::print::pprust;
+
+/// Writes `ir` to `w`, one basic block at a time.
+pub fn write_ir<'tcx, W: Write>(tcx: &ty::ctxt<'tcx>,
+                                ir: &Ir<'tcx>,
+                                w: &mut W) -> io::Result<()> {
+    try!(writeln!(w, "// IR for node {} (entry: bb{}, exit: bb{})",
+                  ir.body_id, ir.entry.index(), ir.exit.index()));
+    for bb in ir.basic_blocks() {
+        try!(writeln!(w, "bb{}: {{", bb.index()));
+        let data = ir.block(bb);
+        for stmt in &data.statements {
+            try!(writeln!(w, "    {};", statement_to_string(tcx, stmt)));
+        }
+        try!(writeln!(w, "    {};", terminator_to_string(tcx, &data.terminator)));
+        try!(writeln!(w, "}}"));
+    }
+    Ok(())
+}
+
+pub fn statement_to_string<'tcx>(tcx: &ty::ctxt<'tcx>, stmt: &Statement<'tcx>) -> String {
+    match *stmt {
+        Statement::Eval(id, Some(ty)) => {
+            format!("N{}: {} = {}", id, ty.user_string(tcx), node_to_string(tcx, id))
+        }
+        Statement::Eval(id, None) => {
+            format!("N{} = {}", id, node_to_string(tcx, id))
+        }
+        Statement::Drop(id, ty) => {
+            format!("drop({}: {})", node_to_string(tcx, id), ty.user_string(tcx))
+        }
+    }
+}
+
+pub fn terminator_to_string(tcx: &ty::ctxt, term: &Terminator) -> String {
+    match *term {
+        Terminator::Goto(bb) => format!("goto -> bb{}", bb.index()),
+        Terminator::Switch(id, ref targets) => {
+            let targets: Vec<String> = targets.iter()
+                                              .map(|bb| format!("bb{}", bb.index()))
+                                              .collect();
+            if id == ast::DUMMY_NODE_ID {
+                format!("switch -> [{}]", targets.connect(", "))
+            } else {
+                format!("switch(N{}: {}) -> [{}]",
+                        id, node_to_string(tcx, id), targets.connect(", "))
+            }
+        }
+        Terminator::Return => "return".to_string(),
+        Terminator::Unreachable => "unreachable".to_string(),
+    }
+}
+
+/// A short description of the AST node `id`, fit for a single line.
+fn node_to_string(tcx: &ty::ctxt, id: ast::NodeId) -> String {
+    let s = match tcx.map.find(id) {
+        Some(ast_map::NodeExpr(e)) => format!("`{}`", pprust::expr_to_string(e)),
+        Some(ast_map::NodeLocal(p)) |
+        Some(ast_map::NodePat(p)) => format!("`{}`", pprust::pat_to_string(p)),
+        Some(ast_map::NodeStmt(_)) => "<stmt>".to_string(),
+        Some(ast_map::NodeBlock(_)) => "<end of block>".to_string(),
+        _ => tcx.map.node_to_string(id),
+    };
+
+    // Compound expressions are printed in full by pprust; keep only
+    // their first line.
+    match s.find('\n') {
+        Some(i) => format!("{} ...`", &s[..i]),
+        None => s,
+    }
+}
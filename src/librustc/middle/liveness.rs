@@ -507,7 +507,7 @@ fn visit_expr(ir: &mut IrMaps, expr: &Expr) {
       ast::ExprBlock(..) | ast::ExprAssign(..) | ast::ExprAssignOp(..) |
       ast::ExprMac(..) | ast::ExprStruct(..) | ast::ExprRepeat(..) |
       ast::ExprParen(..) | ast::ExprInlineAsm(..) | ast::ExprBox(..) |
-      ast::ExprRange(..) => {
+      ast::ExprRange(..) | ast::ExprError => {
           visit::walk_expr(ir, expr);
       }
     }
@@ -1209,7 +1209,7 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
             })
           }
 
-          ast::ExprLit(..) => {
+          ast::ExprLit(..) | ast::ExprError => {
             succ
           }
 
@@ -1220,6 +1220,7 @@ impl<'a, 'tcx> Liveness<'a, 'tcx> {
           ast::ExprMac(..) => {
             self.ir.tcx.sess.span_bug(expr.span, "unexpanded macro");
           }
+
         }
     }
 
@@ -1470,7 +1471,7 @@ fn check_expr(this: &mut Liveness, expr: &Expr) {
       ast::ExprBlock(..) | ast::ExprMac(..) | ast::ExprAddrOf(..) |
       ast::ExprStruct(..) | ast::ExprRepeat(..) | ast::ExprParen(..) |
       ast::ExprClosure(..) | ast::ExprPath(..) | ast::ExprBox(..) |
-      ast::ExprRange(..) => {
+      ast::ExprRange(..) | ast::ExprError => {
         visit::walk_expr(this, expr);
       }
       ast::ExprIfLet(..) => {
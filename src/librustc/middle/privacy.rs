@@ -49,6 +49,9 @@ pub enum LastPrivate {
 #[derive(Copy, Clone, Debug)]
 pub enum PrivateDep {
     AllPublic,
+    // The path goes through a node that is not `pub`. This includes nodes with
+    // restricted visibility (`pub(crate)`, `pub(in path)`), whose scope is only
+    // known once the privacy pass can look at the node's own visibility.
     DependsOn(ast::DefId),
 }
 
@@ -37,6 +37,9 @@ pub enum ObjectSafetyViolation<'tcx> {
 
     /// Method has something illegal
     Method(Rc<ty::Method<'tcx>>, MethodViolationCode),
+
+    /// Associated const, whose value can't be found through a trait object
+    AssociatedConst(Rc<ty::AssociatedConst<'tcx>>),
 }
 
 /// Reasons a method might not be object-safe.
@@ -100,6 +103,9 @@ fn object_safety_violations_for_trait<'tcx>(tcx: &ty::ctxt<'tcx>,
                         .map(|code| ObjectSafetyViolation::Method(m.clone(), code))
                         .into_iter()
                 }
+                ty::ConstTraitItem(ref c) => {
+                    Some(ObjectSafetyViolation::AssociatedConst(c.clone())).into_iter()
+                }
                 ty::TypeTraitItem(_) => {
                     None.into_iter()
                 }
@@ -365,6 +371,8 @@ impl<'tcx> Repr<'tcx> for ObjectSafetyViolation<'tcx> {
                 format!("SupertraitSelf"),
             ObjectSafetyViolation::Method(ref m, code) =>
                 format!("Method({},{:?})", m.repr(tcx), code),
+            ObjectSafetyViolation::AssociatedConst(ref c) =>
+                format!("AssociatedConst({})", c.repr(tcx)),
         }
     }
 }
@@ -133,6 +133,7 @@ impl ImplOrTraitItemContainer {
 
 #[derive(Clone, Debug)]
 pub enum ImplOrTraitItem<'tcx> {
+    ConstTraitItem(Rc<AssociatedConst<'tcx>>),
     MethodTraitItem(Rc<Method<'tcx>>),
     TypeTraitItem(Rc<AssociatedType>),
 }
@@ -140,6 +141,9 @@ pub enum ImplOrTraitItem<'tcx> {
 impl<'tcx> ImplOrTraitItem<'tcx> {
     fn id(&self) -> ImplOrTraitItemId {
         match *self {
+            ConstTraitItem(ref associated_const) => {
+                ConstTraitItemId(associated_const.def_id)
+            }
             MethodTraitItem(ref method) => MethodTraitItemId(method.def_id),
             TypeTraitItem(ref associated_type) => {
                 TypeTraitItemId(associated_type.def_id)
@@ -149,6 +153,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
 
     pub fn def_id(&self) -> ast::DefId {
         match *self {
+            ConstTraitItem(ref associated_const) => associated_const.def_id,
             MethodTraitItem(ref method) => method.def_id,
             TypeTraitItem(ref associated_type) => associated_type.def_id,
         }
@@ -156,6 +161,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
 
     pub fn name(&self) -> ast::Name {
         match *self {
+            ConstTraitItem(ref associated_const) => associated_const.name,
             MethodTraitItem(ref method) => method.name,
             TypeTraitItem(ref associated_type) => associated_type.name,
         }
@@ -163,6 +169,7 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
 
     pub fn container(&self) -> ImplOrTraitItemContainer {
         match *self {
+            ConstTraitItem(ref associated_const) => associated_const.container,
             MethodTraitItem(ref method) => method.container,
             TypeTraitItem(ref associated_type) => associated_type.container,
         }
@@ -171,13 +178,14 @@ impl<'tcx> ImplOrTraitItem<'tcx> {
     pub fn as_opt_method(&self) -> Option<Rc<Method<'tcx>>> {
         match *self {
             MethodTraitItem(ref m) => Some((*m).clone()),
-            TypeTraitItem(_) => None
+            _ => None
         }
     }
 }
 
 #[derive(Clone, Copy, Debug)]
 pub enum ImplOrTraitItemId {
+    ConstTraitItemId(ast::DefId),
     MethodTraitItemId(ast::DefId),
     TypeTraitItemId(ast::DefId),
 }
@@ -185,6 +193,7 @@ pub enum ImplOrTraitItemId {
 impl ImplOrTraitItemId {
     pub fn def_id(&self) -> ast::DefId {
         match *self {
+            ConstTraitItemId(def_id) => def_id,
             MethodTraitItemId(def_id) => def_id,
             TypeTraitItemId(def_id) => def_id,
         }
@@ -238,7 +247,18 @@ impl<'tcx> Method<'tcx> {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
+pub struct AssociatedConst<'tcx> {
+    pub name: ast::Name,
+    pub ty: Ty<'tcx>,
+    pub vis: ast::Visibility,
+    pub def_id: ast::DefId,
+    pub container: ImplOrTraitItemContainer,
+    /// For a trait constant, its own id if it provides a default value.
+    pub default: Option<ast::DefId>,
+}
+
+#[derive(Clone, Debug)]
 pub struct AssociatedType {
     pub name: ast::Name,
     pub vis: ast::Visibility,
@@ -252,7 +272,7 @@ pub struct mt<'tcx> {
     pub mutbl: ast::Mutability,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct field_ty {
     pub name: Name,
     pub id: DefId,
@@ -648,11 +668,13 @@ pub struct CommonTypes<'tcx> {
     pub i16: Ty<'tcx>,
     pub i32: Ty<'tcx>,
     pub i64: Ty<'tcx>,
+    pub i128: Ty<'tcx>,
     pub usize: Ty<'tcx>,
     pub u8: Ty<'tcx>,
     pub u16: Ty<'tcx>,
     pub u32: Ty<'tcx>,
     pub u64: Ty<'tcx>,
+    pub u128: Ty<'tcx>,
     pub f32: Ty<'tcx>,
     pub f64: Ty<'tcx>,
     pub err: Ty<'tcx>,
@@ -799,10 +821,11 @@ pub struct ctxt<'tcx> {
     /// Borrows
     pub upvar_capture_map: RefCell<UpvarCaptureMap>,
 
-    /// These two caches are used by const_eval when decoding external statics
-    /// and variants that are found.
+    /// These caches are used by const_eval when decoding external statics,
+    /// variants and const fns that are found.
     pub extern_const_statics: RefCell<DefIdMap<ast::NodeId>>,
     pub extern_const_variants: RefCell<DefIdMap<ast::NodeId>>,
+    pub extern_const_fns: RefCell<DefIdMap<ast::NodeId>>,
 
     pub method_map: MethodMap<'tcx>,
 
@@ -2320,6 +2343,16 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
         match cx.map.find(id) {
             Some(ast_map::NodeImplItem(ref impl_item)) => {
                 match impl_item.node {
+                    ast::ConstImplItem(_, _) => {
+                        let def_id = ast_util::local_def(id);
+                        let scheme = lookup_item_type(cx, def_id);
+                        let predicates = lookup_predicates(cx, def_id);
+                        construct_parameter_environment(cx,
+                                                        impl_item.span,
+                                                        &scheme.generics,
+                                                        &predicates,
+                                                        id)
+                    }
                     ast::MethodImplItem(_, ref body) => {
                         let method_def_id = ast_util::local_def(id);
                         match ty::impl_or_trait_item(cx, method_def_id) {
@@ -2333,11 +2366,11 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
                                     method_bounds,
                                     body.id)
                             }
-                            TypeTraitItem(_) => {
+                            _ => {
                                 cx.sess
                                   .bug("ParameterEnvironment::for_item(): \
-                                        can't create a parameter environment \
-                                        for type trait items")
+                                        got non-method item from provided \
+                                        method?!")
                             }
                         }
                     }
@@ -2351,6 +2384,25 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
             }
             Some(ast_map::NodeTraitItem(trait_item)) => {
                 match trait_item.node {
+                    ast::ConstTraitItem(_, ref default) => {
+                        match *default {
+                            Some(_) => {
+                                let def_id = ast_util::local_def(id);
+                                let scheme = lookup_item_type(cx, def_id);
+                                let predicates = lookup_predicates(cx, def_id);
+                                construct_parameter_environment(cx,
+                                                                trait_item.span,
+                                                                &scheme.generics,
+                                                                &predicates,
+                                                                id)
+                            }
+                            None => {
+                                cx.sess.bug("ParameterEnvironment::from_item(): \
+                                             can't create a parameter environment \
+                                             for const trait items without defaults")
+                            }
+                        }
+                    }
                     ast::MethodTraitItem(_, None) => {
                         cx.sess.span_bug(trait_item.span,
                                          "ParameterEnvironment::for_item():
@@ -2371,11 +2423,11 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
                                     method_bounds,
                                     body.id)
                             }
-                            TypeTraitItem(_) => {
+                            _ => {
                                 cx.sess
                                   .bug("ParameterEnvironment::for_item(): \
-                                        can't create a parameter environment \
-                                        for type trait items")
+                                        got non-method item from provided \
+                                        method?!")
                             }
                         }
                     }
@@ -2388,7 +2440,7 @@ impl<'a, 'tcx> ParameterEnvironment<'a, 'tcx> {
             }
             Some(ast_map::NodeItem(item)) => {
                 match item.node {
-                    ast::ItemFn(_, _, _, _, ref body) => {
+                    ast::ItemFn(_, _, _, _, _, ref body) => {
                         // We assume this is a function.
                         let fn_def_id = ast_util::local_def(id);
                         let fn_scheme = lookup_item_type(cx, fn_def_id);
@@ -2576,11 +2628,13 @@ impl<'tcx> CommonTypes<'tcx> {
             i16: intern_ty(arena, interner, ty_int(ast::TyI16)),
             i32: intern_ty(arena, interner, ty_int(ast::TyI32)),
             i64: intern_ty(arena, interner, ty_int(ast::TyI64)),
+            i128: intern_ty(arena, interner, ty_int(ast::TyI128)),
             usize: intern_ty(arena, interner, ty_uint(ast::TyUs)),
             u8: intern_ty(arena, interner, ty_uint(ast::TyU8)),
             u16: intern_ty(arena, interner, ty_uint(ast::TyU16)),
             u32: intern_ty(arena, interner, ty_uint(ast::TyU32)),
             u64: intern_ty(arena, interner, ty_uint(ast::TyU64)),
+            u128: intern_ty(arena, interner, ty_uint(ast::TyU128)),
             f32: intern_ty(arena, interner, ty_float(ast::TyF32)),
             f64: intern_ty(arena, interner, ty_float(ast::TyF64)),
         }
@@ -2652,6 +2706,7 @@ pub fn mk_ctxt<'tcx>(s: Session,
         upvar_capture_map: RefCell::new(FnvHashMap()),
         extern_const_statics: RefCell::new(DefIdMap()),
         extern_const_variants: RefCell::new(DefIdMap()),
+        extern_const_fns: RefCell::new(DefIdMap()),
         method_map: RefCell::new(FnvHashMap()),
         dependency_formats: RefCell::new(FnvHashMap()),
         closure_kinds: RefCell::new(DefIdMap()),
@@ -2966,6 +3021,7 @@ pub fn mk_mach_int<'tcx>(tcx: &ctxt<'tcx>, tm: ast::IntTy) -> Ty<'tcx> {
         ast::TyI16  => tcx.types.i16,
         ast::TyI32  => tcx.types.i32,
         ast::TyI64  => tcx.types.i64,
+        ast::TyI128 => tcx.types.i128,
     }
 }
 
@@ -2976,6 +3032,7 @@ pub fn mk_mach_uint<'tcx>(tcx: &ctxt<'tcx>, tm: ast::UintTy) -> Ty<'tcx> {
         ast::TyU16  => tcx.types.u16,
         ast::TyU32  => tcx.types.u32,
         ast::TyU64  => tcx.types.u64,
+        ast::TyU128 => tcx.types.u128,
     }
 }
 
@@ -4325,7 +4382,7 @@ pub fn positional_element_ty<'tcx>(cx: &ctxt<'tcx>,
 
         (&ty_struct(def_id, substs), None) => lookup_struct_fields(cx, def_id)
             .get(i)
-            .map(|&t|lookup_item_type(cx, t.id).ty.subst(cx, substs)),
+            .map(|t|lookup_item_type(cx, t.id).ty.subst(cx, substs)),
 
         (&ty_enum(def_id, substs), Some(variant_def_id)) => {
             let variant_info = enum_variant_with_id(cx, def_id, variant_def_id);
@@ -4354,7 +4411,7 @@ pub fn named_element_ty<'tcx>(cx: &ctxt<'tcx>,
         (&ty_struct(def_id, substs), None) => {
             let r = lookup_struct_fields(cx, def_id);
             r.iter().find(|f| f.name == n)
-                .map(|&f| lookup_field_type(cx, def_id, f.id, substs))
+                .map(|f| lookup_field_type(cx, def_id, f.id, substs))
         }
         (&ty_enum(def_id, substs), Some(variant_def_id)) => {
             let variant_info = enum_variant_with_id(cx, def_id, variant_def_id);
@@ -4802,7 +4859,8 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
                 def::DefUpvar(..) |
                 def::DefLocal(..) => LvalueExpr,
 
-                def::DefConst(..) => RvalueDatumExpr,
+                def::DefConst(..) |
+                def::DefAssociatedConst(..) => RvalueDatumExpr,
 
                 def => {
                     tcx.sess.span_bug(
@@ -4890,6 +4948,9 @@ pub fn expr_kind(tcx: &ctxt, expr: &ast::Expr) -> ExprKind {
                 expr.span,
                 "macro expression remains after expansion");
         }
+
+        // Left behind by a recovered syntax error; it has the error type.
+        ast::ExprError => RvalueDatumExpr,
     }
 }
 
@@ -5143,10 +5204,10 @@ pub fn provided_trait_methods<'tcx>(cx: &ctxt<'tcx>, id: ast::DefId)
                 if let ast::MethodTraitItem(_, Some(_)) = ti.node {
                     match impl_or_trait_item(cx, ast_util::local_def(ti.id)) {
                         MethodTraitItem(m) => Some(m),
-                        TypeTraitItem(_) => {
+                        _ => {
                             cx.sess.bug("provided_trait_methods(): \
-                                         associated type found from \
-                                         looking up ProvidedMethod?!")
+                                         non-method item found from \
+                                         looking up provided method?!")
                         }
                     }
                 } else {
@@ -5161,6 +5222,22 @@ pub fn provided_trait_methods<'tcx>(cx: &ctxt<'tcx>, id: ast::DefId)
     }
 }
 
+/// Returns the associated constants declared by the trait or impl `id`.
+pub fn associated_consts<'tcx>(cx: &ctxt<'tcx>, id: ast::DefId)
+                               -> Vec<Rc<AssociatedConst<'tcx>>> {
+    let impl_items = cx.impl_items.borrow().get(&id).cloned();
+    let item_ids = match impl_items {
+        Some(item_ids) => item_ids,
+        None => (*trait_item_def_ids(cx, id)).clone()
+    };
+    item_ids.iter().filter_map(|item_id| {
+        match impl_or_trait_item(cx, item_id.def_id()) {
+            ConstTraitItem(ac) => Some(ac),
+            _ => None
+        }
+    }).collect()
+}
+
 /// Helper for looking things up in the various maps that are populated during
 /// typeck::collect (e.g., `cx.impl_or_trait_items`, `cx.tcache`, etc).  All of
 /// these share the pattern that if the id is local, it should have been loaded
@@ -5247,7 +5324,7 @@ pub fn is_associated_type(cx: &ctxt, id: ast::DefId) -> bool {
                 Some(ref item) => {
                     match **item {
                         TypeTraitItem(_) => true,
-                        MethodTraitItem(_) => false,
+                        _ => false,
                     }
                 }
                 None => false,
@@ -5376,7 +5453,7 @@ impl<'tcx> VariantInfo<'tcx> {
                     name: ast_variant.node.name.name,
                     id: ast_util::local_def(ast_variant.node.id),
                     disr_val: discriminant,
-                    vis: ast_variant.node.vis
+                    vis: ast_variant.node.vis.clone()
                 };
             },
             ast::StructVariantKind(ref struct_def) => {
@@ -5401,7 +5478,7 @@ impl<'tcx> VariantInfo<'tcx> {
                     name: ast_variant.node.name.name,
                     id: ast_util::local_def(ast_variant.node.id),
                     disr_val: discriminant,
-                    vis: ast_variant.node.vis
+                    vis: ast_variant.node.vis.clone()
                 };
             }
         }
@@ -5499,6 +5576,9 @@ trait IntTypeExt {
     fn disr_wrap_incr(&self, val: Option<Disr>) -> Disr;
 }
 
+// The discriminant methods below never see the 128-bit types, which
+// `#[repr]` does not accept, or the pointer-sized ones, which are
+// normalized away by `enum_repr_type`.
 impl IntTypeExt for attr::IntType {
     fn to_ty<'tcx>(&self, cx: &ctxt<'tcx>) -> Ty<'tcx> {
         match *self {
@@ -5506,11 +5586,13 @@ impl IntTypeExt for attr::IntType {
             SignedInt(ast::TyI16)     => cx.types.i16,
             SignedInt(ast::TyI32)     => cx.types.i32,
             SignedInt(ast::TyI64)     => cx.types.i64,
+            SignedInt(ast::TyI128)    => cx.types.i128,
             SignedInt(ast::TyIs)   => cx.types.isize,
             UnsignedInt(ast::TyU8)    => cx.types.u8,
             UnsignedInt(ast::TyU16)   => cx.types.u16,
             UnsignedInt(ast::TyU32)   => cx.types.u32,
             UnsignedInt(ast::TyU64)   => cx.types.u64,
+            UnsignedInt(ast::TyU128)  => cx.types.u128,
             UnsignedInt(ast::TyUs) => cx.types.usize,
         }
     }
@@ -5526,8 +5608,8 @@ impl IntTypeExt for attr::IntType {
             UnsignedInt(ast::TyU32) => val.to_u32() .map(|v| v as Disr),
             UnsignedInt(ast::TyU64) => val.to_u64() .map(|v| v as Disr),
 
-            UnsignedInt(ast::TyUs) |
-            SignedInt(ast::TyIs) => unreachable!(),
+            UnsignedInt(ast::TyU128) | SignedInt(ast::TyI128) |
+            UnsignedInt(ast::TyUs) | SignedInt(ast::TyIs) => unreachable!(),
         }
     }
 
@@ -5542,8 +5624,8 @@ impl IntTypeExt for attr::IntType {
             UnsignedInt(ast::TyU32) => val.to_u32() .map(|v| v as Disr),
             UnsignedInt(ast::TyU64) => val.to_u64() .map(|v| v as Disr),
 
-            UnsignedInt(ast::TyUs) |
-            SignedInt(ast::TyIs) => unreachable!(),
+            UnsignedInt(ast::TyU128) | SignedInt(ast::TyI128) |
+            UnsignedInt(ast::TyUs) | SignedInt(ast::TyIs) => unreachable!(),
         }
     }
 
@@ -5565,8 +5647,8 @@ impl IntTypeExt for attr::IntType {
             UnsignedInt(ast::TyU32) => add1!(val.to_u32()),
             UnsignedInt(ast::TyU64) => add1!(Some(val)),
 
-            UnsignedInt(ast::TyUs) |
-            SignedInt(ast::TyIs) => unreachable!(),
+            UnsignedInt(ast::TyU128) | SignedInt(ast::TyI128) |
+            UnsignedInt(ast::TyUs) | SignedInt(ast::TyIs) => unreachable!(),
         }
     }
 
@@ -5584,8 +5666,8 @@ impl IntTypeExt for attr::IntType {
             UnsignedInt(ast::TyU32) => format!("{}", val as u32),
             UnsignedInt(ast::TyU64) => format!("{}", val as u64),
 
-            UnsignedInt(ast::TyUs) |
-            SignedInt(ast::TyIs) => unreachable!(),
+            UnsignedInt(ast::TyU128) | SignedInt(ast::TyI128) |
+            UnsignedInt(ast::TyUs) | SignedInt(ast::TyIs) => unreachable!(),
         }
     }
 
@@ -5604,8 +5686,8 @@ impl IntTypeExt for attr::IntType {
             UnsignedInt(ast::TyU32) => add1!(val as u32),
             UnsignedInt(ast::TyU64) => add1!(val as u64),
 
-            UnsignedInt(ast::TyUs) |
-            SignedInt(ast::TyIs) => unreachable!(),
+            UnsignedInt(ast::TyU128) | SignedInt(ast::TyI128) |
+            UnsignedInt(ast::TyUs) | SignedInt(ast::TyIs) => unreachable!(),
         }
     }
 }
@@ -5698,8 +5780,8 @@ fn compute_enum_variants<'tcx>(cx: &ctxt<'tcx>,
                 // more robust (on case-by-case basis).
 
                 match const_eval::eval_const_expr_partial(cx, &**e, Some(repr_type_ty)) {
-                    Ok(const_eval::const_int(val)) => current_disr_val = val as Disr,
-                    Ok(const_eval::const_uint(val)) => current_disr_val = val as Disr,
+                    Ok(const_eval::const_int(val)) => current_disr_val = val.as_u64() as Disr,
+                    Ok(const_eval::const_uint(val)) => current_disr_val = val.as_u64() as Disr,
                     Ok(_) => {
                         span_err!(cx.sess, e.span, E0079,
                                   "expected signed integer constant");
@@ -6023,8 +6105,10 @@ pub fn eval_repeat_count(tcx: &ctxt, count_expr: &ast::Expr) -> usize {
     match const_eval::eval_const_expr_partial(tcx, count_expr, Some(tcx.types.usize)) {
         Ok(val) => {
             let found = match val {
-                const_eval::const_uint(count) => return count as usize,
-                const_eval::const_int(count) if count >= 0 => return count as usize,
+                const_eval::const_uint(count) => return count.as_u64() as usize,
+                const_eval::const_int(count) if !count.is_negative() => {
+                    return count.as_u64() as usize
+                }
                 const_eval::const_int(_) => "negative integer",
                 const_eval::const_float(_) => "float",
                 const_eval::const_str(_) => "string",
@@ -6220,7 +6304,7 @@ pub fn populate_implementations_for_type_if_necessary(tcx: &ctxt,
                            .insert(method_def_id, source);
                     }
                 }
-                TypeTraitItem(_) => {}
+                _ => {}
             }
         }
 
@@ -6272,7 +6356,7 @@ pub fn populate_implementations_for_trait_if_necessary(
                             .insert(method_def_id, source);
                     }
                 }
-                TypeTraitItem(_) => {}
+                _ => {}
             }
         }
 
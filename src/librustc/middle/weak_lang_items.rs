@@ -77,21 +77,50 @@ fn verify(sess: &Session, items: &lang_items::LanguageItems) {
     if !needs_check { return }
 
     let mut missing = HashSet::new();
-    sess.cstore.iter_crate_data(|cnum, _| {
+    let mut unwinds = sess.panic_strategy() == config::PanicStrategy::Unwind;
+    sess.cstore.iter_crate_data(|cnum, data| {
         for item in &csearch::get_missing_lang_items(&sess.cstore, cnum) {
             missing.insert(*item);
         }
+        unwinds = unwinds || data.panic_strategy() == config::PanicStrategy::Unwind;
     });
 
+    // Only landing pads refer to the personality function, and there are
+    // none if everything is compiled with `-C panic=abort`.
+    if !unwinds {
+        missing.remove(&lang_items::EhPersonalityLangItem);
+    }
+
     $(
         if missing.contains(&lang_items::$item) && items.$name().is_none() {
             sess.err(&format!("language item required, but not found: `{}`",
                               stringify!($name)));
-
+            sess.help(missing_help(lang_items::$item));
         }
     )*
 }
 
+// Without `std` a crate has to provide these itself, explain how.
+fn missing_help(item: lang_items::LangItem) -> &'static str {
+    match item {
+        lang_items::PanicFmtLangItem => {
+            "`#![no_std]` programs have to define what happens on a panic with \
+             `#[lang = \"panic_fmt\"] extern fn panic_fmt(msg: core::fmt::Arguments, \
+             file: &'static str, line: usize) -> !`"
+        }
+        lang_items::StackExhaustedLangItem => {
+            "`#![no_std]` programs have to define what happens on a stack overflow \
+             with `#[lang = \"stack_exhausted\"] extern fn stack_exhausted()`"
+        }
+        lang_items::EhPersonalityLangItem => {
+            "`#![no_std]` programs have to define the personality routine used to \
+             unwind with `#[lang = \"eh_personality\"] extern fn eh_personality()`, \
+             unless they and all their dependencies are built with `-C panic=abort`"
+        }
+        _ => unreachable!(),
+    }
+}
+
 impl<'a> Context<'a> {
     fn register(&mut self, name: &str, span: Span) {
         $(if name == stringify!($name) {
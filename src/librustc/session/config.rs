@@ -37,6 +37,7 @@ use getopts;
 use std::collections::HashMap;
 use std::env;
 use std::fmt;
+use std::fs::PathExt;
 use std::path::PathBuf;
 
 use llvm;
@@ -62,6 +63,95 @@ pub enum DebugInfoLevel {
     FullDebugInfo,
 }
 
+/// How a panic is carried out once it has been raised.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PanicStrategy {
+    /// Unwind the stack, running destructors, until the panic is caught.
+    Unwind,
+    /// Abort the process immediately.
+    Abort,
+}
+
+impl PanicStrategy {
+    pub fn desc(&self) -> &'static str {
+        match *self {
+            PanicStrategy::Unwind => "unwind",
+            PanicStrategy::Abort => "abort",
+        }
+    }
+}
+
+/// A runtime error detector that code can be instrumented for.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Sanitizer {
+    /// AddressSanitizer, for out-of-bounds accesses and use-after-free.
+    Address,
+    /// LeakSanitizer, for memory leaks. This only needs the runtime.
+    Leak,
+    /// MemorySanitizer, for reads of uninitialized memory.
+    Memory,
+    /// ThreadSanitizer, for data races.
+    Thread,
+}
+
+impl Sanitizer {
+    pub fn desc(&self) -> &'static str {
+        match *self {
+            Sanitizer::Address => "address",
+            Sanitizer::Leak => "leak",
+            Sanitizer::Memory => "memory",
+            Sanitizer::Thread => "thread",
+        }
+    }
+}
+
+/// The scheme used to turn item paths into symbol names.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SymbolManglingVersion {
+    /// C++-style `_ZN...E` names, ending in an opaque hash.
+    Legacy,
+    /// `_R` names which also encode the crate, generic arguments and impls,
+    /// see `librustc_demangle` for the grammar.
+    V1,
+}
+
+/// Where DWARF debuginfo ends up, see `-C split-debuginfo`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SplitDebuginfo {
+    /// In the object files, and from there in the linked output.
+    Off,
+    /// In a `.dwo` file per object file, which only keeps a skeleton
+    /// pointing at it. The linker never gets to see the bulk of it.
+    Unpacked,
+    /// Like `Unpacked`, but the `.dwo` files of a linked executable or dylib
+    /// are then packaged into one `.dwp` file next to it.
+    Packed,
+}
+
+/// How `-C lto` optimizes across crates.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lto {
+    /// No link-time optimization.
+    No,
+    /// Merge the crate and all its dependencies into one LLVM module and
+    /// optimize that single-threaded.
+    Fat,
+    /// Merge everything, run the global passes once, then split the result
+    /// into several partitions per codegen unit which are optimized and
+    /// code-generated in parallel, each with copies of the small functions
+    /// it calls from the others.
+    Parallel,
+}
+
+/// How `-C remark` prints optimization remarks.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RemarkFormat {
+    /// As notes, pointing at the source the remark is about.
+    Human,
+    /// As one JSON object per line on stderr, for tools to pick up.
+    Json,
+}
+
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
 pub enum OutputType {
     OutputTypeBitcode,
@@ -116,7 +206,9 @@ pub struct Options {
     /// out-of-tree drivers.
     pub alt_std_name: Option<String>,
     /// Indicates how the compiler should treat unstable features
-    pub unstable_features: UnstableFeatures
+    pub unstable_features: UnstableFeatures,
+    /// Source path prefixes to rewrite in compiler output, as `(from, to)`.
+    pub remap_path_prefix: Vec<(String, String)>,
 }
 
 #[derive(Clone, Copy)]
@@ -134,11 +226,16 @@ pub enum UnstableFeatures {
     Cheat
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub enum PrintRequest {
     FileNames,
     Sysroot,
     CrateName,
+    Cfg,
+    TargetList,
+    TargetSpec,
+    /// Printed while linking a staticlib rather than instead of compiling.
+    NativeStaticLibs,
 }
 
 pub enum Input {
@@ -240,6 +337,7 @@ pub fn basic_options() -> Options {
         libs: Vec::new(),
         unstable_features: UnstableFeatures::Disallow,
         debug_assertions: true,
+        remap_path_prefix: Vec::new(),
     }
 }
 
@@ -355,6 +453,7 @@ macro_rules! options {
             Some("one of: `y`, `yes`, `on`, `n`, `no`, or `off`");
         pub const parse_string: Option<&'static str> = Some("a string");
         pub const parse_opt_string: Option<&'static str> = Some("a string");
+        pub const parse_opt_path: Option<&'static str> = Some("a path");
         pub const parse_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_opt_list: Option<&'static str> = Some("a space-separated list of strings");
         pub const parse_uint: Option<&'static str> = Some("a number");
@@ -362,11 +461,25 @@ macro_rules! options {
             Some("a space-separated list of passes, or `all`");
         pub const parse_opt_uint: Option<&'static str> =
             Some("a number");
+        pub const parse_panic_strategy: Option<&'static str> =
+            Some("either `unwind` or `abort`");
+        pub const parse_sanitizer: Option<&'static str> =
+            Some("one of: `address`, `leak`, `memory` or `thread`");
+        pub const parse_symbol_mangling_version: Option<&'static str> =
+            Some("either `legacy` or `v1`");
+        pub const parse_split_debuginfo: Option<&'static str> =
+            Some("one of: `off`, `unpacked` or `packed`");
+        pub const parse_lto: Option<&'static str> =
+            Some("one of: `y`, `yes`, `on`, `fat`, `parallel`, `n`, `no`, or `off`");
+        pub const parse_remark_format: Option<&'static str> =
+            Some("either `human` or `json`");
     }
 
     #[allow(dead_code)]
     mod $mod_set {
-        use super::{$struct_name, Passes, SomePasses, AllPasses};
+        use super::{$struct_name, Passes, SomePasses, AllPasses, PanicStrategy};
+        use super::{Lto, RemarkFormat, Sanitizer, SplitDebuginfo, SymbolManglingVersion};
+        use std::path::PathBuf;
 
         $(
             pub fn $opt(cg: &mut $struct_name, v: Option<&str>) -> bool {
@@ -407,6 +520,13 @@ macro_rules! options {
             }
         }
 
+        fn parse_opt_path(slot: &mut Option<PathBuf>, v: Option<&str>) -> bool {
+            match v {
+                Some(s) => { *slot = Some(PathBuf::from(s)); true },
+                None => false,
+            }
+        }
+
         fn parse_string(slot: &mut String, v: Option<&str>) -> bool {
             match v {
                 Some(s) => { *slot = s.to_string(); true },
@@ -470,6 +590,65 @@ macro_rules! options {
                 }
             }
         }
+
+        fn parse_panic_strategy(slot: &mut PanicStrategy, v: Option<&str>) -> bool {
+            match v {
+                Some("unwind") => *slot = PanicStrategy::Unwind,
+                Some("abort") => *slot = PanicStrategy::Abort,
+                _ => return false
+            }
+            true
+        }
+
+        fn parse_sanitizer(slot: &mut Option<Sanitizer>, v: Option<&str>) -> bool {
+            match v {
+                Some("address") => *slot = Some(Sanitizer::Address),
+                Some("leak") => *slot = Some(Sanitizer::Leak),
+                Some("memory") => *slot = Some(Sanitizer::Memory),
+                Some("thread") => *slot = Some(Sanitizer::Thread),
+                _ => return false
+            }
+            true
+        }
+
+        fn parse_symbol_mangling_version(slot: &mut SymbolManglingVersion,
+                                         v: Option<&str>) -> bool {
+            match v {
+                Some("legacy") => *slot = SymbolManglingVersion::Legacy,
+                Some("v1") => *slot = SymbolManglingVersion::V1,
+                _ => return false
+            }
+            true
+        }
+
+        fn parse_split_debuginfo(slot: &mut SplitDebuginfo, v: Option<&str>) -> bool {
+            match v {
+                Some("off") => *slot = SplitDebuginfo::Off,
+                Some("unpacked") => *slot = SplitDebuginfo::Unpacked,
+                Some("packed") => *slot = SplitDebuginfo::Packed,
+                _ => return false
+            }
+            true
+        }
+
+        fn parse_lto(slot: &mut Lto, v: Option<&str>) -> bool {
+            match v {
+                None | Some("y") | Some("yes") | Some("on") | Some("fat") => *slot = Lto::Fat,
+                Some("n") | Some("no") | Some("off") => *slot = Lto::No,
+                Some("parallel") => *slot = Lto::Parallel,
+                _ => return false
+            }
+            true
+        }
+
+        fn parse_remark_format(slot: &mut RemarkFormat, v: Option<&str>) -> bool {
+            match v {
+                Some("human") => *slot = RemarkFormat::Human,
+                Some("json") => *slot = RemarkFormat::Json,
+                _ => return false
+            }
+            true
+        }
     }
 ) }
 
@@ -477,13 +656,16 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
          build_codegen_options, "C", "codegen",
          CG_OPTIONS, cg_type_desc, cgsetters,
     ar: Option<String> = (None, parse_opt_string,
-        "tool to assemble archives with"),
+        "this option is deprecated and does nothing"),
     linker: Option<String> = (None, parse_opt_string,
         "system linker to link outputs with"),
     link_args: Option<Vec<String>> = (None, parse_opt_list,
         "extra arguments to pass to the linker (space separated)"),
-    lto: bool = (false, parse_bool,
-        "perform LLVM link-time optimizations"),
+    link_script: Option<PathBuf> = (None, parse_opt_path,
+        "linker script laying out the output, for freestanding targets"),
+    lto: Lto = (Lto::No, parse_lto,
+        "perform LLVM link-time optimizations, `parallel` to spread the work \
+         over the codegen units"),
     target_cpu: Option<String> = (None, parse_opt_string,
         "select target processor (llc -mcpu=help for details)"),
     target_feature: String = ("".to_string(), parse_string,
@@ -522,6 +704,11 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "divide crate into N units to optimize in parallel"),
     remark: Passes = (SomePasses(Vec::new()), parse_passes,
         "print remarks for these optimization passes (space separated, or \"all\")"),
+    remark_filter: Vec<String> = (Vec::new(), parse_list,
+        "only print remarks about functions whose paths start with one of these \
+         (space separated)"),
+    remark_format: RemarkFormat = (RemarkFormat::Human, parse_remark_format,
+        "print remarks as `human` readable notes or as `json`"),
     no_stack_check: bool = (false, parse_bool,
         "disable checks for stack exhaustion (a memory-safety hazard!)"),
     debuginfo: Option<usize> = (None, parse_opt_uint,
@@ -531,6 +718,15 @@ options! {CodegenOptions, CodegenSetter, basic_codegen_options,
         "Optimize with possible levels 0-3"),
     debug_assertions: Option<bool> = (None, parse_opt_bool,
         "explicitly enable the cfg(debug_assertions) directive"),
+    panic: PanicStrategy = (PanicStrategy::Unwind, parse_panic_strategy,
+        "panic strategy to compile crate with"),
+    profile_generate: Option<PathBuf> = (None, parse_opt_path,
+        "instrument the generated code to write execution profiles into this directory"),
+    profile_use: Option<PathBuf> = (None, parse_opt_path,
+        "use the given merged profile data file for profile-guided optimization"),
+    split_debuginfo: SplitDebuginfo = (SplitDebuginfo::Off, parse_split_debuginfo,
+        "move DWARF debuginfo out of the linked output into .dwo files (`unpacked`), \
+         optionally packaged into a .dwp file (`packed`)"),
 }
 
 
@@ -553,6 +749,8 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "skip LLVM verification"),
     borrowck_stats: bool = (false, parse_bool,
         "gather borrowck statistics"),
+    nll: bool = (false, parse_bool,
+        "end loans at the last use of the borrowed reference (non-lexical lifetimes)"),
     no_landing_pads: bool = (false, parse_bool,
         "omit landing pads for unwinding"),
     debug_llvm: bool = (false, parse_bool,
@@ -606,6 +804,15 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "Force overflow checks on or off"),
     force_dropflag_checks: Option<bool> = (None, parse_opt_bool,
           "Force drop flag checks on or off"),
+    sanitizer: Option<Sanitizer> = (None, parse_sanitizer,
+          "Instrument the generated code with a sanitizer"),
+    profile: bool = (false, parse_bool,
+          "Insert profiling code to collect gcov coverage data; implies -C debuginfo=1"),
+    symbol_mangling_version: SymbolManglingVersion = (SymbolManglingVersion::Legacy,
+          parse_symbol_mangling_version,
+          "Which scheme to use for mangling symbol names"),
+    icf: bool = (false, parse_bool,
+          "Fold identical functions when linking (needs a linker like gold or lld)"),
 }
 
 pub fn default_lib_output() -> CrateType {
@@ -619,6 +826,7 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
     let arch = &sess.target.target.arch;
     let wordsz = &sess.target.target.target_pointer_width;
     let os = &sess.target.target.target_os;
+    let env = &sess.target.target.target_env;
 
     let fam = match sess.target.target.options.is_like_windows {
         true  => InternedString::new("windows"),
@@ -630,6 +838,7 @@ pub fn default_configuration(sess: &Session) -> ast::CrateConfig {
          attr::mk_word_item(fam.clone()),
          mk(InternedString::new("target_os"), intern(os)),
          mk(InternedString::new("target_family"), fam),
+         mk(InternedString::new("target_env"), intern(env)),
          mk(InternedString::new("target_arch"), intern(arch)),
          mk(InternedString::new("target_endian"), intern(end)),
          mk(InternedString::new("target_pointer_width"),
@@ -782,7 +991,8 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                  "[asm|llvm-bc|llvm-ir|obj|link|dep-info]"),
         opt::multi("", "print", "Comma separated list of compiler information to \
                                print on stdout",
-                 "[crate-name|file-names|sysroot]"),
+                 "[crate-name|file-names|sysroot|cfg|target-list|target-spec-json|\
+                   native-static-libs]"),
         opt::flag("g",  "",  "Equivalent to -C debuginfo=2"),
         opt::flag("O", "", "Equivalent to -C opt-level=2"),
         opt::opt("o", "", "Write output to <filename>", "FILENAME"),
@@ -816,6 +1026,10 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
                                 located",
                  "NAME=PATH"),
         opt::opt("", "sysroot", "Override the system root", "PATH"),
+        opt::multi("", "remap-path-prefix", "Remap source path prefixes in all output, \
+                                           including debuginfo, panic messages and \
+                                           crate metadata",
+                 "FROM=TO"),
         opt::multi("Z", "", "Set internal debugging options", "FLAG"),
         opt::opt("", "color", "Configure coloring of output:
             auto   = colorize, if output goes to a tty (default);
@@ -832,7 +1046,8 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
         opt::flagopt_u("", "xpretty",
                      "Pretty-print the input instead of compiling, unstable variants;
                       valid types are any of the types for `--pretty`, as well as:
-                      `flowgraph=<nodeid>` (graphviz formatted flowgraph for node), or
+                      `flowgraph=<nodeid>` (graphviz formatted flowgraph for node),
+                      `ir=<nodeid>` (basic-block IR for node; `ir,dot` for graphviz), or
                       `everybody_loops` (all function bodies replaced with `loop {}`).",
                      "TYPE"),
         opt::opt_u("", "show-span", "Show spans for compiler debugging", "expr|pat|ty"),
@@ -952,6 +1167,28 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         }
     };
 
+    // The coverage notes are built from the line tables.
+    let debuginfo = if debugging_opts.profile && debuginfo == NoDebugInfo {
+        LimitedDebugInfo
+    } else {
+        debuginfo
+    };
+
+    if cg.profile_generate.is_some() && cg.profile_use.is_some() {
+        early_error("-C profile-generate and -C profile-use both provided");
+    }
+    if let Some(ref path) = cg.profile_use {
+        if !path.is_file() {
+            early_error(&format!("profile data file `{}` does not exist",
+                                 path.display()));
+        }
+    }
+    if (cg.profile_generate.is_some() || cg.profile_use.is_some()) &&
+       opt_level == No {
+        early_warn("-C profile-generate and -C profile-use have no effect \
+                    without optimizations");
+    }
+
     let mut search_paths = SearchPaths::new();
     for s in &matches.opt_strs("L") {
         search_paths.add_path(&s[..]);
@@ -983,6 +1220,16 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
             "crate-name" => PrintRequest::CrateName,
             "file-names" => PrintRequest::FileNames,
             "sysroot" => PrintRequest::Sysroot,
+            "cfg" => PrintRequest::Cfg,
+            "target-list" => PrintRequest::TargetList,
+            "target-spec-json" => {
+                if !debugging_opts.unstable_options {
+                    early_error("the `-Z unstable-options` flag must also be passed to \
+                                 enable the target-spec-json print option");
+                }
+                PrintRequest::TargetSpec
+            }
+            "native-static-libs" => PrintRequest::NativeStaticLibs,
             req => {
                 early_error(&format!("unknown print request `{}`", req))
             }
@@ -1023,6 +1270,14 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         externs.entry(name.to_string()).or_insert(vec![]).push(location.to_string());
     }
 
+    let remap_path_prefix = matches.opt_strs("remap-path-prefix").iter().map(|arg| {
+        let mut parts = arg.splitn(2, '=');
+        match (parts.next(), parts.next()) {
+            (Some(from), Some(to)) => (from.to_string(), to.to_string()),
+            _ => early_error("--remap-path-prefix value must be of the format `from=to`"),
+        }
+    }).collect();
+
     let crate_name = matches.opt_str("crate-name");
 
     Options {
@@ -1054,6 +1309,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         libs: libs,
         unstable_features: get_unstable_features_setting(),
         debug_assertions: debug_assertions,
+        remap_path_prefix: remap_path_prefix,
     }
 }
 
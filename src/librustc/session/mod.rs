@@ -115,6 +115,12 @@ impl Session {
     pub fn abort_if_errors(&self) {
         self.diagnostic().handler().abort_if_errors()
     }
+    pub fn tolerate_errors(&self) {
+        self.diagnostic().handler().tolerate_errors()
+    }
+    pub fn abort_if_any_errors(&self) {
+        self.diagnostic().handler().abort_if_any_errors()
+    }
     pub fn span_warn(&self, sp: Span, msg: &str) {
         if self.can_print_warnings {
             self.diagnostic().span_warn(sp, msg)
@@ -226,10 +232,23 @@ impl Session {
         self.opts.debugging_opts.print_llvm_passes
     }
     pub fn lto(&self) -> bool {
-        self.opts.cg.lto
+        self.opts.cg.lto != config::Lto::No
     }
     pub fn no_landing_pads(&self) -> bool {
-        self.opts.debugging_opts.no_landing_pads
+        self.opts.debugging_opts.no_landing_pads ||
+            self.opts.cg.panic == config::PanicStrategy::Abort
+    }
+    pub fn panic_strategy(&self) -> config::PanicStrategy {
+        self.opts.cg.panic
+    }
+    /// How debuginfo is split out of the objects, `Off` when there is no
+    /// debuginfo to begin with.
+    pub fn split_debuginfo(&self) -> config::SplitDebuginfo {
+        if self.opts.debuginfo == config::NoDebugInfo {
+            config::SplitDebuginfo::Off
+        } else {
+            self.opts.cg.split_debuginfo
+        }
     }
     pub fn unstable_options(&self) -> bool {
         self.opts.debugging_opts.unstable_options
@@ -333,7 +352,8 @@ pub fn build_session(sopts: config::Options,
         .last()
         .unwrap_or(true);
 
-    let codemap = codemap::CodeMap::new();
+    let path_mapping = codemap::FilePathMapping::new(sopts.remap_path_prefix.clone());
+    let codemap = codemap::CodeMap::with_file_path_mapping(path_mapping);
     let diagnostic_handler =
         diagnostic::default_handler(sopts.color, Some(registry), can_print_warnings);
     let span_diagnostic_handler =
@@ -354,6 +374,32 @@ pub fn build_session_(sopts: config::Options,
     }
     };
     let target_cfg = config::build_target_config(&sopts, &span_diagnostic);
+
+    // The sanitizer runtimes are only built for this one target, and only if
+    // cmake was available when the compiler was configured.
+    if let Some(sanitizer) = sopts.debugging_opts.sanitizer {
+        if sopts.target_triple != "x86_64-unknown-linux-gnu" {
+            span_diagnostic.handler()
+                .fatal(&format!("the {} sanitizer is not supported for target `{}`",
+                                sanitizer.desc(), sopts.target_triple));
+        }
+        if option_env!("CFG_DISABLE_SANITIZERS").is_some() {
+            span_diagnostic.handler()
+                .fatal(&format!("the {} sanitizer runtime was not built with this \
+                                 compiler; it needs to be configured with cmake \
+                                 available and without --disable-sanitizers",
+                                sanitizer.desc()));
+        }
+    }
+
+    // Split DWARF relies on the ELF .dwo sections and the tools handling them.
+    if sopts.cg.split_debuginfo != config::SplitDebuginfo::Off &&
+       (target_cfg.target.options.is_like_osx || target_cfg.target.options.is_like_windows) {
+        span_diagnostic.handler()
+            .fatal(&format!("split debuginfo is not supported for target `{}`",
+                            sopts.target_triple));
+    }
+
     let p_s = parse::new_parse_sess_special_handler(span_diagnostic);
     let default_sysroot = match sopts.maybe_sysroot {
         Some(_) => None,
@@ -398,6 +444,11 @@ pub fn build_session_(sopts: config::Options,
         can_print_warnings: can_print_warnings
     };
 
+    if sess.opts.cg.ar.is_some() {
+        sess.warn("`-C ar` is deprecated and ignored; archives are now built \
+                   by the compiler itself");
+    }
+
     sess
 }
 
@@ -823,6 +823,7 @@ impl<'tcx> Repr<'tcx> for ty::TraitDef<'tcx> {
 impl<'tcx> Repr<'tcx> for ast::TraitItem {
     fn repr(&self, _tcx: &ctxt) -> String {
         let kind = match self.node {
+            ast::ConstTraitItem(..) => "ConstTraitItem",
             ast::MethodTraitItem(..) => "MethodTraitItem",
             ast::TypeTraitItem(..) => "TypeTraitItem",
         };
@@ -1059,6 +1060,30 @@ impl<'tcx> Repr<'tcx> for ty::Method<'tcx> {
     }
 }
 
+impl<'tcx> Repr<'tcx> for ty::AssociatedConst<'tcx> {
+    fn repr(&self, tcx: &ctxt<'tcx>) -> String {
+        format!("AssociatedConst(name: {}, ty: {}, vis: {}, def_id: {})",
+                self.name.repr(tcx),
+                self.ty.repr(tcx),
+                self.vis.repr(tcx),
+                self.def_id.repr(tcx))
+    }
+}
+
+impl<'tcx> Repr<'tcx> for ty::ImplOrTraitItem<'tcx> {
+    fn repr(&self, tcx: &ctxt<'tcx>) -> String {
+        match *self {
+            ty::ConstTraitItem(ref associated_const) => associated_const.repr(tcx),
+            ty::MethodTraitItem(ref method) => method.repr(tcx),
+            ty::TypeTraitItem(ref associated_type) => {
+                format!("AssociatedType(name: {}, def_id: {})",
+                        associated_type.name.repr(tcx),
+                        associated_type.def_id.repr(tcx))
+            }
+        }
+    }
+}
+
 impl<'tcx> Repr<'tcx> for ast::Name {
     fn repr(&self, _tcx: &ctxt) -> String {
         token::get_name(*self).to_string()
@@ -9,27 +9,49 @@
 // except according to those terms.
 
 //! A helper class for dealing with static archives
-
-use std::env;
-use std::fs;
+//!
+//! Archives are read and written directly by the compiler instead of by
+//! shelling out to `ar`. Both the GNU (System V) format and the BSD format
+//! used on OSX are supported, including their symbol tables. Every member is
+//! written with a zero timestamp, owner and group, so building the same
+//! inputs twice produces byte-for-byte identical archives.
+
+use std::fs::{self, File, PathExt};
 use std::io::prelude::*;
 use std::io;
+use std::iter::AdditiveIterator;
 use std::path::{Path, PathBuf};
-use std::process::{Command, Output, Stdio};
 use std::str;
+use std::u32;
 use syntax::diagnostic::Handler as ErrorHandler;
 
-use tempdir::TempDir;
+use llvm;
 
 pub const METADATA_FILENAME: &'static str = "rust.metadata.bin";
 
+const ARCHIVE_MAGIC: &'static [u8] = b"!<arch>\n";
+const THIN_ARCHIVE_MAGIC: &'static [u8] = b"!<thin>\n";
+const HEADER_LEN: usize = 60;
+
+/// The on-disk format of a static archive.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArchiveKind {
+    /// The System V format written by GNU `ar`, with a `/` symbol table and a
+    /// `//` table of long member names.
+    Gnu,
+    /// The format written by BSD `ar`, with a `__.SYMDEF` symbol table and
+    /// long member names stored in front of the member's contents. This is
+    /// what the OSX linker expects.
+    Bsd,
+}
+
 pub struct ArchiveConfig<'a> {
     pub handler: &'a ErrorHandler,
     pub dst: PathBuf,
     pub lib_search_paths: Vec<PathBuf>,
     pub slib_prefix: String,
     pub slib_suffix: String,
-    pub maybe_ar_prog: Option<String>
+    pub kind: ArchiveKind,
 }
 
 pub struct Archive<'a> {
@@ -38,61 +60,23 @@ pub struct Archive<'a> {
     lib_search_paths: Vec<PathBuf>,
     slib_prefix: String,
     slib_suffix: String,
-    maybe_ar_prog: Option<String>
+    kind: ArchiveKind,
 }
 
-/// Helper for adding many files to an archive with a single invocation of
-/// `ar`.
+/// Helper for adding many files to an archive, which is written out in one go
+/// by `build`.
 #[must_use = "must call build() to finish building the archive"]
 pub struct ArchiveBuilder<'a> {
     archive: Archive<'a>,
-    work_dir: TempDir,
-    /// Filename of each member that should be added to the archive.
-    members: Vec<PathBuf>,
+    /// Every member of the archive, in the order they will be written.
+    members: Vec<Member>,
     should_update_symbols: bool,
 }
 
-fn run_ar(handler: &ErrorHandler, maybe_ar_prog: &Option<String>,
-          args: &str, cwd: Option<&Path>,
-          paths: &[&Path]) -> Output {
-    let ar = match *maybe_ar_prog {
-        Some(ref ar) => &ar[..],
-        None => "ar"
-    };
-    let mut cmd = Command::new(ar);
-
-    cmd.arg(args).args(paths).stdout(Stdio::piped()).stderr(Stdio::piped());
-    debug!("{:?}", cmd);
-
-    match cwd {
-        Some(p) => {
-            cmd.current_dir(p);
-            debug!("inside {:?}", p.display());
-        }
-        None => {}
-    }
-
-    match cmd.spawn() {
-        Ok(prog) => {
-            let o = prog.wait_with_output().unwrap();
-            if !o.status.success() {
-                handler.err(&format!("{:?} failed with: {}", cmd, o.status));
-                handler.note(&format!("stdout ---\n{}",
-                                  str::from_utf8(&o.stdout).unwrap()));
-                handler.note(&format!("stderr ---\n{}",
-                                  str::from_utf8(&o.stderr).unwrap())
-                             );
-                handler.abort_if_errors();
-            }
-            o
-        },
-        Err(e) => {
-            handler.err(&format!("could not exec `{}`: {}", &ar[..],
-                             e));
-            handler.abort_if_errors();
-            panic!("rustc::back::archive::run_ar() should not reach this point");
-        }
-    }
+/// A file stored in an archive.
+struct Member {
+    name: String,
+    data: Vec<u8>,
 }
 
 pub fn find_library(name: &str, osprefix: &str, ossuffix: &str,
@@ -120,14 +104,14 @@ pub fn find_library(name: &str, osprefix: &str, ossuffix: &str,
 impl<'a> Archive<'a> {
     fn new(config: ArchiveConfig<'a>) -> Archive<'a> {
         let ArchiveConfig { handler, dst, lib_search_paths, slib_prefix, slib_suffix,
-            maybe_ar_prog } = config;
+            kind } = config;
         Archive {
             handler: handler,
             dst: dst,
             lib_search_paths: lib_search_paths,
             slib_prefix: slib_prefix,
             slib_suffix: slib_suffix,
-            maybe_ar_prog: maybe_ar_prog
+            kind: kind,
         }
     }
 
@@ -140,21 +124,38 @@ impl<'a> Archive<'a> {
 
     /// Removes a file from this archive
     pub fn remove_file(&mut self, file: &str) {
-        run_ar(self.handler, &self.maybe_ar_prog, "d", None, &[&self.dst, &Path::new(file)]);
+        let (mut members, has_symbols) = self.read();
+        members.retain(|m| m.name != file);
+        let dst = self.dst.clone();
+        if let Err(e) = write_archive(&dst, self.kind, &members, has_symbols) {
+            self.handler.fatal(&format!("failed to write archive `{}`: {}",
+                                        dst.display(), e));
+        }
     }
 
     /// Lists all files in an archive
     pub fn files(&self) -> Vec<String> {
-        let output = run_ar(self.handler, &self.maybe_ar_prog, "t", None, &[&self.dst]);
-        let output = str::from_utf8(&output.stdout).unwrap();
-        // use lines_any because windows delimits output with `\r\n` instead of
-        // just `\n`
-        output.lines_any().map(|s| s.to_string()).collect()
+        let (members, _) = self.read();
+        members.into_iter().map(|m| m.name).collect()
     }
 
     /// Creates an `ArchiveBuilder` for adding files to this archive.
     pub fn extend(self) -> ArchiveBuilder<'a> {
-        ArchiveBuilder::new(self)
+        let (members, has_symbols) = self.read();
+        let mut builder = ArchiveBuilder::new(self);
+        builder.members = members;
+        builder.should_update_symbols = has_symbols;
+        builder
+    }
+
+    fn read(&self) -> (Vec<Member>, bool) {
+        match read_archive(&self.dst) {
+            Ok(contents) => contents,
+            Err(e) => {
+                self.handler.fatal(&format!("failed to read archive `{}`: {}",
+                                            self.dst.display(), e))
+            }
+        }
     }
 }
 
@@ -162,7 +163,6 @@ impl<'a> ArchiveBuilder<'a> {
     fn new(archive: Archive<'a>) -> ArchiveBuilder<'a> {
         ArchiveBuilder {
             archive: archive,
-            work_dir: TempDir::new("rsar").unwrap(),
             members: vec![],
             should_update_symbols: false,
         }
@@ -210,15 +210,15 @@ impl<'a> ArchiveBuilder<'a> {
 
     /// Adds an arbitrary file to this archive
     pub fn add_file(&mut self, file: &Path) -> io::Result<()> {
-        let filename = Path::new(file.file_name().unwrap());
-        let new_file = self.work_dir.path().join(&filename);
-        try!(fs::copy(file, &new_file));
-        self.members.push(filename.to_path_buf());
+        let name = file.file_name().unwrap().to_string_lossy().into_owned();
+        let mut data = Vec::new();
+        try!(File::open(file).and_then(|mut f| f.read_to_end(&mut data)));
+        self.members.push(Member { name: name, data: data });
         Ok(())
     }
 
-    /// Indicate that the next call to `build` should updates all symbols in
-    /// the archive (run 'ar s' over it).
+    /// Indicate that the next call to `build` should write a symbol table
+    /// for the object files in the archive.
     pub fn update_symbols(&mut self) {
         self.should_update_symbols = true;
     }
@@ -226,88 +226,36 @@ impl<'a> ArchiveBuilder<'a> {
     /// Combine the provided files, rlibs, and native libraries into a single
     /// `Archive`.
     pub fn build(self) -> Archive<'a> {
-        // Get an absolute path to the destination, so `ar` will work even
-        // though we run it from `self.work_dir`.
-        let abs_dst = env::current_dir().unwrap().join(&self.archive.dst);
-        assert!(!abs_dst.is_relative());
-        let mut args = vec![&*abs_dst];
-        let mut total_len = abs_dst.to_string_lossy().len();
-
-        if self.members.is_empty() {
-            // OSX `ar` does not allow using `r` with no members, but it does
-            // allow running `ar s file.a` to update symbols only.
-            if self.should_update_symbols {
-                run_ar(self.archive.handler, &self.archive.maybe_ar_prog,
-                       "s", Some(self.work_dir.path()), &args[..]);
+        let archive = self.archive;
+        match write_archive(&archive.dst, archive.kind, &self.members,
+                            self.should_update_symbols) {
+            Ok(()) => {}
+            Err(e) => {
+                archive.handler.fatal(&format!("failed to write archive `{}`: {}",
+                                               archive.dst.display(), e));
             }
-            return self.archive;
         }
-
-        // Don't allow the total size of `args` to grow beyond 32,000 bytes.
-        // Windows will raise an error if the argument string is longer than
-        // 32,768, and we leave a bit of extra space for the program name.
-        const ARG_LENGTH_LIMIT: usize = 32_000;
-
-        for member_name in &self.members {
-            let len = member_name.to_string_lossy().len();
-
-            // `len + 1` to account for the space that's inserted before each
-            // argument.  (Windows passes command-line arguments as a single
-            // string, not an array of strings.)
-            if total_len + len + 1 > ARG_LENGTH_LIMIT {
-                // Add the archive members seen so far, without updating the
-                // symbol table (`S`).
-                run_ar(self.archive.handler, &self.archive.maybe_ar_prog,
-                       "cruS", Some(self.work_dir.path()), &args[..]);
-
-                args.clear();
-                args.push(&abs_dst);
-                total_len = abs_dst.to_string_lossy().len();
-            }
-
-            args.push(member_name);
-            total_len += len + 1;
-        }
-
-        // Add the remaining archive members, and update the symbol table if
-        // necessary.
-        let flags = if self.should_update_symbols { "crus" } else { "cruS" };
-        run_ar(self.archive.handler, &self.archive.maybe_ar_prog,
-               flags, Some(self.work_dir.path()), &args[..]);
-
-        self.archive
+        archive
     }
 
     fn add_archive<F>(&mut self, archive: &Path, name: &str,
                       mut skip: F) -> io::Result<()>
         where F: FnMut(&str) -> bool,
     {
-        let loc = TempDir::new("rsar").unwrap();
-
-        // First, extract the contents of the archive to a temporary directory.
-        // We don't unpack directly into `self.work_dir` due to the possibility
-        // of filename collisions.
-        let archive = env::current_dir().unwrap().join(archive);
-        run_ar(self.archive.handler, &self.archive.maybe_ar_prog,
-               "x", Some(loc.path()), &[&archive]);
-
-        // Next, we must rename all of the inputs to "guaranteed unique names".
-        // We move each file into `self.work_dir` under its new unique name.
-        // The reason for this renaming is that archives are keyed off the name
-        // of the files, so if two files have the same name they will override
-        // one another in the archive (bad).
+        let (members, _) = try!(read_archive(archive));
+
+        // All of the inputs are renamed to "guaranteed unique names". The
+        // reason for this renaming is that archives are keyed off the name of
+        // the files, so if two files have the same name they will override
+        // one another when the archive is later modified or extracted (bad).
         //
-        // We skip any files explicitly desired for skipping, and we also skip
-        // all SYMDEF files as these are just magical placeholders which get
-        // re-created when we make a new archive anyway.
-        let files = try!(fs::read_dir(loc.path()));
-        for file in files {
-            let file = try!(file).path();
-            let filename = file.file_name().unwrap().to_str().unwrap();
-            if skip(filename) { continue }
-            if filename.contains(".SYMDEF") { continue }
-
-            let filename = format!("r-{}-{}", name, filename);
+        // We skip any files explicitly desired for skipping. The symbol table
+        // of the input was already dropped when reading it, it gets
+        // re-created when we write the new archive anyway.
+        for member in members {
+            if skip(&member.name) { continue }
+
+            let filename = format!("r-{}-{}", name, member.name);
             // LLDB (as mentioned in back::link) crashes on filenames of exactly
             // 16 bytes in length. If we're including an object file with
             // exactly 16-bytes of characters, give it some prefix so that it's
@@ -317,10 +265,380 @@ impl<'a> ArchiveBuilder<'a> {
             } else {
                 filename
             };
-            let new_filename = self.work_dir.path().join(&filename[..]);
-            try!(fs::rename(&file, &new_filename));
-            self.members.push(PathBuf::from(filename));
+            self.members.push(Member { name: filename, data: member.data });
         }
         Ok(())
     }
 }
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg)
+}
+
+/// Reads every member of the archive at `path`, returning them along with
+/// whether the archive had a symbol table.
+fn read_archive(path: &Path) -> io::Result<(Vec<Member>, bool)> {
+    let mut data = Vec::new();
+    try!(File::open(path).and_then(|mut f| f.read_to_end(&mut data)));
+    parse_archive(&data)
+}
+
+fn parse_archive(data: &[u8]) -> io::Result<(Vec<Member>, bool)> {
+    if data.starts_with(THIN_ARCHIVE_MAGIC) {
+        return Err(invalid("thin archives are not supported"))
+    }
+    if !data.starts_with(ARCHIVE_MAGIC) {
+        return Err(invalid("not an archive"))
+    }
+
+    let mut members = Vec::new();
+    let mut has_symbols = false;
+    let mut long_names: &[u8] = &[];
+    let mut pos = ARCHIVE_MAGIC.len();
+    while pos < data.len() {
+        if data.len() - pos < HEADER_LEN {
+            return Err(invalid("truncated archive member header"))
+        }
+        let header = &data[pos..pos + HEADER_LEN];
+        if &header[58..] != &b"`\n"[..] {
+            return Err(invalid("malformed archive member header"))
+        }
+        let size = try!(parse_decimal(&header[48..58]));
+        let start = pos + HEADER_LEN;
+        if data.len() - start < size {
+            return Err(invalid("truncated archive member"))
+        }
+        let mut contents = &data[start..start + size];
+        // Members are aligned to an even offset
+        pos = start + size + size % 2;
+
+        let raw_name = &header[..16];
+        let name = if raw_name.starts_with(b"#1/") {
+            // BSD long name, stored right in front of the contents
+            let len = try!(parse_decimal(&raw_name[3..]));
+            if len > contents.len() {
+                return Err(invalid("truncated archive member name"))
+            }
+            let name = &contents[..len];
+            contents = &contents[len..];
+            trim_trailing(name, b'\0')
+        } else if raw_name.starts_with(b"//") {
+            // GNU table of long names
+            long_names = contents;
+            continue
+        } else if raw_name.starts_with(b"/SYM64/") ||
+                  trim_trailing(raw_name, b' ') == &b"/"[..] {
+            // GNU symbol table
+            has_symbols = true;
+            continue
+        } else if raw_name[0] == b'/' {
+            // GNU long name, an offset into the table of long names
+            let offset = try!(parse_decimal(&raw_name[1..]));
+            if offset > long_names.len() {
+                return Err(invalid("invalid archive member name offset"))
+            }
+            let name = &long_names[offset..];
+            let end = name.iter().position(|&b| b == b'\n').unwrap_or(name.len());
+            trim_trailing(&name[..end], b'/')
+        } else {
+            let name = trim_trailing(raw_name, b' ');
+            if name.ends_with(b"/") { &name[..name.len() - 1] } else { name }
+        };
+
+        if name.starts_with(b"__.SYMDEF") {
+            // BSD symbol table
+            has_symbols = true;
+            continue
+        }
+        members.push(Member {
+            name: String::from_utf8_lossy(name).into_owned(),
+            data: contents.to_vec(),
+        });
+    }
+    Ok((members, has_symbols))
+}
+
+fn parse_decimal(field: &[u8]) -> io::Result<usize> {
+    str::from_utf8(field).ok()
+        .and_then(|s| s.trim().parse().ok())
+        .ok_or_else(|| invalid("malformed archive member header"))
+}
+
+fn trim_trailing(s: &[u8], b: u8) -> &[u8] {
+    let len = s.iter().rposition(|&c| c != b).map_or(0, |i| i + 1);
+    &s[..len]
+}
+
+/// Writes `members` to a new archive at `dst`, optionally preceded by a
+/// symbol table covering all object files among them.
+fn write_archive(dst: &Path, kind: ArchiveKind, members: &[Member],
+                 symbol_table: bool) -> io::Result<()> {
+    // Files which aren't object files (metadata, bytecode, ...) don't
+    // contribute any symbols.
+    let symbols = if symbol_table {
+        members.iter().map(|m| {
+            llvm::object_file_symbols(&m.data).unwrap_or(Vec::new())
+        }).collect()
+    } else {
+        Vec::new()
+    };
+
+    let mut out = Vec::new();
+    out.push_all(ARCHIVE_MAGIC);
+    try!(match kind {
+        ArchiveKind::Gnu => write_gnu_archive(&mut out, members, symbol_table,
+                                              &symbols),
+        ArchiveKind::Bsd => write_bsd_archive(&mut out, members, symbol_table,
+                                              &symbols),
+    });
+
+    // The destination may be left over from a previous compilation, make sure
+    // that no stale members survive.
+    if dst.exists() {
+        try!(fs::remove_file(dst));
+    }
+    let mut file = try!(File::create(dst));
+    file.write_all(&out)
+}
+
+fn write_gnu_archive(out: &mut Vec<u8>, members: &[Member],
+                     symbol_table: bool,
+                     symbols: &[Vec<Vec<u8>>]) -> io::Result<()> {
+    // Names which don't fit in the header, or which would be ambiguous with
+    // the terminating '/', are stored in the `//` member and referred to by
+    // their offset in it.
+    let mut long_names = Vec::new();
+    let names = members.iter().map(|m| {
+        if m.name.len() > 15 || m.name.contains("/") {
+            let name = format!("/{}", long_names.len());
+            long_names.push_all(m.name.as_bytes());
+            long_names.push_all(b"/\n");
+            name
+        } else {
+            format!("{}/", m.name)
+        }
+    }).collect::<Vec<_>>();
+
+    // The symbol table lists the offset of each symbol's member, so the size
+    // of everything in front of the first member has to be known first.
+    let num_symbols = symbols.iter().map(|s| s.len()).sum();
+    let strings_len = symbols.iter().flat_map(|s| s.iter()).map(|s| {
+        s.len() + 1
+    }).sum();
+    let symbol_table_len = 4 + 4 * num_symbols + strings_len;
+    let mut offset = out.len();
+    if symbol_table {
+        offset += HEADER_LEN + padded(symbol_table_len);
+    }
+    if !long_names.is_empty() {
+        offset += HEADER_LEN + padded(long_names.len());
+    }
+    let offsets = try!(member_offsets(offset, members.iter().map(|m| {
+        m.data.len()
+    })));
+
+    if symbol_table {
+        write_header(out, b"/", symbol_table_len);
+        push_u32_be(out, num_symbols as u32);
+        for (syms, &offset) in symbols.iter().zip(offsets.iter()) {
+            for _ in syms {
+                push_u32_be(out, offset);
+            }
+        }
+        for name in symbols.iter().flat_map(|s| s.iter()) {
+            out.push_all(name);
+            out.push(0);
+        }
+        pad(out, b'\n');
+    }
+    if !long_names.is_empty() {
+        write_header(out, b"//", long_names.len());
+        out.push_all(&long_names);
+        pad(out, b'\n');
+    }
+    for (member, name) in members.iter().zip(names.iter()) {
+        write_header(out, name.as_bytes(), member.data.len());
+        out.push_all(&member.data);
+        pad(out, b'\n');
+    }
+    Ok(())
+}
+
+fn write_bsd_archive(out: &mut Vec<u8>, members: &[Member],
+                     symbol_table: bool,
+                     symbols: &[Vec<Vec<u8>>]) -> io::Result<()> {
+    // Names which don't fit in the header or contain spaces are written as
+    // `#1/<len>` followed by the name itself at the start of the contents.
+    let long_name = |m: &Member| m.name.len() > 16 || m.name.contains(" ");
+
+    // The symbol table is a list of (string offset, member offset) pairs
+    // followed by the string table, all in little endian.
+    let num_symbols = symbols.iter().map(|s| s.len()).sum();
+    let strings_len: usize = symbols.iter().flat_map(|s| s.iter()).map(|s| {
+        s.len() + 1
+    }).sum();
+    let strings_len = (strings_len + 3) & !3;
+    let symbol_table_len = 4 + 8 * num_symbols + 4 + strings_len;
+    let mut offset = out.len();
+    if symbol_table {
+        offset += HEADER_LEN + padded(symbol_table_len);
+    }
+    let offsets = try!(member_offsets(offset, members.iter().map(|m| {
+        if long_name(m) { m.name.len() + m.data.len() } else { m.data.len() }
+    })));
+
+    if symbol_table {
+        write_header(out, b"__.SYMDEF", symbol_table_len);
+        push_u32_le(out, (8 * num_symbols) as u32);
+        let mut string_offset = 0;
+        for (syms, &offset) in symbols.iter().zip(offsets.iter()) {
+            for name in syms {
+                push_u32_le(out, string_offset as u32);
+                push_u32_le(out, offset);
+                string_offset += name.len() + 1;
+            }
+        }
+        push_u32_le(out, strings_len as u32);
+        let start = out.len();
+        for name in symbols.iter().flat_map(|s| s.iter()) {
+            out.push_all(name);
+            out.push(0);
+        }
+        while out.len() - start < strings_len {
+            out.push(0);
+        }
+        pad(out, b'\n');
+    }
+    for member in members {
+        if long_name(member) {
+            let header_name = format!("#1/{}", member.name.len());
+            write_header(out, header_name.as_bytes(),
+                         member.name.len() + member.data.len());
+            out.push_all(member.name.as_bytes());
+        } else {
+            write_header(out, member.name.as_bytes(), member.data.len());
+        }
+        out.push_all(&member.data);
+        pad(out, b'\n');
+    }
+    Ok(())
+}
+
+/// Computes the offset of each member's header, given the offset of the
+/// first one and the size of each member's contents.
+fn member_offsets<I>(mut offset: usize, sizes: I) -> io::Result<Vec<u32>>
+    where I: Iterator<Item=usize>,
+{
+    let mut offsets = Vec::new();
+    for size in sizes {
+        if offset > u32::MAX as usize {
+            return Err(invalid("archive is too large for a symbol table"))
+        }
+        offsets.push(offset as u32);
+        offset += HEADER_LEN + padded(size);
+    }
+    Ok(offsets)
+}
+
+/// Writes a member header with deterministic metadata: a zero timestamp,
+/// owner and group, and mode 644.
+fn write_header(out: &mut Vec<u8>, name: &[u8], size: usize) {
+    write_field(out, name, 16);
+    write_field(out, b"0", 12);
+    write_field(out, b"0", 6);
+    write_field(out, b"0", 6);
+    write_field(out, b"644", 8);
+    write_field(out, size.to_string().as_bytes(), 10);
+    out.push_all(b"`\n");
+}
+
+fn write_field(out: &mut Vec<u8>, value: &[u8], width: usize) {
+    assert!(value.len() <= width);
+    out.push_all(value);
+    for _ in value.len()..width {
+        out.push(b' ');
+    }
+}
+
+fn padded(len: usize) -> usize {
+    len + len % 2
+}
+
+fn pad(out: &mut Vec<u8>, byte: u8) {
+    if out.len() % 2 == 1 {
+        out.push(byte);
+    }
+}
+
+fn push_u32_be(out: &mut Vec<u8>, n: u32) {
+    out.push_all(&[(n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8]);
+}
+
+fn push_u32_le(out: &mut Vec<u8>, n: u32) {
+    out.push_all(&[n as u8, (n >> 8) as u8, (n >> 16) as u8, (n >> 24) as u8]);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ArchiveKind, Member, parse_archive, write_gnu_archive,
+                write_bsd_archive, ARCHIVE_MAGIC};
+
+    fn members() -> Vec<Member> {
+        vec![
+            Member { name: "a.o".to_string(), data: b"abc".to_vec() },
+            Member { name: "a-rather-long-member-name.o".to_string(),
+                     data: b"defg".to_vec() },
+            Member { name: "with space.o".to_string(), data: vec![] },
+        ]
+    }
+
+    fn roundtrip(kind: ArchiveKind) -> Vec<u8> {
+        let mut out = ARCHIVE_MAGIC.to_vec();
+        let symbols = vec![vec![b"foo".to_vec()], vec![], vec![]];
+        match kind {
+            ArchiveKind::Gnu => {
+                write_gnu_archive(&mut out, &members(), true, &symbols).unwrap()
+            }
+            ArchiveKind::Bsd => {
+                write_bsd_archive(&mut out, &members(), true, &symbols).unwrap()
+            }
+        }
+        let (read, has_symbols) = parse_archive(&out).unwrap();
+        assert!(has_symbols);
+        let expected = members();
+        assert_eq!(read.len(), expected.len());
+        for (a, b) in read.iter().zip(expected.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.data, b.data);
+        }
+        out
+    }
+
+    #[test]
+    fn gnu_roundtrip() {
+        let out = roundtrip(ArchiveKind::Gnu);
+        // One symbol, defined by the first member
+        let symtab = &out[ARCHIVE_MAGIC.len() + 60..];
+        assert_eq!(&symtab[..4], &[0u8, 0, 0, 1][..]);
+    }
+
+    #[test]
+    fn bsd_roundtrip() {
+        roundtrip(ArchiveKind::Bsd);
+    }
+
+    #[test]
+    fn deterministic() {
+        assert_eq!(roundtrip(ArchiveKind::Gnu), roundtrip(ArchiveKind::Gnu));
+        assert_eq!(roundtrip(ArchiveKind::Bsd), roundtrip(ArchiveKind::Bsd));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(parse_archive(b"not an archive").is_err());
+        assert!(parse_archive(b"!<thin>\n").is_err());
+        let mut truncated = ARCHIVE_MAGIC.to_vec();
+        truncated.push_all(b"a.o/");
+        assert!(parse_archive(&truncated).is_err());
+    }
+}
@@ -47,6 +47,7 @@
 
 extern crate syntax;
 extern crate serialize;
+extern crate rustc_llvm as llvm;
 #[macro_use] extern crate log;
 
 pub mod abi;
@@ -290,6 +290,9 @@ mod svh_visitor {
             ExprIfLet(..)            => unreachable!(),
             ExprWhileLet(..)         => unreachable!(),
             ExprMac(..)              => unreachable!(),
+
+            // compilation stops before SVH if parsing produced these.
+            ExprError                => unreachable!(),
         }
     }
 
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A freestanding x86_64 target for bootloaders, kernels and firmware: there
+// is no operating system, no libc and no C runtime, so crates are
+// `#![no_std]` and define their own entry point. The layout of the output is
+// usually given with `-C link-script`.
+
+use target::{Target, TargetOptions};
+use std::default::Default;
+
+pub fn target() -> Target {
+    Target {
+        data_layout: "e-m:e-i64:64-f80:128-n8:16:32:64-S128".to_string(),
+        llvm_target: "x86_64-unknown-none-elf".to_string(),
+        target_endian: "little".to_string(),
+        target_pointer_width: "64".to_string(),
+        arch: "x86_64".to_string(),
+        target_os: "none".to_string(),
+        target_env: "".to_string(),
+        options: TargetOptions {
+            cpu: "x86-64".to_string(),
+            pre_link_args: vec!["-m64".to_string(), "-static".to_string()],
+            linker_is_gnu: true,
+            executables: true,
+
+            // Interrupt handlers run on the interrupted code's stack, so
+            // nothing below the stack pointer is safe, and the SSE state
+            // isn't saved on a switch to the kernel.
+            disable_redzone: true,
+            features: "-mmx,-sse,+soft-float".to_string(),
+
+            // Code runs wherever the linker script puts it, there is no
+            // dynamic loader to relocate it.
+            relocation_model: "static".to_string(),
+            position_independent_executables: false,
+            dynamic_linking: false,
+            has_rpath: false,
+
+            // There is no runtime to provide `__morestack` or the compiler
+            // builtins.
+            morestack: false,
+            no_compiler_rt: true,
+            eliminate_frame_pointer: false,
+            .. Default::default()
+        },
+    }
+}
@@ -20,6 +20,7 @@ use self::UseError::*;
 
 use borrowck::*;
 use borrowck::InteriorKind::{InteriorElement, InteriorField};
+use borrowck::loan_liveness::LoanLiveness;
 use rustc::middle::expr_use_visitor as euv;
 use rustc::middle::mem_categorization as mc;
 use rustc::middle::region;
@@ -86,6 +87,7 @@ fn owned_ptr_base_path_rc<'tcx>(loan_path: &Rc<LoanPath<'tcx>>) -> Rc<LoanPath<'
 struct CheckLoanCtxt<'a, 'tcx: 'a> {
     bccx: &'a BorrowckCtxt<'a, 'tcx>,
     dfcx_loans: &'a LoanDataFlow<'a, 'tcx>,
+    liveness: &'a LoanLiveness,
     move_data: move_data::FlowedMoveData<'a, 'tcx>,
     all_loans: &'a [Loan<'tcx>],
     param_env: &'a ty::ParameterEnvironment<'a, 'tcx>,
@@ -191,6 +193,7 @@ impl<'a, 'tcx> euv::Delegate<'tcx> for CheckLoanCtxt<'a, 'tcx> {
 
 pub fn check_loans<'a, 'b, 'c, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
                                      dfcx_loans: &LoanDataFlow<'b, 'tcx>,
+                                     liveness: &LoanLiveness,
                                      move_data: move_data::FlowedMoveData<'c, 'tcx>,
                                      all_loans: &[Loan<'tcx>],
                                      fn_id: ast::NodeId,
@@ -203,6 +206,7 @@ pub fn check_loans<'a, 'b, 'c, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
     let mut clcx = CheckLoanCtxt {
         bccx: bccx,
         dfcx_loans: dfcx_loans,
+        liveness: liveness,
         move_data: move_data,
         all_loans: all_loans,
         param_env: &param_env,
@@ -248,11 +252,12 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
         F: FnMut(&Loan<'tcx>) -> bool,
     {
         //! Like `each_issued_loan()`, but only considers loans that are
-        //! currently in scope.
+        //! currently in scope and whose reference may still be used.
 
         let tcx = self.tcx();
         self.each_issued_loan(scope, |loan| {
-            if tcx.region_maps.is_subscope_of(scope, loan.kill_scope) {
+            if tcx.region_maps.is_subscope_of(scope, loan.kill_scope) &&
+               self.liveness.is_live_at(tcx, loan, scope) {
                 op(loan)
             } else {
                 true
@@ -362,6 +367,9 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
         debug!("new_loan_indices = {:?}", new_loan_indices);
 
         self.each_issued_loan(scope, |issued_loan| {
+            if !self.liveness.is_live_at(self.tcx(), issued_loan, scope) {
+                return true;
+            }
             for &new_loan_index in &new_loan_indices {
                 let new_loan = &self.all_loans[new_loan_index];
                 self.report_error_if_loans_conflict(issued_loan, new_loan);
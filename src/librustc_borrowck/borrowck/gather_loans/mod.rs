@@ -341,6 +341,8 @@ impl<'a, 'tcx> GatherLoanCtxt<'a, 'tcx> {
                     kind: req_kind,
                     gen_scope: gen_scope,
                     kill_scope: kill_scope,
+                    loan_region: loan_region,
+                    borrow_id: borrow_id,
                     span: borrow_span,
                     restricted_paths: restricted_paths,
                     cause: cause,
@@ -0,0 +1,564 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Computes, for each loan, the points in the control-flow graph at
+//! which the reference created by the loan may still be used. This is
+//! used by `check_loans` (under `-Z nll`) to ignore loans that are
+//! lexically in scope but whose reference is dead, so that programs
+//! like the following are accepted:
+//!
+//!     let r = &mut x;
+//!     *r += 1;
+//!     x += 1; // `r` is never used again, so the loan is dead here
+//!
+//! The reference is followed from the borrow expression through the
+//! expressions that consume it: as long as the value of an expression
+//! may contain the reference (its type mentions a region that the loan
+//! region outlives), the enclosing expression is a use of the loan too.
+//! When the value is bound by a pattern or assigned to a local, that
+//! local becomes a *carrier* of the loan, and its uses are followed in
+//! turn. Locals which may have the reference written into them in
+//! place, through a `&mut` pointing at them or at their contents, are
+//! carriers as well. The loan is live at a CFG node if one of these
+//! uses is reachable from it without passing a (re)definition of the
+//! carrier involved.
+//!
+//! A loan whose region is not a scope within the fn body, such as one
+//! whose reference is returned to the caller, is handled the same way:
+//! the reference can only escape through `return` or the value of the
+//! body, which are uses like any other, so the loan is dead on the paths
+//! that don't return it:
+//!
+//!     match map.get_mut(&key) {
+//!         Some(value) => return value,
+//!         None => {}
+//!     }
+//!     map.insert(key, value); // the loan is dead here
+//!
+//! The analysis is deliberately conservative: loans with a `'static`
+//! region, loans carried by a value whose type has a destructor,
+//! returned loans that some local could hold in place (such as an
+//! argument of type `&mut Vec<&'a T>`), and fns containing closures all
+//! fall back to the lexical treatment.
+
+use borrowck::*;
+use rustc::middle::cfg;
+use rustc::middle::cfg::CFGIndex;
+use rustc::middle::def;
+use rustc::middle::pat_util;
+use rustc::middle::region;
+use rustc::middle::ty;
+use rustc::middle::ty_fold;
+use rustc::util::nodemap::{NodeMap, NodeSet};
+use rustc::util::ppaux::Repr;
+use syntax::ast;
+use syntax::ast_map;
+use syntax::codemap::Span;
+use syntax::visit;
+use syntax::visit::Visitor;
+
+pub struct LoanLiveness {
+    /// Mapping from node to the cfg nodes that represent it.
+    nodeid_to_index: NodeMap<Vec<CFGIndex>>,
+
+    /// Liveness data for each loan, indexed by loan index. `None`
+    /// indicates the loan is treated lexically.
+    loans: Vec<Option<LiveLoan>>,
+}
+
+struct LiveLoan {
+    /// Temporary scopes of the expressions whose value may hold the
+    /// reference and has a destructor; the loan is live anywhere within
+    /// these, as the temporary is dropped at their end.
+    temp_scopes: Vec<region::CodeExtent>,
+
+    /// For each cfg node, whether a use of the reference is reachable
+    /// from it.
+    live_nodes: Vec<bool>,
+}
+
+impl LoanLiveness {
+    /// Liveness information under which every loan is live whenever it
+    /// is in scope, i.e. the lexical behavior.
+    pub fn lexical() -> LoanLiveness {
+        LoanLiveness { nodeid_to_index: NodeMap(), loans: Vec::new() }
+    }
+
+    pub fn compute<'a, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
+                             all_loans: &[Loan<'tcx>],
+                             cfg: &cfg::CFG,
+                             decl: &ast::FnDecl,
+                             body: &ast::Block) -> LoanLiveness {
+        if !bccx.tcx.sess.opts.debugging_opts.nll {
+            return LoanLiveness::lexical();
+        }
+
+        let mut locals = LocalsAndUses {
+            tcx: bccx.tcx,
+            locals: Vec::new(),
+            uses: NodeMap(),
+            defs: NodeMap(),
+            consumers: NodeMap(),
+            parents: Vec::new(),
+            assigned_paths: NodeSet(),
+            mutably_borrowed: NodeSet(),
+            has_closures: false,
+        };
+        for arg in &decl.inputs {
+            locals.visit_pat(&*arg.pat);
+        }
+        locals.consumers.insert(body.id, Consumer::Return(body.id));
+        locals.visit_block(body);
+        if locals.has_closures {
+            debug!("LoanLiveness::compute: fn contains closures, using lexical loans");
+            return LoanLiveness::lexical();
+        }
+
+        // A local of type `&T` can only come to hold a different
+        // reference by being assigned to, unless it is itself borrowed
+        // mutably.
+        for loan in all_loans {
+            if loan.kind == ty::ImmBorrow {
+                continue;
+            }
+            if let LpVar(id) = loan.loan_path.kind {
+                locals.mutably_borrowed.insert(id);
+            }
+        }
+
+        let mut nodeid_to_index = NodeMap();
+        cfg.graph.each_node(|node_idx, node| {
+            if let cfg::CFGNodeData::AST(id) = node.data {
+                nodeid_to_index.entry(id).or_insert(vec![]).push(node_idx);
+            }
+            true
+        });
+
+        let loans = all_loans.iter().map(|loan| {
+            let mut lcx = LiveLoanCtxt {
+                tcx: bccx.tcx,
+                loan: loan,
+                locals: &locals,
+                nodeid_to_index: &nodeid_to_index,
+                cfg: cfg,
+                carriers: Vec::new(),
+                temp_scopes: Vec::new(),
+                live_nodes: cfg.graph.all_nodes().iter().map(|_| false).collect(),
+            };
+            lcx.compute()
+        }).collect();
+
+        LoanLiveness { nodeid_to_index: nodeid_to_index, loans: loans }
+    }
+
+    /// Returns true if `loan` may still be used on entry to `scope`.
+    pub fn is_live_at(&self,
+                      tcx: &ty::ctxt,
+                      loan: &Loan,
+                      scope: region::CodeExtent) -> bool {
+        let live_loan = match self.loans.get(loan.index) {
+            Some(&Some(ref l)) => l,
+            _ => return true,
+        };
+
+        if live_loan.temp_scopes.iter().any(|&t| tcx.region_maps.is_subscope_of(scope, t)) {
+            return true;
+        }
+
+        match self.nodeid_to_index.get(&scope.node_id()) {
+            Some(indices) => indices.iter().any(|i| live_loan.live_nodes[i.node_id()]),
+            None => true,
+        }
+    }
+}
+
+struct LiveLoanCtxt<'a, 'b: 'a, 'tcx: 'b> {
+    tcx: &'b ty::ctxt<'tcx>,
+    loan: &'a Loan<'tcx>,
+    locals: &'a LocalsAndUses<'b, 'tcx>,
+    nodeid_to_index: &'a NodeMap<Vec<CFGIndex>>,
+    cfg: &'a cfg::CFG,
+
+    /// The locals which may hold the reference.
+    carriers: Vec<ast::NodeId>,
+    temp_scopes: Vec<region::CodeExtent>,
+    live_nodes: Vec<bool>,
+}
+
+impl<'a, 'b, 'tcx> LiveLoanCtxt<'a, 'b, 'tcx> {
+    fn compute(mut self) -> Option<LiveLoan> {
+        let tcx = self.tcx;
+        let loan = self.loan;
+        let locals = self.locals;
+
+        // References whose region is a scope within the body, or one
+        // of the fn's own region parameters, can only be observed
+        // through the uses followed below; a `'static` one may be
+        // stored anywhere.
+        let returnable = match loan.loan_region {
+            ty::ReScope(_) => false,
+            ty::ReFree(_) | ty::ReEarlyBound(..) => true,
+            _ => return None,
+        };
+
+        for &(var_id, var_ty) in &locals.locals {
+            let ty = match var_ty.sty {
+                ty::ty_rptr(_, ref mt) if !locals.mutably_borrowed.contains(&var_id) => {
+                    mt.ty
+                }
+                _ => var_ty,
+            };
+            if self.may_hold(ty) {
+                // A returned reference written into such a local may
+                // end up in memory of the caller, where it outlives
+                // every use in this fn.
+                if returnable {
+                    debug!("live_loan({}): returned loan may be held by {}",
+                           loan.repr(tcx), var_id);
+                    return None;
+                }
+                if !self.add_carrier(var_id) {
+                    return None;
+                }
+            }
+        }
+
+        // `ref` bindings borrow directly into the bound local.
+        if locals.locals.iter().any(|&(id, _)| id == loan.borrow_id) {
+            if !self.add_carrier(loan.borrow_id) {
+                return None;
+            }
+        } else {
+            match tcx.map.find(loan.borrow_id) {
+                Some(ast_map::NodeExpr(_)) => {}
+                _ => return None,
+            }
+            if !self.follow_value(loan.borrow_id) {
+                return None;
+            }
+        }
+
+        let mut i = 0;
+        while i < self.carriers.len() {
+            let var_id = self.carriers[i];
+            i += 1;
+
+            let uses = locals.uses.get(&var_id).map(|v| &v[..]).unwrap_or(&[]);
+            let defs = locals.defs.get(&var_id).map(|v| &v[..]).unwrap_or(&[]);
+            if !self.mark_live(uses, defs) {
+                return None;
+            }
+            for &use_id in uses {
+                if !self.follow_value(use_id) {
+                    return None;
+                }
+            }
+        }
+
+        Some(LiveLoan { temp_scopes: self.temp_scopes, live_nodes: self.live_nodes })
+    }
+
+    /// Whether a value of type `ty` may contain the reference created
+    /// by the loan.
+    fn may_hold(&self, ty: ty::Ty<'tcx>) -> bool {
+        let loan_region = self.loan.loan_region;
+        ty_fold::collect_regions(self.tcx, &ty).into_iter().any(|r| {
+            match r {
+                ty::ReScope(_) => self.tcx.region_maps.is_subregion_of(r, loan_region),
+                ty::ReStatic | ty::ReLateBound(..) => false,
+                // Free regions outlive the body, so they can only hold
+                // references that do so as well.
+                _ => match loan_region {
+                    ty::ReScope(_) => false,
+                    _ => true,
+                },
+            }
+        })
+    }
+
+    fn add_carrier(&mut self, var_id: ast::NodeId) -> bool {
+        if self.carriers.contains(&var_id) {
+            return true;
+        }
+
+        // A destructor may observe the reference when the carrier
+        // goes out of scope.
+        let var_ty = ty::node_id_to_type(self.tcx, var_id);
+        if ty::type_contents(self.tcx, var_ty).needs_drop(self.tcx) {
+            debug!("live_loan({}): carrier {} needs drop",
+                   self.loan.repr(self.tcx), var_id);
+            return false;
+        }
+
+        self.carriers.push(var_id);
+        true
+    }
+
+    /// Follows the value of the expression `origin` through the
+    /// expressions that consume it, for as long as it may contain the
+    /// reference, marking them as uses and recording the locals it is
+    /// stored in as carriers.
+    fn follow_value(&mut self, origin: ast::NodeId) -> bool {
+        let tcx = self.tcx;
+        let locals = self.locals;
+        let mut uses = Vec::new();
+        let mut id = origin;
+        loop {
+            match locals.consumers.get(&id) {
+                None => break,
+                Some(&Consumer::Node(parent)) => {
+                    uses.push(parent);
+
+                    // Places based on the reference are followed to
+                    // wherever they are read, borrowed or written.
+                    if !is_place_projection(tcx, parent) {
+                        let ty = match ty::node_id_to_type_opt(tcx, parent) {
+                            Some(ty) => ty,
+                            None => return false,
+                        };
+                        if !self.may_hold(ty) {
+                            break;
+                        }
+                        if ty::type_contents(tcx, ty).needs_drop(tcx) {
+                            match tcx.region_maps.temporary_scope(parent) {
+                                Some(s) => self.temp_scopes.push(s),
+                                None => return false,
+                            }
+                        }
+                    }
+                    id = parent;
+                }
+                Some(&Consumer::Bind(ref pats, ref bindings)) => {
+                    uses.extend(pats.iter().cloned());
+                    for &var_id in bindings {
+                        if self.may_hold(ty::node_id_to_type(tcx, var_id)) &&
+                           !self.add_carrier(var_id) {
+                            return false;
+                        }
+                    }
+                    break;
+                }
+                Some(&Consumer::Assign(assign_id, local)) => {
+                    uses.push(assign_id);
+                    if let Some(var_id) = local {
+                        if !self.add_carrier(var_id) {
+                            return false;
+                        }
+                    }
+                    break;
+                }
+                Some(&Consumer::Return(return_id)) => {
+                    uses.push(return_id);
+                    break;
+                }
+            }
+        }
+
+        self.mark_live(&uses, &[origin])
+    }
+
+    /// Marks the cfg nodes from which one of `uses` is reachable
+    /// without going through one of `defs`.
+    fn mark_live(&mut self, uses: &[ast::NodeId], defs: &[ast::NodeId]) -> bool {
+        let mut stop: Vec<bool> = self.live_nodes.iter().map(|_| false).collect();
+        for def in defs {
+            if let Some(indices) = self.nodeid_to_index.get(def) {
+                for i in indices {
+                    stop[i.node_id()] = true;
+                }
+            }
+        }
+
+        let mut worklist = Vec::new();
+        for use_id in uses {
+            match self.nodeid_to_index.get(use_id) {
+                Some(indices) => worklist.extend(indices.iter().cloned()),
+                None => return false,
+            }
+        }
+
+        let mut visited: Vec<bool> = stop.iter().map(|_| false).collect();
+        while let Some(node) = worklist.pop() {
+            if visited[node.node_id()] {
+                continue;
+            }
+            visited[node.node_id()] = true;
+            self.live_nodes[node.node_id()] = true;
+            if stop[node.node_id()] {
+                continue;
+            }
+            self.cfg.graph.each_incoming_edge(node, |_, edge| {
+                worklist.push(edge.source());
+                true
+            });
+        }
+        true
+    }
+}
+
+fn is_place_projection(tcx: &ty::ctxt, id: ast::NodeId) -> bool {
+    match tcx.map.find(id) {
+        Some(ast_map::NodeExpr(e)) => match e.node {
+            ast::ExprField(..) |
+            ast::ExprTupField(..) |
+            ast::ExprIndex(..) |
+            ast::ExprParen(..) |
+            ast::ExprUnary(ast::UnDeref, _) => true,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// How the value of an expression or block is used.
+enum Consumer {
+    /// As part of evaluating the given expression or block.
+    Node(ast::NodeId),
+    /// Matched against the given patterns, whose bindings are listed
+    /// too.
+    Bind(Vec<ast::NodeId>, Vec<ast::NodeId>),
+    /// Assigned by the given assignment, to the given local if the
+    /// left-hand side is one.
+    Assign(ast::NodeId, Option<ast::NodeId>),
+    /// Returned from the fn by the given `return` expression or as the
+    /// value of the body.
+    Return(ast::NodeId),
+}
+
+/// Collects the local variables of a fn body along with the
+/// expressions that use and define each of them, and what consumes
+/// the value of each expression.
+struct LocalsAndUses<'a, 'tcx: 'a> {
+    tcx: &'a ty::ctxt<'tcx>,
+    locals: Vec<(ast::NodeId, ty::Ty<'tcx>)>,
+    uses: NodeMap<Vec<ast::NodeId>>,
+    defs: NodeMap<Vec<ast::NodeId>>,
+    consumers: NodeMap<Consumer>,
+
+    /// The expressions being visited, innermost last; `None` for a
+    /// block, whose statements discard their values.
+    parents: Vec<Option<ast::NodeId>>,
+
+    /// Paths on the left-hand side of an assignment to a local, which
+    /// define the local rather than use it.
+    assigned_paths: NodeSet,
+
+    /// Locals which are borrowed mutably as a whole.
+    mutably_borrowed: NodeSet,
+
+    has_closures: bool,
+}
+
+impl<'a, 'tcx> LocalsAndUses<'a, 'tcx> {
+    fn local_of(&self, e: &ast::Expr) -> Option<ast::NodeId> {
+        match e.node {
+            ast::ExprPath(..) => {
+                match self.tcx.def_map.borrow().get(&e.id).map(|d| d.full_def()) {
+                    Some(def::DefLocal(var_id)) => Some(var_id),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn bind(&mut self, e: &ast::Expr, pats: &[&ast::Pat]) {
+        let mut bindings = Vec::new();
+        for pat in pats {
+            pat_util::pat_bindings(&self.tcx.def_map, pat, |_, id, _: Span, _| {
+                bindings.push(id);
+            });
+        }
+        let pats = pats.iter().map(|p| p.id).collect();
+        self.consumers.insert(e.id, Consumer::Bind(pats, bindings));
+    }
+}
+
+impl<'a, 'tcx, 'v> Visitor<'v> for LocalsAndUses<'a, 'tcx> {
+    fn visit_pat(&mut self, p: &ast::Pat) {
+        {
+            let tcx = self.tcx;
+            let locals = &mut self.locals;
+            let defs = &mut self.defs;
+            pat_util::pat_bindings(&tcx.def_map, p, |_, id, _: Span, _| {
+                locals.push((id, ty::node_id_to_type(tcx, id)));
+                defs.entry(id).or_insert(vec![]).push(id);
+            });
+        }
+        visit::walk_pat(self, p);
+    }
+
+    fn visit_local(&mut self, l: &ast::Local) {
+        if let Some(ref init) = l.init {
+            self.bind(&**init, &[&*l.pat]);
+        }
+        visit::walk_local(self, l);
+    }
+
+    fn visit_block(&mut self, b: &ast::Block) {
+        if let Some(ref e) = b.expr {
+            self.consumers.insert(e.id, Consumer::Node(b.id));
+        }
+        self.parents.push(None);
+        visit::walk_block(self, b);
+        self.parents.pop();
+    }
+
+    fn visit_expr(&mut self, e: &ast::Expr) {
+        if !self.consumers.contains_key(&e.id) {
+            if let Some(&Some(parent)) = self.parents.last() {
+                self.consumers.insert(e.id, Consumer::Node(parent));
+            }
+        }
+
+        match e.node {
+            ast::ExprClosure(..) => {
+                self.has_closures = true;
+            }
+            ast::ExprPath(..) => {
+                if !self.assigned_paths.contains(&e.id) {
+                    if let Some(var_id) = self.local_of(e) {
+                        self.uses.entry(var_id).or_insert(vec![]).push(e.id);
+                    }
+                }
+            }
+            ast::ExprMatch(ref discr, ref arms, _) => {
+                let pats: Vec<_> = arms.iter().flat_map(|arm| arm.pats.iter().map(|p| &**p))
+                                       .collect();
+                self.bind(&**discr, &pats);
+            }
+            ast::ExprAssign(ref lhs, ref rhs) => {
+                let local = self.local_of(&**lhs);
+                if let Some(var_id) = local {
+                    self.assigned_paths.insert(lhs.id);
+                    self.defs.entry(var_id).or_insert(vec![]).push(e.id);
+                }
+                self.consumers.insert(rhs.id, Consumer::Assign(e.id, local));
+            }
+            ast::ExprRet(Some(ref v)) => {
+                self.consumers.insert(v.id, Consumer::Return(e.id));
+            }
+            ast::ExprBlock(ref b) => {
+                self.consumers.insert(b.id, Consumer::Node(e.id));
+            }
+            ast::ExprIf(_, ref then, _) => {
+                self.consumers.insert(then.id, Consumer::Node(e.id));
+            }
+            _ => {}
+        }
+
+        self.parents.push(Some(e.id));
+        visit::walk_expr(self, e);
+        self.parents.pop();
+    }
+
+    // Nested items are checked separately.
+    fn visit_item(&mut self, _: &ast::Item) { }
+}
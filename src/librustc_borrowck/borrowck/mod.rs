@@ -45,6 +45,8 @@ pub mod check_loans;
 
 pub mod gather_loans;
 
+pub mod loan_liveness;
+
 pub mod move_data;
 
 #[derive(Clone, Copy)]
@@ -134,8 +136,11 @@ fn borrowck_fn(this: &mut BorrowckCtxt,
     move_data::fragments::instrument_move_fragments(&flowed_moves.move_data,
                                                     this.tcx, sp, id);
 
+    let liveness = loan_liveness::LoanLiveness::compute(this, &all_loans[..], &cfg, decl, body);
+
     check_loans::check_loans(this,
                              &loan_dfcx,
+                             &liveness,
                              flowed_moves,
                              &all_loans[..],
                              id,
@@ -268,6 +273,13 @@ pub struct Loan<'tcx> {
     /// which roots the loan-path goes out of scope, whichever happens
     /// faster. See also `GatherLoanCtxt::compute_kill_scope`.
     kill_scope: region::CodeExtent,
+
+    /// loan_region is the region of the reference created by the
+    /// borrow, and borrow_id the borrow expression itself. These are
+    /// used to compute where the reference may still be used; see
+    /// `loan_liveness`.
+    loan_region: ty::Region,
+    borrow_id: ast::NodeId,
     span: Span,
     cause: euv::LoanCause,
 }
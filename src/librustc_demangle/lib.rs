@@ -0,0 +1,671 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Demangling of Rust symbol names.
+//!
+//! The compiler mangles symbols in one of two ways, picked with
+//! `-Z symbol-mangling-version`:
+//!
+//! * `legacy` symbols follow the C++ nested-name scheme: `_ZN`, then each
+//!   path component prefixed with its length, then `E`. The last component is
+//!   a hash, and generic arguments aren't encoded at all.
+//! * `v1` symbols start with `_R` and follow the grammar below. They encode
+//!   the crate, the item path, the generic arguments of the instance and the
+//!   impl a method belongs to.
+//!
+//! ```text
+//! <symbol>   = "_R" <path> ["h" {<hex-digit>}]
+//!
+//! <path>     = "C" [<disambiguator>] <ident>          // crate root
+//!            | "N" <path> [<disambiguator>] <ident>   // item nested in <path>
+//!            | "M" <type>                             // <T>, an inherent impl
+//!            | "X" <type> <path>                      // <T as Trait>
+//!            | "I" <path> {<type>} "E"                // generic arguments
+//!
+//! <ident>    = <decimal-number> ["_"] <bytes>         // the "_" is there if
+//!                                                     // <bytes> starts with a
+//!                                                     // digit or "_"
+//! <disambiguator>  = "s" <base-62-number>
+//! <base-62-number> = {<0-9a-zA-Z>} "_"
+//!
+//! <type>     = <basic-type>
+//!            | <path>                                 // named types
+//!            | "R" <type>                             // &T
+//!            | "Q" <type>                             // &mut T
+//!            | "P" <type>                             // *const T
+//!            | "O" <type>                             // *mut T
+//!            | "A" <type> <decimal-number> "_"        // [T; N]
+//!            | "S" <type>                             // [T]
+//!            | "T" {<type>} "E"                       // (A, B)
+//!            | "F" ["U"] {<type>} "E" <type>          // [unsafe] fn(A, B) -> R
+//!            | "D" <path>                             // trait objects
+//!            | "p"                                    // anything else
+//!
+//! <basic-type> = "a" i8   | "b" bool | "c" char | "d" f64   | "e" str
+//!              | "f" f32  | "h" u8   | "i" isize | "j" usize | "l" i32
+//!              | "m" u32  | "n" i128 | "o" u128  | "s" i16   | "t" u16
+//!              | "u" ()   | "x" i64  | "y" u64   | "z" !
+//! ```
+//!
+//! Disambiguators keep otherwise identical paths apart and aren't printed.
+//! Identifiers, like legacy path components, escape the characters that
+//! can't appear in symbols with `$` sequences, e.g. `$LT$` for `<`.
+//!
+//! # Examples
+//!
+//! ```
+//! # #![feature(rustc_private)]
+//! extern crate rustc_demangle;
+//!
+//! use rustc_demangle::demangle;
+//!
+//! fn main() {
+//!     assert_eq!(demangle("_ZN4test1a2bcE").to_string(), "test::a::bc");
+//!     assert_eq!(demangle("_RNMINNCs_5alloc3vec3VechE4push").to_string(),
+//!                "<alloc::vec::Vec<u8>>::push");
+//!     assert_eq!(demangle("printf").to_string(), "printf");
+//! }
+//! ```
+
+// Do not remove on snapshot creation. Needed for bootstrap. (Issue #22364)
+#![cfg_attr(stage0, feature(custom_attribute))]
+#![crate_name = "rustc_demangle"]
+#![unstable(feature = "rustc_private")]
+#![staged_api]
+#![crate_type = "rlib"]
+#![doc(html_logo_url = "http://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
+       html_favicon_url = "http://www.rust-lang.org/favicon.ico",
+       html_root_url = "http://doc.rust-lang.org/nightly/")]
+#![feature(core)]
+#![feature(no_std)]
+#![feature(staged_api)]
+#![no_std]
+
+extern crate core;
+
+#[cfg(test)] #[macro_use] extern crate std;
+
+use core::prelude::*;
+
+use core::char;
+use core::fmt;
+
+/// Symbols nesting deeper than this are treated as invalid rather than
+/// risking a stack overflow.
+const MAX_DEPTH: u32 = 200;
+
+/// A symbol which may or may not be a Rust one. Its `Display` implementation
+/// prints the demangled path, or the symbol itself if it couldn't be
+/// demangled.
+pub struct Demangle<'a> {
+    original: &'a str,
+    style: Style<'a>,
+}
+
+enum Style<'a> {
+    Legacy(&'a str),
+    V1(&'a str),
+    Unknown,
+}
+
+/// Demangles a symbol produced by rustc, in either the legacy or the v1
+/// scheme. Anything else, e.g. a C symbol, is passed through untouched.
+pub fn demangle(s: &str) -> Demangle {
+    let style = if let Some(inner) = legacy_inner(s) {
+        Style::Legacy(inner)
+    } else if let Some(inner) = v1_inner(s) {
+        Style::V1(inner)
+    } else {
+        Style::Unknown
+    };
+    Demangle { original: s, style: style }
+}
+
+impl<'a> Demangle<'a> {
+    /// Returns whether the symbol was recognized as a mangled Rust symbol.
+    pub fn is_rust(&self) -> bool {
+        match self.style {
+            Style::Unknown => false,
+            Style::Legacy(..) | Style::V1(..) => true,
+        }
+    }
+
+    /// Returns the symbol this was created from.
+    pub fn as_str(&self) -> &'a str {
+        self.original
+    }
+}
+
+impl<'a> fmt::Display for Demangle<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.style {
+            Style::Legacy(inner) => print_legacy(inner, f),
+            Style::V1(inner) => {
+                let mut printer = Printer {
+                    parser: Parser { sym: inner, next: 0 },
+                    out: Some(f),
+                    depth: 0,
+                };
+                match printer.print_symbol() {
+                    Ok(()) => Ok(()),
+                    Err(Error::Fmt) => Err(fmt::Error),
+                    // `v1_inner` already made sure the symbol parses.
+                    Err(Error::Invalid) => unreachable!(),
+                }
+            }
+            Style::Unknown => f.write_str(self.original),
+        }
+    }
+}
+
+// Returns the components of a legacy symbol, if it is one.
+fn legacy_inner(s: &str) -> Option<&str> {
+    let inner = if s.len() > 4 && s.starts_with("_ZN") && s.ends_with("E") {
+        &s[3..s.len() - 1]
+    // On Windows, dbghelp strips leading underscores, so we accept "ZN...E"
+    // form too.
+    } else if s.len() > 3 && s.starts_with("ZN") && s.ends_with("E") {
+        &s[2..s.len() - 1]
+    } else {
+        return None
+    };
+
+    // Every component has to be exactly as long as it claims to be.
+    let mut parser = Parser { sym: inner, next: 0 };
+    while parser.next < inner.len() {
+        let len = match parser.decimal() {
+            Ok(len) if len > 0 => len,
+            _ => return None,
+        };
+        match parser.next.checked_add(len) {
+            Some(end) if end <= inner.len() => parser.next = end,
+            _ => return None,
+        }
+    }
+    if inner.bytes().all(|b| b < 0x80) {
+        Some(inner)
+    } else {
+        None
+    }
+}
+
+fn print_legacy(inner: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    let mut parser = Parser { sym: inner, next: 0 };
+    let mut first = true;
+    while parser.next < inner.len() {
+        if !first {
+            try!(f.write_str("::"));
+        }
+        first = false;
+        let len = match parser.decimal() {
+            Ok(len) => len,
+            // `legacy_inner` already made sure the lengths parse.
+            Err(..) => unreachable!(),
+        };
+        let component = &inner[parser.next..parser.next + len];
+        parser.next += len;
+        try!(print_escaped(component, f));
+    }
+    Ok(())
+}
+
+// Returns the part of a v1 symbol after the `_R`, if it is one.
+fn v1_inner(s: &str) -> Option<&str> {
+    let inner = if s.starts_with("_R") {
+        &s[2..]
+    // dbghelp strips the leading underscore here as well.
+    } else if s.starts_with("R") {
+        &s[1..]
+    } else {
+        return None
+    };
+
+    let mut printer = Printer {
+        parser: Parser { sym: inner, next: 0 },
+        out: None,
+        depth: 0,
+    };
+    match printer.print_symbol() {
+        Ok(()) => Some(inner),
+        Err(..) => None,
+    }
+}
+
+// Undoes the `$` escapes of `sanitize` in librustc_trans/back/link.rs. An
+// escape we don't know about is printed as is, along with everything after
+// it.
+fn print_escaped(mut rest: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    while !rest.is_empty() {
+        if !rest.starts_with("$") {
+            let end = rest.find('$').unwrap_or(rest.len());
+            try!(f.write_str(&rest[..end]));
+            rest = &rest[end..];
+            continue
+        }
+
+        let end = match rest[1..].find('$') {
+            Some(i) => i + 1,
+            None => return f.write_str(rest),
+        };
+        let c = match &rest[1..end] {
+            "SP" => '@',
+            "BP" => '*',
+            "RF" => '&',
+            "LT" => '<',
+            "GT" => '>',
+            "LP" => '(',
+            "RP" => ')',
+            "C" => ',',
+            escape if escape.starts_with("u") => {
+                let c = u32::from_str_radix(&escape[1..], 16).ok()
+                                                            .and_then(char::from_u32);
+                match c {
+                    Some(c) => c,
+                    None => return f.write_str(rest),
+                }
+            }
+            _ => return f.write_str(rest),
+        };
+        try!(fmt::Display::fmt(&c, f));
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+enum Error {
+    // The symbol doesn't follow the grammar.
+    Invalid,
+    // Writing to the formatter failed.
+    Fmt,
+}
+
+struct Parser<'s> {
+    sym: &'s str,
+    next: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn peek(&self) -> Option<u8> {
+        self.sym.as_bytes().get(self.next).cloned()
+    }
+
+    fn eat(&mut self, b: u8) -> bool {
+        if self.peek() == Some(b) {
+            self.next += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn next(&mut self) -> Result<u8, Error> {
+        let b = try!(self.peek().ok_or(Error::Invalid));
+        self.next += 1;
+        Ok(b)
+    }
+
+    fn decimal(&mut self) -> Result<usize, Error> {
+        let mut n = match self.peek() {
+            Some(d @ b'0' ... b'9') => (d - b'0') as usize,
+            _ => return Err(Error::Invalid),
+        };
+        self.next += 1;
+        // No leading zeroes.
+        if n == 0 {
+            return Ok(0)
+        }
+        while let Some(d @ b'0' ... b'9') = self.peek() {
+            self.next += 1;
+            n = try!(n.checked_mul(10)
+                      .and_then(|n| n.checked_add((d - b'0') as usize))
+                      .ok_or(Error::Invalid));
+        }
+        Ok(n)
+    }
+
+    fn disambiguator(&mut self) -> Result<(), Error> {
+        if !self.eat(b's') {
+            return Ok(())
+        }
+        loop {
+            match try!(self.next()) {
+                b'_' => return Ok(()),
+                b'0' ... b'9' | b'a' ... b'z' | b'A' ... b'Z' => {}
+                _ => return Err(Error::Invalid),
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Result<&'s str, Error> {
+        let len = try!(self.decimal());
+        if len == 0 {
+            return Err(Error::Invalid)
+        }
+        self.eat(b'_');
+        let start = self.next;
+        let end = match start.checked_add(len) {
+            Some(end) if end <= self.sym.len() => end,
+            _ => return Err(Error::Invalid),
+        };
+        let ident = &self.sym.as_bytes()[start..end];
+        let valid = ident.iter().all(|&b| match b {
+            b'a' ... b'z' | b'A' ... b'Z' | b'0' ... b'9' | b'_' | b'.' | b'$' => true,
+            _ => false,
+        });
+        if !valid {
+            return Err(Error::Invalid)
+        }
+        self.next = end;
+        Ok(&self.sym[start..end])
+    }
+}
+
+// Walks a v1 symbol, printing it as it goes if there's a formatter to print
+// to. Without one it merely checks that the symbol is well-formed.
+struct Printer<'a, 'b: 'a, 's> {
+    parser: Parser<'s>,
+    out: Option<&'a mut fmt::Formatter<'b>>,
+    depth: u32,
+}
+
+impl<'a, 'b, 's> Printer<'a, 'b, 's> {
+    fn print(&mut self, s: &str) -> Result<(), Error> {
+        match self.out {
+            Some(ref mut f) => f.write_str(s).map_err(|_| Error::Fmt),
+            None => Ok(()),
+        }
+    }
+
+    fn print_ident(&mut self, ident: &str) -> Result<(), Error> {
+        match self.out {
+            Some(ref mut f) => print_escaped(ident, f).map_err(|_| Error::Fmt),
+            None => Ok(()),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), Error> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            Err(Error::Invalid)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn print_symbol(&mut self) -> Result<(), Error> {
+        try!(self.print_path());
+        if self.parser.eat(b'h') {
+            let start = self.parser.next;
+            loop {
+                match self.parser.peek() {
+                    Some(b'0' ... b'9') | Some(b'a' ... b'f') => self.parser.next += 1,
+                    _ => break,
+                }
+            }
+            if start == self.parser.next {
+                return Err(Error::Invalid)
+            }
+            let hash = &self.parser.sym[start..self.parser.next];
+            try!(self.print("::h"));
+            try!(self.print(hash));
+        }
+        if self.parser.next != self.parser.sym.len() {
+            return Err(Error::Invalid)
+        }
+        Ok(())
+    }
+
+    fn print_path(&mut self) -> Result<(), Error> {
+        try!(self.enter());
+        match try!(self.parser.next()) {
+            b'C' => {
+                try!(self.parser.disambiguator());
+                let name = try!(self.parser.ident());
+                try!(self.print_ident(name));
+            }
+            b'N' => {
+                try!(self.print_path());
+                try!(self.parser.disambiguator());
+                let name = try!(self.parser.ident());
+                try!(self.print("::"));
+                try!(self.print_ident(name));
+            }
+            b'M' => {
+                try!(self.print("<"));
+                try!(self.print_type());
+                try!(self.print(">"));
+            }
+            b'X' => {
+                try!(self.print("<"));
+                try!(self.print_type());
+                try!(self.print(" as "));
+                try!(self.print_path());
+                try!(self.print(">"));
+            }
+            b'I' => {
+                try!(self.print_path());
+                try!(self.print("<"));
+                try!(self.print_type_list());
+                try!(self.print(">"));
+            }
+            _ => return Err(Error::Invalid),
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+
+    // Prints types up to the closing "E", returning how many there were.
+    fn print_type_list(&mut self) -> Result<usize, Error> {
+        let mut n = 0;
+        while !self.parser.eat(b'E') {
+            if n > 0 {
+                try!(self.print(", "));
+            }
+            try!(self.print_type());
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    fn print_type(&mut self) -> Result<(), Error> {
+        try!(self.enter());
+        let b = try!(self.parser.next());
+        if let Some(name) = basic_type(b) {
+            try!(self.print(name));
+            self.depth -= 1;
+            return Ok(())
+        }
+        match b {
+            b'C' | b'N' | b'M' | b'X' | b'I' => {
+                self.parser.next -= 1;
+                try!(self.print_path());
+            }
+            b'R' => {
+                try!(self.print("&"));
+                try!(self.print_type());
+            }
+            b'Q' => {
+                try!(self.print("&mut "));
+                try!(self.print_type());
+            }
+            b'P' => {
+                try!(self.print("*const "));
+                try!(self.print_type());
+            }
+            b'O' => {
+                try!(self.print("*mut "));
+                try!(self.print_type());
+            }
+            b'A' => {
+                try!(self.print("["));
+                try!(self.print_type());
+                try!(self.print("; "));
+                let start = self.parser.next;
+                try!(self.parser.decimal());
+                let len = &self.parser.sym[start..self.parser.next];
+                try!(self.print(len));
+                if !self.parser.eat(b'_') {
+                    return Err(Error::Invalid)
+                }
+                try!(self.print("]"));
+            }
+            b'S' => {
+                try!(self.print("["));
+                try!(self.print_type());
+                try!(self.print("]"));
+            }
+            b'T' => {
+                try!(self.print("("));
+                if try!(self.print_type_list()) == 1 {
+                    try!(self.print(","));
+                }
+                try!(self.print(")"));
+            }
+            b'F' => {
+                if self.parser.eat(b'U') {
+                    try!(self.print("unsafe "));
+                }
+                try!(self.print("fn("));
+                try!(self.print_type_list());
+                try!(self.print(")"));
+                if !self.parser.eat(b'u') {
+                    try!(self.print(" -> "));
+                    try!(self.print_type());
+                }
+            }
+            b'D' => try!(self.print_path()),
+            b'p' => try!(self.print("_")),
+            _ => return Err(Error::Invalid),
+        }
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+fn basic_type(b: u8) -> Option<&'static str> {
+    Some(match b {
+        b'a' => "i8",
+        b'b' => "bool",
+        b'c' => "char",
+        b'd' => "f64",
+        b'e' => "str",
+        b'f' => "f32",
+        b'h' => "u8",
+        b'i' => "isize",
+        b'j' => "usize",
+        b'l' => "i32",
+        b'm' => "u32",
+        b'n' => "i128",
+        b'o' => "u128",
+        b's' => "i16",
+        b't' => "u16",
+        b'u' => "()",
+        b'x' => "i64",
+        b'y' => "u64",
+        b'z' => "!",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::prelude::v1::*;
+    use super::demangle;
+
+    macro_rules! t {
+        ($a:expr, $b:expr) => (assert_eq!(demangle($a).to_string(), $b))
+    }
+
+    #[test]
+    fn not_rust() {
+        t!("test", "test");
+        t!("printf", "printf");
+        t!("_ZN4test", "_ZN4test");
+        t!("_ZN5testE", "_ZN5testE");
+        t!("_R", "_R");
+        t!("_RC3foo7", "_RC3foo7");
+        t!("_RC3foohxyz", "_RC3foohxyz");
+        assert!(!demangle("test").is_rust());
+    }
+
+    #[test]
+    fn legacy() {
+        t!("_ZN4testE", "test");
+        t!("_ZN4test1a2bcE", "test::a::bc");
+        t!("_ZN3foo3bar17h05af221e174051e9E", "foo::bar::h05af221e174051e9");
+        assert!(demangle("_ZN4testE").is_rust());
+    }
+
+    #[test]
+    fn legacy_dollars() {
+        t!("_ZN4$RP$E", ")");
+        t!("_ZN8$RF$testE", "&test");
+        t!("_ZN8$BP$test4foobE", "*test::foob");
+        t!("_ZN9$u20$test4foobE", " test::foob");
+        t!("_ZN13test$u20$test4foobE", "test test::foob");
+        t!("_ZN12test$BP$test4foobE", "test*test::foob");
+        t!("_ZN20$LT$Vec$LT$T$GT$$GT$4pushE", "<Vec<T>>::push");
+        t!("_ZN8$u3c3$abE", "\u{3c3}ab");
+        t!("_ZN8test$XX$E", "test$XX$");
+    }
+
+    #[test]
+    fn legacy_windows() {
+        t!("ZN4testE", "test");
+        t!("ZN13test$u20$test4foobE", "test test::foob");
+        t!("ZN12test$RF$test4foobE", "test&test::foob");
+    }
+
+    #[test]
+    fn v1_paths() {
+        t!("_RC3std", "std");
+        t!("_RNCs1a_3std2io", "std::io");
+        t!("_RNNCs_3foo3barsK_4main", "foo::bar::main");
+        t!("_RNCs_3foo6__1_2_3", "foo::_1_2_3");
+        t!("_RNCs_3foo3barh0123abcd", "foo::bar::h0123abcd");
+        t!("RNCs_3foo3bar", "foo::bar");
+        assert!(demangle("_RC3std").is_rust());
+    }
+
+    #[test]
+    fn v1_generics() {
+        t!("_RNMINNCs_5alloc3vec3VechE4push",
+           "<alloc::vec::Vec<u8>>::push");
+        t!("_RNMINNCs_5alloc3vec3VecNCs_5alloc6StringE4push",
+           "<alloc::vec::Vec<alloc::String>>::push");
+        t!("_RNXINCs_3foo3BarlECs_5Clone5clone",
+           "<foo::Bar<i32> as Clone>::clone");
+        t!("_RINCs_3foo3bazRSQPOalE",
+           "foo::baz<&[&mut *const *mut i8], i32>");
+        t!("_RINCs_3foo3bazAh16_uTjETbcEE",
+           "foo::baz<[u8; 16], (), (usize,), (bool, char)>");
+        t!("_RINCs_3foo3bazabcdefhijlmnostuxyzE",
+           "foo::baz<i8, bool, char, f64, str, f32, u8, isize, usize, i32, u32, \
+                     i128, u128, i16, u16, (), i64, u64, !>");
+        t!("_RINCs_3foo3bazFUlEuFEzDCs_5TraitpE",
+           "foo::baz<unsafe fn(i32), fn() -> !, Trait, _>");
+    }
+
+    #[test]
+    fn v1_escapes() {
+        t!("_RNCs_3foo12Vec$LT$T$GT$", "foo::Vec<T>");
+        t!("_RNCs_3foos2_10closure.12", "foo::closure.12");
+    }
+
+    #[test]
+    fn v1_too_deep() {
+        let mut s = String::from_str("_RINCs_3foo");
+        for _ in 0..300 {
+            s.push('R');
+        }
+        s.push_str("hE");
+        t!(&s, &s[..]);
+    }
+}
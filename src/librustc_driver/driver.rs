@@ -52,12 +52,15 @@ pub fn compile_input(sess: Session,
                      output: &Option<PathBuf>,
                      addl_plugins: Option<Vec<String>>,
                      control: CompileController) {
-    macro_rules! controller_entry_point{($point: ident, $make_state: expr) => ({
+    macro_rules! controller_entry_point{($point: ident, $tsess: expr, $make_state: expr) => ({
         {
             let state = $make_state;
             (control.$point.callback)(state);
         }
         if control.$point.stop == Compilation::Stop {
+            // Syntax errors are tolerated until the end of analysis; don't
+            // stop early with a successful exit status.
+            $tsess.abort_if_any_errors();
             return;
         }
     })}
@@ -70,6 +73,7 @@ pub fn compile_input(sess: Session,
             let krate = phase_1_parse_input(&sess, cfg, input);
 
             controller_entry_point!(after_parse,
+                                    sess,
                                     CompileState::state_after_parse(input,
                                                                     &sess,
                                                                     outdir,
@@ -96,6 +100,7 @@ pub fn compile_input(sess: Session,
         };
 
         controller_entry_point!(after_expand,
+                                sess,
                                 CompileState::state_after_expand(input,
                                                                  &sess,
                                                                  outdir,
@@ -109,6 +114,7 @@ pub fn compile_input(sess: Session,
         write_out_deps(&sess, input, &outputs, &id[..]);
 
         controller_entry_point!(after_write_deps,
+                                sess,
                                 CompileState::state_after_write_deps(input,
                                                                      &sess,
                                                                      outdir,
@@ -123,6 +129,7 @@ pub fn compile_input(sess: Session,
                                                    control.make_glob_map);
 
         controller_entry_point!(after_analysis,
+                                analysis.ty_cx.sess,
                                 CompileState::state_after_analysis(input,
                                                                    &analysis.ty_cx.sess,
                                                                    outdir,
@@ -134,7 +141,7 @@ pub fn compile_input(sess: Session,
             println!("Pre-trans");
             analysis.ty_cx.print_debug_stats();
         }
-        let (tcx, trans) = phase_4_translate_to_llvm(analysis);
+        let (tcx, trans) = phase_4_translate_to_llvm(analysis, &outputs);
 
         if log_enabled!(::log::INFO) {
             println!("Post-trans");
@@ -149,6 +156,7 @@ pub fn compile_input(sess: Session,
     phase_5_run_llvm_passes(&sess, &trans, &outputs);
 
     controller_entry_point!(after_llvm,
+                            sess,
                             CompileState::state_after_llvm(input,
                                                            &sess,
                                                            outdir,
@@ -358,6 +366,11 @@ pub fn phase_1_parse_input(sess: &Session, cfg: ast::CrateConfig, input: &Input)
         syntax::show_span::run(sess.diagnostic(), s, &krate);
     }
 
+    // The parser recovers from syntax errors. Let the passes that follow
+    // check the recovered crate and report their own errors as well; the
+    // syntax errors stop compilation at the end of analysis.
+    sess.tolerate_errors();
+
     krate
 }
 
@@ -690,7 +703,7 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
          lint::check_crate(&ty_cx, &exported_items));
 
     // The above three passes generate errors w/o aborting
-    ty_cx.sess.abort_if_errors();
+    ty_cx.sess.abort_if_any_errors();
 
     ty::CrateAnalysis {
         export_map: export_map,
@@ -705,7 +718,8 @@ pub fn phase_3_run_analysis_passes<'tcx>(sess: Session,
 
 /// Run the translation phase to LLVM, after which the AST and analysis can
 /// be discarded.
-pub fn phase_4_translate_to_llvm<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
+pub fn phase_4_translate_to_llvm<'tcx>(analysis: ty::CrateAnalysis<'tcx>,
+                                       outputs: &OutputFilenames)
                                        -> (ty::ctxt<'tcx>, trans::CrateTranslation) {
     let time_passes = analysis.ty_cx.sess.time_passes();
 
@@ -714,7 +728,7 @@ pub fn phase_4_translate_to_llvm<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
 
     // Option dance to work around the lack of stack once closures.
     time(time_passes, "translation", analysis, |analysis|
-         trans::trans_crate(analysis))
+         trans::trans_crate(analysis, outputs))
 }
 
 /// Run LLVM itself, producing a bitcode file, assembly file or object file
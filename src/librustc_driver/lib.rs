@@ -70,6 +70,8 @@ use rustc::lint;
 use rustc::metadata;
 use rustc::util::common::time;
 
+use serialize::json::ToJson;
+
 use std::cmp::Ordering::Equal;
 use std::env;
 use std::io::{self, Read, Write};
@@ -424,7 +426,9 @@ impl RustcDefaultCalls {
                         odir: &Option<PathBuf>,
                         ofile: &Option<PathBuf>)
                         -> Compilation {
-        if sess.opts.prints.len() == 0 {
+        // Only `NativeStaticLibs` is printed while compiling, all of the
+        // others are instead of it.
+        if sess.opts.prints.iter().all(|p| *p == PrintRequest::NativeStaticLibs) {
             return Compilation::Continue;
         }
 
@@ -432,6 +436,30 @@ impl RustcDefaultCalls {
         for req in &sess.opts.prints {
             match *req {
                 PrintRequest::Sysroot => println!("{}", sess.sysroot().display()),
+                PrintRequest::TargetList => {
+                    for target in rustc_back::target::get_targets() {
+                        println!("{}", target);
+                    }
+                }
+                PrintRequest::TargetSpec => {
+                    println!("{}", sess.target.target.to_json().pretty());
+                }
+                PrintRequest::Cfg => {
+                    for cfg in &config::build_configuration(sess) {
+                        match cfg.node {
+                            ast::MetaWord(ref word) => println!("{}", word),
+                            ast::MetaNameValue(ref name, ref value) => {
+                                if let ast::LitStr(ref s, _) = value.node {
+                                    println!("{}=\"{}\"", name, s);
+                                }
+                            }
+                            // Can't be given on the command line nor come
+                            // from the target.
+                            ast::MetaList(..) => {}
+                        }
+                    }
+                }
+                PrintRequest::NativeStaticLibs => {}
                 PrintRequest::FileNames |
                 PrintRequest::CrateName => {
                     let input = match input {
@@ -22,6 +22,8 @@ use driver;
 use rustc::middle::ty;
 use rustc::middle::cfg;
 use rustc::middle::cfg::graphviz::LabelledCFG;
+use rustc::middle::ir;
+use rustc::middle::ir::graphviz::LabelledIr;
 use rustc::session::Session;
 use rustc::session::config::Input;
 use rustc::util::ppaux;
@@ -66,9 +68,17 @@ pub enum PpFlowGraphMode {
     UnlabelledEdges,
 }
 #[derive(Copy, Clone, PartialEq, Debug)]
+pub enum PpIrMode {
+    /// Prints the basic-block IR as text.
+    Text,
+    /// Renders the basic-block IR as a graphviz graph.
+    Graphviz,
+}
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub enum PpMode {
     PpmSource(PpSourceMode),
     PpmFlowGraph(PpFlowGraphMode),
+    PpmIr(PpIrMode),
 }
 
 pub fn parse_pretty(sess: &Session,
@@ -87,12 +97,15 @@ pub fn parse_pretty(sess: &Session,
         ("identified", _)   => PpmSource(PpmIdentified),
         ("flowgraph", true)    => PpmFlowGraph(PpFlowGraphMode::Default),
         ("flowgraph,unlabelled", true)    => PpmFlowGraph(PpFlowGraphMode::UnlabelledEdges),
+        ("ir", true)        => PpmIr(PpIrMode::Text),
+        ("ir,dot", true)    => PpmIr(PpIrMode::Graphviz),
         _ => {
             if extended {
                 sess.fatal(&format!(
                     "argument to `xpretty` must be one of `normal`, \
-                     `expanded`, `flowgraph[,unlabelled]=<nodeid>`, `typed`, `identified`, \
-                     `expanded,identified`, or `everybody_loops`; got {}", name));
+                     `expanded`, `flowgraph[,unlabelled]=<nodeid>`, `ir[,dot]=<nodeid>`, \
+                     `typed`, `identified`, `expanded,identified`, or `everybody_loops`; \
+                     got {}", name));
             } else {
                 sess.fatal(&format!(
                     "argument to `pretty` must be one of `normal`, \
@@ -432,7 +445,8 @@ fn needs_ast_map(ppm: &PpMode, opt_uii: &Option<UserIdentifiedItem>) -> bool {
         PpmSource(PpmExpandedIdentified) |
         PpmSource(PpmExpandedHygiene) |
         PpmSource(PpmTyped) |
-        PpmFlowGraph(_) => true
+        PpmFlowGraph(_) |
+        PpmIr(_) => true
     }
 }
 
@@ -446,7 +460,8 @@ fn needs_expansion(ppm: &PpMode) -> bool {
         PpmSource(PpmExpandedIdentified) |
         PpmSource(PpmExpandedHygiene) |
         PpmSource(PpmTyped) |
-        PpmFlowGraph(_) => true
+        PpmFlowGraph(_) |
+        PpmIr(_) => true
     }
 }
 
@@ -643,6 +658,39 @@ pub fn pretty_print_input(sess: Session,
                 }
             }
         }
+
+        (PpmIr(mode), opt_uii) => {
+            debug!("pretty printing IR for {:?}", opt_uii);
+            let uii = opt_uii.unwrap_or_else(|| {
+                sess.fatal("`pretty ir=..` needs NodeId (int) or unique path suffix (b::c::d)")
+            });
+            let ast_map = ast_map.expect("--pretty ir missing ast_map");
+            let nodeid = uii.to_one_node_id("--pretty", &sess, &ast_map);
+
+            let node = ast_map.find(nodeid).unwrap_or_else(|| {
+                sess.fatal(&format!("--pretty ir couldn't find id: {}", nodeid))
+            });
+
+            let out: &mut Write = &mut out;
+            match blocks::Code::from_node(node) {
+                Some(code) => {
+                    let analysis = driver::phase_3_run_analysis_passes(sess,
+                                                                       ast_map,
+                                                                       &arenas,
+                                                                       id,
+                                                                       resolve::MakeGlobMap::No);
+                    print_ir(analysis, code, mode, out)
+                }
+                None => {
+                    let message = format!("--pretty=ir needs block, fn, or method; got {:?}",
+                                          node);
+                    match ast_map.opt_span(nodeid) {
+                        Some(sp) => sess.span_fatal(sp, &message[..]),
+                        None => sess.fatal(&message[..])
+                    }
+                }
+            }
+        }
     }.unwrap();
 
     match ofile {
@@ -657,6 +705,33 @@ pub fn pretty_print_input(sess: Session,
     }
 }
 
+fn print_ir<W: Write>(analysis: ty::CrateAnalysis,
+                      code: blocks::Code,
+                      mode: PpIrMode,
+                      mut out: W) -> io::Result<()> {
+    let ty_cx = &analysis.ty_cx;
+    let ir = match code {
+        blocks::BlockCode(block) => ir::build(ty_cx, &[], &*block),
+        blocks::FnLikeCode(fn_like) => {
+            ir::build(ty_cx, &fn_like.decl().inputs, &*fn_like.body())
+        }
+    };
+    match mode {
+        PpIrMode::Text => ir::pretty::write_ir(ty_cx, &ir, &mut out),
+        PpIrMode::Graphviz => {
+            let lir = LabelledIr {
+                tcx: ty_cx,
+                ir: &ir,
+                name: format!("node_{}", code.id()),
+            };
+            dot::render(&lir, &mut out).map_err(|ioerr| {
+                io::Error::new(io::ErrorKind::Other,
+                               &format!("graphviz::render failed: {}", ioerr)[..])
+            })
+        }
+    }
+}
+
 fn print_flowgraph<W: Write>(variants: Vec<borrowck_dot::Variant>,
                              analysis: ty::CrateAnalysis,
                              code: blocks::Code,
@@ -506,6 +506,8 @@ pub type SMDiagnosticRef = *mut SMDiagnostic_opaque;
 
 pub type DiagnosticHandler = unsafe extern "C" fn(DiagnosticInfoRef, *mut c_void);
 pub type InlineAsmDiagHandler = unsafe extern "C" fn(SMDiagnosticRef, *const c_void, c_uint);
+pub type BitcodeCallback = unsafe extern "C" fn(*mut c_void, *const c_char, size_t);
+pub type ProfileMismatchCallback = unsafe extern "C" fn(*mut c_void, *const c_char);
 
 pub mod debuginfo {
     pub use self::DIDescriptorFlags::*;
@@ -729,6 +731,12 @@ extern {
                                         -> ValueRef;
     pub fn LLVMConstIntGetZExtValue(ConstantVal: ValueRef) -> c_ulonglong;
     pub fn LLVMConstIntGetSExtValue(ConstantVal: ValueRef) -> c_longlong;
+    /// Extends a constant integer of at most 128 bits to 128 bits.
+    pub fn LLVMRustConstInt128Get(ConstantVal: ValueRef,
+                                  SignExtend: Bool,
+                                  High: *mut u64,
+                                  Low: *mut u64)
+                                  -> Bool;
 
 
     /* Operations on composite constants */
@@ -1743,6 +1751,10 @@ extern {
                          AlignStack: Bool,
                          Dialect: c_uint)
                          -> ValueRef;
+    /// Checks that the constraints of inline assembly match its type.
+    pub fn LLVMRustInlineAsmVerify(Ty: TypeRef,
+                                   Constraints: *const c_char)
+                                   -> bool;
 
     pub static LLVMRustDebugMetadataVersion: u32;
 
@@ -1750,6 +1762,8 @@ extern {
                                  name: *const c_char,
                                  value: u32);
 
+    pub fn LLVMRustAddGCOVMetadata(M: ModuleRef, Path: *const c_char);
+
     pub fn LLVMDIBuilderCreate(M: ModuleRef) -> DIBuilderRef;
 
     pub fn LLVMDIBuilderDispose(Builder: DIBuilderRef);
@@ -2025,6 +2039,11 @@ extern {
                                          DisableSimplifyLibCalls: bool);
     pub fn LLVMRustAddLibraryInfo(PM: PassManagerRef, M: ModuleRef,
                                   DisableSimplifyLibCalls: bool);
+    pub fn LLVMRustInstrumentForProfiling(M: ModuleRef, Path: *const c_char);
+    pub fn LLVMRustApplyProfile(M: ModuleRef,
+                                Path: *const c_char,
+                                Callback: ProfileMismatchCallback,
+                                Payload: *mut c_void) -> bool;
     pub fn LLVMRustRunFunctionPassManager(PM: PassManagerRef, M: ModuleRef);
     pub fn LLVMRustWriteOutputFile(T: TargetMachineRef,
                                    PM: PassManagerRef,
@@ -2046,11 +2065,17 @@ extern {
                                       syms: *const *const c_char,
                                       len: size_t);
     pub fn LLVMRustMarkAllFunctionsNounwind(M: ModuleRef);
+    pub fn LLVMRustWriteBitcodeToCallback(M: ModuleRef,
+                                          Callback: BitcodeCallback,
+                                          Payload: *mut c_void);
+    pub fn LLVMRustDropDefinition(V: ValueRef);
 
     pub fn LLVMRustOpenArchive(path: *const c_char) -> ArchiveRef;
     pub fn LLVMRustArchiveReadSection(AR: ArchiveRef, name: *const c_char,
                                       out_len: *mut size_t) -> *const c_char;
     pub fn LLVMRustDestroyArchive(AR: ArchiveRef);
+    pub fn LLVMRustGetObjectSymbols(data: *const c_char, len: size_t,
+                                    s: RustStringRef) -> bool;
 
     pub fn LLVMRustSetDLLExportStorageClass(V: ValueRef);
     pub fn LLVMVersionMajor() -> c_int;
@@ -2080,6 +2105,12 @@ extern {
     pub fn LLVMGetDiagInfoKind(DI: DiagnosticInfoRef) -> DiagnosticKind;
 
     pub fn LLVMWriteDebugLocToString(C: ContextRef, DL: DebugLocRef, s: RustStringRef);
+    pub fn LLVMRustUnpackDebugLoc(C: ContextRef,
+                                  DL: DebugLocRef,
+                                  file_out: RustStringRef,
+                                  line_out: *mut c_uint,
+                                  col_out: *mut c_uint)
+                                  -> bool;
 
     pub fn LLVMSetInlineAsmDiagnosticHandler(C: ContextRef,
                                              H: InlineAsmDiagHandler,
@@ -2237,6 +2268,25 @@ pub fn build_string<F>(f: F) -> Option<String> where F: FnOnce(RustStringRef){
     String::from_utf8(buf.into_inner()).ok()
 }
 
+/// Returns the names of the global symbols defined by an object file, or
+/// `None` if `data` isn't an object file LLVM knows how to read.
+pub fn object_file_symbols(data: &[u8]) -> Option<Vec<Vec<u8>>> {
+    let mut buf = RefCell::new(Vec::new());
+    let ok = unsafe {
+        LLVMRustGetObjectSymbols(data.as_ptr() as *const c_char,
+                                 data.len() as size_t,
+                                 &mut buf as RustStringRepr as RustStringRef)
+    };
+    if !ok {
+        return None
+    }
+    let buf = buf.into_inner();
+    Some(buf.split(|&b| b == 0)
+            .filter(|name| !name.is_empty())
+            .map(|name| name.to_vec())
+            .collect())
+}
+
 pub unsafe fn twine_to_string(tr: TwineRef) -> String {
     build_string(|s| LLVMWriteTwineToString(tr, s))
         .expect("got a non-UTF8 Twine from LLVM")
@@ -2247,6 +2297,16 @@ pub unsafe fn debug_loc_to_string(c: ContextRef, tr: DebugLocRef) -> String {
         .expect("got a non-UTF8 DebugLoc from LLVM")
 }
 
+/// The file, line and column a debug location points at, or `None` if the
+/// location is unknown.
+pub unsafe fn debug_loc_parts(c: ContextRef, dl: DebugLocRef) -> Option<(String, u32, u32)> {
+    let (mut line, mut col) = (0, 0);
+    let mut known = false;
+    let file = build_string(|s| known = LLVMRustUnpackDebugLoc(c, dl, s, &mut line, &mut col))
+        .expect("got a non-UTF8 file name from LLVM");
+    if known { Some((file, line as u32, col as u32)) } else { None }
+}
+
 // The module containing the native LLVM dependencies, generated by the build system
 // Note that this must come after the rustllvm extern declaration so that
 // parts of LLVM that rustllvm depends on aren't thrown away by the linker.
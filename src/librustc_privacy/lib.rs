@@ -272,6 +272,12 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EmbargoVisitor<'a, 'tcx> {
                 if public_ty || public_trait {
                     for impl_item in impl_items {
                         match impl_item.node {
+                            ast::ConstImplItem(..) => {
+                                if (public_ty && impl_item.vis == ast::Public)
+                                    || tr.is_some() {
+                                    self.exported_items.insert(impl_item.id);
+                                }
+                            }
                             ast::MethodImplItem(ref sig, _) => {
                                 let meth_public = match sig.explicit_self.node {
                                     ast::SelfStatic => public_ty,
@@ -306,9 +312,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for EmbargoVisitor<'a, 'tcx> {
                 // fields can be public or private, so lets check
                 for field in &def.fields {
                     let vis = match field.node.kind {
-                        ast::NamedField(_, vis) | ast::UnnamedField(vis) => vis
+                        ast::NamedField(_, ref vis) | ast::UnnamedField(ref vis) => vis
                     };
-                    if vis == ast::Public {
+                    if *vis == ast::Public {
                         self.public_items.insert(field.node.id);
                     }
                 }
@@ -399,6 +405,32 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             debug!("privacy - is {:?} a public method", did);
 
             return match self.tcx.impl_or_trait_items.borrow().get(&did) {
+                Some(&ty::ConstTraitItem(ref ac)) => {
+                    debug!("privacy - it's a const: {:?}", *ac);
+                    match ac.container {
+                        ty::TraitContainer(id) => {
+                            debug!("privacy - recursing on trait {:?}", id);
+                            self.def_privacy(id)
+                        }
+                        ty::ImplContainer(id) => {
+                            match ty::impl_trait_ref(self.tcx, id) {
+                                Some(t) => {
+                                    debug!("privacy - impl of trait {:?}", id);
+                                    self.def_privacy(t.def_id)
+                                }
+                                None => {
+                                    if ac.vis == ast::Public {
+                                        debug!("privacy - it's public");
+                                        Allowable
+                                    } else {
+                                        debug!("privacy - it's private");
+                                        ExternallyDenied
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
                 Some(&ty::MethodTraitItem(ref meth)) => {
                     debug!("privacy - well at least it's a method: {:?}",
                            *meth);
@@ -469,6 +501,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
         // We now know that there is at least one private member between the
         // destination and the root.
         let mut closest_private_id = did.node;
+        let closest_private_vis;
         loop {
             debug!("privacy - examining {}", self.nodestr(closest_private_id));
             let vis = match self.tcx.map.find(closest_private_id) {
@@ -490,6 +523,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                 //               where the method was defined?
                 Some(ast_map::NodeImplItem(ii)) => {
                     match ii.node {
+                        ast::ConstImplItem(..) |
                         ast::MethodImplItem(..) => {
                             let imp = self.tcx.map
                                           .get_parent_did(closest_private_id);
@@ -498,7 +532,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                                 _ if ii.vis == ast::Public => {
                                     return Allowable
                                 }
-                                _ => ii.vis
+                                _ => ii.vis.clone()
                             }
                         }
                         ast::TypeImplItem(_) |
@@ -511,7 +545,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
 
                 // This is not a method call, extract the visibility as one
                 // would normally look at it
-                Some(ast_map::NodeItem(it)) => it.vis,
+                Some(ast_map::NodeItem(it)) => it.vis.clone(),
                 Some(ast_map::NodeForeignItem(_)) => {
                     self.tcx.map.get_foreign_vis(closest_private_id)
                 }
@@ -520,7 +554,10 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
                 }
                 _ => ast::Public,
             };
-            if vis != ast::Public { break }
+            if vis != ast::Public {
+                closest_private_vis = vis;
+                break
+            }
             // if we've reached the root, then everything was allowable and this
             // access is public.
             if closest_private_id == ast::CRATE_NODE_ID { return Allowable }
@@ -531,7 +568,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             if closest_private_id == ast::DUMMY_NODE_ID { return Allowable }
         }
         debug!("privacy - closest priv {}", self.nodestr(closest_private_id));
-        if self.private_accessible(closest_private_id) {
+        if self.private_accessible(closest_private_id, &closest_private_vis) {
             Allowable
         } else {
             DisallowedBy(closest_private_id)
@@ -540,15 +577,27 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
 
     /// For a local private node in the AST, this function will determine
     /// whether the node is accessible by the current module that iteration is
-    /// inside.
-    fn private_accessible(&self, id: ast::NodeId) -> bool {
-        let parent = *self.parents.get(&id).unwrap();
+    /// inside. `vis` is the node's own visibility, which may widen the scope
+    /// from its parent module to the whole crate or to an ancestor module.
+    fn private_accessible(&self, id: ast::NodeId, vis: &ast::Visibility) -> bool {
+        let parent = match *vis {
+            ast::PubCrate => return true,
+            ast::Restricted { id: path_id, .. } => {
+                match self.tcx.def_map.borrow().get(&path_id).map(|d| d.full_def()) {
+                    Some(def::DefMod(did)) => did.node,
+                    _ => self.tcx.sess.bug("restricted visibility path did not \
+                                            resolve to a module"),
+                }
+            }
+            ast::Public | ast::Inherited => *self.parents.get(&id).unwrap(),
+        };
         debug!("privacy - accessible parent {}", self.nodestr(parent));
 
         // After finding `did`'s closest private member, we roll ourselves back
-        // to see if this private member's parent is anywhere in our ancestry.
-        // By the privacy rules, we can access all of our ancestor's private
-        // members, so that's why we test the parent, and not the did itself.
+        // to see if this private member's parent (or the module it is restricted
+        // to) is anywhere in our ancestry. By the privacy rules, we can access
+        // all of our ancestor's private members, so that's why we test the
+        // parent, and not the did itself.
         let mut cur = self.curitem;
         loop {
             debug!("privacy - questioning {}, {}", self.nodestr(cur), cur);
@@ -661,7 +710,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             UnnamedField(idx) => &fields[idx]
         };
         if field.vis == ast::Public ||
-            (is_local(field.id) && self.private_accessible(field.id.node)) {
+            (is_local(field.id) && self.private_accessible(field.id.node, &field.vis)) {
             return
         }
 
@@ -693,7 +742,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             ty::MethodTraitItem(method_type) => {
                 method_type.provided_source.unwrap_or(method_id)
             }
-            ty::TypeTraitItem(_) => method_id,
+            _ => method_id,
         };
 
         let string = token::get_ident(name);
@@ -787,6 +836,7 @@ impl<'a, 'tcx> PrivacyVisitor<'a, 'tcx> {
             def::DefFn(..) => ck("function"),
             def::DefStatic(..) => ck("static"),
             def::DefConst(..) => ck("const"),
+            def::DefAssociatedConst(..) => ck("associated const"),
             def::DefVariant(..) => ck("variant"),
             def::DefTy(_, false) => ck("type"),
             def::DefTy(_, true) => ck("enum"),
@@ -905,7 +955,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivacyVisitor<'a, 'tcx> {
                     let any_priv = fields.iter().any(|f| {
                         f.vis != ast::Public && (
                             !is_local(f.id) ||
-                            !self.private_accessible(f.id.node))
+                            !self.private_accessible(f.id.node, &f.vis))
                     });
                     if any_priv {
                         self.tcx.sess.span_err(expr.span,
@@ -1052,8 +1102,8 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
     /// later on down the road...
     fn check_sane_privacy(&self, item: &ast::Item) {
         let tcx = self.tcx;
-        let check_inherited = |sp: Span, vis: ast::Visibility, note: &str| {
-            if vis != ast::Inherited {
+        let check_inherited = |sp: Span, vis: &ast::Visibility, note: &str| {
+            if *vis != ast::Inherited {
                 tcx.sess.span_err(sp, "unnecessary visibility qualifier");
                 if note.len() > 0 {
                     tcx.sess.span_note(sp, note);
@@ -1064,20 +1114,20 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
             // implementations of traits don't need visibility qualifiers because
             // that's controlled by having the trait in scope.
             ast::ItemImpl(_, _, _, Some(..), _, ref impl_items) => {
-                check_inherited(item.span, item.vis,
+                check_inherited(item.span, &item.vis,
                                 "visibility qualifiers have no effect on trait \
                                  impls");
                 for impl_item in impl_items {
-                    check_inherited(impl_item.span, impl_item.vis, "");
+                    check_inherited(impl_item.span, &impl_item.vis, "");
                 }
             }
 
             ast::ItemImpl(..) => {
-                check_inherited(item.span, item.vis,
+                check_inherited(item.span, &item.vis,
                                 "place qualifiers on individual methods instead");
             }
             ast::ItemForeignMod(..) => {
-                check_inherited(item.span, item.vis,
+                check_inherited(item.span, &item.vis,
                                 "place qualifiers on individual functions \
                                  instead");
             }
@@ -1091,6 +1141,10 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
                                                            visibility");
                             }
                         }
+                        ast::PubCrate | ast::Restricted { .. } => {
+                            tcx.sess.span_err(v.span, "enum variants cannot have \
+                                                       restricted visibility");
+                        }
                         ast::Inherited => {}
                     }
                 }
@@ -1107,26 +1161,27 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
     /// control over anything so this forbids any mention of any visibility
     fn check_all_inherited(&self, item: &ast::Item) {
         let tcx = self.tcx;
-        fn check_inherited(tcx: &ty::ctxt, sp: Span, vis: ast::Visibility) {
-            if vis != ast::Inherited {
+        fn check_inherited(tcx: &ty::ctxt, sp: Span, vis: &ast::Visibility) {
+            if *vis != ast::Inherited {
                 tcx.sess.span_err(sp, "visibility has no effect inside functions");
             }
         }
         let check_struct = |def: &ast::StructDef| {
             for f in &def.fields {
                match f.node.kind {
-                    ast::NamedField(_, p) => check_inherited(tcx, f.span, p),
+                    ast::NamedField(_, ref p) => check_inherited(tcx, f.span, p),
                     ast::UnnamedField(..) => {}
                 }
             }
         };
-        check_inherited(tcx, item.span, item.vis);
+        check_inherited(tcx, item.span, &item.vis);
         match item.node {
             ast::ItemImpl(_, _, _, _, _, ref impl_items) => {
                 for impl_item in impl_items {
                     match impl_item.node {
+                        ast::ConstImplItem(..) |
                         ast::MethodImplItem(..) => {
-                            check_inherited(tcx, impl_item.span, impl_item.vis);
+                            check_inherited(tcx, impl_item.span, &impl_item.vis);
                         }
                         ast::TypeImplItem(_) |
                         ast::MacImplItem(_) => {}
@@ -1135,12 +1190,12 @@ impl<'a, 'tcx> SanePrivacyVisitor<'a, 'tcx> {
             }
             ast::ItemForeignMod(ref fm) => {
                 for i in &fm.items {
-                    check_inherited(tcx, i.span, i.vis);
+                    check_inherited(tcx, i.span, &i.vis);
                 }
             }
             ast::ItemEnum(ref def, _) => {
                 for v in &def.variants {
-                    check_inherited(tcx, v.span, v.node.vis);
+                    check_inherited(tcx, v.span, &v.node.vis);
                 }
             }
 
@@ -1212,8 +1267,8 @@ impl<'a, 'tcx> VisiblePrivateTypesVisitor<'a, 'tcx> {
         }
     }
 
-    fn item_is_public(&self, id: &ast::NodeId, vis: ast::Visibility) -> bool {
-        self.exported_items.contains(id) || vis == ast::Public
+    fn item_is_public(&self, id: &ast::NodeId, vis: &ast::Visibility) -> bool {
+        self.exported_items.contains(id) || *vis == ast::Public
     }
 }
 
@@ -1307,6 +1362,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                     impl_items.iter()
                               .any(|impl_item| {
                                   match impl_item.node {
+                                      ast::ConstImplItem(..) |
                                       ast::MethodImplItem(..) => {
                                           self.exported_items.contains(&impl_item.id)
                                       }
@@ -1330,8 +1386,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                 // don't erroneously report errors for private
                                 // types in private items.
                                 match impl_item.node {
+                                    ast::ConstImplItem(..) |
                                     ast::MethodImplItem(..)
-                                        if self.item_is_public(&impl_item.id, impl_item.vis) =>
+                                        if self.item_is_public(&impl_item.id, &impl_item.vis) =>
                                     {
                                         visit::walk_impl_item(self, impl_item)
                                     }
@@ -1364,6 +1421,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                                     ast::TypeImplItem(ref ty) => {
                                         self.visit_ty(ty);
                                     }
+                                    ast::ConstImplItem(..) |
                                     ast::MethodImplItem(..) |
                                     ast::MacImplItem(_) => {},
                                 }
@@ -1376,9 +1434,15 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
                     let mut found_pub_static = false;
                     for impl_item in impl_items {
                         match impl_item.node {
+                            ast::ConstImplItem(..) => {
+                                if self.item_is_public(&impl_item.id, &impl_item.vis) {
+                                    found_pub_static = true;
+                                    visit::walk_impl_item(self, impl_item);
+                                }
+                            }
                             ast::MethodImplItem(ref sig, _) => {
                                 if sig.explicit_self.node == ast::SelfStatic &&
-                                        self.item_is_public(&impl_item.id, impl_item.vis) {
+                                        self.item_is_public(&impl_item.id, &impl_item.vis) {
                                     found_pub_static = true;
                                     visit::walk_impl_item(self, impl_item);
                                 }
@@ -1399,7 +1463,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
             ast::ItemTy(..) => return,
 
             // not at all public, so we don't care
-            _ if !self.item_is_public(&item.id, item.vis) => {
+            _ if !self.item_is_public(&item.id, &item.vis) => {
                 return;
             }
 
@@ -1462,7 +1526,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for VisiblePrivateTypesVisitor<'a, 'tcx> {
 
     fn visit_struct_field(&mut self, s: &ast::StructField) {
         match s.node.kind {
-            ast::NamedField(_, vis) if vis == ast::Public || self.in_variant => {
+            ast::NamedField(_, ref vis) if *vis == ast::Public || self.in_variant => {
                 visit::walk_struct_field(self, s);
             }
             _ => {}
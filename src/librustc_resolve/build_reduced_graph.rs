@@ -257,12 +257,50 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
         ModuleParentLink(parent.downgrade(), name)
     }
 
+    /// Returns the module that a `pub(crate)` or `pub(in path)` item in
+    /// `parent` is visible in. A path that does not name a module is
+    /// reported when the visibility is resolved; until then the item is
+    /// treated as private.
+    fn restricted_scope(&mut self, vis: &Visibility, parent: &Rc<Module>) -> Option<DefId> {
+        let path = match *vis {
+            ast::PubCrate => return self.graph_root.get_module().def_id.get(),
+            ast::Restricted { ref path, .. } => path,
+            ast::Public | ast::Inherited => return None,
+        };
+
+        // Like `resolve_module_prefix`, start from the item's module for
+        // `self` and `super`, and from the crate root otherwise.
+        let mut module = self.graph_root.get_module();
+        for (i, segment) in path.segments.iter().enumerate() {
+            let name = segment.identifier.name;
+            let string = token::get_name(name);
+            if i == 0 && (&string[..] == "self" || &string[..] == "super") {
+                module = self.get_nearest_normal_module_parent_or_self(parent.clone());
+            }
+            let next = if &string[..] == "self" {
+                Some(module)
+            } else if &string[..] == "super" {
+                self.get_nearest_normal_module_parent(module)
+            } else {
+                module.children.borrow().get(&name).and_then(|bindings| {
+                    bindings.get_module_if_available()
+                })
+            };
+            module = match next {
+                Some(module) => module,
+                None => return None,
+            };
+        }
+        module.def_id.get()
+    }
+
     /// Constructs the reduced graph for one item.
     fn build_reduced_graph_for_item(&mut self, item: &Item, parent: &Rc<Module>) -> Rc<Module> {
         let name = item.ident.name;
         let sp = item.span;
         let is_public = item.vis == ast::Public;
         let modifiers = if is_public { PUBLIC } else { DefModifiers::empty() } | IMPORTABLE;
+        let restricted_to = self.restricted_scope(&item.vis, parent);
 
         match item.node {
             ItemUse(ref view_path) => {
@@ -312,6 +350,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                                     view_path.span,
                                                     item.id,
                                                     is_public,
+                                                    restricted_to,
                                                     shadowable);
                     }
                     ViewPathList(_, ref source_items) => {
@@ -354,6 +393,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                 source_item.span,
                                 source_item.node.id(),
                                 is_public,
+                                restricted_to,
                                 shadowable);
                         }
                     }
@@ -364,6 +404,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                                     view_path.span,
                                                     item.id,
                                                     is_public,
+                                                    restricted_to,
                                                     shadowable);
                     }
                 }
@@ -402,6 +443,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                             false,
                                             is_public,
                                             sp);
+                name_bindings.restrict(TypeNS, restricted_to);
 
                 name_bindings.get_module()
             }
@@ -414,18 +456,22 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                 let mutbl = m == ast::MutMutable;
 
                 name_bindings.define_value(DefStatic(local_def(item.id), mutbl), sp, modifiers);
+                name_bindings.restrict(ValueNS, restricted_to);
                 parent.clone()
             }
             ItemConst(_, _) => {
-                self.add_child(name, parent, ForbidDuplicateValues, sp)
-                    .define_value(DefConst(local_def(item.id)), sp, modifiers);
+                let name_bindings = self.add_child(name, parent, ForbidDuplicateValues, sp);
+
+                name_bindings.define_value(DefConst(local_def(item.id)), sp, modifiers);
+                name_bindings.restrict(ValueNS, restricted_to);
                 parent.clone()
             }
-            ItemFn(_, _, _, _, _) => {
+            ItemFn(_, _, _, _, _, _) => {
                 let name_bindings = self.add_child(name, parent, ForbidDuplicateValues, sp);
 
                 let def = DefFn(local_def(item.id), false);
                 name_bindings.define_value(def, sp, modifiers);
+                name_bindings.restrict(ValueNS, restricted_to);
                 parent.clone()
             }
 
@@ -444,6 +490,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                               false,
                                               is_public,
                                               sp);
+                name_bindings.restrict(TypeNS, restricted_to);
                 parent.clone()
             }
 
@@ -460,6 +507,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                               false,
                                               is_public,
                                               sp);
+                name_bindings.restrict(TypeNS, restricted_to);
 
                 let module = name_bindings.get_module();
 
@@ -484,11 +532,13 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
 
                 // Define a name in the type namespace.
                 name_bindings.define_type(DefTy(local_def(item.id), false), sp, modifiers);
+                name_bindings.restrict(TypeNS, restricted_to);
 
                 // If this is a newtype or unit-like struct, define a name
                 // in the value namespace as well
                 if let Some(cid) = ctor_id {
                     name_bindings.define_value(DefStruct(local_def(cid)), sp, modifiers);
+                    name_bindings.restrict(ValueNS, restricted_to);
                 }
 
                 // Record the def ID and fields of this struct.
@@ -530,6 +580,12 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                                         trait_item.span);
 
                     match trait_item.node {
+                        ast::ConstTraitItem(..) => {
+                            let def = DefAssociatedConst(local_def(trait_item.id),
+                                                         FromTrait(local_def(item.id)));
+                            // NB: not IMPORTABLE
+                            name_bindings.define_value(def, trait_item.span, PUBLIC);
+                        }
                         ast::MethodTraitItem(..) => {
                             let def = DefMethod(local_def(trait_item.id),
                                                 FromTrait(local_def(item.id)));
@@ -549,6 +605,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                 }
 
                 name_bindings.define_type(DefTrait(def_id), sp, modifiers);
+                name_bindings.restrict(TypeNS, restricted_to);
                 parent.clone()
             }
             ItemMac(..) => parent.clone()
@@ -591,6 +648,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
         let name = foreign_item.ident.name;
         let is_public = foreign_item.vis == ast::Public;
         let modifiers = if is_public { PUBLIC } else { DefModifiers::empty() } | IMPORTABLE;
+        let restricted_to = self.restricted_scope(&foreign_item.vis, parent);
         let name_bindings =
             self.add_child(name, parent, ForbidDuplicateValues,
                            foreign_item.span);
@@ -604,6 +662,7 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
             }
         };
         name_bindings.define_value(def, foreign_item.span, modifiers);
+        name_bindings.restrict(ValueNS, restricted_to);
     }
 
     fn build_reduced_graph_for_block(&mut self, block: &Block, parent: &Rc<Module>) -> Rc<Module> {
@@ -637,6 +696,8 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
         debug!("(building reduced graph for \
                 external crate) building external def {}, priv {:?}",
                final_ident, vis);
+        // `pub(crate)` and `pub(in path)` items of other crates are private
+        // to them, so only `pub` makes an external item importable here.
         let is_public = vis == ast::Public;
         let modifiers = if is_public { PUBLIC } else { DefModifiers::empty() } | IMPORTABLE;
         let is_exported = is_public && match new_parent.def_id.get() {
@@ -703,7 +764,8 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                 csearch::get_tuple_struct_definition_if_ctor(&self.session.cstore, ctor_id)
                     .map_or(def, |_| DefStruct(ctor_id)), DUMMY_SP, modifiers);
           }
-          DefFn(..) | DefStatic(..) | DefConst(..) | DefMethod(..) => {
+          DefFn(..) | DefStatic(..) | DefConst(..) | DefAssociatedConst(..) |
+          DefMethod(..) => {
             debug!("(building reduced graph for external \
                     crate) building value (fn/static) {}", final_ident);
             // impl methods have already been defined with the correct importability modifier
@@ -890,12 +952,14 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                               span: Span,
                               id: NodeId,
                               is_public: bool,
+                              restricted_to: Option<DefId>,
                               shadowable: Shadowable) {
         module_.imports.borrow_mut().push(ImportDirective::new(module_path,
                                                                subclass,
                                                                span,
                                                                id,
                                                                is_public,
+                                                               restricted_to,
                                                                shadowable));
         self.unresolved_imports += 1;
         // Bump the reference count on the name. Or, if this is a glob, set
@@ -917,12 +981,13 @@ impl<'a, 'b:'a, 'tcx:'b> GraphBuilder<'a, 'b, 'tcx> {
                         resolution.type_id = id;
                         resolution.value_id = id;
                         resolution.is_public = is_public;
+                        resolution.restricted_to = restricted_to;
                         return;
                     }
                     None => {}
                 }
                 debug!("(building import directive) creating new");
-                let mut resolution = ImportResolution::new(id, is_public);
+                let mut resolution = ImportResolution::new(id, is_public, restricted_to);
                 resolution.outstanding_references = 1;
                 import_resolutions.insert(target, resolution);
             }
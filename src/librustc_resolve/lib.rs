@@ -72,10 +72,10 @@ use syntax::ast::{Local, MethodImplItem, Name, NodeId};
 use syntax::ast::{Pat, PatEnum, PatIdent, PatLit};
 use syntax::ast::{PatRange, PatStruct, Path, PrimTy};
 use syntax::ast::{TraitRef, Ty, TyBool, TyChar, TyF32};
-use syntax::ast::{TyF64, TyFloat, TyIs, TyI8, TyI16, TyI32, TyI64, TyInt};
+use syntax::ast::{TyF64, TyFloat, TyIs, TyI8, TyI16, TyI32, TyI64, TyI128, TyInt};
 use syntax::ast::{TyPath, TyPtr};
-use syntax::ast::{TyRptr, TyStr, TyUs, TyU8, TyU16, TyU32, TyU64, TyUint};
-use syntax::ast::TypeImplItem;
+use syntax::ast::{TyRptr, TyStr, TyUs, TyU8, TyU16, TyU32, TyU64, TyU128, TyUint};
+use syntax::ast::{ConstImplItem, TypeImplItem};
 use syntax::ast;
 use syntax::ast_map;
 use syntax::ast_util::{local_def, walk_pat};
@@ -224,7 +224,16 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Resolver<'a, 'tcx> {
             }
         }
     }
+    fn visit_struct_field(&mut self, field: &ast::StructField) {
+        match field.node.kind {
+            ast::NamedField(_, ref vis) | ast::UnnamedField(ref vis) => {
+                self.resolve_visibility(vis);
+            }
+        }
+        visit::walk_struct_field(self, field);
+    }
     fn visit_foreign_item(&mut self, foreign_item: &ast::ForeignItem) {
+        self.resolve_visibility(&foreign_item.vis);
         let type_parameters = match foreign_item.node {
             ForeignItemFn(_, ref generics) => {
                 HasTypeParameters(generics, FnSpace, ItemRibKind)
@@ -242,7 +251,7 @@ impl<'a, 'v, 'tcx> Visitor<'v> for Resolver<'a, 'tcx> {
                 _: Span,
                 node_id: NodeId) {
         let rib_kind = match function_kind {
-            visit::FkItemFn(_, generics, _, _) => {
+            visit::FkItemFn(_, generics, _, _, _) => {
                 self.visit_generics(generics);
                 ItemRibKind
             }
@@ -449,6 +458,16 @@ impl Module {
         }
     }
 
+    /// Returns true if `scope` is this module or one of its ancestors.
+    fn is_within(&self, scope: DefId) -> bool {
+        self.def_id.get() == Some(scope) || match self.parent_link {
+            NoParentLink => false,
+            ModuleParentLink(ref parent, _) | BlockParentLink(ref parent, _) => {
+                parent.upgrade().map_or(false, |parent| parent.is_within(scope))
+            }
+        }
+    }
+
     fn all_imports_resolved(&self) -> bool {
         self.imports.borrow().len() == self.resolved_import_count.get()
     }
@@ -475,6 +494,7 @@ bitflags! {
 #[derive(Clone,Debug)]
 struct TypeNsDef {
     modifiers: DefModifiers, // see note in ImportResolution about how to use this
+    restricted_to: Option<DefId>, // see note in ImportResolution
     module_def: Option<Rc<Module>>,
     type_def: Option<Def>,
     type_span: Option<Span>
@@ -484,6 +504,7 @@ struct TypeNsDef {
 #[derive(Clone, Copy, Debug)]
 struct ValueNsDef {
     modifiers: DefModifiers, // see note in ImportResolution about how to use this
+    restricted_to: Option<DefId>, // see note in ImportResolution
     def: Def,
     value_span: Option<Span>,
 }
@@ -524,6 +545,7 @@ impl NameBindings {
             None => {
                 *self.type_def.borrow_mut() = Some(TypeNsDef {
                     modifiers: modifiers,
+                    restricted_to: None,
                     module_def: Some(module_),
                     type_def: None,
                     type_span: Some(sp)
@@ -532,6 +554,7 @@ impl NameBindings {
             Some(type_def) => {
                 *self.type_def.borrow_mut() = Some(TypeNsDef {
                     modifiers: modifiers,
+                    restricted_to: None,
                     module_def: Some(module_),
                     type_span: Some(sp),
                     type_def: type_def.type_def
@@ -559,6 +582,7 @@ impl NameBindings {
                                          is_public);
                 *self.type_def.borrow_mut() = Some(TypeNsDef {
                     modifiers: modifiers,
+                    restricted_to: None,
                     module_def: Some(Rc::new(module)),
                     type_def: None,
                     type_span: None,
@@ -574,6 +598,7 @@ impl NameBindings {
                                                  is_public);
                         *self.type_def.borrow_mut() = Some(TypeNsDef {
                             modifiers: modifiers,
+                            restricted_to: None,
                             module_def: Some(Rc::new(module)),
                             type_def: type_def.type_def,
                             type_span: None,
@@ -597,6 +622,7 @@ impl NameBindings {
                     type_def: Some(def),
                     type_span: Some(sp),
                     modifiers: modifiers,
+                    restricted_to: None,
                 });
             }
             Some(type_def) => {
@@ -605,6 +631,7 @@ impl NameBindings {
                     type_def: Some(def),
                     type_span: Some(sp),
                     modifiers: modifiers,
+                    restricted_to: None,
                 });
             }
         }
@@ -617,6 +644,7 @@ impl NameBindings {
             def: def,
             value_span: Some(sp),
             modifiers: modifiers,
+            restricted_to: None,
         });
     }
 
@@ -727,6 +755,34 @@ impl NameBindings {
             }
         }
     }
+
+    /// Restricts the definition in the given namespace to `scope`, the
+    /// module named by its `pub(crate)` or `pub(in path)` visibility.
+    fn restrict(&self, namespace: Namespace, scope: Option<DefId>) {
+        match namespace {
+            TypeNS => {
+                if let Some(ref mut type_def) = *self.type_def.borrow_mut() {
+                    type_def.restricted_to = scope;
+                }
+            }
+            ValueNS => {
+                if let Some(ref mut value_def) = *self.value_def.borrow_mut() {
+                    value_def.restricted_to = scope;
+                }
+            }
+        }
+    }
+
+    /// Returns true if the definition in the given namespace can be named
+    /// from `module_`, either because it is public or because its
+    /// visibility is restricted to `module_` or one of its ancestors.
+    fn is_visible_from(&self, namespace: Namespace, module_: &Module) -> bool {
+        let restricted_to = match namespace {
+            TypeNS => self.type_def.borrow().as_ref().and_then(|def| def.restricted_to),
+            ValueNS => self.value_def.borrow().as_ref().and_then(|def| def.restricted_to),
+        };
+        self.is_public(namespace) || restricted_to.map_or(false, |scope| module_.is_within(scope))
+    }
 }
 
 /// Interns the names of the primitive types.
@@ -749,12 +805,14 @@ impl PrimitiveTypeTable {
         table.intern("i16",     TyInt(TyI16));
         table.intern("i32",     TyInt(TyI32));
         table.intern("i64",     TyInt(TyI64));
+        table.intern("i128",    TyInt(TyI128));
         table.intern("str",     TyStr);
         table.intern("usize",   TyUint(TyUs));
         table.intern("u8",      TyUint(TyU8));
         table.intern("u16",     TyUint(TyU16));
         table.intern("u32",     TyUint(TyU32));
         table.intern("u64",     TyUint(TyU64));
+        table.intern("u128",    TyUint(TyU128));
 
         table
     }
@@ -911,6 +969,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         NameBindings {
             type_def: RefCell::new(Some(TypeNsDef {
                 modifiers: IMPORTABLE,
+                restricted_to: None,
                 module_def: Some(module),
                 type_def: None,
                 type_span: None
@@ -1481,9 +1540,9 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         // Check the list of resolved imports.
         match module_.import_resolutions.borrow().get(&name) {
             Some(import_resolution) if allow_private_imports ||
-                                       import_resolution.is_public => {
+                    import_resolution.is_visible_from(&self.current_module) => {
 
-                if import_resolution.is_public &&
+                if import_resolution.is_visible_from(&self.current_module) &&
                         import_resolution.outstanding_references != 0 {
                     debug!("(resolving name in module) import \
                            unresolved; bailing out");
@@ -1778,6 +1837,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         debug!("(resolving item) resolving {}",
                token::get_name(name));
 
+        self.resolve_visibility(&item.vis);
+
         match item.node {
             ItemEnum(_, ref generics) |
             ItemTy(_, ref generics) |
@@ -1789,7 +1850,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                                                ItemRibKind),
                                              |this| visit::walk_item(this, item));
             }
-            ItemFn(_, _, _, ref generics, _) => {
+            ItemFn(_, _, _, _, ref generics, _) => {
                 self.with_type_parameter_rib(HasTypeParameters(generics,
                                                                FnSpace,
                                                                ItemRibKind),
@@ -1836,6 +1897,14 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         // FIXME #4951: Do we need a node ID here?
 
                         let type_parameters = match trait_item.node {
+                            ast::ConstTraitItem(..) => {
+                                // Associated constants are resolved like
+                                // other constant items.
+                                this.with_constant_rib(|this| {
+                                    visit::walk_trait_item(this, trait_item)
+                                });
+                                continue;
+                            }
                             ast::MethodTraitItem(ref sig, _) => {
                                 HasTypeParameters(&sig.generics,
                                                   FnSpace,
@@ -1886,6 +1955,66 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         }
     }
 
+    /// Resolves the module named by a `pub(in path)` visibility, which must
+    /// be the current module or one of its ancestors.
+    fn resolve_visibility(&mut self, vis: &ast::Visibility) {
+        let (path, id) = match *vis {
+            ast::Restricted { ref path, id } => (path, id),
+            ast::Public | ast::PubCrate | ast::Inherited => return,
+        };
+
+        let module_path = path.segments.iter()
+                                       .map(|ps| ps.identifier.name)
+                                       .collect::<Vec<_>>();
+        let current_module = self.current_module.clone();
+        let module = match self.resolve_module_path(current_module,
+                                                    &module_path[..],
+                                                    DontUseLexicalScope,
+                                                    path.span,
+                                                    PathSearch) {
+            Failed(err) => {
+                let (span, msg) = match err {
+                    Some((span, msg)) => (span, msg),
+                    None => {
+                        let msg = format!("Use of undeclared module `{}`",
+                                          names_to_string(&module_path[..]));
+                        (path.span, msg)
+                    }
+                };
+                self.resolve_error(span, &format!("failed to resolve. {}", msg));
+                return;
+            }
+            Indeterminate => {
+                panic!("indeterminate unexpected");
+            }
+            Success((module, _)) => module,
+        };
+
+        let target = module.def_id.get();
+        let mut current = Some(self.current_module.clone());
+        while let Some(m) = current {
+            if m.def_id.get() == target {
+                self.record_def(id, PathResolution {
+                    base_def: DefMod(target.unwrap()),
+                    last_private: LastMod(AllPublic),
+                    depth: 0
+                });
+                return;
+            }
+            current = match m.parent_link {
+                NoParentLink => None,
+                ModuleParentLink(ref parent, _) |
+                BlockParentLink(ref parent, _) => parent.upgrade(),
+            };
+        }
+
+        self.resolve_error(path.span,
+                           &format!("`{}` is not an ancestor of this item's module; \
+                                     visibility can only be restricted to an \
+                                     enclosing module",
+                                    names_to_string(&module_path[..])));
+    }
+
     fn with_type_parameter_rib<F>(&mut self, type_parameters: TypeParameters, f: F) where
         F: FnOnce(&mut Resolver),
     {
@@ -2083,7 +2212,17 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
                 this.with_current_self_type(self_type, |this| {
                     for impl_item in impl_items {
+                        this.resolve_visibility(&impl_item.vis);
                         match impl_item.node {
+                            ConstImplItem(..) => {
+                                // If this is a trait impl, ensure the const
+                                // exists in trait
+                                this.check_trait_item(impl_item.ident.name,
+                                                      impl_item.span);
+                                this.with_constant_rib(|this| {
+                                    visit::walk_impl_item(this, impl_item);
+                                });
+                            }
                             MethodImplItem(ref sig, _) => {
                                 // If this is a trait impl, ensure the method
                                 // exists in trait
@@ -2319,6 +2458,10 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         let msg = format!("use of undeclared {} `{}`", kind,
                                           path_names_to_string(path, 0));
                         self.resolve_error(ty.span, &msg[..]);
+                        if maybe_qself.is_none() && path.segments.len() == 1 {
+                            let name = path.segments[0].identifier.name;
+                            self.suggest_imports(ty.span, name, TypeNS);
+                        }
                     }
                 }
             }
@@ -2668,7 +2811,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
         // Next, search import resolutions.
         match containing_module.import_resolutions.borrow().get(&name) {
-            Some(import_resolution) if import_resolution.is_public => {
+            Some(import_resolution) if import_resolution.is_visible_from(&self.current_module) => {
                 if let Some(target) = (*import_resolution).target_for_namespace(namespace) {
                     match target.bindings.def_for_namespace(namespace) {
                         Some(def) => {
@@ -3074,6 +3217,100 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         }
     }
 
+    /// Searches all modules of the crate, and of the crates it links with
+    /// `extern crate`, for public items named `name` in the namespace `ns`.
+    /// Returns the paths the items can be imported through, shortest first.
+    fn lookup_candidates(&mut self, name: Name, ns: Namespace) -> Vec<String> {
+        let mut candidates = Vec::new();
+        let mut seen_modules = DefIdSet();
+
+        // Walk the module graph breadth-first, so that each module is
+        // reached through its shortest path.
+        let mut worklist = vec![(self.graph_root.get_module(), Vec::new())];
+        let mut i = 0;
+        while i < worklist.len() {
+            let (module, path) = worklist[i].clone();
+            i += 1;
+
+            // External modules are only read from metadata when needed.
+            build_reduced_graph::populate_module_if_necessary(self, &module);
+            let is_local = module.def_id.get().map_or(true, |did| did.krate == ast::LOCAL_CRATE);
+
+            let children = module.children.borrow().iter()
+                                 .map(|(&child_name, bindings)| (child_name, bindings.clone()))
+                                 .collect::<Vec<_>>();
+            for (child_name, bindings) in children {
+                let is_item = match bindings.def_for_namespace(ns) {
+                    Some(DefMod(..)) | None => false,
+                    Some(_) => true,
+                };
+                if child_name == name && is_item && bindings.defined_in_public_namespace(ns) {
+                    let mut item_path = path.clone();
+                    item_path.push(child_name);
+                    candidates.push(item_path);
+                }
+
+                let child_module = match bindings.get_module_if_available() {
+                    Some(child_module) => child_module,
+                    None => continue,
+                };
+                match child_module.kind.get() {
+                    NormalModuleKind | EnumModuleKind => {}
+                    _ => continue,
+                }
+                // Private modules of other crates cannot be imported from.
+                if !is_local && !child_module.is_public {
+                    continue;
+                }
+                if let Some(did) = child_module.def_id.get() {
+                    if !seen_modules.insert(did) {
+                        continue;
+                    }
+                }
+                let mut child_path = path.clone();
+                child_path.push(child_name);
+                worklist.push((child_module, child_path));
+            }
+
+            if is_local {
+                for (&crate_name, crate_module) in module.external_module_children.borrow().iter() {
+                    if let Some(did) = crate_module.def_id.get() {
+                        if !seen_modules.insert(did) {
+                            continue;
+                        }
+                    }
+                    let mut crate_path = path.clone();
+                    crate_path.push(crate_name);
+                    worklist.push((crate_module.clone(), crate_path));
+                }
+            }
+        }
+
+        let mut candidates = candidates.iter()
+                                       .map(|path| (path.len(), names_to_string(path)))
+                                       .collect::<Vec<_>>();
+        candidates.sort();
+        candidates.dedup();
+        candidates.into_iter().map(|(_, path)| path).collect()
+    }
+
+    /// Points out the imports that would bring an unresolved `name` into
+    /// scope, if there are any.
+    fn suggest_imports(&mut self, span: Span, name: Name, ns: Namespace) {
+        if !self.emit_errors {
+            return;
+        }
+        let candidates = self.lookup_candidates(name, ns);
+        if candidates.is_empty() {
+            return;
+        }
+        let imports = candidates.iter()
+                                .map(|path| format!("use {};", path))
+                                .collect::<Vec<_>>();
+        self.session.fileline_help(span, &format!("possible candidates: {}",
+                                                  imports.connect(" ")));
+    }
+
     fn resolve_expr(&mut self, expr: &Expr) {
         // First, record candidate traits for this expression if it could
         // result in the invocation of a method call.
@@ -3225,6 +3462,9 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                     expr.span,
                                     &format!("unresolved name `{}`{}",
                                              path_name, msg));
+                                if path.segments.len() == 1 {
+                                    self.suggest_imports(expr.span, last_name, ValueNS);
+                                }
                             }
                         }
                     }
@@ -10,7 +10,7 @@
 
 use self::ImportDirectiveSubclass::*;
 
-use {PUBLIC, IMPORTABLE};
+use IMPORTABLE;
 use Module;
 use Namespace::{self, TypeNS, ValueNS};
 use NameBindings;
@@ -58,6 +58,7 @@ pub struct ImportDirective {
     pub span: Span,
     pub id: NodeId,
     pub is_public: bool, // see note in ImportResolution about how to use this
+    pub restricted_to: Option<DefId>, // see note in ImportResolution
     pub shadowable: Shadowable,
 }
 
@@ -67,6 +68,7 @@ impl ImportDirective {
            span: Span,
            id: NodeId,
            is_public: bool,
+           restricted_to: Option<DefId>,
            shadowable: Shadowable)
            -> ImportDirective {
         ImportDirective {
@@ -75,6 +77,7 @@ impl ImportDirective {
             span: span,
             id: id,
             is_public: is_public,
+            restricted_to: restricted_to,
             shadowable: shadowable,
         }
     }
@@ -109,6 +112,12 @@ pub struct ImportResolution {
     /// testing occurs during a later phase of compilation.
     pub is_public: bool,
 
+    /// The module that a `pub(crate) use` or `pub(in path) use` makes this
+    /// resolution visible in. Like `is_public`, this only decides which
+    /// names other modules can import or resolve through this one; the
+    /// privacy pass still checks every use.
+    pub restricted_to: Option<DefId>,
+
     // The number of outstanding references to this name. When this reaches
     // zero, outside modules can count on the targets being correct. Before
     // then, all bets are off; future imports could override this name.
@@ -131,7 +140,7 @@ pub struct ImportResolution {
 }
 
 impl ImportResolution {
-    pub fn new(id: NodeId, is_public: bool) -> ImportResolution {
+    pub fn new(id: NodeId, is_public: bool, restricted_to: Option<DefId>) -> ImportResolution {
         ImportResolution {
             type_id: id,
             value_id: id,
@@ -139,9 +148,17 @@ impl ImportResolution {
             value_target: None,
             type_target: None,
             is_public: is_public,
+            restricted_to: restricted_to,
         }
     }
 
+    /// Returns true if this resolution can be named from `module_`, either
+    /// because it came from a `pub use` or because it is restricted to
+    /// `module_` or one of its ancestors.
+    pub fn is_visible_from(&self, module_: &Module) -> bool {
+        self.is_public || self.restricted_to.map_or(false, |scope| module_.is_within(scope))
+    }
+
     pub fn target_for_namespace(&self, namespace: Namespace)
                                 -> Option<Target> {
         match namespace {
@@ -484,14 +501,16 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                             if import_resolution.outstanding_references == 0 => {
 
                         fn get_binding(this: &mut Resolver,
+                                       module_: &Module,
                                        import_resolution: &ImportResolution,
                                        namespace: Namespace,
                                        source: &Name)
                                     -> NamespaceResult {
 
-                            // Import resolutions must be declared with "pub"
-                            // in order to be exported.
-                            if !import_resolution.is_public {
+                            // Import resolutions must be declared with "pub",
+                            // or restricted to an ancestor of the importing
+                            // module, in order to be exported.
+                            if !import_resolution.is_visible_from(module_) {
                                 return UnboundResult;
                             }
 
@@ -525,6 +544,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         // resolved. We can, therefore, just follow it.
                         if value_result.is_unknown() {
                             value_result = get_binding(self.resolver,
+                                                       module_,
                                                        import_resolution,
                                                        ValueNS,
                                                        &source);
@@ -532,6 +552,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         }
                         if type_result.is_unknown() {
                             type_result = get_binding(self.resolver,
+                                                      module_,
                                                       import_resolution,
                                                       TypeNS,
                                                       &source);
@@ -634,6 +655,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                                       directive.shadowable));
                         import_resolution.set_target_and_id(namespace, target, directive.id);
                         import_resolution.is_public = directive.is_public;
+                        import_resolution.restricted_to = directive.restricted_to;
                         *used_public = name_bindings.defined_in_public_namespace(namespace);
                     }
                     UnboundResult => { /* Continue. */ }
@@ -714,6 +736,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                            -> ResolveResult<()> {
         let id = import_directive.id;
         let is_public = import_directive.is_public;
+        let restricted_to = import_directive.restricted_to;
 
         // This function works in a highly imperative manner; it eagerly adds
         // everything it can to the list of import resolutions of the module
@@ -738,7 +761,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                    token::get_name(*ident),
                    module_to_string(module_));
 
-            if !target_import_resolution.is_public {
+            if !target_import_resolution.is_visible_from(module_) {
                 debug!("(resolving glob import) nevermind, just kidding");
                 continue
             }
@@ -775,13 +798,14 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                         }
                     }
                     dest_import_resolution.is_public = is_public;
+                    dest_import_resolution.restricted_to = restricted_to;
                     continue;
                 }
                 None => {}
             }
 
             // Simple: just copy the old import resolution.
-            let mut new_import_resolution = ImportResolution::new(id, is_public);
+            let mut new_import_resolution = ImportResolution::new(id, is_public, restricted_to);
             new_import_resolution.value_target =
                 target_import_resolution.value_target.clone();
             new_import_resolution.type_target =
@@ -834,10 +858,11 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
                                name_bindings: Rc<NameBindings>) {
         let id = import_directive.id;
         let is_public = import_directive.is_public;
+        let restricted_to = import_directive.restricted_to;
 
         let mut import_resolutions = module_.import_resolutions.borrow_mut();
         let dest_import_resolution = import_resolutions.entry(name)
-            .or_insert_with(|| ImportResolution::new(id, is_public));
+            .or_insert_with(|| ImportResolution::new(id, is_public, restricted_to));
 
         debug!("(resolving glob import) writing resolution `{}` in `{}` \
                to `{}`",
@@ -848,7 +873,8 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         // Merge the child item into the import resolution.
         {
             let mut merge_child_item = |namespace| {
-                if name_bindings.defined_in_namespace_with(namespace, IMPORTABLE | PUBLIC) {
+                if name_bindings.defined_in_namespace_with(namespace, IMPORTABLE) &&
+                        name_bindings.is_visible_from(namespace, module_) {
                     let namespace_name = match namespace {
                         TypeNS => "type",
                         ValueNS => "value",
@@ -875,6 +901,7 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         }
 
         dest_import_resolution.is_public = is_public;
+        dest_import_resolution.restricted_to = restricted_to;
 
         self.check_for_conflicts_between_imports_and_items(
             module_,
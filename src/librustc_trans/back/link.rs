@@ -8,13 +8,15 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::archive::{Archive, ArchiveBuilder, ArchiveConfig, METADATA_FILENAME};
-use super::archive;
+use super::archive::{Archive, ArchiveBuilder, ArchiveConfig, ArchiveKind};
+use super::archive::METADATA_FILENAME;
+use super::linker::{self, LinkCommand, Linker};
+use super::mangle;
 use super::rpath;
 use super::rpath::RPathConfig;
 use super::svh::Svh;
 use session::config;
-use session::config::NoDebugInfo;
+use session::config::{NoDebugInfo, SymbolManglingVersion};
 use session::config::{OutputFilenames, Input, OutputTypeBitcode, OutputTypeExe, OutputTypeObject};
 use session::search_paths::PathKind;
 use session::Session;
@@ -22,13 +24,13 @@ use metadata::common::LinkMeta;
 use metadata::{encoder, cstore, filesearch, csearch, creader};
 use metadata::filesearch::FileDoesntMatch;
 use trans::{CrateContext, CrateTranslation, gensym_name};
+use middle::subst::Substs;
 use middle::ty::{self, Ty};
 use util::common::time;
 use util::ppaux;
 use util::sha2::{Digest, Sha256};
 use rustc_back::tempdir::TempDir;
 
-use std::ffi::OsString;
 use std::fs::{self, PathExt};
 use std::io::{self, Read, Write};
 use std::mem;
@@ -321,6 +323,11 @@ pub fn exported_name(path: PathElems, hash: &str) -> String {
 
 pub fn mangle_exported_name<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, path: PathElems,
                                       t: Ty<'tcx>, id: ast::NodeId) -> String {
+    if uses_v1_mangling(ccx) {
+        // The crate hash in the crate root takes the place of the type hash.
+        return mangle::mangle_item(ccx, id, &Substs::trans_empty(), Some(id as u64), None);
+    }
+
     let mut hash = get_symbol_hash(ccx, t);
 
     // Paths can be completely identical for different nodes,
@@ -347,17 +354,29 @@ pub fn mangle_exported_name<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>, path: PathEl
 pub fn mangle_internal_name_by_type_and_seq<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                                                       t: Ty<'tcx>,
                                                       name: &str) -> String {
+    let hash = get_symbol_hash(ccx, t);
+    if uses_v1_mangling(ccx) {
+        return mangle::mangle_type_item(ccx, t, name, &hash);
+    }
     let s = ppaux::ty_to_string(ccx.tcx(), t);
     let path = [PathName(token::intern(&s[..])),
                 gensym_name(name)];
-    let hash = get_symbol_hash(ccx, t);
     mangle(path.iter().cloned(), Some(&hash[..]))
 }
 
-pub fn mangle_internal_name_by_path_and_seq(path: PathElems, flav: &str) -> String {
+pub fn mangle_internal_name_by_path_and_seq<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                                      path: PathElems,
+                                                      flav: &str) -> String {
+    if uses_v1_mangling(ccx) {
+        return mangle::mangle_nested_item(ccx, path, flav);
+    }
     mangle(path.chain(Some(gensym_name(flav)).into_iter()), None)
 }
 
+pub fn uses_v1_mangling(ccx: &CrateContext) -> bool {
+    ccx.sess().opts.debugging_opts.symbol_mangling_version == SymbolManglingVersion::V1
+}
+
 pub fn get_cc_prog(sess: &Session) -> String {
     match sess.opts.cg.linker {
         Some(ref linker) => return linker.to_string(),
@@ -530,6 +549,16 @@ fn archive_search_paths(sess: &Session) -> Vec<PathBuf> {
     return search;
 }
 
+// The OSX linker only understands the BSD flavor of archives, everything else
+// gets the GNU one.
+fn archive_kind(sess: &Session) -> ArchiveKind {
+    if sess.target.target.options.is_like_osx {
+        ArchiveKind::Bsd
+    } else {
+        ArchiveKind::Gnu
+    }
+}
+
 // Create an 'rlib'
 //
 // An rlib in its current incarnation is essentially a renamed .a file. The
@@ -547,7 +576,7 @@ fn link_rlib<'a>(sess: &'a Session,
         lib_search_paths: archive_search_paths(sess),
         slib_prefix: sess.target.target.options.staticlib_prefix.clone(),
         slib_suffix: sess.target.target.options.staticlib_suffix.clone(),
-        maybe_ar_prog: sess.opts.cg.ar.clone()
+        kind: archive_kind(sess),
     };
     let mut ab = ArchiveBuilder::create(config);
     ab.add_file(obj_filename).unwrap();
@@ -565,14 +594,6 @@ fn link_rlib<'a>(sess: &'a Session,
     // symbol table of the archive.
     ab.update_symbols();
 
-    let mut ab = match sess.target.target.options.is_like_osx {
-        // For OSX/iOS, we must be careful to update symbols only when adding
-        // object files.  We're about to start adding non-object files, so run
-        // `ar` now to process the object files.
-        true => ab.build().extend(),
-        false => ab,
-    };
-
     // Note that it is important that we add all of our non-object "magical
     // files" *after* all of the object files in the archive. The reason for
     // this is as follows:
@@ -670,13 +691,6 @@ fn link_rlib<'a>(sess: &'a Session,
                     remove(sess, &bc_filename);
                 }
             }
-
-            // After adding all files to the archive, we need to update the
-            // symbol table of the archive. This currently dies on OSX (see
-            // #11162), and isn't necessary there anyway
-            if !sess.target.target.options.is_like_osx {
-                ab.update_symbols();
-            }
         }
 
         None => {}
@@ -732,17 +746,17 @@ fn write_rlib_bytecode_object_v1(writer: &mut Write,
 // link in the metadata object file (and also don't prepare the archive with a
 // metadata file).
 fn link_staticlib(sess: &Session, obj_filename: &Path, out_filename: &Path) {
-    let ab = link_rlib(sess, None, obj_filename, out_filename);
-    let mut ab = match sess.target.target.options.is_like_osx {
-        true => ab.build().extend(),
-        false => ab,
-    };
+    let mut ab = link_rlib(sess, None, obj_filename, out_filename);
     if sess.target.target.options.morestack {
         ab.add_native_library("morestack").unwrap();
     }
     if !sess.target.target.options.no_compiler_rt {
         ab.add_native_library("compiler-rt").unwrap();
     }
+    if sess.opts.cg.profile_generate.is_some() ||
+       sess.opts.debugging_opts.profile {
+        ab.add_native_library("profiler-rt").unwrap();
+    }
 
     let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
     let mut all_native_libs = vec![];
@@ -765,6 +779,11 @@ fn link_staticlib(sess: &Session, obj_filename: &Path, out_filename: &Path) {
     ab.update_symbols();
     let _ = ab.build();
 
+    if sess.opts.prints.contains(&config::PrintRequest::NativeStaticLibs) {
+        print_native_static_libs(sess, &all_native_libs);
+        return
+    }
+
     if !all_native_libs.is_empty() {
         sess.note("link against the following native artifacts when linking against \
                   this static library");
@@ -782,6 +801,50 @@ fn link_staticlib(sess: &Session, obj_filename: &Path, out_filename: &Path) {
     }
 }
 
+// Prints the linker arguments for the native libraries which whatever the
+// static library gets linked into has to link to as well, see
+// `--print native-static-libs`. Static native libraries are already in the
+// archive.
+fn print_native_static_libs(sess: &Session,
+                            all_native_libs: &[(cstore::NativeLibraryKind, String)]) {
+    let local_libs = sess.cstore.get_used_libraries().borrow();
+    let local_libs = local_libs.iter().map(|&(ref lib, kind)| (kind, lib));
+    let upstream_libs = all_native_libs.iter().map(|&(kind, ref lib)| (kind, lib));
+
+    let args = local_libs.chain(upstream_libs).filter_map(|(kind, lib)| {
+        match kind {
+            cstore::NativeStatic => None,
+            cstore::NativeUnknown => Some(format!("-l{}", lib)),
+            cstore::NativeFramework => Some(format!("-framework {}", lib)),
+        }
+    }).collect::<Vec<_>>();
+    println!("{}", args.connect(" "));
+}
+
+// The sanitizer runtimes replace `malloc` and friends and have to be present
+// exactly once in a process, so they only get linked into executables. Their
+// contents are all needed even though nothing references them directly,
+// hence the --whole-archive.
+fn add_sanitizer_runtime(cmd: &mut Linker, sess: &Session,
+                         sanitizer: config::Sanitizer, dylib: bool) {
+    if dylib {
+        return
+    }
+    let runtime = format!("{}-rt", match sanitizer {
+        config::Sanitizer::Address => "asan",
+        config::Sanitizer::Leak => "lsan",
+        config::Sanitizer::Memory => "msan",
+        config::Sanitizer::Thread => "tsan",
+    });
+    cmd.link_whole_staticlib(&runtime, &archive_search_paths(sess));
+    // The interceptors in the runtime have to be visible to shared libraries
+    // loaded by the program.
+    cmd.export_dynamic();
+    for lib in &["pthread", "rt", "m", "dl"] {
+        cmd.link_dylib(lib);
+    }
+}
+
 // Create a dynamic library or executable
 //
 // This will invoke the system linker/cc to create the resulting file. This
@@ -792,14 +855,42 @@ fn link_natively(sess: &Session, trans: &CrateTranslation, dylib: bool,
 
     // The invocations of cc share some flags across platforms
     let pname = get_cc_prog(sess);
-    let mut cmd = Command::new(&pname[..]);
-
-    cmd.args(&sess.target.target.options.pre_link_args);
-    link_args(&mut cmd, sess, dylib, tmpdir.path(),
-              trans, obj_filename, out_filename);
-    cmd.args(&sess.target.target.options.post_link_args);
-    if !sess.target.target.options.no_compiler_rt {
-        cmd.arg("-lcompiler-rt");
+    let mut cmd = LinkCommand::new(&pname[..]);
+
+    {
+        let mut linker = linker::for_command(sess, &mut cmd);
+        // Targets which link their libc statically also bring their own C
+        // runtime objects rather than relying on the ones of the system's
+        // compiler, these are shipped in the target's lib dir.
+        let root = sess.target_filesearch(PathKind::Native).get_lib_path();
+        linker.args(&sess.target.target.options.pre_link_args);
+        for obj in &sess.target.target.options.pre_link_objects {
+            linker.add_object(&root.join(obj));
+        }
+        link_args(&mut *linker, sess, dylib, tmpdir.path(),
+                  trans, obj_filename, out_filename);
+        for obj in &sess.target.target.options.post_link_objects {
+            linker.add_object(&root.join(obj));
+        }
+        linker.args(&sess.target.target.options.post_link_args);
+        if sess.opts.cg.profile_generate.is_some() {
+            // The instrumented code only references the profiling runtime on
+            // some platforms, so make sure the linker pulls it in; it's what
+            // writes out the profile when the program exits.
+            if !sess.target.target.options.is_like_osx {
+                linker.force_undefined("__llvm_profile_runtime");
+            }
+        }
+        if sess.opts.cg.profile_generate.is_some() ||
+           sess.opts.debugging_opts.profile {
+            linker.link_staticlib("profiler-rt");
+        }
+        if !sess.target.target.options.no_compiler_rt {
+            linker.link_staticlib("compiler-rt");
+        }
+        if let Some(sanitizer) = sess.opts.debugging_opts.sanitizer {
+            add_sanitizer_runtime(&mut *linker, sess, sanitizer, dylib);
+        }
     }
 
     if sess.opts.debugging_opts.print_link_args {
@@ -809,9 +900,17 @@ fn link_natively(sess: &Session, trans: &CrateTranslation, dylib: bool,
     // May have not found libraries in the right formats.
     sess.abort_if_errors();
 
+    // Long command lines go into a response file in the temporary directory
+    let mut command = match cmd.command(tmpdir.path()) {
+        Ok(command) => command,
+        Err(e) => {
+            sess.fatal(&format!("failed to write the linker's response file: {}", e));
+        }
+    };
+
     // Invoke the system linker
     debug!("{:?}", &cmd);
-    let prog = time(sess.time_passes(), "running linker", (), |()| cmd.output());
+    let prog = time(sess.time_passes(), "running linker", (), |()| command.output());
     match prog {
         Ok(prog) => {
             if !prog.status.success() {
@@ -847,9 +946,32 @@ fn link_natively(sess: &Session, trans: &CrateTranslation, dylib: bool,
             }
         }
     }
+
+    // Package the .dwo files of everything linked in, upstream crates
+    // included, into `out_filename.dwp`, where debuggers look for it.
+    if sess.split_debuginfo() == config::SplitDebuginfo::Packed {
+        let mut dwp = out_filename.as_os_str().to_os_string();
+        dwp.push(".dwp");
+        let mut cmd = Command::new("dwp");
+        cmd.arg("-e").arg(out_filename).arg("-o").arg(&dwp);
+        match cmd.output() {
+            Ok(prog) => {
+                if !prog.status.success() {
+                    sess.err(&format!("packaging debuginfo with `{:?}` failed: {}",
+                                      cmd, prog.status));
+                    sess.note(str::from_utf8(&prog.stderr).unwrap());
+                    sess.abort_if_errors();
+                }
+            }
+            Err(e) => {
+                sess.err(&format!("failed to run dwp: {}", e));
+                sess.abort_if_errors();
+            }
+        }
+    }
 }
 
-fn link_args(cmd: &mut Command,
+fn link_args(cmd: &mut Linker,
              sess: &Session,
              dylib: bool,
              tmpdir: &Path,
@@ -864,9 +986,10 @@ fn link_args(cmd: &mut Command,
     // target descriptor
     let t = &sess.target.target;
 
-    cmd.arg("-L").arg(&lib_path);
+    cmd.include_path(&lib_path);
 
-    cmd.arg("-o").arg(out_filename).arg(obj_filename);
+    cmd.output_filename(out_filename);
+    cmd.add_object(obj_filename);
 
 
     // Stack growth requires statically linking a __morestack function. Note
@@ -886,47 +1009,24 @@ fn link_args(cmd: &mut Command,
     // will include the __morestack symbol 100% of the time, always resolving
     // references to it even if the object above didn't use it.
     if t.options.morestack {
-        if t.options.is_like_osx {
-            let morestack = lib_path.join("libmorestack.a");
-
-            let mut v = OsString::from("-Wl,-force_load,");
-            v.push(&morestack);
-            cmd.arg(&v);
-        } else {
-            cmd.args(&["-Wl,--whole-archive", "-lmorestack", "-Wl,--no-whole-archive"]);
-        }
+        cmd.link_whole_staticlib("morestack", &[lib_path.clone()]);
     }
 
     // When linking a dynamic library, we put the metadata into a section of the
     // executable. This metadata is in a separate object file from the main
     // object file, so we link that in here.
     if dylib {
-        cmd.arg(&obj_filename.with_extension("metadata.o"));
-    }
-
-    if t.options.is_like_osx {
-        // The dead_strip option to the linker specifies that functions and data
-        // unreachable by the entry point will be removed. This is quite useful
-        // with Rust's compilation model of compiling libraries at a time into
-        // one object file. For example, this brings hello world from 1.7MB to
-        // 458K.
-        //
-        // Note that this is done for both executables and dynamic libraries. We
-        // won't get much benefit from dylibs because LLVM will have already
-        // stripped away as much as it could. This has not been seen to impact
-        // link times negatively.
-        //
-        // -dead_strip can't be part of the pre_link_args because it's also used for partial
-        // linking when using multiple codegen units (-r). So we insert it here.
-        cmd.arg("-Wl,-dead_strip");
+        cmd.add_object(&obj_filename.with_extension("metadata.o"));
     }
 
     // If we're building a dylib, we don't use --gc-sections because LLVM has
     // already done the best it can do, and we also don't want to eliminate the
     // metadata. If we're building an executable, however, --gc-sections drops
     // the size of hello world from 1.8MB to 597K, a 67% reduction.
-    if !dylib && !t.options.is_like_osx {
-        cmd.arg("-Wl,--gc-sections");
+    cmd.gc_sections(dylib);
+
+    if sess.opts.debugging_opts.icf {
+        cmd.identical_code_folding();
     }
 
     let used_link_args = sess.cstore.get_used_link_args().borrow();
@@ -941,26 +1041,25 @@ fn link_args(cmd: &mut Command,
                 || *sess.opts.cg.relocation_model.as_ref()
                    .unwrap_or(&empty_str) == "pic")
             && !args.any(|x| *x == "-static") {
-            cmd.arg("-pie");
+            cmd.position_independent_executable();
         }
     }
 
-    if t.options.linker_is_gnu {
-        // GNU-style linkers support optimization with -O. GNU ld doesn't need a
-        // numeric argument, but other linkers do.
-        if sess.opts.optimize == config::Default ||
-           sess.opts.optimize == config::Aggressive {
-            cmd.arg("-Wl,-O1");
-        }
-    }
+    cmd.optimize();
+    cmd.no_default_libraries();
 
-    // We want to prevent the compiler from accidentally leaking in any system
-    // libraries, so we explicitly ask gcc to not link to any libraries by
-    // default. Note that this does not happen for windows because windows pulls
-    // in some large number of libraries and I couldn't quite figure out which
-    // subset we wanted.
-    if !t.options.is_like_windows {
-        cmd.arg("-nodefaultlibs");
+    // Freestanding programs have no C runtime to start them, they define
+    // their own entry point and usually need a linker script to put it
+    // where the loader or the hardware will look for it.
+    if t.target_os == "none" {
+        cmd.no_crt_objects();
+    }
+    if let Some(ref script) = sess.opts.cg.link_script {
+        if !script.is_file() {
+            sess.fatal(&format!("linker script `{}` does not exist",
+                                script.display()));
+        }
+        cmd.link_script(script);
     }
 
     // Mark all dynamic libraries and executables as compatible with ASLR
@@ -1009,18 +1108,7 @@ fn link_args(cmd: &mut Command,
     // # Telling the linker what we're doing
 
     if dylib {
-        // On mac we need to tell the linker to let this library be rpathed
-        if sess.target.target.options.is_like_osx {
-            cmd.args(&["-dynamiclib", "-Wl,-dylib"]);
-
-            if sess.opts.cg.rpath {
-                let mut v = OsString::from("-Wl,-install_name,@rpath/");
-                v.push(out_filename.file_name().unwrap());
-                cmd.arg(&v);
-            }
-        } else {
-            cmd.arg("-shared");
-        }
+        cmd.build_dylib(out_filename);
     }
 
     // FIXME (#2397): At some point we want to rpath our guesses as to
@@ -1045,7 +1133,7 @@ fn link_args(cmd: &mut Command,
             get_install_prefix_lib_path: &mut get_install_prefix_lib_path,
             realpath: &mut ::util::fs::realpath
         };
-        cmd.args(&rpath::get_rpath_flags(&mut rpath_config));
+        cmd.cc_args(&rpath::get_rpath_flags(&mut rpath_config));
     }
 
     // Finally add all the linker arguments provided on the command line along
@@ -1066,21 +1154,15 @@ fn link_args(cmd: &mut Command,
 // Also note that the native libraries linked here are only the ones located
 // in the current crate. Upstream crates with native library dependencies
 // may have their native library pulled in above.
-fn add_local_native_libraries(cmd: &mut Command, sess: &Session) {
+fn add_local_native_libraries(cmd: &mut Linker, sess: &Session) {
     sess.target_filesearch(PathKind::All).for_each_lib_search_path(|path, k| {
         match k {
-            PathKind::Framework => { cmd.arg("-F").arg(path); }
-            _ => { cmd.arg("-L").arg(path); }
+            PathKind::Framework => { cmd.framework_path(path); }
+            _ => { cmd.include_path(path); }
         }
         FileDoesntMatch
     });
 
-    // Some platforms take hints about whether a library is static or dynamic.
-    // For those that support this, we ensure we pass the option if the library
-    // was flagged "static" (most defaults are dynamic) to ensure that if
-    // libfoo.a and libfoo.so both exist that the right one is chosen.
-    let takes_hints = !sess.target.target.options.is_like_osx;
-
     let libs = sess.cstore.get_used_libraries();
     let libs = libs.borrow();
 
@@ -1091,45 +1173,30 @@ fn add_local_native_libraries(cmd: &mut Command, sess: &Session) {
         kind != cstore::NativeStatic
     });
 
-    // Platforms that take hints generally also support the --whole-archive
-    // flag. We need to pass this flag when linking static native libraries to
-    // ensure the entire library is included.
+    // Some platforms take hints about whether a library is static or dynamic.
+    // For those that support this, we ensure we pass the option if the library
+    // was flagged "static" (most defaults are dynamic) to ensure that if
+    // libfoo.a and libfoo.so both exist that the right one is chosen.
     //
-    // For more details see #15460, but the gist is that the linker will strip
-    // away any unused objects in the archive if we don't otherwise explicitly
-    // reference them. This can occur for libraries which are just providing
-    // bindings, libraries with generic functions, etc.
-    if takes_hints {
-        cmd.arg("-Wl,--whole-archive").arg("-Wl,-Bstatic");
-    }
+    // Static native libraries are also linked in whole, to ensure the entire
+    // library is included. For more details see #15460, but the gist is that
+    // the linker will strip away any unused objects in the archive if we don't
+    // otherwise explicitly reference them. This can occur for libraries which
+    // are just providing bindings, libraries with generic functions, etc.
+    cmd.hint_static();
     let search_path = archive_search_paths(sess);
     for l in staticlibs {
-        if takes_hints {
-            cmd.arg(&format!("-l{}", l));
-        } else {
-            // -force_load is the OSX equivalent of --whole-archive, but it
-            // involves passing the full path to the library to link.
-            let lib = archive::find_library(&l[..],
-                                            &sess.target.target.options.staticlib_prefix,
-                                            &sess.target.target.options.staticlib_suffix,
-                                            &search_path[..],
-                                            &sess.diagnostic().handler);
-            let mut v = OsString::from("-Wl,-force_load,");
-            v.push(&lib);
-            cmd.arg(&v);
-        }
-    }
-    if takes_hints {
-        cmd.arg("-Wl,--no-whole-archive").arg("-Wl,-Bdynamic");
+        cmd.link_whole_staticlib(l, &search_path);
     }
+    cmd.hint_dynamic();
 
     for &(ref l, kind) in others {
         match kind {
             cstore::NativeUnknown => {
-                cmd.arg(&format!("-l{}", l));
+                cmd.link_dylib(l);
             }
             cstore::NativeFramework => {
-                cmd.arg("-framework").arg(&l[..]);
+                cmd.link_framework(l);
             }
             cstore::NativeStatic => unreachable!(),
         }
@@ -1141,7 +1208,7 @@ fn add_local_native_libraries(cmd: &mut Command, sess: &Session) {
 // Rust crates are not considered at all when creating an rlib output. All
 // dependencies will be linked when producing the final output (instead of
 // the intermediate rlib version)
-fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
+fn add_upstream_rust_crates(cmd: &mut Linker, sess: &Session,
                             dylib: bool, tmpdir: &Path,
                             trans: &CrateTranslation) {
     // All of the heavy lifting has previously been accomplished by the
@@ -1182,7 +1249,7 @@ fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
 
     }
 
-    // Converts a library file-stem into a cc -l argument
+    // Converts a library file-stem into a -l argument
     fn unlib<'a>(config: &config::Config, stem: &'a str) -> &'a str {
         if stem.starts_with("lib") && !config.target.options.is_like_windows {
             &stem[3..]
@@ -1192,7 +1259,7 @@ fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
     }
 
     // Adds the static "rlib" versions of all crates to the command line.
-    fn add_static_crate(cmd: &mut Command, sess: &Session, tmpdir: &Path,
+    fn add_static_crate(cmd: &mut Linker, sess: &Session, tmpdir: &Path,
                         cratepath: &Path) {
         // When performing LTO on an executable output, all of the
         // bytecode from the upstream libraries has already been
@@ -1248,22 +1315,22 @@ fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
                     lib_search_paths: archive_search_paths(sess),
                     slib_prefix: sess.target.target.options.staticlib_prefix.clone(),
                     slib_suffix: sess.target.target.options.staticlib_suffix.clone(),
-                    maybe_ar_prog: sess.opts.cg.ar.clone()
+                    kind: archive_kind(sess),
                 };
                 let mut archive = Archive::open(config);
                 archive.remove_file(&format!("{}.o", name));
                 let files = archive.files();
                 if files.iter().any(|s| s.ends_with(".o")) {
-                    cmd.arg(&dst);
+                    cmd.add_object(&dst);
                 }
             });
         } else {
-            cmd.arg(cratepath);
+            cmd.add_object(cratepath);
         }
     }
 
     // Same thing as above, but for dynamic crates instead of static crates.
-    fn add_dynamic_crate(cmd: &mut Command, sess: &Session, cratepath: &Path) {
+    fn add_dynamic_crate(cmd: &mut Linker, sess: &Session, cratepath: &Path) {
         // If we're performing LTO, then it should have been previously required
         // that all upstream rust dependencies were available in an rlib format.
         assert!(!sess.lto());
@@ -1271,10 +1338,10 @@ fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
         // Just need to tell the linker about where the library lives and
         // what its name is
         if let Some(dir) = cratepath.parent() {
-            cmd.arg("-L").arg(dir);
+            cmd.include_path(dir);
         }
         let filestem = cratepath.file_stem().unwrap().to_str().unwrap();
-        cmd.arg(&format!("-l{}", unlib(&sess.target, filestem)));
+        cmd.link_dylib(unlib(&sess.target, filestem));
     }
 }
 
@@ -1296,7 +1363,7 @@ fn add_upstream_rust_crates(cmd: &mut Command, sess: &Session,
 // generic function calls a native function, then the generic function must
 // be instantiated in the target crate, meaning that the native symbol must
 // also be resolved in the target crate.
-fn add_upstream_native_libraries(cmd: &mut Command, sess: &Session) {
+fn add_upstream_native_libraries(cmd: &mut Linker, sess: &Session) {
     // Be sure to use a topological sorting of crates because there may be
     // interdependencies between native libraries. When passing -nodefaultlibs,
     // for example, almost all native libraries depend on libc, so we have to
@@ -1312,11 +1379,10 @@ fn add_upstream_native_libraries(cmd: &mut Command, sess: &Session) {
         for &(kind, ref lib) in &libs {
             match kind {
                 cstore::NativeUnknown => {
-                    cmd.arg(&format!("-l{}", *lib));
+                    cmd.link_dylib(lib);
                 }
                 cstore::NativeFramework => {
-                    cmd.arg("-framework");
-                    cmd.arg(&lib[..]);
+                    cmd.link_framework(lib);
                 }
                 cstore::NativeStatic => {
                     sess.bug("statics shouldn't be propagated");
@@ -0,0 +1,390 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Linker command lines.
+//!
+//! `back::link` decides *what* goes into the final artifact; the `Linker`
+//! implementations here know how to spell that for a particular kind of
+//! linker. There are two flavors: `GccLinker` drives the linker through a
+//! cc-style compiler driver, passing linker options along with `-Wl,`, and
+//! `LdLinker` runs a GNU-compatible `ld` (bfd, gold or lld) directly. The
+//! target spec's `linker_flavor` picks between them.
+
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use back::archive;
+use rustc_back::target::LinkerFlavor;
+use session::config;
+use session::Session;
+
+// Windows limits a whole command line to 32k characters, which is also well
+// below what unix systems accept, so longer command lines are passed in a
+// response file instead.
+const MAX_COMMAND_LINE_LEN: usize = 32 * 1024;
+
+/// A linker invocation under construction.
+///
+/// The arguments are kept around, rather than pushed straight into a
+/// `Command`, so that they can be moved into a response file when there are
+/// too many of them.
+pub struct LinkCommand {
+    program: String,
+    args: Vec<OsString>,
+}
+
+impl LinkCommand {
+    pub fn new(program: &str) -> LinkCommand {
+        LinkCommand { program: program.to_string(), args: Vec::new() }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut LinkCommand {
+        self.args.push(arg.as_ref().to_os_string());
+        self
+    }
+
+    pub fn args<S: AsRef<OsStr>>(&mut self, args: &[S]) -> &mut LinkCommand {
+        for arg in args {
+            self.arg(arg);
+        }
+        self
+    }
+
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Builds the `Command` to run. If the arguments don't comfortably fit on
+    /// a command line they're written to `linker-arguments` in `tmpdir`, and
+    /// the linker is passed `@path/to/linker-arguments` instead, which both
+    /// cc drivers and `ld` understand.
+    pub fn command(&self, tmpdir: &Path) -> io::Result<Command> {
+        let mut cmd = Command::new(&self.program);
+        let len = self.args.iter().fold(self.program.len(), |len, arg| {
+            len + arg.to_string_lossy().len() + 1
+        });
+        if len <= MAX_COMMAND_LINE_LEN {
+            cmd.args(&self.args);
+            return Ok(cmd)
+        }
+
+        let path = tmpdir.join("linker-arguments");
+        let mut contents = String::new();
+        for arg in &self.args {
+            let arg = try!(arg.to_str().ok_or_else(|| {
+                io::Error::new(io::ErrorKind::InvalidInput,
+                               "linker argument is not valid unicode")
+            }));
+            escape_response_file_arg(&mut contents, arg);
+            contents.push('\n');
+        }
+        try!(File::create(&path).and_then(|mut f| f.write_all(contents.as_bytes())));
+
+        let mut file_arg = OsString::from("@");
+        file_arg.push(&path);
+        cmd.arg(&file_arg);
+        Ok(cmd)
+    }
+}
+
+impl fmt::Debug for LinkCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(write!(f, "{:?}", self.program));
+        for arg in &self.args {
+            try!(write!(f, " {:?}", arg));
+        }
+        Ok(())
+    }
+}
+
+// Response files are split on whitespace, with quotes grouping words and a
+// backslash escaping the character after it.
+fn escape_response_file_arg(out: &mut String, arg: &str) {
+    for c in arg.chars() {
+        match c {
+            '\\' | '\'' | '"' | ' ' | '\t' | '\n' | '\r' => out.push('\\'),
+            _ => {}
+        }
+        out.push(c);
+    }
+}
+
+/// Returns the `Linker` which knows how to drive the program of `cmd`, as
+/// given by the target's linker flavor.
+pub fn for_command<'a>(sess: &'a Session, cmd: &'a mut LinkCommand) -> Box<Linker + 'a> {
+    match sess.target.target.options.linker_flavor {
+        LinkerFlavor::Gcc => Box::new(GccLinker { cmd: cmd, sess: sess }) as Box<Linker + 'a>,
+        LinkerFlavor::Ld => Box::new(LdLinker { cmd: cmd, sess: sess }) as Box<Linker + 'a>,
+    }
+}
+
+/// The operations `back::link` needs to describe a link, independent of how
+/// a particular linker spells them.
+pub trait Linker {
+    /// Appends arguments verbatim, such as those from the target spec or
+    /// `-C link-args`.
+    fn args(&mut self, args: &[String]);
+    /// Appends arguments written for a cc-style driver, such as rpath flags.
+    fn cc_args(&mut self, args: &[String]) {
+        self.args(args)
+    }
+
+    fn output_filename(&mut self, path: &Path);
+    fn add_object(&mut self, path: &Path);
+    fn include_path(&mut self, path: &Path);
+    fn framework_path(&mut self, path: &Path);
+
+    fn link_dylib(&mut self, lib: &str);
+    fn link_staticlib(&mut self, lib: &str);
+    fn link_framework(&mut self, framework: &str);
+    /// Links all of the static library `lib`, not just the objects which
+    /// resolve an undefined symbol.
+    fn link_whole_staticlib(&mut self, lib: &str, search_path: &[PathBuf]);
+    /// Prefer static libraries for the following `-l`s.
+    fn hint_static(&mut self);
+    /// Go back to preferring dynamic libraries.
+    fn hint_dynamic(&mut self);
+
+    /// Drops unreferenced sections. Dylibs keep everything, their metadata
+    /// included, except where the linker can only strip both or neither.
+    fn gc_sections(&mut self, is_dylib: bool);
+    /// Folds identical functions into one.
+    fn identical_code_folding(&mut self);
+    fn optimize(&mut self);
+    fn position_independent_executable(&mut self);
+    fn no_default_libraries(&mut self);
+    /// Leaves out the C runtime's startup objects (`crt1.o` and friends), the
+    /// program brings its own entry point.
+    fn no_crt_objects(&mut self);
+    /// Lays out the output as described by the linker script at `path`.
+    fn link_script(&mut self, path: &Path);
+    fn build_dylib(&mut self, out_filename: &Path);
+    fn export_dynamic(&mut self);
+    /// Treats `symbol` as undefined, pulling in whatever defines it.
+    fn force_undefined(&mut self, symbol: &str);
+}
+
+pub struct GccLinker<'a> {
+    cmd: &'a mut LinkCommand,
+    sess: &'a Session,
+}
+
+impl<'a> GccLinker<'a> {
+    fn is_like_osx(&self) -> bool {
+        self.sess.target.target.options.is_like_osx
+    }
+}
+
+impl<'a> Linker for GccLinker<'a> {
+    fn args(&mut self, args: &[String]) { self.cmd.args(args); }
+    fn output_filename(&mut self, path: &Path) { self.cmd.arg("-o").arg(path); }
+    fn add_object(&mut self, path: &Path) { self.cmd.arg(path); }
+    fn include_path(&mut self, path: &Path) { self.cmd.arg("-L").arg(path); }
+    fn framework_path(&mut self, path: &Path) { self.cmd.arg("-F").arg(path); }
+
+    fn link_dylib(&mut self, lib: &str) { self.cmd.arg(&format!("-l{}", lib)); }
+    fn link_staticlib(&mut self, lib: &str) { self.cmd.arg(&format!("-l{}", lib)); }
+    fn link_framework(&mut self, framework: &str) {
+        self.cmd.arg("-framework").arg(framework);
+    }
+
+    fn link_whole_staticlib(&mut self, lib: &str, search_path: &[PathBuf]) {
+        if self.is_like_osx() {
+            // -force_load is the OSX equivalent of --whole-archive, but it
+            // involves passing the full path to the library to link.
+            let lib = archive::find_library(lib,
+                                            &self.sess.target.target.options.staticlib_prefix,
+                                            &self.sess.target.target.options.staticlib_suffix,
+                                            search_path,
+                                            &self.sess.diagnostic().handler);
+            let mut v = OsString::from("-Wl,-force_load,");
+            v.push(&lib);
+            self.cmd.arg(&v);
+        } else {
+            self.cmd.arg("-Wl,--whole-archive")
+                    .arg(&format!("-l{}", lib))
+                    .arg("-Wl,--no-whole-archive");
+        }
+    }
+
+    // OSX's linker doesn't take hints about whether a library is static or
+    // dynamic.
+    fn hint_static(&mut self) {
+        if !self.is_like_osx() {
+            self.cmd.arg("-Wl,-Bstatic");
+        }
+    }
+    fn hint_dynamic(&mut self) {
+        if !self.is_like_osx() {
+            self.cmd.arg("-Wl,-Bdynamic");
+        }
+    }
+
+    fn gc_sections(&mut self, is_dylib: bool) {
+        // The dead_strip option to the linker specifies that functions and data
+        // unreachable by the entry point will be removed. This is quite useful
+        // with Rust's compilation model of compiling libraries at a time into
+        // one object file. For example, this brings hello world from 1.7MB to
+        // 458K.
+        //
+        // Note that this is done for both executables and dynamic libraries. We
+        // won't get much benefit from dylibs because LLVM will have already
+        // stripped away as much as it could. This has not been seen to impact
+        // link times negatively.
+        //
+        // -dead_strip can't be part of the pre_link_args because it's also used for partial
+        // linking when using multiple codegen units (-r). So we insert it here.
+        if self.is_like_osx() {
+            self.cmd.arg("-Wl,-dead_strip");
+        } else if !is_dylib {
+            self.cmd.arg("-Wl,--gc-sections");
+        }
+    }
+
+    fn identical_code_folding(&mut self) {
+        if self.is_like_osx() {
+            self.sess.warn("identical code folding is not supported by the OSX linker");
+        } else {
+            self.cmd.arg("-Wl,--icf=all");
+        }
+    }
+
+    fn optimize(&mut self) {
+        if !self.sess.target.target.options.linker_is_gnu { return }
+
+        // GNU-style linkers support optimization with -O. GNU ld doesn't need a
+        // numeric argument, but other linkers do.
+        if self.sess.opts.optimize == config::Default ||
+           self.sess.opts.optimize == config::Aggressive {
+            self.cmd.arg("-Wl,-O1");
+        }
+    }
+
+    fn position_independent_executable(&mut self) { self.cmd.arg("-pie"); }
+
+    fn no_default_libraries(&mut self) {
+        // We want to prevent the compiler from accidentally leaking in any
+        // system libraries, so we explicitly ask gcc to not link to any
+        // libraries by default. Note that this does not happen for windows
+        // because windows pulls in some large number of libraries and I
+        // couldn't quite figure out which subset we wanted.
+        if !self.sess.target.target.options.is_like_windows {
+            self.cmd.arg("-nodefaultlibs");
+        }
+    }
+
+    fn no_crt_objects(&mut self) { self.cmd.arg("-nostartfiles"); }
+
+    fn link_script(&mut self, path: &Path) {
+        if self.is_like_osx() {
+            self.sess.err("linker scripts are not supported by the OSX linker");
+        } else {
+            self.cmd.arg("-T").arg(path);
+        }
+    }
+
+    fn build_dylib(&mut self, out_filename: &Path) {
+        // On mac we need to tell the linker to let this library be rpathed
+        if self.is_like_osx() {
+            self.cmd.args(&["-dynamiclib", "-Wl,-dylib"]);
+
+            if self.sess.opts.cg.rpath {
+                let mut v = OsString::from("-Wl,-install_name,@rpath/");
+                v.push(out_filename.file_name().unwrap());
+                self.cmd.arg(&v);
+            }
+        } else {
+            self.cmd.arg("-shared");
+        }
+    }
+
+    fn export_dynamic(&mut self) { self.cmd.arg("-Wl,--export-dynamic"); }
+
+    fn force_undefined(&mut self, symbol: &str) {
+        self.cmd.arg(&format!("-Wl,-u,{}", symbol));
+    }
+}
+
+pub struct LdLinker<'a> {
+    cmd: &'a mut LinkCommand,
+    sess: &'a Session,
+}
+
+impl<'a> Linker for LdLinker<'a> {
+    fn args(&mut self, args: &[String]) { self.cmd.args(args); }
+
+    // Unwraps the options a cc driver would have passed on with `-Wl,`.
+    fn cc_args(&mut self, args: &[String]) {
+        for arg in args {
+            if arg.starts_with("-Wl,") {
+                for part in arg[4..].split(',') {
+                    self.cmd.arg(part);
+                }
+            } else {
+                self.cmd.arg(arg);
+            }
+        }
+    }
+
+    fn output_filename(&mut self, path: &Path) { self.cmd.arg("-o").arg(path); }
+    fn add_object(&mut self, path: &Path) { self.cmd.arg(path); }
+    fn include_path(&mut self, path: &Path) { self.cmd.arg("-L").arg(path); }
+
+    fn framework_path(&mut self, _path: &Path) {
+        self.sess.fatal("frameworks are not supported by the `ld` linker flavor");
+    }
+
+    fn link_dylib(&mut self, lib: &str) { self.cmd.arg(&format!("-l{}", lib)); }
+    fn link_staticlib(&mut self, lib: &str) { self.cmd.arg(&format!("-l{}", lib)); }
+
+    fn link_framework(&mut self, _framework: &str) {
+        self.sess.fatal("frameworks are not supported by the `ld` linker flavor");
+    }
+
+    fn link_whole_staticlib(&mut self, lib: &str, _search_path: &[PathBuf]) {
+        self.cmd.arg("--whole-archive")
+                .arg(&format!("-l{}", lib))
+                .arg("--no-whole-archive");
+    }
+
+    fn hint_static(&mut self) { self.cmd.arg("-Bstatic"); }
+    fn hint_dynamic(&mut self) { self.cmd.arg("-Bdynamic"); }
+
+    fn gc_sections(&mut self, is_dylib: bool) {
+        if !is_dylib {
+            self.cmd.arg("--gc-sections");
+        }
+    }
+
+    fn identical_code_folding(&mut self) { self.cmd.arg("--icf=all"); }
+
+    fn optimize(&mut self) {
+        if self.sess.opts.optimize == config::Default ||
+           self.sess.opts.optimize == config::Aggressive {
+            self.cmd.arg("-O1");
+        }
+    }
+
+    fn position_independent_executable(&mut self) { self.cmd.arg("-pie"); }
+
+    // ld only links what it's told to, so there are no defaults to disable.
+    fn no_default_libraries(&mut self) {}
+    fn no_crt_objects(&mut self) {}
+
+    fn link_script(&mut self, path: &Path) { self.cmd.arg("-T").arg(path); }
+
+    fn build_dylib(&mut self, _out_filename: &Path) { self.cmd.arg("-shared"); }
+    fn export_dynamic(&mut self) { self.cmd.arg("--export-dynamic"); }
+    fn force_undefined(&mut self, symbol: &str) { self.cmd.arg("-u").arg(symbol); }
+}
@@ -13,40 +13,371 @@ use super::write;
 use rustc::session::{self, config};
 use llvm;
 use llvm::archive_ro::ArchiveRO;
-use llvm::{ModuleRef, TargetMachineRef, True, False};
+use llvm::{ModuleRef, TargetMachineRef, ValueRef, True, False};
 use rustc::metadata::cstore;
-use rustc::util::common::time;
+use rustc::session::config::OutputFilenames;
+use rustc::util::common::{time, path2cstr};
+use trans::ModuleTranslation;
 
 use libc;
 use flate;
 
-use std::ffi::CString;
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::ffi::{CStr, CString};
 use std::mem;
+use std::slice;
 #[allow(deprecated)]
 use std::num::Int;
 
+/// How many partitions `-C lto=parallel` splits the program into for each
+/// codegen unit. The partitions are handed out to the codegen threads as
+/// they become idle, so a few more of them than threads keeps one expensive
+/// partition from holding up all the others.
+const PARTITIONS_PER_CODEGEN_UNIT: usize = 4;
+
+/// Functions with at most this many instructions are imported into every
+/// partition that doesn't own them, so that they can still be inlined there.
+const IMPORT_INSTRUCTION_LIMIT: usize = 100;
+
 pub fn run(sess: &session::Session, llmod: ModuleRef,
            tm: TargetMachineRef, reachable: &[String]) {
+    check_crate_types(sess);
+    link_upstream_and_internalize(sess, llmod, reachable);
+
+    if sess.no_landing_pads() {
+        unsafe {
+            llvm::LLVMRustMarkAllFunctionsNounwind(llmod);
+        }
+    }
+
+    // Now we have one massive module inside of llmod. Time to run the
+    // LTO-specific optimization passes that LLVM provides.
+    //
+    // This code is based off the code found in llvm's LTO code generator:
+    //      tools/lto/LTOCodeGenerator.cpp
+    debug!("running the pass manager");
+    unsafe {
+        let pm = llvm::LLVMCreatePassManager();
+        llvm::LLVMRustAddAnalysisPasses(tm, pm, llmod);
+        llvm::LLVMRustAddPass(pm, "verify\0".as_ptr() as *const _);
+
+        let opt = match sess.opts.optimize {
+            config::No => 0,
+            config::Less => 1,
+            config::Default => 2,
+            config::Aggressive => 3,
+        };
+
+        let builder = llvm::LLVMPassManagerBuilderCreate();
+        llvm::LLVMPassManagerBuilderSetOptLevel(builder, opt);
+        llvm::LLVMPassManagerBuilderPopulateLTOPassManager(builder, pm,
+            /* Internalize = */ False,
+            /* RunInliner = */ True);
+        llvm::LLVMPassManagerBuilderDispose(builder);
+
+        llvm::LLVMRustAddPass(pm, "verify\0".as_ptr() as *const _);
+
+        time(sess.time_passes(), "LTO passes", (), |()|
+             llvm::LLVMRunPassManager(pm, llmod));
+
+        llvm::LLVMDisposePassManager(pm);
+    }
+    debug!("lto done");
+}
+
+/// Performs LTO over the codegen units in `modules` and all the upstream
+/// crates, then splits the result into several new modules per codegen unit,
+/// which are optimized and code-generated in parallel like any other codegen
+/// unit. The modules passed in are disposed of.
+///
+/// Only the cheap interprocedural passes run on the merged module. They
+/// resolve what is visible across the whole program, after which a summary
+/// of it is taken: which partition owns each definition, and which functions
+/// are small enough to be imported into the other partitions. Each partition
+/// then gets a copy of the program in which everything it doesn't own is
+/// only declared, apart from the imported functions and the constants, which
+/// are kept as `available_externally` definitions for the optimizer to look
+/// into. Those are never emitted, so every symbol is still defined exactly
+/// once.
+pub fn run_parallel(sess: &session::Session,
+                    modules: &[ModuleTranslation],
+                    tm: TargetMachineRef,
+                    reachable: &[String],
+                    outputs: &OutputFilenames) -> Vec<ModuleTranslation> {
+    check_crate_types(sess);
+
+    let (llcx, llmod) = unsafe {
+        let llcx = llvm::LLVMContextCreate();
+        let llmod = llvm::LLVMModuleCreateWithNameInContext("lto\0".as_ptr() as *const _,
+                                                            llcx);
+        (llcx, llmod)
+    };
+
+    // Every codegen unit lives in an LLVM context of its own, so it can only
+    // be linked with the others by going through bitcode.
+    for (i, mtrans) in modules.iter().enumerate() {
+        let name = format!("codegen unit {}", i);
+        time(sess.time_passes(), &format!("ll link {}", name), (), |()| unsafe {
+            let bc = write_bitcode(mtrans.llmod);
+            llvm::LLVMDisposeModule(mtrans.llmod);
+            llvm::LLVMContextDispose(mtrans.llcx);
+            link_bitcode(sess, llmod, &bc, &name);
+        });
+    }
+
+    link_upstream_and_internalize(sess, llmod, reachable);
+
+    if sess.no_landing_pads() {
+        unsafe {
+            llvm::LLVMRustMarkAllFunctionsNounwind(llmod);
+        }
+    }
+
+    debug!("running the global passes");
+    unsafe {
+        let pm = llvm::LLVMCreatePassManager();
+        llvm::LLVMRustAddAnalysisPasses(tm, pm, llmod);
+        for pass in &["verify\0", "ipsccp\0", "globalopt\0", "deadargelim\0",
+                      "constmerge\0", "globaldce\0", "verify\0"] {
+            assert!(llvm::LLVMRustAddPass(pm, pass.as_ptr() as *const _));
+        }
+
+        time(sess.time_passes(), "LTO global passes", (), |()|
+             llvm::LLVMRunPassManager(pm, llmod));
+
+        llvm::LLVMDisposePassManager(pm);
+
+        if sess.opts.cg.save_temps {
+            let out = path2cstr(&outputs.with_extension("lto.bc"));
+            llvm::LLVMWriteBitcodeToFile(llmod, out.as_ptr());
+        }
+    }
+
+    // With a single codegen unit there's only one thread to hand the
+    // partitions to anyway.
+    let num_partitions = if modules.len() > 1 {
+        modules.len() * PARTITIONS_PER_CODEGEN_UNIT
+    } else {
+        1
+    };
+
+    let (summary, bc) = time(sess.time_passes(), "LTO summary", (), |()| unsafe {
+        externalize(llmod);
+        let summary = summarize(llmod, num_partitions);
+        let bc = write_bitcode(llmod);
+        llvm::LLVMDisposeModule(llmod);
+        llvm::LLVMContextDispose(llcx);
+        (summary, bc)
+    });
+
+    (0..summary.partitions).map(|i| {
+        let name = format!("lto.{}", i);
+        time(sess.time_passes(), &format!("LTO import into {}", name), (), |()| unsafe {
+            let llcx = llvm::LLVMContextCreate();
+            let llmod = llvm::LLVMModuleCreateWithNameInContext(
+                CString::new(&name[..]).unwrap().as_ptr(), llcx);
+            link_bitcode(sess, llmod, &bc, &name);
+            restrict_to_partition(llmod, &summary, i);
+            ModuleTranslation { llcx: llcx, llmod: llmod }
+        })
+    }).collect()
+}
+
+/// What `run_parallel` knows about the merged module when it splits it up.
+struct Summary {
+    /// How many partitions there are. There may be fewer than requested if
+    /// the program is small.
+    partitions: usize,
+    /// The partition each function and global variable definition is
+    /// emitted in.
+    owners: HashMap<CString, usize>,
+    /// The functions to import into the partitions that don't own them.
+    imports: HashSet<CString>,
+}
+
+/// Gives every definition in `llmod` that could be dropped or renamed by the
+/// optimizer a linkage that keeps it around under its current name, as the
+/// other partitions may refer to it. Internal symbols are hidden, so they
+/// still aren't visible outside of the output.
+unsafe fn externalize(llmod: ModuleRef) {
+    let mut anonymous = 0;
+    let mut fix = |val: ValueRef| {
+        if llvm::LLVMIsDeclaration(val) != 0 {
+            return
+        }
+        let linkage = llvm::LLVMGetLinkage(val);
+        if linkage == llvm::InternalLinkage as libc::c_uint ||
+           linkage == llvm::PrivateLinkage as libc::c_uint {
+            if *llvm::LLVMGetValueName(val) == 0 {
+                let name = CString::new(format!("lto.anon.{}", anonymous)).unwrap();
+                anonymous += 1;
+                llvm::LLVMSetValueName(val, name.as_ptr());
+            }
+            llvm::SetLinkage(val, llvm::ExternalLinkage);
+            llvm::LLVMSetVisibility(val, llvm::HiddenVisibility as libc::c_uint);
+        } else if linkage == llvm::LinkOnceODRLinkage as libc::c_uint {
+            llvm::SetLinkage(val, llvm::WeakODRLinkage);
+        } else if linkage == llvm::LinkOnceAnyLinkage as libc::c_uint {
+            llvm::SetLinkage(val, llvm::WeakAnyLinkage);
+        }
+    };
+    each_global(llmod, &mut fix);
+    each_function(llmod, &mut fix);
+}
+
+/// Assigns the functions of `llmod` to at most `partitions` partitions,
+/// keeping functions that are next to each other in the module together
+/// and giving every partition about as many instructions. Global variables
+/// go to the partitions round-robin, except for the appending ones such as
+/// `llvm.used`, which have to stay in a single partition.
+unsafe fn summarize(llmod: ModuleRef, partitions: usize) -> Summary {
+    let mut functions = Vec::new();
+    each_function(llmod, &mut |val| {
+        let linkage = llvm::LLVMGetLinkage(val);
+        if llvm::LLVMIsDeclaration(val) == 0 &&
+           linkage != llvm::AvailableExternallyLinkage as libc::c_uint {
+            functions.push((val, instruction_count(val)));
+        }
+    });
+
+    let partitions = cmp::max(1, cmp::min(partitions, functions.len()));
+    let total = functions.iter().fold(0, |total, &(_, size)| total + size);
+    let per_partition = cmp::max(1, total / partitions);
+
+    let mut owners = HashMap::new();
+    let mut imports = HashSet::new();
+    let mut seen = 0;
+    for &(val, size) in &functions {
+        let owner = cmp::min(seen / per_partition, partitions - 1);
+        seen += size;
+        let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+        let name = CString::new(name).unwrap();
+        // A weak definition other than an ODR one may be replaced at link
+        // time, so its body can't be relied upon elsewhere.
+        let linkage = llvm::LLVMGetLinkage(val);
+        if size <= IMPORT_INSTRUCTION_LIMIT &&
+           (linkage == llvm::ExternalLinkage as libc::c_uint ||
+            linkage == llvm::WeakODRLinkage as libc::c_uint) {
+            imports.insert(name.clone());
+        }
+        owners.insert(name, owner);
+    }
+
+    let mut next = 0;
+    each_global(llmod, &mut |val| {
+        if llvm::LLVMIsDeclaration(val) != 0 {
+            return
+        }
+        let owner = if llvm::LLVMGetLinkage(val) == llvm::AppendingLinkage as libc::c_uint {
+            0
+        } else {
+            next += 1;
+            (next - 1) % partitions
+        };
+        let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+        owners.insert(CString::new(name).unwrap(), owner);
+    });
+
+    Summary { partitions: partitions, owners: owners, imports: imports }
+}
+
+/// Drops every definition from `llmod` that partition `partition` doesn't
+/// own, except for the imported functions and the constants, which are
+/// made `available_externally`.
+unsafe fn restrict_to_partition(llmod: ModuleRef, summary: &Summary, partition: usize) {
+    let owned_elsewhere = |val: ValueRef| {
+        let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+        match summary.owners.get(&CString::new(name).unwrap()) {
+            Some(&owner) => owner != partition,
+            None => false,
+        }
+    };
+
+    let mut val = llvm::LLVMGetFirstGlobal(llmod);
+    while !val.is_null() {
+        let next = llvm::LLVMGetNextGlobal(val);
+        if owned_elsewhere(val) {
+            let linkage = llvm::LLVMGetLinkage(val);
+            if linkage == llvm::AppendingLinkage as libc::c_uint {
+                llvm::LLVMDeleteGlobal(val);
+            } else if llvm::LLVMIsGlobalConstant(val) != 0 &&
+                      linkage != llvm::WeakAnyLinkage as libc::c_uint &&
+                      linkage != llvm::CommonLinkage as libc::c_uint {
+                llvm::SetLinkage(val, llvm::AvailableExternallyLinkage);
+            } else {
+                llvm::LLVMRustDropDefinition(val);
+            }
+        }
+        val = next;
+    }
+
+    each_function(llmod, &mut |val| {
+        if owned_elsewhere(val) {
+            let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+            if summary.imports.contains(&CString::new(name).unwrap()) {
+                llvm::SetLinkage(val, llvm::AvailableExternallyLinkage);
+            } else {
+                llvm::LLVMRustDropDefinition(val);
+            }
+        }
+    });
+}
+
+unsafe fn instruction_count(llfn: ValueRef) -> usize {
+    let mut count = 0;
+    let mut bb = llvm::LLVMGetFirstBasicBlock(llfn);
+    while !bb.is_null() {
+        let mut inst = llvm::LLVMGetFirstInstruction(bb);
+        while !inst.is_null() {
+            count += 1;
+            inst = llvm::LLVMGetNextInstruction(inst);
+        }
+        bb = llvm::LLVMGetNextBasicBlock(bb);
+    }
+    count
+}
+
+unsafe fn each_global<F: FnMut(ValueRef)>(llmod: ModuleRef, f: &mut F) {
+    let mut val = llvm::LLVMGetFirstGlobal(llmod);
+    while !val.is_null() {
+        f(val);
+        val = llvm::LLVMGetNextGlobal(val);
+    }
+}
+
+unsafe fn each_function<F: FnMut(ValueRef)>(llmod: ModuleRef, f: &mut F) {
+    let mut val = llvm::LLVMGetFirstFunction(llmod);
+    while !val.is_null() {
+        f(val);
+        val = llvm::LLVMGetNextFunction(val);
+    }
+}
+
+fn check_crate_types(sess: &session::Session) {
     if sess.opts.cg.prefer_dynamic {
         sess.err("cannot prefer dynamic linking when performing LTO");
-        sess.note("only 'staticlib' and 'bin' outputs are supported with LTO");
+        sess.note("only 'staticlib', 'dylib' and 'bin' outputs are supported with LTO");
         sess.abort_if_errors();
     }
 
     // Make sure we actually can run LTO
     for crate_type in &*sess.crate_types.borrow() {
         match *crate_type {
-            config::CrateTypeExecutable | config::CrateTypeStaticlib => {}
+            config::CrateTypeExecutable |
+            config::CrateTypeStaticlib |
+            config::CrateTypeDylib => {}
             _ => {
-                sess.fatal("lto can only be run for executables and \
+                sess.fatal("lto can only be run for executables, dynamic and \
                             static library outputs");
             }
         }
     }
+}
 
-    // For each of our upstream dependencies, find the corresponding rlib and
-    // load the bitcode from the archive. Then merge it into the current LLVM
-    // module that we've got.
+/// For each of our upstream dependencies, find the corresponding rlib and
+/// load the bitcode from the archive. Then merge it into `llmod`.
+fn link_upstream_bitcode(sess: &session::Session, llmod: ModuleRef) {
     let crates = sess.cstore.get_used_crates(cstore::RequireStatic);
     for (cnum, path) in crates {
         let name = sess.cstore.get_crate_data(cnum).name.clone();
@@ -121,74 +452,93 @@ pub fn run(sess: &session::Session, llmod: ModuleRef,
                 })
             };
 
-            let ptr = bc_decoded.as_ptr();
             debug!("linking {}, part {}", name, i);
             time(sess.time_passes(),
                  &format!("ll link {}.{}", name, i),
                  (),
-                 |()| unsafe {
-                if !llvm::LLVMRustLinkInExternalBitcode(llmod,
-                                                        ptr as *const libc::c_char,
-                                                        bc_decoded.len() as libc::size_t) {
-                    write::llvm_err(sess.diagnostic().handler(),
-                                    format!("failed to load bc of `{}`",
-                                            &name[..]));
-                }
-            });
+                 |()| link_bitcode(sess, llmod, &bc_decoded, &name));
         }
     }
+}
+
+fn link_bitcode(sess: &session::Session, llmod: ModuleRef, bc: &[u8], name: &str) {
+    unsafe {
+        if !llvm::LLVMRustLinkInExternalBitcode(llmod,
+                                                bc.as_ptr() as *const libc::c_char,
+                                                bc.len() as libc::size_t) {
+            write::llvm_err(sess.diagnostic().handler(),
+                            format!("failed to load bc of `{}`", name));
+        }
+    }
+}
+
+unsafe fn write_bitcode(llmod: ModuleRef) -> Vec<u8> {
+    let mut bc = Vec::new();
+    llvm::LLVMRustWriteBitcodeToCallback(llmod, push_bitcode,
+                                         &mut bc as *mut Vec<Vec<u8>> as *mut libc::c_void);
+    bc.pop().unwrap()
+}
+
+unsafe extern "C" fn push_bitcode(payload: *mut libc::c_void,
+                                  data: *const libc::c_char,
+                                  len: libc::size_t) {
+    let bcs = &mut *(payload as *mut Vec<Vec<u8>>);
+    bcs.push(slice::from_raw_parts(data as *const u8, len as usize).to_vec());
+}
+
+/// Links the upstream crates into `llmod`, then internalizes every symbol
+/// that isn't exported from the output: the reachable symbols of the
+/// current crate and, for a dylib, the symbols of the upstream crates linked
+/// into it, as downstream crates may still refer to them through inlined or
+/// generic code.
+fn link_upstream_and_internalize(sess: &session::Session, llmod: ModuleRef,
+                                 reachable: &[String]) {
+    let is_dylib = sess.crate_types.borrow().contains(&config::CrateTypeDylib);
+
+    // The upstream symbols are the ones the upstream bitcode adds.
+    let local = if is_dylib {
+        unsafe { defined_symbols(llmod) }
+    } else {
+        HashSet::new()
+    };
 
-    // Internalize everything but the reachable symbols of the current module
-    let cstrs: Vec<CString> = reachable.iter().map(|s| {
+    link_upstream_bitcode(sess, llmod);
+
+    let mut exported: Vec<CString> = reachable.iter().map(|s| {
         CString::new(s.clone()).unwrap()
     }).collect();
-    let arr: Vec<*const libc::c_char> = cstrs.iter().map(|c| c.as_ptr()).collect();
+    if is_dylib {
+        let all = unsafe { defined_symbols(llmod) };
+        exported.extend(all.difference(&local).cloned());
+    }
+
+    let arr: Vec<*const libc::c_char> = exported.iter().map(|c| c.as_ptr()).collect();
     let ptr = arr.as_ptr();
     unsafe {
         llvm::LLVMRustRunRestrictionPass(llmod,
                                          ptr as *const *const libc::c_char,
                                          arr.len() as libc::size_t);
     }
+}
 
-    if sess.no_landing_pads() {
-        unsafe {
-            llvm::LLVMRustMarkAllFunctionsNounwind(llmod);
-        }
-    }
-
-    // Now we have one massive module inside of llmod. Time to run the
-    // LTO-specific optimization passes that LLVM provides.
-    //
-    // This code is based off the code found in llvm's LTO code generator:
-    //      tools/lto/LTOCodeGenerator.cpp
-    debug!("running the pass manager");
-    unsafe {
-        let pm = llvm::LLVMCreatePassManager();
-        llvm::LLVMRustAddAnalysisPasses(tm, pm, llmod);
-        llvm::LLVMRustAddPass(pm, "verify\0".as_ptr() as *const _);
-
-        let opt = match sess.opts.optimize {
-            config::No => 0,
-            config::Less => 1,
-            config::Default => 2,
-            config::Aggressive => 3,
+/// The names of the functions and globals `llmod` defines that can be
+/// referred to from outside of it.
+unsafe fn defined_symbols(llmod: ModuleRef) -> HashSet<CString> {
+    let mut symbols = HashSet::new();
+    {
+        let mut add = |val: ValueRef| {
+            let linkage = llvm::LLVMGetLinkage(val);
+            if llvm::LLVMIsDeclaration(val) == 0 &&
+               linkage != llvm::InternalLinkage as libc::c_uint &&
+               linkage != llvm::PrivateLinkage as libc::c_uint {
+                let name = CStr::from_ptr(llvm::LLVMGetValueName(val)).to_bytes();
+                symbols.insert(CString::new(name).unwrap());
+            }
         };
-
-        let builder = llvm::LLVMPassManagerBuilderCreate();
-        llvm::LLVMPassManagerBuilderSetOptLevel(builder, opt);
-        llvm::LLVMPassManagerBuilderPopulateLTOPassManager(builder, pm,
-            /* Internalize = */ False,
-            /* RunInliner = */ True);
-        llvm::LLVMPassManagerBuilderDispose(builder);
-
-        llvm::LLVMRustAddPass(pm, "verify\0".as_ptr() as *const _);
-
-        time(sess.time_passes(), "LTO passes", (), |()|
-             llvm::LLVMRunPassManager(pm, llmod));
-
-        llvm::LLVMDisposePassManager(pm);
+        each_global(llmod, &mut add);
+        each_function(llmod, &mut add);
     }
-    debug!("lto done");
+    symbols
 }
 
 fn is_versioned_bytecode_format(bc: &[u8]) -> bool {
@@ -0,0 +1,363 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The v1 symbol mangling, `-Z symbol-mangling-version=v1`.
+//!
+//! Unlike the legacy mangling in `back::link`, these symbols spell out the
+//! crate an item comes from, the impl a method belongs to and the generic
+//! arguments of an instance, so `Vec<u8>::push` and `Vec<String>::push` can
+//! be told apart after demangling. The grammar is documented in
+//! `librustc_demangle`, which is what turns these back into paths.
+
+use back::link::sanitize;
+use back::svh::Svh;
+use metadata::csearch;
+use middle::subst::{self, Subst, Substs};
+use middle::ty::{self, Ty};
+use trans::CrateContext;
+
+use syntax::ast;
+use syntax::ast_map::{self, PathElem};
+use syntax::ast_util::local_def;
+use syntax::parse::token;
+
+/// Mangles the symbol of the item `id`, instantiated with `substs`.
+///
+/// Items with identical paths, e.g. functions of the same name in two blocks,
+/// are kept apart by `disambiguator`. `hash`, which has to be an `h` followed
+/// by lowercase hex digits, ends up at the very end of the symbol.
+pub fn mangle_item<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                             id: ast::NodeId,
+                             substs: &Substs<'tcx>,
+                             disambiguator: Option<u64>,
+                             hash: Option<&str>) -> String {
+    let mut m = Mangler::new(ccx);
+    // The generic arguments of an impl are part of its self type already.
+    let types = if is_method(ccx.tcx(), id) {
+        substs.types.get_slice(subst::FnSpace)
+    } else {
+        substs.types.as_slice()
+    };
+    if !types.is_empty() {
+        m.out.push('I');
+    }
+    m.push_item_path(id, substs, disambiguator);
+    if !types.is_empty() {
+        m.push_types(types);
+    }
+    m.finish(hash)
+}
+
+/// Mangles the symbol of a function generated for the type `t`, like its drop
+/// glue, which demangles as `<t>::name`.
+pub fn mangle_type_item<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
+                                  t: Ty<'tcx>,
+                                  name: &str,
+                                  hash: &str) -> String {
+    let mut m = Mangler::new(ccx);
+    m.out.push_str("NM");
+    m.push_type(t);
+    m.push_disambiguator(token::gensym(name).usize() as u64);
+    m.push_ident(name);
+    m.finish(Some(hash))
+}
+
+/// Mangles the symbol of a function generated inside the local item at
+/// `path`, like a closure, which demangles as `path::name`.
+pub fn mangle_nested_item<'a, 'tcx, PI>(ccx: &CrateContext<'a, 'tcx>,
+                                        path: PI,
+                                        name: &str) -> String
+    where PI: Iterator<Item=PathElem>
+{
+    let path: Vec<PathElem> = path.collect();
+    let mut m = Mangler::new(ccx);
+    m.push_path(ast::LOCAL_CRATE, &path, None, 1);
+    m.push_disambiguator(token::gensym(name).usize() as u64);
+    m.push_ident(name);
+    m.finish(None)
+}
+
+struct Mangler<'a, 'b: 'a, 'tcx: 'b> {
+    ccx: &'a CrateContext<'b, 'tcx>,
+    out: String,
+}
+
+impl<'a, 'b, 'tcx> Mangler<'a, 'b, 'tcx> {
+    fn new(ccx: &'a CrateContext<'b, 'tcx>) -> Mangler<'a, 'b, 'tcx> {
+        Mangler { ccx: ccx, out: String::from_str("_R") }
+    }
+
+    fn finish(mut self, hash: Option<&str>) -> String {
+        if let Some(hash) = hash {
+            assert!(hash.starts_with("h"));
+            self.out.push_str(hash);
+        }
+        self.out
+    }
+
+    fn push_ident(&mut self, ident: &str) {
+        let ident = sanitize(ident);
+        self.out.push_str(&ident.len().to_string());
+        match ident.as_bytes()[0] {
+            b'0' ... b'9' | b'_' => self.out.push('_'),
+            _ => {}
+        }
+        self.out.push_str(&ident);
+    }
+
+    fn push_disambiguator(&mut self, n: u64) {
+        const DIGITS: &'static [u8] =
+            b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+        self.out.push('s');
+        if n > 0 {
+            let mut digits = Vec::new();
+            let mut n = n - 1;
+            loop {
+                digits.push(DIGITS[(n % 62) as usize] as char);
+                n /= 62;
+                if n == 0 {
+                    break
+                }
+            }
+            self.out.extend(digits.into_iter().rev());
+        }
+        self.out.push('_');
+    }
+
+    fn push_crate_root(&mut self, cnum: ast::CrateNum) {
+        let (name, hash) = if cnum == ast::LOCAL_CRATE {
+            let link_meta = self.ccx.link_meta();
+            (link_meta.crate_name.clone(), link_meta.crate_hash.clone())
+        } else {
+            let cstore = &self.ccx.sess().cstore;
+            (cstore.get_crate_data(cnum).name.clone(), cstore.get_crate_hash(cnum))
+        };
+        self.out.push('C');
+        self.push_disambiguator(svh_to_u64(&hash));
+        self.push_ident(&name);
+    }
+
+    // Pushes `path` nested in the root of `cnum`, leaving room for `extra`
+    // more components after it. The last component of `path` gets
+    // `disambiguator`.
+    fn push_path(&mut self,
+                 cnum: ast::CrateNum,
+                 path: &[PathElem],
+                 disambiguator: Option<u64>,
+                 extra: usize) {
+        for _ in 0..path.len() + extra {
+            self.out.push('N');
+        }
+        self.push_crate_root(cnum);
+        for (i, elem) in path.iter().enumerate() {
+            if i == path.len() - 1 {
+                if let Some(n) = disambiguator {
+                    self.push_disambiguator(n);
+                }
+            }
+            self.push_ident(&token::get_name(elem.name()));
+        }
+    }
+
+    // The crate and path of `def_id`, looking through items inlined from
+    // other crates.
+    fn def_path(&self, def_id: ast::DefId) -> (ast::DefId, Vec<PathElem>) {
+        let tcx = self.ccx.tcx();
+        let def_id = if def_id.krate == ast::LOCAL_CRATE {
+            match self.ccx.external_srcs().borrow().get(&def_id.node) {
+                Some(&orig) => orig,
+                None => def_id,
+            }
+        } else {
+            def_id
+        };
+        let path = if def_id.krate == ast::LOCAL_CRATE {
+            tcx.map.with_path(def_id.node, |path| path.collect())
+        } else {
+            // Leave out the crate name, it's in the crate root.
+            let mut path = csearch::get_item_path(tcx, def_id);
+            path.remove(0);
+            path
+        };
+        (def_id, path)
+    }
+
+    fn push_def_path(&mut self, def_id: ast::DefId, types: &[Ty<'tcx>]) {
+        if !types.is_empty() {
+            self.out.push('I');
+        }
+        let (def_id, path) = self.def_path(def_id);
+        self.push_path(def_id.krate, &path, None, 0);
+        if !types.is_empty() {
+            self.push_types(types);
+        }
+    }
+
+    // Pushes the path of the function `id`. Methods are nested in their impl
+    // or trait rather than in the made up name of their impl.
+    fn push_item_path(&mut self,
+                      id: ast::NodeId,
+                      substs: &Substs<'tcx>,
+                      disambiguator: Option<u64>) {
+        let tcx = self.ccx.tcx();
+        let (def_id, path) = self.def_path(local_def(id));
+        if !is_method(tcx, id) {
+            return self.push_path(def_id.krate, &path, disambiguator, 0)
+        }
+        let item = ty::impl_or_trait_item(tcx, def_id);
+
+        self.out.push('N');
+        match item.container() {
+            ty::ImplContainer(impl_def_id) => {
+                let self_ty = self.subst(ty::lookup_item_type(tcx, impl_def_id).ty,
+                                         substs);
+                match ty::impl_trait_ref(tcx, impl_def_id) {
+                    Some(trait_ref) => {
+                        self.out.push('X');
+                        self.push_type(self_ty);
+                        let trait_ref = self.subst(trait_ref, substs);
+                        self.push_def_path(trait_ref.def_id,
+                                           trait_ref.substs.types.get_slice(subst::TypeSpace));
+                    }
+                    None => {
+                        self.out.push('M');
+                        self.push_type(self_ty);
+                    }
+                }
+            }
+            ty::TraitContainer(trait_def_id) => {
+                // A provided method, `Self` is the type it's called on.
+                self.out.push('X');
+                match substs.self_ty() {
+                    Some(self_ty) => self.push_type(self_ty),
+                    None => self.out.push('p'),
+                }
+                self.push_def_path(trait_def_id, substs.types.get_slice(subst::TypeSpace));
+            }
+        }
+        if let Some(n) = disambiguator {
+            self.push_disambiguator(n);
+        }
+        self.push_ident(&token::get_name(item.name()));
+    }
+
+    // Items that aren't generic are mangled with empty substitutions, which
+    // leaves nothing to substitute.
+    fn subst<T: Subst<'tcx>>(&self, t: T, substs: &Substs<'tcx>) -> T {
+        if substs.types.is_empty() {
+            t
+        } else {
+            t.subst(self.ccx.tcx(), substs)
+        }
+    }
+
+    fn push_types(&mut self, types: &[Ty<'tcx>]) {
+        for &t in types {
+            self.push_type(t);
+        }
+        self.out.push('E');
+    }
+
+    fn push_type(&mut self, t: Ty<'tcx>) {
+        if let Some(c) = basic_type(t) {
+            return self.out.push(c)
+        }
+
+        match t.sty {
+            ty::ty_enum(def_id, substs) | ty::ty_struct(def_id, substs) => {
+                self.push_def_path(def_id, substs.types.get_slice(subst::TypeSpace));
+            }
+            ty::ty_uniq(inner) => {
+                match self.ccx.tcx().lang_items.owned_box() {
+                    Some(def_id) => self.push_def_path(def_id, &[inner]),
+                    None => self.out.push('p'),
+                }
+            }
+            ty::ty_vec(inner, Some(len)) => {
+                self.out.push('A');
+                self.push_type(inner);
+                self.out.push_str(&len.to_string());
+                self.out.push('_');
+            }
+            ty::ty_vec(inner, None) => {
+                self.out.push('S');
+                self.push_type(inner);
+            }
+            ty::ty_ptr(mt) => {
+                self.out.push(if mt.mutbl == ast::MutMutable { 'O' } else { 'P' });
+                self.push_type(mt.ty);
+            }
+            ty::ty_rptr(_, mt) => {
+                self.out.push(if mt.mutbl == ast::MutMutable { 'Q' } else { 'R' });
+                self.push_type(mt.ty);
+            }
+            ty::ty_tup(ref tys) => {
+                self.out.push('T');
+                self.push_types(tys);
+            }
+            ty::ty_bare_fn(_, ref f) => {
+                self.out.push('F');
+                if f.unsafety == ast::Unsafety::Unsafe {
+                    self.out.push('U');
+                }
+                self.push_types(&f.sig.0.inputs);
+                match f.sig.0.output {
+                    ty::FnConverging(output) => self.push_type(output),
+                    ty::FnDiverging => self.out.push('z'),
+                }
+            }
+            ty::ty_trait(ref data) => {
+                self.out.push('D');
+                let principal = &data.principal.0;
+                self.push_def_path(principal.def_id,
+                                   principal.substs.types.get_slice(subst::TypeSpace));
+            }
+            // Closures, projections and whatever else can't be named are
+            // left to the hash.
+            _ => self.out.push('p'),
+        }
+    }
+}
+
+fn is_method(tcx: &ty::ctxt, id: ast::NodeId) -> bool {
+    match tcx.map.find(id) {
+        Some(ast_map::NodeImplItem(..)) | Some(ast_map::NodeTraitItem(..)) => true,
+        _ => false,
+    }
+}
+
+fn basic_type(t: Ty) -> Option<char> {
+    Some(match t.sty {
+        ty::ty_bool => 'b',
+        ty::ty_char => 'c',
+        ty::ty_int(ast::TyI8) => 'a',
+        ty::ty_int(ast::TyI16) => 's',
+        ty::ty_int(ast::TyI32) => 'l',
+        ty::ty_int(ast::TyI64) => 'x',
+        ty::ty_int(ast::TyI128) => 'n',
+        ty::ty_int(ast::TyIs) => 'i',
+        ty::ty_uint(ast::TyU8) => 'h',
+        ty::ty_uint(ast::TyU16) => 't',
+        ty::ty_uint(ast::TyU32) => 'm',
+        ty::ty_uint(ast::TyU64) => 'y',
+        ty::ty_uint(ast::TyU128) => 'o',
+        ty::ty_uint(ast::TyUs) => 'j',
+        ty::ty_float(ast::TyF32) => 'f',
+        ty::ty_float(ast::TyF64) => 'd',
+        ty::ty_str => 'e',
+        ty::ty_tup(ref tys) if tys.is_empty() => 'u',
+        _ => return None,
+    })
+}
+
+fn svh_to_u64(svh: &Svh) -> u64 {
+    u64::from_str_radix(svh.as_str(), 16).unwrap_or(0)
+}
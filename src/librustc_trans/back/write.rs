@@ -19,15 +19,21 @@ use llvm::SMDiagnosticRef;
 use trans::{CrateTranslation, ModuleTranslation};
 use util::common::time;
 use util::common::path2cstr;
-use syntax::codemap;
+use syntax::codemap::{self, BytePos, Span};
 use syntax::diagnostic;
 use syntax::diagnostic::{Emitter, Handler, Level, mk_handler};
 
+use rustc_demangle::demangle;
+use serialize::json::{Json, ToJson};
+
+use std::collections::BTreeMap;
+use std::env;
 use std::ffi::{CStr, CString};
 use std::fs;
+use std::io::{self, Write};
 use std::iter::Unfold;
 use std::mem;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::ptr;
 use std::str;
@@ -279,6 +285,18 @@ struct ModuleConfig {
     no_prepopulate_passes: bool,
     no_builtins: bool,
     time_passes: bool,
+
+    // Directory to write execution profiles to, or profile data to optimize
+    // with. See `-C profile-generate` and `-C profile-use`.
+    pgo_gen: Option<PathBuf>,
+    pgo_use: Option<PathBuf>,
+
+    sanitizer: Option<config::Sanitizer>,
+    // Instrument the code to collect gcov coverage data, see `-Z profile`.
+    profile: bool,
+    // Move the DWARF of the object file into a `.dwo` file next to it, see
+    // `-C split-debuginfo`.
+    split_dwarf: bool,
 }
 
 unsafe impl Send for ModuleConfig { }
@@ -301,6 +319,13 @@ impl ModuleConfig {
             no_prepopulate_passes: false,
             no_builtins: false,
             time_passes: false,
+
+            pgo_gen: None,
+            pgo_use: None,
+
+            sanitizer: None,
+            profile: false,
+            split_dwarf: false,
         }
     }
 
@@ -309,6 +334,16 @@ impl ModuleConfig {
         self.no_prepopulate_passes = sess.opts.cg.no_prepopulate_passes;
         self.no_builtins = trans.no_builtins;
         self.time_passes = sess.time_passes();
+        // The program may run anywhere, so it needs the full path.
+        self.pgo_gen = sess.opts.cg.profile_generate.as_ref().map(|dir| {
+            match env::current_dir() {
+                Ok(cwd) => cwd.join(dir),
+                Err(..) => dir.clone(),
+            }
+        });
+        self.pgo_use = sess.opts.cg.profile_use.clone();
+        self.sanitizer = sess.opts.debugging_opts.sanitizer;
+        self.profile = sess.opts.debugging_opts.profile;
     }
 }
 
@@ -321,6 +356,9 @@ struct CodegenContext<'a> {
     handler: &'a Handler,
     // LLVM optimizations for which we want to print remarks.
     remark: Passes,
+    // Paths of the functions to print remarks about, all if empty.
+    remark_filter: Vec<String>,
+    remark_format: config::RemarkFormat,
 }
 
 impl<'a> CodegenContext<'a> {
@@ -329,6 +367,8 @@ impl<'a> CodegenContext<'a> {
             lto_ctxt: Some((sess, reachable)),
             handler: sess.diagnostic().handler(),
             remark: sess.opts.cg.remark.clone(),
+            remark_filter: sess.opts.cg.remark_filter.clone(),
+            remark_format: sess.opts.cg.remark_format,
         }
     }
 }
@@ -391,12 +431,7 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
             };
 
             if enabled {
-                let loc = llvm::debug_loc_to_string(llcx, opt.debug_loc);
-                cgcx.handler.note(&format!("optimization {} for {} at {}: {}",
-                                           opt.kind.describe(),
-                                           pass_name,
-                                           if loc.is_empty() { "[unknown]" } else { &*loc },
-                                           llvm::twine_to_string(opt.message)));
+                report_remark(cgcx, llcx, &opt, pass_name);
             }
         }
 
@@ -404,6 +439,110 @@ unsafe extern "C" fn diagnostic_handler(info: DiagnosticInfoRef, user: *mut c_vo
     }
 }
 
+/// Turns the symbol of the function a remark is about back into its Rust
+/// path, without the hash legacy symbols end in.
+fn remark_function_path(symbol: &str) -> String {
+    let path = demangle(symbol).to_string();
+    match path.rfind("::") {
+        Some(i) if is_legacy_hash(&path[i + 2..]) => path[..i].to_string(),
+        _ => path,
+    }
+}
+
+fn is_legacy_hash(component: &str) -> bool {
+    // Check the bytes, slicing the string could split a character.
+    let bytes = component.as_bytes();
+    bytes.len() > 16 && bytes[0] == b'h' &&
+        bytes[1..17].iter().all(|&b| (b as char).is_digit(16))
+}
+
+unsafe extern "C" fn profile_mismatch(user: *mut c_void, symbol: *const libc::c_char) {
+    let cgcx = &*(user as *const CodegenContext);
+    let symbol = CStr::from_ptr(symbol);
+    let function = remark_function_path(&String::from_utf8_lossy(symbol.to_bytes()));
+    cgcx.handler.warn(&format!("the profile data for `{}` doesn't match its code, \
+                                ignoring it", function));
+}
+
+/// Finds the span of `line` and `col` in the source file LLVM calls `file`,
+/// if it's part of this crate's codemap.
+fn remark_span(sess: &Session, file: &str, line: u32, col: u32) -> Option<Span> {
+    if line == 0 {
+        return None
+    }
+    let files = sess.codemap().files.borrow();
+    let fm = match files.iter().find(|fm| {
+        Path::new(&fm.name).ends_with(file) || Path::new(file).ends_with(&fm.name)
+    }) {
+        Some(fm) => fm,
+        None => return None,
+    };
+    let lines = fm.lines.borrow();
+    let index = line as usize - 1;
+    let start = match lines.get(index) {
+        Some(&start) => start,
+        None => return None,
+    };
+    // LLVM's column may lie past the end of the line, keep the span on it.
+    let end = lines.get(index + 1).map(|&next| next).unwrap_or(fm.end_pos);
+    let last = if end.0 > start.0 { end.0 - start.0 - 1 } else { 0 };
+    let col = if col > 0 { col - 1 } else { 0 };
+    let pos = start + BytePos(if col > last { last } else { col });
+    Some(codemap::mk_sp(pos, pos))
+}
+
+unsafe fn report_remark(cgcx: &CodegenContext, llcx: ContextRef,
+                        opt: &llvm::diagnostic::OptimizationDiagnostic,
+                        pass_name: &str) {
+    let symbol = CStr::from_ptr(llvm::LLVMGetValueName(opt.function));
+    let function = remark_function_path(&String::from_utf8_lossy(symbol.to_bytes()));
+    if !cgcx.remark_filter.is_empty() &&
+       !cgcx.remark_filter.iter().any(|p| {
+           function == *p || function.starts_with(&format!("{}::", p))
+       }) {
+        return
+    }
+
+    let loc = llvm::debug_loc_parts(llcx, opt.debug_loc);
+    let message = llvm::twine_to_string(opt.message);
+
+    match cgcx.remark_format {
+        config::RemarkFormat::Json => {
+            let mut obj = BTreeMap::new();
+            obj.insert("kind".to_string(), opt.kind.describe().to_json());
+            obj.insert("pass".to_string(), pass_name.to_json());
+            obj.insert("function".to_string(), function.to_json());
+            obj.insert("file".to_string(), loc.as_ref().map(|l| l.0.clone()).to_json());
+            obj.insert("line".to_string(), loc.as_ref().map(|l| l.1).to_json());
+            obj.insert("column".to_string(), loc.as_ref().map(|l| l.2).to_json());
+            obj.insert("message".to_string(), message.to_json());
+            // Straight to stderr, the workers of other codegen units may be
+            // writing their remarks at the same time, but a line at a time.
+            let line = format!("{}\n", Json::Object(obj));
+            let _ = io::stderr().write_all(line.as_bytes());
+        }
+        config::RemarkFormat::Human => {
+            let msg = format!("optimization {} for {} in `{}`: {}",
+                              opt.kind.describe(), pass_name, function, message);
+            // Only the main thread can map the location to a span in the
+            // source, the workers just name it.
+            let span = match (cgcx.lto_ctxt, &loc) {
+                (Some((sess, _)), &Some((ref file, line, col))) => {
+                    remark_span(sess, file, line, col).map(|sp| (sess, sp))
+                }
+                _ => None,
+            };
+            match (span, loc) {
+                (Some((sess, sp)), _) => sess.span_note(sp, &msg),
+                (None, Some((file, line, col))) => {
+                    cgcx.handler.note(&format!("{}:{}:{}: {}", file, line, col, msg))
+                }
+                (None, None) => cgcx.handler.note(&msg),
+            }
+        }
+    }
+}
+
 // Unsafe due to LLVM calls.
 unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
                                mtrans: ModuleTranslation,
@@ -430,6 +569,24 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
         llvm::LLVMWriteBitcodeToFile(llmod, out.as_ptr());
     }
 
+    // Profile-guided optimization works on the code as trans produced it,
+    // so that the profile and the code it's used for are the same.
+    if let Some(ref dir) = config.pgo_gen {
+        // The profiling runtime replaces `%p` with the process id, so that
+        // several runs don't clobber each other's profiles. The raw profiles
+        // are merged with `llvm-profdata` afterwards.
+        let path = path2cstr(&dir.join("default_%p.profraw"));
+        llvm::LLVMRustInstrumentForProfiling(llmod, path.as_ptr());
+    }
+    if let Some(ref file) = config.pgo_use {
+        let path = path2cstr(file);
+        let cgcx_ptr = cgcx as *const CodegenContext as *mut c_void;
+        if !llvm::LLVMRustApplyProfile(llmod, path.as_ptr(), profile_mismatch, cgcx_ptr) {
+            llvm_err(cgcx.handler, format!("failed to read profile data from `{}`",
+                                           file.display()));
+        }
+    }
+
     match config.opt_level {
         Some(opt_level) => {
             // Create the two optimizing pass managers. These mirror what clang
@@ -454,6 +611,43 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
                                      config.no_builtins);
             }
 
+            // The sanitizers instrument the code as it looks after all of
+            // the optimizations, like clang does. LeakSanitizer is purely a
+            // runtime library and doesn't need any instrumentation.
+            let sanitizer_passes: &[&str] = match config.sanitizer {
+                Some(config::Sanitizer::Address) => &["asan", "asan-module"],
+                Some(config::Sanitizer::Memory) => &["msan"],
+                Some(config::Sanitizer::Thread) => &["tsan"],
+                Some(config::Sanitizer::Leak) | None => &[],
+            };
+            for pass in sanitizer_passes {
+                let pass = CString::new(*pass).unwrap();
+                if !llvm::LLVMRustAddPass(mpm, pass.as_ptr()) {
+                    llvm_err(cgcx.handler,
+                             format!("LLVM doesn't support the {:?} pass", pass));
+                }
+            }
+
+            if config.profile {
+                // Put the coverage notes next to the other outputs, and have
+                // the data written there as well no matter where the program
+                // runs. Every codegen unit needs its own files.
+                let path = output_names.with_extension(&format!("{}.gcno",
+                                                                name_extra));
+                let path = match env::current_dir() {
+                    Ok(dir) => dir.join(&path),
+                    Err(..) => path,
+                };
+                let path = path2cstr(&path);
+                llvm::LLVMRustAddGCOVMetadata(llmod, path.as_ptr());
+
+                let pass = CString::new("insert-gcov-profiling").unwrap();
+                if !llvm::LLVMRustAddPass(mpm, pass.as_ptr()) {
+                    llvm_err(cgcx.handler,
+                             format!("LLVM doesn't support the {:?} pass", pass));
+                }
+            }
+
             for pass in &config.passes {
                 let pass = CString::new(pass.clone()).unwrap();
                 if !llvm::LLVMRustAddPass(mpm, pass.as_ptr()) {
@@ -472,7 +666,7 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             llvm::LLVMDisposePassManager(mpm);
 
             match cgcx.lto_ctxt {
-                Some((sess, reachable)) if sess.lto() =>  {
+                Some((sess, reachable)) if sess.opts.cg.lto == config::Lto::Fat =>  {
                     time(sess.time_passes(), "all lto passes", (), |()|
                          lto::run(sess, llmod, tm, reachable));
 
@@ -539,6 +733,10 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
             with_codegen(tm, llmod, config.no_builtins, |cpm| {
                 write_output_file(cgcx.handler, tm, cpm, llmod, &path, llvm::ObjectFileType);
             });
+            if config.split_dwarf {
+                let dwo = output_names.with_extension(&format!("{}.dwo", name_extra));
+                split_dwarf(cgcx.handler, &path, &dwo);
+            }
         }
     });
 
@@ -547,6 +745,33 @@ unsafe fn optimize_and_codegen(cgcx: &CodegenContext,
     llvm::LLVMRustDisposeTargetMachine(tm);
 }
 
+// With `-split-dwarf=Enable` LLVM writes both the skeleton debuginfo and the
+// .dwo sections into the object file. Like `clang -gsplit-dwarf`, we then
+// move the .dwo sections out into the file the skeleton names.
+fn split_dwarf(handler: &Handler, obj: &Path, dwo: &Path) {
+    let mut extract = Command::new("objcopy");
+    extract.arg("--extract-dwo").arg(obj).arg(dwo);
+    let mut strip = Command::new("objcopy");
+    strip.arg("--strip-dwo").arg(obj);
+
+    for cmd in &mut [extract, strip] {
+        match cmd.output() {
+            Ok(output) => {
+                if !output.status.success() {
+                    handler.err(&format!("splitting debuginfo with `{:?}` failed: {}",
+                                         cmd, output.status));
+                    handler.note(&String::from_utf8_lossy(&output.stderr));
+                    return
+                }
+            }
+            Err(e) => {
+                handler.err(&format!("could not exec `objcopy`: {}", e));
+                return
+            }
+        }
+    }
+}
+
 pub fn run_passes(sess: &Session,
                   trans: &CrateTranslation,
                   output_types: &[config::OutputType],
@@ -556,17 +781,26 @@ pub fn run_passes(sess: &Session,
     // case, but it would be confusing to have the validity of
     // `-Z lto -C codegen-units=2` depend on details of the crate being
     // compiled, so we complain regardless.
-    if sess.lto() && sess.opts.cg.codegen_units > 1 {
+    if sess.opts.cg.lto == config::Lto::Fat && sess.opts.cg.codegen_units > 1 {
         // This case is impossible to handle because LTO expects to be able
         // to combine the entire crate and all its dependencies into a
         // single compilation unit, but each codegen unit is in a separate
         // LLVM context, so they can't easily be combined.
-        sess.fatal("can't perform LTO when using multiple codegen units");
+        sess.err("can't perform LTO when using multiple codegen units");
+        sess.note("`-C lto=parallel` works with multiple codegen units");
+        sess.abort_if_errors();
     }
 
     // Sanity check
     assert!(trans.modules.len() == sess.opts.cg.codegen_units);
 
+    if let Some(ref dir) = sess.opts.cg.profile_generate {
+        if let Err(e) = fs::create_dir_all(dir) {
+            sess.fatal(&format!("failed to create profile directory `{}`: {}",
+                                dir.display(), e));
+        }
+    }
+
     unsafe {
         configure_llvm(sess);
     }
@@ -623,6 +857,9 @@ pub fn run_passes(sess: &Session,
     modules_config.set_flags(sess, trans);
     metadata_config.set_flags(sess, trans);
 
+    // The metadata module has no debuginfo to split out.
+    modules_config.split_dwarf = sess.split_debuginfo() != config::SplitDebuginfo::Off;
+
 
     // Populate a buffer with a list of codegen tasks.  Items are processed in
     // LIFO order, just because it's a tiny bit simpler that way.  (The order
@@ -638,7 +875,16 @@ pub fn run_passes(sess: &Session,
         work_items.push(work);
     }
 
-    for (index, mtrans) in trans.modules.iter().enumerate() {
+    // With parallel LTO the codegen units are merged with the upstream crates
+    // and split up again before any of them is optimized.
+    let modules = if sess.opts.cg.lto == config::Lto::Parallel {
+        time(sess.time_passes(), "parallel lto", (), |()|
+             lto::run_parallel(sess, &trans.modules, tm, &trans.reachable, crate_output))
+    } else {
+        trans.modules.clone()
+    };
+
+    for (index, mtrans) in modules.iter().enumerate() {
         let work = build_work_item(sess,
                                    *mtrans,
                                    modules_config.clone(),
@@ -668,7 +914,7 @@ pub fn run_passes(sess: &Session,
 
     let copy_if_one_unit = |ext: &str, output_type: config::OutputType, keep_numbered: bool| {
         // Three cases:
-        if sess.opts.cg.codegen_units == 1 {
+        if modules.len() == 1 {
             // 1) Only one codegen unit.  In this case it's no difficulty
             //    to copy `foo.0.x` to `foo.x`.
             copy_gracefully(&crate_output.with_extension(ext), &crate_output.path(output_type));
@@ -692,7 +938,7 @@ pub fn run_passes(sess: &Session,
 
     let link_obj = |output_path: &Path| {
         // Running `ld -r` on a single input is kind of pointless.
-        if sess.opts.cg.codegen_units == 1 {
+        if modules.len() == 1 {
             copy_gracefully(&crate_output.with_extension("0.o"), output_path);
             // Leave the .0.o file around, to mimic the behavior of the normal
             // code path.
@@ -718,7 +964,7 @@ pub fn run_passes(sess: &Session,
         cmd.args(&sess.target.target.options.pre_link_args);
         cmd.arg("-nostdlib");
 
-        for index in 0..trans.modules.len() {
+        for index in 0..modules.len() {
             cmd.arg(&crate_output.with_extension(&format!("{}.o", index)));
         }
 
@@ -823,9 +1069,9 @@ pub fn run_passes(sess: &Session,
         // If you change how this works, also update back::link::link_rlib,
         // where .0.bc files are (maybe) deleted after making an rlib.
         let keep_numbered_bitcode = needs_crate_bitcode ||
-                (user_wants_bitcode && sess.opts.cg.codegen_units > 1);
+                (user_wants_bitcode && modules.len() > 1);
 
-        for i in 0..trans.modules.len() {
+        for i in 0..modules.len() {
             if modules_config.emit_obj {
                 let ext = format!("{}.o", i);
                 remove(sess, &crate_output.with_extension(&ext[..]));
@@ -846,6 +1092,7 @@ pub fn run_passes(sess: &Session,
     //  - crate.o
     //  - crate.metadata.o
     //  - crate.bc
+    //  - crate.##.dwo (with split debuginfo, as the objects refer to them)
     // These are used in linking steps and will be cleaned up afterward.
 
     // FIXME: time_llvm_passes support - does this use a global context or
@@ -908,6 +1155,8 @@ fn run_work_multithreaded(sess: &Session,
         let work_items_arc = work_items_arc.clone();
         let diag_emitter = diag_emitter.clone();
         let remark = sess.opts.cg.remark.clone();
+        let remark_filter = sess.opts.cg.remark_filter.clone();
+        let remark_format = sess.opts.cg.remark_format;
 
         let (tx, rx) = channel();
         let mut tx = Some(tx);
@@ -922,6 +1171,8 @@ fn run_work_multithreaded(sess: &Session,
                 lto_ctxt: None,
                 handler: &diag_handler,
                 remark: remark,
+                remark_filter: remark_filter,
+                remark_format: remark_format,
             };
 
             loop {
@@ -1014,6 +1265,9 @@ unsafe fn configure_llvm(sess: &Session) {
         if vectorize_slp  { add("-vectorize-slp");   }
         if sess.time_llvm_passes() { add("-time-passes"); }
         if sess.print_llvm_passes() { add("-debug-pass=Structure"); }
+        if sess.split_debuginfo() != config::SplitDebuginfo::Off {
+            add("-split-dwarf=Enable");
+        }
 
         // FIXME #21627 disable faulty FastISel on AArch64 (even for -O0)
         if sess.target.target.arch == "aarch64" { add("-fast-isel=0"); }
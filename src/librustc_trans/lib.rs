@@ -50,6 +50,7 @@ extern crate graphviz;
 extern crate libc;
 extern crate rustc;
 extern crate rustc_back;
+extern crate rustc_demangle;
 extern crate serialize;
 extern crate rustc_llvm as llvm;
 
@@ -76,7 +77,9 @@ pub mod back {
     pub use rustc_back::x86_64;
 
     pub mod link;
+    pub mod linker;
     pub mod lto;
+    pub mod mangle;
     pub mod write;
 
 }
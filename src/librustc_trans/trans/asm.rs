@@ -9,29 +9,329 @@
 // except according to those terms.
 
 //! # Translation of inline assembly.
+//!
+//! LLVM takes the operand constraints of `asm!` as they are and either
+//! rejects bad ones with an assertion or quietly miscompiles them, so they
+//! are checked here first: every constraint has to be one the target
+//! understands, and the operand's type has to fit the registers it asks for.
 
 use llvm;
+use middle::ty::Ty;
 use trans::build::*;
 use trans::callee;
 use trans::common::*;
 use trans::cleanup;
 use trans::cleanup::CleanupMethods;
 use trans::expr;
+use trans::machine;
 use trans::type_of;
 use trans::type_::Type;
 
 use syntax::ast;
+use syntax::codemap::Span;
 use std::ffi::CString;
 use libc::{c_uint, c_char};
 
+/// What a single constraint code lets LLVM pick for an operand.
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum OperandClass {
+    /// A register holding values from the first to the second number of
+    /// bits in size.
+    Reg(u64, u64),
+    Memory,
+    Immediate,
+    /// Anything at all, or something we don't know enough about to check.
+    Any,
+}
+
+/// A parsed operand constraint.
+enum Constraint {
+    /// The classes the constraint's codes allow, any one of which LLVM may
+    /// pick.
+    Classes(Vec<OperandClass>),
+    /// An input tied to the output with the given index.
+    Tied(usize),
+}
+
+/// The class of the constraint code `c` on `arch`, or `None` if the target
+/// doesn't know about the code. Architectures without a table here accept
+/// any code and leave the checking to LLVM.
+fn code_class(arch: &str, ptr_bits: u64, c: char) -> Option<OperandClass> {
+    use self::OperandClass::*;
+
+    match (arch, c) {
+        (_, 'r') => Some(Reg(1, ptr_bits)),
+        (_, 'm') | (_, 'o') | (_, 'V') | (_, '<') | (_, '>') => Some(Memory),
+        (_, 'i') | (_, 'n') | (_, 's') | (_, 'E') | (_, 'F') => Some(Immediate),
+        (_, 'g') | (_, 'X') => Some(Any),
+
+        ("x86", c) | ("x86_64", c) => match c {
+            'q' | 'Q' | 'R' | 'l' | 'a' | 'b' | 'c' | 'd' | 'S' | 'D' => {
+                Some(Reg(1, ptr_bits))
+            }
+            // The `eax:edx` (or `rax:rdx`) pair.
+            'A' => Some(Reg(2 * ptr_bits, 2 * ptr_bits)),
+            // SSE registers, up to AVX-512's.
+            'x' => Some(Reg(32, 512)),
+            'y' => Some(Reg(64, 64)),
+            'f' | 't' | 'u' => Some(Reg(32, 80)),
+            'I' | 'J' | 'K' | 'L' | 'M' | 'N' | 'O' | 'e' | 'Z' | 'G' | 'C' => Some(Immediate),
+            _ => None,
+        },
+        ("arm", c) => match c {
+            'l' | 'h' | 'k' => Some(Reg(1, 32)),
+            't' => Some(Reg(32, 32)),
+            'w' => Some(Reg(32, 128)),
+            'I' | 'J' | 'K' | 'L' | 'M' | 'j' => Some(Immediate),
+            'Q' | 'U' => Some(Memory),
+            _ => None,
+        },
+        ("aarch64", c) => match c {
+            'w' | 'x' => Some(Reg(8, 128)),
+            'I' | 'J' | 'K' | 'L' | 'M' | 'N' | 'Y' | 'Z' | 'S' => Some(Immediate),
+            'Q' => Some(Memory),
+            _ => None,
+        },
+        ("mips", c) | ("mipsel", c) => match c {
+            'c' | 'd' | 'l' | 'y' => Some(Reg(1, 32)),
+            'f' => Some(Reg(32, 64)),
+            'I' | 'J' | 'K' | 'L' | 'N' | 'O' | 'P' => Some(Immediate),
+            'R' => Some(Memory),
+            _ => None,
+        },
+        ("powerpc", c) => match c {
+            'b' => Some(Reg(1, 32)),
+            'f' => Some(Reg(32, 64)),
+            'v' => Some(Reg(128, 128)),
+            'I' | 'J' | 'K' | 'L' | 'M' | 'N' | 'O' | 'P' => Some(Immediate),
+            'Z' | 'Q' => Some(Memory),
+            _ => None,
+        },
+        _ => Some(Any),
+    }
+}
+
+/// The class of the explicitly named register `{reg}` on `arch`, or `None`
+/// if there's no such register. Only the x86 families have a table.
+fn register_class(arch: &str, ptr_bits: u64, reg: &str) -> Option<OperandClass> {
+    use self::OperandClass::*;
+
+    if arch != "x86" && arch != "x86_64" {
+        return Some(Any)
+    }
+
+    let x86_64 = arch == "x86_64";
+    let numbered = |prefix: &str, count: usize| {
+        reg.starts_with(prefix) &&
+            reg[prefix.len()..].parse::<usize>().map(|n| n < count).unwrap_or(false)
+    };
+    let gpr = ["ax", "bx", "cx", "dx", "si", "di", "bp", "sp"];
+    let byte_regs = ["al", "bl", "cl", "dl", "ah", "bh", "ch", "dh"];
+    let x86_64_byte_regs = ["sil", "dil", "bpl", "spl"];
+
+    if gpr.contains(&reg) || byte_regs.contains(&reg) ||
+       (reg.starts_with("e") && gpr.contains(&&reg[1..])) {
+        Some(Reg(1, ptr_bits))
+    } else if x86_64 && ((reg.starts_with("r") && gpr.contains(&&reg[1..])) ||
+                         x86_64_byte_regs.contains(&reg) ||
+                         (numbered("r", 16) && !numbered("r", 8)) ||
+                         (reg.starts_with("r") && reg.ends_with(|c| "dwb".contains(c)) &&
+                          reg[1..reg.len() - 1].parse::<usize>().map(|n| n < 16)
+                                                               .unwrap_or(false))) {
+        Some(Reg(1, 64))
+    } else if numbered("xmm", if x86_64 { 16 } else { 8 }) {
+        Some(Reg(32, 128))
+    } else if numbered("ymm", if x86_64 { 16 } else { 8 }) {
+        Some(Reg(32, 256))
+    } else if numbered("mm", 8) {
+        Some(Reg(64, 64))
+    } else if reg == "st" || (reg.starts_with("st(") && reg.ends_with(")") &&
+                              reg[3..reg.len() - 1].parse::<usize>()
+                                                   .map(|n| n < 8).unwrap_or(false)) {
+        Some(Reg(32, 80))
+    } else {
+        None
+    }
+}
+
+/// Parses an operand constraint, with any leading `=` already stripped.
+fn parse_constraint(arch: &str, ptr_bits: u64, constraint: &str)
+                    -> Result<Constraint, String> {
+    if constraint.is_empty() {
+        return Err("empty operand constraint".to_string())
+    }
+    if constraint.chars().all(|c| c.is_digit(10)) {
+        return match constraint.parse() {
+            Ok(i) => Ok(Constraint::Tied(i)),
+            Err(_) => Err(format!("invalid operand constraint `{}`", constraint)),
+        }
+    }
+
+    let mut classes = Vec::new();
+    let mut rest = constraint;
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+        match c {
+            // Early clobber, commutative, alternative separator and weights
+            // don't change what the operand can be.
+            '&' | '%' | ',' | '?' | '!' | '#' => {}
+            // The operand is a pointer to the memory to use.
+            '*' => classes.push(OperandClass::Any),
+            '{' => {
+                let end = match rest.find('}') {
+                    Some(end) => end,
+                    None => return Err(format!("unterminated register name in `{}`",
+                                               constraint)),
+                };
+                let reg = rest[..end].to_lowercase();
+                match register_class(arch, ptr_bits, &reg) {
+                    Some(class) => classes.push(class),
+                    None => return Err(format!("unknown register `{}` for {}", reg, arch)),
+                }
+                rest = &rest[end + 1..];
+            }
+            c => match code_class(arch, ptr_bits, c) {
+                Some(class) => classes.push(class),
+                None => return Err(format!("unknown operand constraint `{}` for {}",
+                                           c, arch)),
+            },
+        }
+    }
+    Ok(Constraint::Classes(classes))
+}
+
+/// Checks that an operand of type `ty` fits one of `classes`.
+fn check_operand<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, span: Span, constraint: &str,
+                             classes: &[OperandClass], ty: Ty<'tcx>) {
+    let sess = bcx.sess();
+    let ranges = classes.iter().filter_map(|class| match *class {
+        OperandClass::Reg(min, max) => Some((min, max)),
+        _ => None,
+    }).collect::<Vec<_>>();
+
+    // Memory and immediates can be of any size, LLVM's concern is only that
+    // immediates are constant, which is checked once they're translated.
+    if ranges.len() != classes.len() {
+        return
+    }
+
+    // What ends up in the register is the LLVM value, so thin references and
+    // boxes go in as the pointers they are, while fat pointers and aggregates
+    // don't fit any register.
+    let llty = type_of::type_of(bcx.ccx(), ty);
+    match llty.kind() {
+        llvm::Integer | llvm::Pointer | llvm::Vector |
+        llvm::Half | llvm::Float | llvm::Double |
+        llvm::X86_FP80 | llvm::FP128 | llvm::PPC_FP128 => {}
+        _ => {
+            sess.span_err(span, &format!("cannot pass a value of type `{}` in a register \
+                                          with the constraint `{}`",
+                                         bcx.ty_to_string(ty), constraint));
+            return
+        }
+    }
+
+    let bits = machine::llbitsize_of_real(bcx.ccx(), llty);
+    if !ranges.iter().any(|&(min, max)| min <= bits && bits <= max) {
+        let (min, max) = ranges.iter().fold((!0, 0), |(lo, hi), &(min, max)| {
+            (if min < lo { min } else { lo }, if max > hi { max } else { hi })
+        });
+        sess.span_err(span, &format!("the constraint `{}` takes values of {} to {} bits \
+                                      on {}, but `{}` has {} bits",
+                                     constraint, min, max,
+                                     sess.target.target.arch,
+                                     bcx.ty_to_string(ty), bits));
+    }
+}
+
+fn operand_bits<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ty: Ty<'tcx>) -> u64 {
+    machine::llbitsize_of_real(bcx.ccx(), type_of::type_of(bcx.ccx(), ty))
+}
+
+/// Checks the operands of `ia` against the target, reporting any problems
+/// at `sp` or at the offending operand.
+fn check_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm, sp: Span) {
+    let sess = bcx.sess();
+    let arch = &sess.target.target.arch[..];
+    let ptr_bits = machine::llbitsize_of_real(bcx.ccx(), bcx.ccx().int_type());
+
+    if ia.dialect == ast::AsmIntel && arch != "x86" && arch != "x86_64" {
+        sess.span_err(sp, &format!("Intel syntax is only supported on x86, not on {}",
+                                   arch));
+    }
+
+    for &(ref c, ref out, _) in &ia.outputs {
+        let constraint = c.trim_left_matches('=');
+        match parse_constraint(arch, ptr_bits, constraint) {
+            Ok(Constraint::Classes(ref classes)) => {
+                if classes.iter().all(|&class| class == OperandClass::Immediate) {
+                    sess.span_err(out.span, &format!("output operand constraint `{}` \
+                                                      only allows immediates", c));
+                } else {
+                    check_operand(bcx, out.span, constraint, classes,
+                                  expr_ty(bcx, &**out));
+                }
+            }
+            Ok(Constraint::Tied(_)) => {
+                sess.span_err(out.span, &format!("output operand constraint `{}` can't \
+                                                  be tied to another operand", c));
+            }
+            Err(msg) => sess.span_err(out.span, &msg),
+        }
+    }
+
+    for &(ref c, ref input) in &ia.inputs {
+        let ty = expr_ty(bcx, &**input);
+        match parse_constraint(arch, ptr_bits, c) {
+            Ok(Constraint::Classes(ref classes)) => {
+                check_operand(bcx, input.span, c, classes, ty);
+            }
+            Ok(Constraint::Tied(i)) => {
+                match ia.outputs.get(i) {
+                    Some(&(_, ref out, _)) => {
+                        let out_ty = expr_ty(bcx, &**out);
+                        let (in_bits, out_bits) = (operand_bits(bcx, ty),
+                                                   operand_bits(bcx, out_ty));
+                        if in_bits != out_bits {
+                            sess.span_err(input.span,
+                                          &format!("input operand of {} bits is tied to \
+                                                    output {} of {} bits",
+                                                   in_bits, i, out_bits));
+                            sess.span_note(out.span, "the output is here");
+                        }
+                    }
+                    None => {
+                        sess.span_err(input.span,
+                                      &format!("input operand is tied to output {}, but \
+                                                there {} only {} output{}",
+                                               i,
+                                               if ia.outputs.len() == 1 { "is" } else { "are" },
+                                               ia.outputs.len(),
+                                               if ia.outputs.len() == 1 { "" } else { "s" }));
+                    }
+                }
+            }
+            Err(msg) => sess.span_err(input.span, &msg),
+        }
+    }
+}
+
 // Take an inline assembly expression and splat it out via LLVM
-pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
+pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm, sp: Span)
                                     -> Block<'blk, 'tcx> {
     let fcx = bcx.fcx;
     let mut bcx = bcx;
     let mut constraints = Vec::new();
     let mut output_types = Vec::new();
 
+    // Whatever's wrong with the operands is reported here, LLVM would only
+    // stumble over it.
+    let errors = bcx.sess().err_count();
+    check_inline_asm(bcx, ia, sp);
+    if bcx.sess().err_count() > errors {
+        return bcx;
+    }
+
     let temp_scope = fcx.push_custom_cleanup_scope();
 
     let mut ext_inputs = Vec::new();
@@ -76,6 +376,26 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
     // no failure occurred preparing operands, no need to cleanup
     fcx.pop_custom_cleanup_scope(temp_scope);
 
+    // Immediates have to be known when the assembly is emitted.
+    let arch = &bcx.sess().target.target.arch[..];
+    let ptr_bits = machine::llbitsize_of_real(bcx.ccx(), bcx.ccx().int_type());
+    for (&(ref c, ref input), &v) in ia.inputs.iter().zip(inputs.iter()) {
+        let immediate_only = match parse_constraint(arch, ptr_bits, c) {
+            Ok(Constraint::Classes(ref classes)) => {
+                classes.iter().all(|&class| class == OperandClass::Immediate)
+            }
+            _ => false,
+        };
+        if immediate_only && unsafe { llvm::LLVMIsConstant(v) } == llvm::False {
+            bcx.sess().span_err(input.span,
+                                &format!("the operand for the constraint `{}` must be \
+                                          a constant", c));
+        }
+    }
+    if bcx.sess().err_count() > errors {
+        return bcx;
+    }
+
     let clobbers = ia.clobbers.iter()
                               .map(|s| format!("~{{{}}}", &s));
 
@@ -111,7 +431,17 @@ pub fn trans_inline_asm<'blk, 'tcx>(bcx: Block<'blk, 'tcx>, ia: &ast::InlineAsm)
     };
 
     let asm = CString::new(ia.asm.as_bytes()).unwrap();
-    let constraint_cstr = CString::new(all_constraints).unwrap();
+    let constraint_cstr = CString::new(&all_constraints[..]).unwrap();
+
+    // A last line of defense for anything the checks above let through:
+    // `InlineAsm::get` asserts on constraints that don't match the types.
+    let argtys = inputs.iter().map(|&v| val_ty(v)).collect::<Vec<_>>();
+    let fty = Type::func(&argtys[..], &output_type);
+    if unsafe { !llvm::LLVMRustInlineAsmVerify(fty.to_ref(), constraint_cstr.as_ptr()) } {
+        bcx.sess().span_err(sp, &format!("invalid constraints for inline assembly: `{}`",
+                                         all_constraints));
+        return bcx;
+    }
     let r = InlineAsmCall(bcx,
                           asm.as_ptr(),
                           constraint_cstr.as_ptr(),
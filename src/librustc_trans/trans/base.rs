@@ -42,7 +42,7 @@ use middle::lang_items::{LangItem, ExchangeMallocFnLangItem, StartFnLangItem};
 use middle::weak_lang_items;
 use middle::subst::{Subst, Substs};
 use middle::ty::{self, Ty, ClosureTyper, type_is_simd, simd_size};
-use session::config::{self, NoDebugInfo};
+use session::config::{self, NoDebugInfo, OutputFilenames};
 use session::Session;
 use trans::_match;
 use trans::adt;
@@ -207,6 +207,18 @@ pub fn decl_fn(ccx: &CrateContext, name: &str, cc: llvm::CallConv,
         set_split_stack(llfn);
     }
 
+    // The sanitizer passes only instrument functions carrying the matching
+    // attribute.
+    let sanitize = match ccx.sess().opts.debugging_opts.sanitizer {
+        Some(config::Sanitizer::Address) => Some(llvm::SanitizeAddressAttribute),
+        Some(config::Sanitizer::Memory) => Some(llvm::SanitizeMemoryAttribute),
+        Some(config::Sanitizer::Thread) => Some(llvm::SanitizeThreadAttribute),
+        Some(config::Sanitizer::Leak) | None => None,
+    };
+    if let Some(attr) = sanitize {
+        attr.apply_llfn(llvm::FunctionIndex as c_uint, llfn);
+    }
+
     llfn
 }
 
@@ -862,6 +874,15 @@ pub fn fail_if_zero_or_overflows<'blk, 'tcx>(
                     ast::TyI16 => i16::MIN as u64,
                     ast::TyI32 => i32::MIN as u64,
                     ast::TyI64 => i64::MIN as u64,
+                    // Does not fit in a u64, so it is built as `1 << 127`
+                    // below.
+                    ast::TyI128 => 1,
+                };
+                let min = C_integral(llty, min, true);
+                let min = if t == ast::TyI128 {
+                    unsafe { llvm::LLVMConstShl(min, C_integral(llty, 127, false)) }
+                } else {
+                    min
                 };
                 (llty, min)
             }
@@ -870,8 +891,7 @@ pub fn fail_if_zero_or_overflows<'blk, 'tcx>(
         let minus_one = ICmp(bcx, llvm::IntEQ, rhs,
                              C_integral(llty, !0, false), debug_loc);
         with_cond(bcx, minus_one, |bcx| {
-            let is_min = ICmp(bcx, llvm::IntEQ, lhs,
-                              C_integral(llty, min, true), debug_loc);
+            let is_min = ICmp(bcx, llvm::IntEQ, lhs, min, debug_loc);
             with_cond(bcx, is_min, |bcx| {
                 controlflow::trans_fail(bcx,
                                         call_info,
@@ -1280,7 +1300,7 @@ fn build_cfg(tcx: &ty::ctxt, id: ast::NodeId) -> (ast::NodeId, Option<cfg::CFG>)
     let blk = match tcx.map.find(id) {
         Some(ast_map::NodeItem(i)) => {
             match i.node {
-                ast::ItemFn(_, _, _, _, ref blk) => {
+                ast::ItemFn(_, _, _, _, _, ref blk) => {
                     blk
                 }
                 _ => tcx.sess.bug("unexpected item variant in has_nested_returns")
@@ -1289,6 +1309,10 @@ fn build_cfg(tcx: &ty::ctxt, id: ast::NodeId) -> (ast::NodeId, Option<cfg::CFG>)
         Some(ast_map::NodeTraitItem(trait_item)) => {
             match trait_item.node {
                 ast::MethodTraitItem(_, Some(ref body)) => body,
+                ast::ConstTraitItem(..) => {
+                    tcx.sess.bug("unexpected variant: associated const trait item in \
+                                  has_nested_returns")
+                }
                 ast::MethodTraitItem(_, None) => {
                     tcx.sess.bug("unexpected variant: required trait method \
                                   in has_nested_returns")
@@ -1302,6 +1326,10 @@ fn build_cfg(tcx: &ty::ctxt, id: ast::NodeId) -> (ast::NodeId, Option<cfg::CFG>)
         Some(ast_map::NodeImplItem(impl_item)) => {
             match impl_item.node {
                 ast::MethodImplItem(_, ref body) => body,
+                ast::ConstImplItem(..) => {
+                    tcx.sess.bug("unexpected variant: associated const impl item in \
+                                  has_nested_returns")
+                }
                 ast::TypeImplItem(_) => {
                     tcx.sess.bug("unexpected variant: associated type impl item in \
                                   has_nested_returns")
@@ -2184,7 +2212,7 @@ pub fn trans_item(ccx: &CrateContext, item: &ast::Item) {
     let from_external = ccx.external_srcs().borrow().contains_key(&item.id);
 
     match item.node {
-      ast::ItemFn(ref decl, _fn_style, abi, ref generics, ref body) => {
+      ast::ItemFn(ref decl, _fn_style, _, abi, ref generics, ref body) => {
         if !generics.is_type_parameterized() {
             let trans_everywhere = attr::requests_inline(&item.attrs);
             // Ignore `trans_everywhere` for cross-crate inlined items
@@ -2697,7 +2725,7 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                     // We need the translated value here, because for enums the
                     // LLVM type is not fully determined by the Rust type.
                     let empty_substs = ccx.tcx().mk_substs(Substs::trans_empty());
-                    let (v, ty) = consts::const_expr(ccx, &**expr, empty_substs);
+                    let (v, ty) = consts::const_expr(ccx, &**expr, empty_substs, None);
                     ccx.static_values().borrow_mut().insert(id, v);
                     unsafe {
                         // boolean SSA values are i1, but they have to be stored in i8 slots,
@@ -2725,7 +2753,7 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                     }
                 }
 
-                ast::ItemFn(_, _, abi, _, _) => {
+                ast::ItemFn(_, _, _, abi, _, _) => {
                     let sym = sym();
                     let llfn = if abi == Rust {
                         register_fn(ccx, i.span, sym, i.id, ty)
@@ -2767,6 +2795,10 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                     ccx.sess().span_bug(trait_item.span,
                         "unexpected variant: required trait method in get_item_val()");
                 }
+                ast::ConstTraitItem(..) => {
+                    ccx.sess().span_bug(trait_item.span,
+                        "unexpected variant: associated const in get_item_val()");
+                }
                 ast::MethodTraitItem(_, Some(_)) => {
                     register_method(ccx, id, &trait_item.attrs, trait_item.span)
                 }
@@ -2778,6 +2810,10 @@ pub fn get_item_val(ccx: &CrateContext, id: ast::NodeId) -> ValueRef {
                 ast::MethodImplItem(..) => {
                     register_method(ccx, id, &impl_item.attrs, impl_item.span)
                 }
+                ast::ConstImplItem(..) => {
+                    ccx.sess().span_bug(impl_item.span,
+                        "unexpected variant: associated const in get_item_val()")
+                }
                 ast::TypeImplItem(_) => {
                     ccx.sess().span_bug(impl_item.span,
                         "unexpected variant: associated type in get_item_val()")
@@ -3027,7 +3063,8 @@ fn internalize_symbols(cx: &SharedCrateContext, reachable: &HashSet<String>) {
     }
 }
 
-pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
+pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>,
+                         outputs: &OutputFilenames)
                          -> (ty::ctxt<'tcx>, CrateTranslation) {
     let ty::CrateAnalysis { ty_cx: tcx, export_map, reachable, name, .. } = analysis;
     let krate = tcx.map.krate();
@@ -3088,9 +3125,15 @@ pub fn trans_crate<'tcx>(analysis: ty::CrateAnalysis<'tcx>)
         }
     }
 
-    for ccx in shared_ccx.iter() {
+    for (i, ccx) in shared_ccx.iter().enumerate() {
         if ccx.sess().opts.debuginfo != NoDebugInfo {
-            debuginfo::finalize(&ccx);
+            // The object file of codegen unit `i` is `crate.i.o`, see
+            // `back::write`, so its split-out DWARF goes to `crate.i.dwo`.
+            let split_dwarf_path = match ccx.sess().split_debuginfo() {
+                config::SplitDebuginfo::Off => None,
+                _ => Some(outputs.with_extension(&format!("{}.dwo", i))),
+            };
+            debuginfo::finalize(&ccx, split_dwarf_path.as_ref().map(|p| &**p));
         }
     }
 
@@ -158,7 +158,7 @@ pub fn get_or_create_declaration_if_closure<'a, 'tcx>(ccx: &CrateContext<'a, 'tc
     }
 
     let symbol = ccx.tcx().map.with_path(closure_id.node, |path| {
-        mangle_internal_name_by_path_and_seq(path, "closure")
+        mangle_internal_name_by_path_and_seq(ccx, path, "closure")
     });
 
     let llfn = decl_internal_rust_fn(ccx, function_type, &symbol[..]);
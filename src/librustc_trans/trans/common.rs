@@ -54,6 +54,7 @@ use syntax::ast_map::{PathElem, PathName};
 use syntax::codemap::{DUMMY_SP, Span};
 use syntax::parse::token::InternedString;
 use syntax::parse::token;
+use syntax::util::int128::{I128, U128};
 use util::common::memoized;
 use util::nodemap::FnvHashSet;
 
@@ -969,20 +970,22 @@ fn is_const_integral(v: ValueRef) -> bool {
     }
 }
 
-pub fn const_to_opt_int(v: ValueRef) -> Option<i64> {
-    unsafe {
-        if is_const_integral(v) {
-            Some(llvm::LLVMConstIntGetSExtValue(v))
-        } else {
-            None
-        }
-    }
+pub fn const_to_opt_int(v: ValueRef) -> Option<I128> {
+    const_to_opt_u128(v, true).map(|u| u.as_i128())
+}
+
+pub fn const_to_opt_uint(v: ValueRef) -> Option<U128> {
+    const_to_opt_u128(v, false)
 }
 
-pub fn const_to_opt_uint(v: ValueRef) -> Option<u64> {
+fn const_to_opt_u128(v: ValueRef, sign_extend: bool) -> Option<U128> {
     unsafe {
-        if is_const_integral(v) {
-            Some(llvm::LLVMConstIntGetZExtValue(v))
+        if !is_const_integral(v) {
+            return None;
+        }
+        let (mut high, mut low) = (0, 0);
+        if llvm::LLVMRustConstInt128Get(v, sign_extend as Bool, &mut high, &mut low) == True {
+            Some(U128::from_parts(high, low))
         } else {
             None
         }
@@ -413,7 +413,8 @@ pub fn trans_fail<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
     let v_str = C_str_slice(ccx, fail_str);
     let loc = bcx.sess().codemap().lookup_char_pos(call_info.span.lo);
-    let filename = token::intern_and_get_ident(&loc.file.name);
+    let filename = bcx.sess().codemap().path_mapping().map_prefix(&loc.file.name);
+    let filename = token::intern_and_get_ident(&filename);
     let filename = C_str_slice(ccx, filename);
     let line = C_u32(ccx, loc.line as u32);
     let expr_file_line_const = C_struct(ccx, &[v_str, filename, line], false);
@@ -444,7 +445,8 @@ pub fn trans_fail_bounds_check<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
 
     // Extract the file/line from the span
     let loc = bcx.sess().codemap().lookup_char_pos(call_info.span.lo);
-    let filename = token::intern_and_get_ident(&loc.file.name);
+    let filename = bcx.sess().codemap().path_mapping().map_prefix(&loc.file.name);
+    let filename = token::intern_and_get_ident(&filename);
 
     // Invoke the lang item
     let filename = C_str_slice(ccx,  filename);
@@ -714,13 +714,16 @@ enum VariableKind {
 }
 
 /// Create any deferred debug metadata nodes
-pub fn finalize(cx: &CrateContext) {
+///
+/// With split DWARF, `split_dwarf_path` is where the bulk of the debuginfo
+/// of this codegen unit ends up; the compile unit refers to it by that name.
+pub fn finalize(cx: &CrateContext, split_dwarf_path: Option<&Path>) {
     if cx.dbg_cx().is_none() {
         return;
     }
 
     debug!("finalize");
-    let _ = compile_unit_metadata(cx);
+    let _ = compile_unit_metadata(cx, split_dwarf_path);
 
     if needs_gdb_debug_scripts_section(cx) {
         // Add a .debug_gdb_scripts section to this compile-unit. This will
@@ -1290,7 +1293,7 @@ pub fn create_function_debug_context<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             }
 
             match item.node {
-                ast::ItemFn(ref fn_decl, _, _, ref generics, ref top_level_block) => {
+                ast::ItemFn(ref fn_decl, _, _, _, ref generics, ref top_level_block) => {
                     (item.ident, fn_decl, generics, top_level_block, item.span, true)
                 }
                 _ => {
@@ -1313,6 +1316,11 @@ pub fn create_function_debug_context<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
                      impl_item.span,
                      true)
                 }
+                ast::ConstImplItem(..) => {
+                    cx.sess().span_bug(impl_item.span,
+                                       "create_function_debug_context() \
+                                        called on associated const?!")
+                }
                 ast::TypeImplItem(_) => {
                     cx.sess().span_bug(impl_item.span,
                                        "create_function_debug_context() \
@@ -1622,7 +1630,7 @@ fn create_DIArray(builder: DIBuilderRef, arr: &[DIDescriptor]) -> DIArray {
     };
 }
 
-fn compile_unit_metadata(cx: &CrateContext) -> DIDescriptor {
+fn compile_unit_metadata(cx: &CrateContext, split_dwarf_path: Option<&Path>) -> DIDescriptor {
     let work_dir = &cx.sess().working_dir;
     let compile_unit_name = match cx.sess().local_crate_source_file {
         None => fallback_path(cx),
@@ -1650,10 +1658,14 @@ fn compile_unit_metadata(cx: &CrateContext) -> DIDescriptor {
                            (option_env!("CFG_VERSION")).expect("CFG_VERSION"));
 
     let compile_unit_name = compile_unit_name.as_ptr();
-    let work_dir = path2cstr(&work_dir);
+    let path_mapping = cx.sess().codemap().path_mapping();
+    let work_dir = path2cstr(&path_mapping.map_prefix_path(work_dir));
     let producer = CString::new(producer).unwrap();
     let flags = "\0";
-    let split_name = "\0";
+    let split_name = match split_dwarf_path {
+        Some(path) => path2cstr(&path_mapping.map_prefix_path(path)),
+        None => CString::new("").unwrap(),
+    };
     return unsafe {
         llvm::LLVMDIBuilderCreateCompileUnit(
             debug_context(cx).builder,
@@ -1664,7 +1676,7 @@ fn compile_unit_metadata(cx: &CrateContext) -> DIDescriptor {
             cx.sess().opts.optimize != config::No,
             flags.as_ptr() as *const _,
             0,
-            split_name.as_ptr() as *const _)
+            split_name.as_ptr())
     };
 
     fn fallback_path(cx: &CrateContext) -> CString {
@@ -1751,17 +1763,16 @@ fn file_metadata(cx: &CrateContext, full_path: &str) -> DIFile {
 
     debug!("file_metadata: {}", full_path);
 
-    // FIXME (#9639): This needs to handle non-utf8 paths
-    let work_dir = cx.sess().working_dir.to_str().unwrap();
-    let file_name =
-        if full_path.starts_with(work_dir) {
-            &full_path[work_dir.len() + 1..full_path.len()]
-        } else {
-            full_path
-        };
-
-    let file_name = CString::new(file_name).unwrap();
-    let work_dir = CString::new(work_dir).unwrap();
+    // Both paths are remapped first, so that a file under a remapped working
+    // directory is still recorded relative to it.
+    let path_mapping = cx.sess().codemap().path_mapping();
+    let work_dir = path_mapping.map_prefix_path(&cx.sess().working_dir);
+    let mapped_path = path_mapping.map_prefix_path(Path::new(full_path));
+    let file_name = match mapped_path.relative_from(&work_dir) {
+        Some(rel) if rel.components().next().is_some() => path2cstr(rel),
+        _ => path2cstr(&mapped_path),
+    };
+    let work_dir = path2cstr(&work_dir);
     let file_metadata = unsafe {
         llvm::LLVMDIBuilderCreateFile(DIB(cx), file_name.as_ptr(),
                                       work_dir.as_ptr())
@@ -1818,14 +1829,16 @@ fn basic_type_metadata<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
             ast::TyI8 => ("i8".to_string(), DW_ATE_signed),
             ast::TyI16 => ("i16".to_string(), DW_ATE_signed),
             ast::TyI32 => ("i32".to_string(), DW_ATE_signed),
-            ast::TyI64 => ("i64".to_string(), DW_ATE_signed)
+            ast::TyI64 => ("i64".to_string(), DW_ATE_signed),
+            ast::TyI128 => ("i128".to_string(), DW_ATE_signed)
         },
         ty::ty_uint(uint_ty) => match uint_ty {
             ast::TyUs => ("usize".to_string(), DW_ATE_unsigned),
             ast::TyU8 => ("u8".to_string(), DW_ATE_unsigned),
             ast::TyU16 => ("u16".to_string(), DW_ATE_unsigned),
             ast::TyU32 => ("u32".to_string(), DW_ATE_unsigned),
-            ast::TyU64 => ("u64".to_string(), DW_ATE_unsigned)
+            ast::TyU64 => ("u64".to_string(), DW_ATE_unsigned),
+            ast::TyU128 => ("u128".to_string(), DW_ATE_unsigned)
         },
         ty::ty_float(float_ty) => match float_ty {
             ast::TyF32 => ("f32".to_string(), DW_ATE_float),
@@ -3622,6 +3635,11 @@ fn create_scope_map(cx: &CrateContext,
                                               Found unexpanded macro.");
             }
 
+            ast::ExprError => {
+                cx.sess().span_bug(exp.span, "debuginfo::create_scope_map() - \
+                                              Found erroneous expression.");
+            }
+
             ast::ExprLoop(ref block, _) |
             ast::ExprBlock(ref block)   => {
                 with_new_scope(cx,
@@ -3750,11 +3768,13 @@ fn push_debuginfo_type_name<'a, 'tcx>(cx: &CrateContext<'a, 'tcx>,
         ty::ty_int(ast::TyI16)   => output.push_str("i16"),
         ty::ty_int(ast::TyI32)   => output.push_str("i32"),
         ty::ty_int(ast::TyI64)   => output.push_str("i64"),
+        ty::ty_int(ast::TyI128)  => output.push_str("i128"),
         ty::ty_uint(ast::TyUs)    => output.push_str("usize"),
         ty::ty_uint(ast::TyU8)   => output.push_str("u8"),
         ty::ty_uint(ast::TyU16)  => output.push_str("u16"),
         ty::ty_uint(ast::TyU32)  => output.push_str("u32"),
         ty::ty_uint(ast::TyU64)  => output.push_str("u64"),
+        ty::ty_uint(ast::TyU128) => output.push_str("u128"),
         ty::ty_float(ast::TyF32) => output.push_str("f32"),
         ty::ty_float(ast::TyF64) => output.push_str("f64"),
         ty::ty_struct(def_id, substs) |
@@ -146,8 +146,9 @@ pub fn trans_into<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             match expr.node {
                 ast::ExprPath(..) => {
                     match bcx.def(expr.id) {
-                        def::DefConst(did) => {
-                            let const_expr = consts::get_const_expr(bcx.ccx(), did, expr);
+                        def::DefConst(did) | def::DefAssociatedConst(did, _) => {
+                            let const_expr = consts::get_const_expr(bcx.ccx(), did, expr,
+                                                                    bcx.fcx.param_substs);
                             // Temporarily get cleanup scopes out of the way,
                             // as they require sub-expressions to be contained
                             // inside the current AST scope.
@@ -953,6 +954,24 @@ fn trans_def<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             bcx.sess().span_bug(ref_expr.span,
                 "constant expression should not reach expr::trans_def")
         }
+        def::DefAssociatedConst(did, _) => {
+            // Constants declared in traits aren't translated ahead of time,
+            // as the impl providing the value depends on the monomorphized
+            // types. Copy the value out of its global.
+            let const_ty = expr_ty(bcx, ref_expr);
+            let global = consts::get_const_val(bcx.ccx(), did, ref_expr,
+                                               bcx.fcx.param_substs);
+            let llty = type_of::type_of(bcx.ccx(), const_ty);
+            let global = PointerCast(bcx, global, llty.ptr_to());
+            let datum = if type_is_immediate(bcx.ccx(), const_ty) {
+                immediate_rvalue(load_ty(bcx, global, const_ty), const_ty)
+            } else {
+                let scratch = alloca_no_lifetime(bcx, llty, "const");
+                memcpy_ty(bcx, scratch, global, const_ty);
+                Datum::new(scratch, const_ty, Rvalue::new(ByRef))
+            };
+            DatumBlock::new(bcx, datum.to_expr_datum())
+        }
         _ => {
             DatumBlock::new(bcx, trans_local_var(bcx, def).to_expr_datum())
         }
@@ -1049,7 +1068,7 @@ fn trans_rvalue_stmt_unadjusted<'blk, 'tcx>(bcx: Block<'blk, 'tcx>,
             trans_assign_op(bcx, expr, op, &**dst, &**src)
         }
         ast::ExprInlineAsm(ref a) => {
-            asm::trans_inline_asm(bcx, a)
+            asm::trans_inline_asm(bcx, a, expr.span)
         }
         _ => {
             bcx.tcx().sess.span_bug(
@@ -2449,11 +2468,13 @@ impl OverflowOpViaIntrinsic {
                 ty_int(TyI16) => "llvm.sadd.with.overflow.i16",
                 ty_int(TyI32) => "llvm.sadd.with.overflow.i32",
                 ty_int(TyI64) => "llvm.sadd.with.overflow.i64",
+                ty_int(TyI128) => "llvm.sadd.with.overflow.i128",
 
                 ty_uint(TyU8) => "llvm.uadd.with.overflow.i8",
                 ty_uint(TyU16) => "llvm.uadd.with.overflow.i16",
                 ty_uint(TyU32) => "llvm.uadd.with.overflow.i32",
                 ty_uint(TyU64) => "llvm.uadd.with.overflow.i64",
+                ty_uint(TyU128) => "llvm.uadd.with.overflow.i128",
 
                 _ => unreachable!(),
             },
@@ -2462,11 +2483,13 @@ impl OverflowOpViaIntrinsic {
                 ty_int(TyI16) => "llvm.ssub.with.overflow.i16",
                 ty_int(TyI32) => "llvm.ssub.with.overflow.i32",
                 ty_int(TyI64) => "llvm.ssub.with.overflow.i64",
+                ty_int(TyI128) => "llvm.ssub.with.overflow.i128",
 
                 ty_uint(TyU8) => "llvm.usub.with.overflow.i8",
                 ty_uint(TyU16) => "llvm.usub.with.overflow.i16",
                 ty_uint(TyU32) => "llvm.usub.with.overflow.i32",
                 ty_uint(TyU64) => "llvm.usub.with.overflow.i64",
+                ty_uint(TyU128) => "llvm.usub.with.overflow.i128",
 
                 _ => unreachable!(),
             },
@@ -2475,11 +2498,13 @@ impl OverflowOpViaIntrinsic {
                 ty_int(TyI16) => "llvm.smul.with.overflow.i16",
                 ty_int(TyI32) => "llvm.smul.with.overflow.i32",
                 ty_int(TyI64) => "llvm.smul.with.overflow.i64",
+                ty_int(TyI128) => "llvm.smul.with.overflow.i128",
 
                 ty_uint(TyU8) => "llvm.umul.with.overflow.i8",
                 ty_uint(TyU16) => "llvm.umul.with.overflow.i16",
                 ty_uint(TyU32) => "llvm.umul.with.overflow.i32",
                 ty_uint(TyU64) => "llvm.umul.with.overflow.i64",
+                ty_uint(TyU128) => "llvm.umul.with.overflow.i128",
 
                 _ => unreachable!(),
             },
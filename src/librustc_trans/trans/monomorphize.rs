@@ -8,7 +8,8 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use back::link::exported_name;
+use back::link::{exported_name, uses_v1_mangling};
+use back::mangle;
 use session;
 use llvm::ValueRef;
 use llvm;
@@ -129,10 +130,15 @@ pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
         hash_id.hash(&mut state);
         mono_ty.hash(&mut state);
 
-        hash = format!("h{}", state.finish());
-        ccx.tcx().map.with_path(fn_id.node, |path| {
-            exported_name(path, &hash[..])
-        })
+        if uses_v1_mangling(ccx) {
+            hash = format!("h{:x}", state.finish());
+            mangle::mangle_item(ccx, fn_id.node, psubsts, None, Some(&hash[..]))
+        } else {
+            hash = format!("h{}", state.finish());
+            ccx.tcx().map.with_path(fn_id.node, |path| {
+                exported_name(path, &hash[..])
+            })
+        }
     };
 
     debug!("monomorphize_fn mangled to {}", s);
@@ -173,7 +179,7 @@ pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
         ast_map::NodeItem(i) => {
             match *i {
               ast::Item {
-                  node: ast::ItemFn(ref decl, _, abi, _, ref body),
+                  node: ast::ItemFn(ref decl, _, _, abi, _, ref body),
                   ..
               } => {
                   let d = mk_lldecl(abi);
@@ -232,6 +238,9 @@ pub fn monomorphic_fn<'a, 'tcx>(ccx: &CrateContext<'a, 'tcx>,
                     }
                     d
                 }
+                ast::ConstImplItem(..) => {
+                    ccx.sess().bug("can't monomorphize an associated const")
+                }
                 ast::TypeImplItem(_) => {
                     ccx.sess().bug("can't monomorphize an associated type")
                 }
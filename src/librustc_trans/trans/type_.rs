@@ -83,6 +83,10 @@ impl Type {
         ty!(llvm::LLVMInt64TypeInContext(ccx.llcx()))
     }
 
+    pub fn i128(ccx: &CrateContext) -> Type {
+        ty!(llvm::LLVMIntTypeInContext(ccx.llcx(), 128))
+    }
+
     // Creates an integer type with the given number of bits, e.g. i24
     pub fn ix(ccx: &CrateContext, num_bits: u64) -> Type {
         ty!(llvm::LLVMIntTypeInContext(ccx.llcx(), num_bits as c_uint))
@@ -122,7 +126,8 @@ impl Type {
             ast::TyI8 => Type::i8(ccx),
             ast::TyI16 => Type::i16(ccx),
             ast::TyI32 => Type::i32(ccx),
-            ast::TyI64 => Type::i64(ccx)
+            ast::TyI64 => Type::i64(ccx),
+            ast::TyI128 => Type::i128(ccx)
         }
     }
 
@@ -132,7 +137,8 @@ impl Type {
             ast::TyU8 => Type::i8(ccx),
             ast::TyU16 => Type::i16(ccx),
             ast::TyU32 => Type::i32(ccx),
-            ast::TyU64 => Type::i64(ccx)
+            ast::TyU64 => Type::i64(ccx),
+            ast::TyU128 => Type::i128(ccx)
         }
     }
 
@@ -1301,7 +1301,7 @@ pub fn finish_resolving_def_to_ty<'tcx>(this: &AstConv<'tcx>,
             opt_self_ty.expect("missing T in <T>::a::b::c")
         }
         def::DefPrimTy(prim_ty) => {
-            prim_ty_to_ty(tcx, base_segments, prim_ty)
+            prim_ty_to_ty(tcx, span, base_segments, prim_ty)
         }
         _ => {
             span_err!(tcx.sess, span, E0248,
@@ -1443,10 +1443,10 @@ pub fn ast_ty_to_ty<'tcx>(this: &AstConv<'tcx>,
                     match r {
                         const_eval::const_int(i) =>
                             ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
-                                        Some(i as usize)),
+                                        Some(i.as_u64() as usize)),
                         const_eval::const_uint(i) =>
                             ty::mk_vec(tcx, ast_ty_to_ty(this, rscope, &**ty),
-                                        Some(i as usize)),
+                                        Some(i.as_u64() as usize)),
                         _ => {
                             span_err!(tcx.sess, ast_ty.span, E0249,
                                       "expected constant expr for array length");
@@ -401,3 +401,55 @@ pub fn compare_impl_method<'tcx>(tcx: &ty::ctxt<'tcx>,
         return true;
     }
 }
+
+/// Checks that the type of an associated constant in an impl matches
+/// the type declared for it in the trait.
+pub fn compare_const_impl<'tcx>(tcx: &ty::ctxt<'tcx>,
+                                impl_c: &ty::AssociatedConst<'tcx>,
+                                impl_c_span: Span,
+                                trait_c: &ty::AssociatedConst<'tcx>,
+                                impl_trait_ref: &ty::TraitRef<'tcx>) {
+    debug!("compare_const_impl(impl_trait_ref={})",
+           impl_trait_ref.repr(tcx));
+
+    let infcx = infer::new_infer_ctxt(tcx);
+    let mut fulfillment_cx = traits::FulfillmentContext::new();
+
+    // The doc comments for compare_impl_method explain the use of
+    // skolemized substitutions here.
+    let impl_param_env =
+        ty::ParameterEnvironment::for_item(tcx, impl_c.def_id.node);
+    let impl_to_skol_substs = &impl_param_env.free_substs;
+    let trait_to_skol_substs = impl_trait_ref.substs.subst(tcx, impl_to_skol_substs);
+
+    let impl_ty = impl_c.ty.subst(tcx, impl_to_skol_substs);
+    let impl_ty = assoc::normalize_associated_types_in(&infcx,
+                                                       &impl_param_env,
+                                                       &mut fulfillment_cx,
+                                                       impl_c_span,
+                                                       impl_c.def_id.node,
+                                                       &impl_ty);
+    let trait_ty = trait_c.ty.subst(tcx, &trait_to_skol_substs);
+    let trait_ty = assoc::normalize_associated_types_in(&infcx,
+                                                        &impl_param_env,
+                                                        &mut fulfillment_cx,
+                                                        impl_c_span,
+                                                        impl_c.def_id.node,
+                                                        &trait_ty);
+
+    debug!("compare_const_impl: impl_ty={} trait_ty={}",
+           impl_ty.repr(tcx),
+           trait_ty.repr(tcx));
+
+    let origin = infer::Misc(impl_c_span);
+    match infer::mk_subty(&infcx, false, origin, impl_ty, trait_ty) {
+        Ok(()) => {}
+        Err(terr) => {
+            span_err!(tcx.sess, impl_c_span, E0326,
+                      "implemented const `{}` has an incompatible type for \
+                      trait: {}",
+                      token::get_name(trait_c.name),
+                      ty::type_err_to_str(tcx, &terr));
+        }
+    }
+}
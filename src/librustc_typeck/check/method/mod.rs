@@ -314,18 +314,30 @@ pub fn resolve_ufcs<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
 {
     let mode = probe::Mode::Path;
     let pick = try!(probe::probe(fcx, span, mode, method_name, self_ty, expr_id));
-    let def_id = pick.method_ty.def_id;
+    let def_id = pick.item.def_id();
     let mut lp = LastMod(AllPublic);
     let provenance = match pick.kind {
         probe::InherentImplPick(impl_def_id) => {
-            if pick.method_ty.vis != ast::Public {
+            let vis = match pick.item {
+                ty::ConstTraitItem(ref associated_const) => &associated_const.vis,
+                ty::MethodTraitItem(ref method) => &method.vis,
+                ty::TypeTraitItem(ref associated_type) => &associated_type.vis,
+            };
+            if *vis != ast::Public {
                 lp = LastMod(DependsOn(def_id));
             }
             def::FromImpl(impl_def_id)
         }
-        _ => def::FromTrait(pick.method_ty.container.id())
+        _ => def::FromTrait(pick.item.container().id())
     };
-    Ok((def::DefMethod(def_id, provenance), lp))
+    let def = match pick.item {
+        ty::ConstTraitItem(..) => def::DefAssociatedConst(def_id, provenance),
+        ty::MethodTraitItem(..) => def::DefMethod(def_id, provenance),
+        ty::TypeTraitItem(..) => {
+            fcx.tcx().sess.span_bug(span, "resolve_ufcs: probe picked associated type");
+        }
+    };
+    Ok((def, lp))
 }
 
 
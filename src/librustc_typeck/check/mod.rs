@@ -78,7 +78,7 @@ type parameter).
 
 pub use self::LvaluePreference::*;
 pub use self::Expectation::*;
-pub use self::compare_method::compare_impl_method;
+pub use self::compare_method::{compare_impl_method, compare_const_impl};
 use self::TupleArgumentsFlag::*;
 
 use astconv::{self, ast_region_to_region, ast_ty_to_ty, AstConv, PathParamMode};
@@ -736,7 +736,7 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
                             &enum_definition.variants,
                             it.id);
       }
-      ast::ItemFn(ref decl, _, _, _, ref body) => {
+      ast::ItemFn(ref decl, _, _, _, _, ref body) => {
         let fn_pty = ty::lookup_item_type(ccx.tcx, ast_util::local_def(it.id));
         let param_env = ParameterEnvironment::for_item(ccx.tcx, it.id);
         check_bare_fn(ccx, &**decl, &**body, it.id, it.span, fn_pty.ty, param_env);
@@ -758,6 +758,9 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
 
         for impl_item in impl_items {
             match impl_item.node {
+                ast::ConstImplItem(_, ref expr) => {
+                    check_associated_const(ccx, impl_item.span, &**expr, impl_item.id)
+                }
                 ast::MethodImplItem(ref sig, ref body) => {
                     check_method_body(ccx, &impl_pty.generics, sig, body,
                                       impl_item.id, impl_item.span);
@@ -775,9 +778,13 @@ pub fn check_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>, it: &'tcx ast::Item) {
         let trait_def = ty::lookup_trait_def(ccx.tcx, local_def(it.id));
         for trait_item in trait_items {
             match trait_item.node {
+                ast::ConstTraitItem(_, Some(ref expr)) => {
+                    check_associated_const(ccx, trait_item.span, &**expr, trait_item.id)
+                }
+                ast::ConstTraitItem(_, None) |
                 ast::MethodTraitItem(_, None) => {
-                    // Nothing to do, since required methods don't have
-                    // bodies to check.
+                    // Nothing to do, since required constants and methods
+                    // don't have bodies to check.
                 }
                 ast::MethodTraitItem(ref sig, Some(ref body)) => {
                     check_method_body(ccx, &trait_def.generics, sig, body,
@@ -901,6 +908,48 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     // and compatible with trait signature
     for impl_item in impl_items {
         match impl_item.node {
+            ast::ConstImplItem(..) => {
+                let impl_const_def_id = local_def(impl_item.id);
+                let impl_const_ty = ty::impl_or_trait_item(ccx.tcx,
+                                                           impl_const_def_id);
+
+                // Find associated const definition.
+                let opt_associated_const =
+                    trait_items.iter()
+                               .find(|ac| ac.name() == impl_const_ty.name());
+                match opt_associated_const {
+                    Some(associated_const) => {
+                        match (associated_const, &impl_const_ty) {
+                            (&ty::ConstTraitItem(ref const_trait),
+                             &ty::ConstTraitItem(ref const_impl)) => {
+                                compare_const_impl(ccx.tcx,
+                                                   &const_impl,
+                                                   impl_item.span,
+                                                   &const_trait,
+                                                   &*impl_trait_ref);
+                            }
+                            _ => {
+                                span_err!(tcx.sess, impl_item.span, E0323,
+                                          "item `{}` is an associated const, \
+                                          which doesn't match its trait `{}`",
+                                          token::get_name(impl_const_ty.name()),
+                                          impl_trait_ref.repr(tcx))
+                            }
+                        }
+                    }
+                    None => {
+                        // This is `span_bug` as it should have already been
+                        // caught in resolve.
+                        tcx.sess.span_bug(
+                            impl_item.span,
+                            &format!(
+                                "associated const `{}` is not a member of \
+                                 trait `{}`",
+                                token::get_name(impl_const_ty.name()),
+                                impl_trait_ref.repr(tcx)));
+                    }
+                }
+            }
             ast::MethodImplItem(_, ref body) => {
                 let impl_method_def_id = local_def(impl_item.id);
                 let impl_item_ty = ty::impl_or_trait_item(ccx.tcx,
@@ -923,6 +972,13 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                                     &**trait_method_ty,
                                                     &*impl_trait_ref);
                             }
+                            (&ty::ConstTraitItem(_), _) => {
+                                span_err!(tcx.sess, impl_item.span, E0324,
+                                          "item `{}` is an associated method, \
+                                          which doesn't match its trait `{}`",
+                                          token::get_name(impl_item_ty.name()),
+                                          impl_trait_ref.repr(tcx))
+                            }
                             _ => {
                                 // This is span_bug as it should have already been
                                 // caught in resolve.
@@ -959,6 +1015,13 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                     Some(associated_type) => {
                         match (associated_type, &typedef_ty) {
                             (&ty::TypeTraitItem(_), &ty::TypeTraitItem(_)) => {}
+                            (&ty::ConstTraitItem(_), _) => {
+                                span_err!(tcx.sess, impl_item.span, E0325,
+                                          "item `{}` is an associated type, \
+                                          which doesn't match its trait `{}`",
+                                          token::get_name(typedef_ty.name()),
+                                          impl_trait_ref.repr(tcx))
+                            }
                             _ => {
                                 // This is `span_bug` as it should have
                                 // already been caught in resolve.
@@ -993,6 +1056,21 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
     let mut missing_methods = Vec::new();
     for trait_item in &*trait_items {
         match *trait_item {
+            ty::ConstTraitItem(ref associated_const) => {
+                let is_implemented = impl_items.iter().any(|ii| {
+                    match ii.node {
+                        ast::ConstImplItem(..) => {
+                            ii.ident.name == associated_const.name
+                        }
+                        _ => false,
+                    }
+                });
+                let is_provided = associated_const.default.is_some();
+                if !is_implemented && !is_provided {
+                    missing_methods.push(format!("`{}`",
+                                                 token::get_name(associated_const.name)));
+                }
+            }
             ty::MethodTraitItem(ref trait_method) => {
                 let is_implemented =
                     impl_items.iter().any(|ii| {
@@ -1000,6 +1078,7 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                             ast::MethodImplItem(..) => {
                                 ii.ident.name == trait_method.name
                             }
+                            ast::ConstImplItem(..) |
                             ast::TypeImplItem(_) |
                             ast::MacImplItem(_) => false,
                         }
@@ -1016,6 +1095,7 @@ fn check_impl_items_against_trait<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                         ast::TypeImplItem(_) => {
                             ii.ident.name == associated_type.name
                         }
+                        ast::ConstImplItem(..) |
                         ast::MethodImplItem(..) |
                         ast::MacImplItem(_) => false,
                     }
@@ -3396,6 +3476,7 @@ fn check_expr_with_unifier<'a, 'tcx, F>(fcx: &FnCtxt<'a, 'tcx>,
           fcx.write_nil(id);
       }
       ast::ExprMac(_) => tcx.sess.bug("unexpanded macro"),
+      ast::ExprError => fcx.write_error(id),
       ast::ExprBreak(_) => { fcx.write_ty(id, fcx.infcx().next_diverging_ty_var()); }
       ast::ExprAgain(_) => { fcx.write_ty(id, fcx.infcx().next_diverging_ty_var()); }
       ast::ExprRet(ref expr_opt) => {
@@ -4156,6 +4237,19 @@ fn check_const<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
     check_const_with_ty(&fcx, sp, e, declty);
 }
 
+/// Checks the value of an associated constant against its declared type,
+/// in the parameter environment of the enclosing impl or trait.
+fn check_associated_const<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
+                                   sp: Span,
+                                   e: &'tcx ast::Expr,
+                                   id: ast::NodeId) {
+    let param_env = ParameterEnvironment::for_item(ccx.tcx, id);
+    let declty = ty::node_id_to_type(ccx.tcx, id).subst(ccx.tcx, &param_env.free_substs);
+    let inh = Inherited::new(ccx.tcx, param_env);
+    let fcx = blank_fn_ctxt(ccx, &inh, ty::FnConverging(declty), e.id);
+    check_const_with_ty(&fcx, sp, e, declty);
+}
+
 fn check_const_with_ty<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                  _: Span,
                                  e: &'tcx ast::Expr,
@@ -4273,7 +4367,7 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                 ast::TyU8 => disr as u8 as Disr == disr,
                 ast::TyU16 => disr as u16 as Disr == disr,
                 ast::TyU32 => disr as u32 as Disr == disr,
-                ast::TyU64 => disr as u64 as Disr == disr,
+                ast::TyU64 | ast::TyU128 => disr as u64 as Disr == disr,
                 ast::TyUs => uint_in_range(ccx, ccx.tcx.sess.target.uint_type, disr)
             }
         }
@@ -4282,7 +4376,7 @@ pub fn check_enum_variants<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
                 ast::TyI8 => disr as i8 as Disr == disr,
                 ast::TyI16 => disr as i16 as Disr == disr,
                 ast::TyI32 => disr as i32 as Disr == disr,
-                ast::TyI64 => disr as i64 as Disr == disr,
+                ast::TyI64 | ast::TyI128 => disr as i64 as Disr == disr,
                 ast::TyIs => int_in_range(ccx, ccx.tcx.sess.target.int_type, disr)
             }
         }
@@ -4386,7 +4480,7 @@ fn type_scheme_and_predicates_for_def<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         }
         def::DefFn(id, _) | def::DefMethod(id, _) |
         def::DefStatic(id, _) | def::DefVariant(_, id, _) |
-        def::DefStruct(id) | def::DefConst(id) => {
+        def::DefStruct(id) | def::DefConst(id) | def::DefAssociatedConst(id, _) => {
             (ty::lookup_item_type(fcx.tcx(), id), ty::lookup_predicates(fcx.tcx(), id))
         }
         def::DefTrait(_) |
@@ -4529,6 +4623,21 @@ pub fn instantiate_path<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             }
         }
 
+        // Case 4. Reference to an associated constant, which is like a
+        // method without any type parameters of its own.
+        def::DefAssociatedConst(_, provenance) => {
+            if segments.len() >= 2 {
+                segment_spaces = repeat(None).take(segments.len() - 2).collect();
+                segment_spaces.push(Some(subst::TypeSpace));
+                segment_spaces.push(None);
+            } else {
+                // `<T>::CONST` will end up here, and so can `T::CONST`.
+                let self_ty = opt_self_ty.expect("UFCS sugared const missing Self");
+                segment_spaces = vec![None];
+                ufcs_method = Some((provenance, self_ty));
+            }
+        }
+
         // Other cases. Various nonsense that really shouldn't show up
         // here. If they do, an error will have been reported
         // elsewhere. (I hope)
@@ -5151,17 +5260,21 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
             "ctpop16"      => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "ctpop32"      => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "ctpop64"      => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "ctpop128"     => (0, vec!( tcx.types.u128), tcx.types.u128),
             "ctlz8"        => (0, vec!( tcx.types.u8  ), tcx.types.u8),
             "ctlz16"       => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "ctlz32"       => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "ctlz64"       => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "ctlz128"      => (0, vec!( tcx.types.u128), tcx.types.u128),
             "cttz8"        => (0, vec!( tcx.types.u8  ), tcx.types.u8),
             "cttz16"       => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "cttz32"       => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "cttz64"       => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "cttz128"      => (0, vec!( tcx.types.u128), tcx.types.u128),
             "bswap16"      => (0, vec!( tcx.types.u16 ), tcx.types.u16),
             "bswap32"      => (0, vec!( tcx.types.u32 ), tcx.types.u32),
             "bswap64"      => (0, vec!( tcx.types.u64 ), tcx.types.u64),
+            "bswap128"     => (0, vec!( tcx.types.u128), tcx.types.u128),
 
             "volatile_load" =>
                 (1, vec!( ty::mk_imm_ptr(tcx, param(ccx, 0)) ), param(ccx, 0)),
@@ -5184,6 +5297,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (0, vec!(tcx.types.i64, tcx.types.i64),
                 ty::mk_tup(tcx, vec!(tcx.types.i64, tcx.types.bool))),
 
+            "i128_add_with_overflow" | "i128_sub_with_overflow" | "i128_mul_with_overflow" =>
+                (0, vec!(tcx.types.i128, tcx.types.i128),
+                ty::mk_tup(tcx, vec!(tcx.types.i128, tcx.types.bool))),
+
             "u8_add_with_overflow" | "u8_sub_with_overflow" | "u8_mul_with_overflow" =>
                 (0, vec!(tcx.types.u8, tcx.types.u8),
                 ty::mk_tup(tcx, vec!(tcx.types.u8, tcx.types.bool))),
@@ -5200,6 +5317,10 @@ pub fn check_intrinsic_type(ccx: &CrateCtxt, it: &ast::ForeignItem) {
                 (0, vec!(tcx.types.u64, tcx.types.u64),
                 ty::mk_tup(tcx, vec!(tcx.types.u64, tcx.types.bool))),
 
+            "u128_add_with_overflow" | "u128_sub_with_overflow" | "u128_mul_with_overflow" =>
+                (0, vec!(tcx.types.u128, tcx.types.u128),
+                ty::mk_tup(tcx, vec!(tcx.types.u128, tcx.types.bool))),
+
             "overflowing_add" | "overflowing_sub" | "overflowing_mul" =>
                 (1, vec![param(ccx, 0), param(ccx, 0)], param(ccx, 0)),
 
@@ -75,6 +75,13 @@ pub fn check_object_safety<'tcx>(tcx: &ty::ctxt<'tcx>,
                     &format!("method `{}` has generic type parameters",
                              method.name.user_string(tcx)));
             }
+
+            ObjectSafetyViolation::AssociatedConst(c) => {
+                tcx.sess.span_note(
+                    span,
+                    &format!("the trait cannot contain associated const `{}`",
+                             c.name.user_string(tcx)));
+            }
         }
     }
 }
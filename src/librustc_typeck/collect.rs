@@ -196,7 +196,7 @@ impl<'a,'tcx> CrateCtxt<'a,'tcx> {
         let def_id = local_def(method_id);
         match *self.tcx.impl_or_trait_items.borrow().get(&def_id).unwrap() {
             ty::MethodTraitItem(ref mty) => mty.clone(),
-            ty::TypeTraitItem(..) => {
+            _ => {
                 self.tcx.sess.bug(&format!("method with id {} has the wrong type", method_id));
             }
         }
@@ -671,25 +671,58 @@ fn convert_field<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                            struct_predicates.clone());
 
     match v.node.kind {
-        ast::NamedField(ident, visibility) => {
+        ast::NamedField(ident, ref visibility) => {
             ty::field_ty {
                 name: ident.name,
                 id: local_def(v.node.id),
-                vis: visibility,
+                vis: visibility.clone(),
                 origin: origin,
             }
         }
-        ast::UnnamedField(visibility) => {
+        ast::UnnamedField(ref visibility) => {
             ty::field_ty {
                 name: special_idents::unnamed_field.name,
                 id: local_def(v.node.id),
-                vis: visibility,
+                vis: visibility.clone(),
                 origin: origin,
             }
         }
     }
 }
 
+fn convert_associated_const<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
+                                      container: ImplOrTraitItemContainer,
+                                      ident: ast::Ident,
+                                      id: ast::NodeId,
+                                      vis: ast::Visibility,
+                                      ty: Ty<'tcx>,
+                                      generics: &ty::Generics<'tcx>,
+                                      predicates: &ty::GenericPredicates<'tcx>,
+                                      has_default: bool)
+{
+    // Associated constants are generic over the parameters of the
+    // containing impl or trait.
+    ccx.tcx.tcache.borrow_mut().insert(local_def(id),
+                                       TypeScheme {
+                                           generics: generics.clone(),
+                                           ty: ty,
+                                       });
+    ccx.tcx.predicates.borrow_mut().insert(local_def(id), predicates.clone());
+    write_ty_to_tcx(ccx.tcx, id, ty);
+
+    let default_id = if has_default { Some(local_def(id)) } else { None };
+    let associated_const = Rc::new(ty::AssociatedConst {
+        name: ident.name,
+        vis: vis,
+        def_id: local_def(id),
+        container: container,
+        ty: ty,
+        default: default_id,
+    });
+    ccx.tcx.impl_or_trait_items.borrow_mut()
+       .insert(local_def(id), ty::ConstTraitItem(associated_const));
+}
+
 fn as_refsociated_type<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
                                      container: ImplOrTraitItemContainer,
                                      ident: ast::Ident,
@@ -824,9 +857,25 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
             let parent_visibility = if opt_trait_ref.is_some() {
                 ast::Public
             } else {
-                it.vis
+                it.vis.clone()
             };
 
+            // Convert all the associated constants.
+            for impl_item in impl_items {
+                if let ast::ConstImplItem(ref ty, _) = impl_item.node {
+                    let ty = ccx.icx(&ty_predicates).to_ty(&ExplicitRscope, &*ty);
+                    convert_associated_const(ccx,
+                                             ImplContainer(local_def(it.id)),
+                                             impl_item.ident,
+                                             impl_item.id,
+                                             impl_item.vis.inherit_from(&parent_visibility),
+                                             ty,
+                                             &ty_generics,
+                                             &ty_predicates,
+                                             true)
+                }
+            }
+
             // Convert all the associated types.
             for impl_item in impl_items {
                 match impl_item.node {
@@ -837,7 +886,7 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
                         }
 
                         as_refsociated_type(ccx, ImplContainer(local_def(it.id)),
-                                                impl_item.ident, impl_item.id, impl_item.vis);
+                                                impl_item.ident, impl_item.id, impl_item.vis.clone());
 
                         let typ = ccx.icx(&ty_predicates).to_ty(&ExplicitRscope, ty);
                         tcx.tcache.borrow_mut().insert(local_def(impl_item.id),
@@ -849,6 +898,7 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
                                                            ty::GenericPredicates::empty());
                         write_ty_to_tcx(tcx, impl_item.id, typ);
                     }
+                    ast::ConstImplItem(..) |
                     ast::MethodImplItem(..) |
                     ast::MacImplItem(_) => {}
                 }
@@ -861,9 +911,10 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
                         // inherit the visibility from the impl (so `foo` in `pub impl
                         // { fn foo(); }` is public, but private in `priv impl { fn
                         // foo(); }`).
-                        let method_vis = ii.vis.inherit_from(parent_visibility);
+                        let method_vis = ii.vis.inherit_from(&parent_visibility);
                         Some((sig, ii.id, ii.ident, method_vis, ii.span))
                     }
+                    ast::ConstImplItem(..) |
                     ast::TypeImplItem(_) |
                     ast::MacImplItem(_) => None
                 }
@@ -886,6 +937,7 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
                                                &sig.explicit_self,
                                                body_id);
                     }
+                    ast::ConstImplItem(..) |
                     ast::TypeImplItem(_) |
                     ast::MacImplItem(_) => {}
                 }
@@ -914,9 +966,26 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
 
             debug!("convert: trait_bounds={:?}", trait_predicates);
 
+            // Convert all the associated constants.
+            for trait_item in trait_items {
+                if let ast::ConstTraitItem(ref ty, ref default) = trait_item.node {
+                    let ty = ccx.icx(&trait_predicates).to_ty(&ExplicitRscope, ty);
+                    convert_associated_const(ccx,
+                                             TraitContainer(local_def(it.id)),
+                                             trait_item.ident,
+                                             trait_item.id,
+                                             ast::Public,
+                                             ty,
+                                             &trait_def.generics,
+                                             &trait_predicates,
+                                             default.is_some())
+                }
+            }
+
             // Convert all the associated types.
             for trait_item in trait_items {
                 match trait_item.node {
+                    ast::ConstTraitItem(..) |
                     ast::MethodTraitItem(..) => {}
                     ast::TypeTraitItem(..) => {
                         as_refsociated_type(ccx, TraitContainer(local_def(it.id)),
@@ -928,6 +997,7 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
             let methods = trait_items.iter().filter_map(|ti| {
                 let sig = match ti.node {
                     ast::MethodTraitItem(ref sig, _) => sig,
+                    ast::ConstTraitItem(..) |
                     ast::TypeTraitItem(..) => return None,
                 };
                 Some((sig, ti.id, ti.ident, ast::Inherited, ti.span))
@@ -945,6 +1015,9 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
             let trait_item_def_ids = Rc::new(trait_items.iter().map(|trait_item| {
                 let def_id = local_def(trait_item.id);
                 match trait_item.node {
+                    ast::ConstTraitItem(..) => {
+                        ty::ConstTraitItemId(def_id)
+                    }
                     ast::MethodTraitItem(..) => {
                         ty::MethodTraitItemId(def_id)
                     }
@@ -960,6 +1033,7 @@ fn convert_item(ccx: &CrateCtxt, it: &ast::Item) {
             for trait_item in trait_items {
                 let sig = match trait_item.node {
                     ast::MethodTraitItem(ref sig, _) => sig,
+                    ast::ConstTraitItem(..) |
                     ast::TypeTraitItem(..) => continue
                 };
                 check_method_self_type(ccx,
@@ -1183,6 +1257,7 @@ fn trait_def_of_item<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
 
     let associated_type_names: Vec<_> = items.iter().filter_map(|trait_item| {
         match trait_item.node {
+            ast::ConstTraitItem(..) |
             ast::MethodTraitItem(..) => None,
             ast::TypeTraitItem(..) => Some(trait_item.ident.name),
         }
@@ -1256,6 +1331,7 @@ fn trait_defines_associated_type_named(ccx: &CrateCtxt,
     trait_items.iter().any(|trait_item| {
         match trait_item.node {
             ast::TypeTraitItem(..) => trait_item.ident.name == assoc_name,
+            ast::ConstTraitItem(..) |
             ast::MethodTraitItem(..) => false,
         }
     })
@@ -1316,6 +1392,7 @@ fn convert_trait_predicates<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>, it: &ast::Item)
         trait_items.iter().flat_map(|trait_item| {
             let bounds = match trait_item.node {
                 ast::TypeTraitItem(ref bounds, _) => bounds,
+                ast::ConstTraitItem(..) |
                 ast::MethodTraitItem(..) => {
                     return vec!().into_iter();
                 }
@@ -1379,7 +1456,7 @@ fn compute_type_scheme_of_item<'a,'tcx>(ccx: &CrateCtxt<'a,'tcx>,
             let ty = ccx.icx(&()).to_ty(&ExplicitRscope, &**t);
             ty::TypeScheme { ty: ty, generics: ty::Generics::empty() }
         }
-        ast::ItemFn(ref decl, unsafety, abi, ref generics, _) => {
+        ast::ItemFn(ref decl, unsafety, _, abi, ref generics, _) => {
             let ty_generics = ty_generics_for_fn(ccx, generics, &ty::Generics::empty());
             let tofd = astconv::ty_of_bare_fn(&ccx.icx(generics), unsafety, abi, &**decl);
             let ty = ty::mk_bare_fn(tcx, Some(local_def(it.id)), tcx.mk_bare_fn(tofd));
@@ -1431,7 +1508,7 @@ fn convert_typed_item<'a, 'tcx>(ccx: &CrateCtxt<'a, 'tcx>,
         ast::ItemStatic(..) | ast::ItemConst(..) => {
             ty::GenericPredicates::empty()
         }
-        ast::ItemFn(_, _, _, ref ast_generics, _) => {
+        ast::ItemFn(_, _, _, _, ref ast_generics, _) => {
             ty_generic_predicates_for_fn(ccx, ast_generics, &ty::GenericPredicates::empty())
         }
         ast::ItemTy(_, ref generics) => {
@@ -13,9 +13,11 @@
 use syntax::ast;
 use syntax::ast_util;
 use syntax::attr::AttrMetaMethods;
+use syntax::print::pprust;
 
 use rustc::metadata::csearch;
 use rustc::metadata::decoder;
+use rustc::middle::const_eval;
 use rustc::middle::def;
 use rustc::middle::ty;
 use rustc::middle::subst;
@@ -165,10 +167,16 @@ fn build_external_function(cx: &DocContext, tcx: &ty::ctxt, did: ast::DefId) ->
         _ => panic!("bad function"),
     };
     let predicates = ty::lookup_predicates(tcx, did);
+    let constness = if csearch::is_const_fn(&tcx.sess.cstore, did) {
+        ast::Constness::Const
+    } else {
+        ast::Constness::NotConst
+    };
     clean::Function {
         decl: decl,
         generics: (&t.generics, &predicates, subst::FnSpace).clean(cx),
         unsafety: style,
+        constness: constness,
     }
 }
 
@@ -303,6 +311,25 @@ fn build_impl(cx: &DocContext,
         let did = did.def_id();
         let impl_item = ty::impl_or_trait_item(tcx, did);
         match impl_item {
+            ty::ConstTraitItem(ref assoc_const) => {
+                let did = assoc_const.def_id;
+                let type_scheme = ty::lookup_item_type(tcx, did);
+                let expr = const_eval::lookup_const_by_id(tcx, did, None).unwrap_or_else(|| {
+                    panic!("expected lookup_const_by_id to succeed for {:?}", did);
+                });
+                Some(clean::Item {
+                    name: Some(assoc_const.name.clean(cx)),
+                    inner: clean::ConstantItem(clean::Constant {
+                        type_: type_scheme.ty.clean(cx),
+                        expr: pprust::expr_to_string(expr),
+                    }),
+                    source: clean::Span::empty(),
+                    attrs: vec![],
+                    visibility: None,
+                    stability: stability::lookup(tcx, did).clean(cx),
+                    def_id: did
+                })
+            }
             ty::MethodTraitItem(method) => {
                 if method.vis != ast::Public && associated_trait.is_none() {
                     return None
@@ -315,8 +342,15 @@ fn build_impl(cx: &DocContext,
                     clean::TyMethodItem(clean::TyMethod {
                         unsafety, decl, self_, generics, abi
                     }) => {
+                        let constness = if csearch::is_const_fn(&tcx.sess.cstore,
+                                                                method.def_id) {
+                            ast::Constness::Const
+                        } else {
+                            ast::Constness::NotConst
+                        };
                         clean::MethodItem(clean::Method {
                             unsafety: unsafety,
+                            constness: constness,
                             decl: decl,
                             self_: self_,
                             generics: generics,
@@ -421,10 +455,7 @@ fn build_module(cx: &DocContext, tcx: &ty::ctxt,
 
 fn build_const(cx: &DocContext, tcx: &ty::ctxt,
                did: ast::DefId) -> clean::Constant {
-    use rustc::middle::const_eval;
-    use syntax::print::pprust;
-
-    let expr = const_eval::lookup_const_by_id(tcx, did).unwrap_or_else(|| {
+    let expr = const_eval::lookup_const_by_id(tcx, did, None).unwrap_or_else(|| {
         panic!("expected lookup_const_by_id to succeed for {:?}", did);
     });
     debug!("converting constant expr {:?} to snippet", expr);
@@ -355,9 +355,7 @@ pub fn find_testable_code(doc: &str, tests: &mut ::test::Collector) {
                 stripped_filtered_line(l).unwrap_or(l)
             });
             let text = lines.collect::<Vec<&str>>().connect("\n");
-            tests.add_test(text.to_string(),
-                           block_info.should_panic, block_info.no_run,
-                           block_info.ignore, block_info.test_harness);
+            tests.add_test(text.to_string(), block_info);
         }
     }
 
@@ -395,13 +393,24 @@ pub fn find_testable_code(doc: &str, tests: &mut ::test::Collector) {
     }
 }
 
+/// What the info string of a code block says about the block, and about
+/// testing it.
 #[derive(Eq, PartialEq, Clone, Debug)]
-struct LangString {
-    should_panic: bool,
-    no_run: bool,
-    ignore: bool,
-    rust: bool,
-    test_harness: bool,
+pub struct LangString {
+    pub should_panic: bool,
+    pub no_run: bool,
+    pub ignore: bool,
+    pub rust: bool,
+    pub test_harness: bool,
+    /// The block must fail to compile, with each of `error_codes` if any.
+    pub compile_fail: bool,
+    pub error_codes: Vec<String>,
+    /// `ignore-<target>`: targets, or parts of target triples, to skip the
+    /// test on.
+    pub ignore_targets: Vec<String>,
+    /// `rustc-flag=<flag>`: extra arguments to build the test with, one per
+    /// attribute.
+    pub rustc_flags: Vec<String>,
 }
 
 impl LangString {
@@ -412,6 +421,10 @@ impl LangString {
             ignore: false,
             rust: true,  // NB This used to be `notrust = false`
             test_harness: false,
+            compile_fail: false,
+            error_codes: Vec::new(),
+            ignore_targets: Vec::new(),
+            rustc_flags: Vec::new(),
         }
     }
 
@@ -420,7 +433,22 @@ impl LangString {
         let mut seen_other_tags = false;
         let mut data = LangString::all_false();
 
-        let tokens = string.split(|c: char|
+        // Flags are taken verbatim, so they're picked out before the rest
+        // is split into tokens.
+        let mut rest = String::new();
+        for word in string.split(|c: char| c == ',' || c.is_whitespace()) {
+            let word = word.trim_left_matches(|c| c == '{' || c == '.')
+                           .trim_right_matches('}');
+            if word.starts_with("rustc-flag=") {
+                data.rustc_flags.push(word["rustc-flag=".len()..].to_string());
+                seen_rust_tags = true;
+            } else {
+                rest.push_str(word);
+                rest.push(' ');
+            }
+        }
+
+        let tokens = rest.split(|c: char|
             !(c == '_' || c == '-' || c.is_alphanumeric())
         );
 
@@ -432,16 +460,33 @@ impl LangString {
                 "ignore" => { data.ignore = true; seen_rust_tags = true; },
                 "rust" => { data.rust = true; seen_rust_tags = true; },
                 "test_harness" => { data.test_harness = true; seen_rust_tags = true; }
+                "compile_fail" => { data.compile_fail = true; seen_rust_tags = true; }
+                x if x.starts_with("ignore-") => {
+                    data.ignore_targets.push(x["ignore-".len()..].to_string());
+                    seen_rust_tags = true;
+                }
+                x if is_error_code(x) => { data.error_codes.push(x.to_string()); }
                 _ => { seen_other_tags = true }
             }
         }
 
+        // Error codes only mean something on a `compile_fail` block; on
+        // their own they're just another foreign tag.
+        if !data.error_codes.is_empty() && !data.compile_fail {
+            data.error_codes.clear();
+            seen_other_tags = true;
+        }
         data.rust &= !seen_other_tags || seen_rust_tags;
 
         data
     }
 }
 
+// Diagnostic codes look like `E0382`.
+fn is_error_code(s: &str) -> bool {
+    s.len() == 5 && s.starts_with("E") && s[1..].chars().all(|c| c.is_digit(10))
+}
+
 /// By default this markdown renderer generates anchors for each header in the
 /// rendered document. The anchor name is the contents of the header separated
 /// by hyphens, and a task-local map is used to disambiguate among duplicate
@@ -535,6 +580,7 @@ mod tests {
                 ignore: ignore,
                 rust: rust,
                 test_harness: test_harness,
+                .. LangString::all_false()
             })
         }
 
@@ -552,6 +598,35 @@ mod tests {
         t("{.test_harness .rust}", false,        false,   false,   true,  true);
     }
 
+    #[test]
+    fn test_lang_string_parse_test_attributes() {
+        let s = LangString::parse("compile_fail");
+        assert!(s.compile_fail && s.rust && s.error_codes.is_empty());
+
+        let s = LangString::parse("compile_fail,E0382,E0499");
+        assert!(s.compile_fail && s.rust);
+        assert_eq!(s.error_codes, ["E0382", "E0499"]);
+
+        let s = LangString::parse("E0382");
+        assert!(!s.compile_fail && !s.rust && s.error_codes.is_empty());
+
+        let s = LangString::parse("E0382,compile_fail");
+        assert!(s.compile_fail && s.rust);
+        assert_eq!(s.error_codes, ["E0382"]);
+
+        let s = LangString::parse("rust,ignore-windows,ignore-x86_64-apple-darwin");
+        assert!(s.rust && !s.ignore);
+        assert_eq!(s.ignore_targets, ["windows", "x86_64-apple-darwin"]);
+
+        let s = LangString::parse("rustc-flag=-Copt-level=3,rustc-flag=--cfg=foo,no_run");
+        assert!(s.rust && s.no_run);
+        assert_eq!(s.rustc_flags, ["-Copt-level=3", "--cfg=foo"]);
+
+        let s = LangString::parse("{.rust .rustc-flag=-Cpanic=abort}");
+        assert!(s.rust);
+        assert_eq!(s.rustc_flags, ["-Cpanic=abort"]);
+    }
+
     #[test]
     fn issue_17736() {
         let markdown = "# title";
@@ -22,6 +22,7 @@
 
 #![feature(box_patterns)]
 #![feature(box_syntax)]
+#![feature(catch_panic)]
 #![feature(collections)]
 #![feature(exit_status)]
 #![feature(set_stdio)]
@@ -19,7 +19,9 @@ use std::path::PathBuf;
 use std::process::Command;
 use std::str;
 use std::sync::{Arc, Mutex};
+use std::thread;
 
+use getopts;
 use testing;
 use rustc_lint;
 use rustc::session::{self, config};
@@ -139,17 +141,39 @@ fn should_inject_crate(krate: &::syntax::ast::Crate) -> bool {
     return inject_crate;
 }
 
+/// The session options a test's `rustc-flag=` attributes ask for, parsed the
+/// way rustc parses its command line.
+fn rustc_flag_options(flags: &[String]) -> config::Options {
+    if flags.is_empty() {
+        return config::basic_options()
+    }
+    let groups = config::rustc_optgroups().into_iter()
+                                          .map(|x| x.opt_group)
+                                          .collect::<Vec<_>>();
+    match getopts::getopts(flags, &groups) {
+        Ok(matches) => config::build_session_options(&matches),
+        Err(e) => panic!("invalid rustc-flag for the test: {}", e),
+    }
+}
+
 #[allow(deprecated)]
 fn runtest(test: &str, cratename: &str, libs: SearchPaths,
-           externs: core::Externs,
-           should_panic: bool, no_run: bool, as_test_harness: bool,
+           externs: core::Externs, info: &markdown::LangString,
            inject_crate: bool) {
+    let as_test_harness = info.test_harness;
     // the test harness wants its own `main` & top level functions, so
     // never wrap the test in `fn main() { ... }`
     let test = maketest(test, Some(cratename), true, as_test_harness,
                         inject_crate);
     let input = config::Input::Str(test.to_string());
 
+    // Whatever the test's own flags say, it's still built into an
+    // executable against the crate being documented.
+    let base = rustc_flag_options(&info.rustc_flags);
+    let cg = config::CodegenOptions {
+        prefer_dynamic: true,
+        .. base.cg.clone()
+    };
     let sessopts = config::Options {
         maybe_sysroot: Some(env::current_exe().unwrap().parent().unwrap()
                                               .parent().unwrap().to_path_buf()),
@@ -157,13 +181,10 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
         crate_types: vec!(config::CrateTypeExecutable),
         output_types: vec!(config::OutputTypeExe),
         externs: externs,
-        cg: config::CodegenOptions {
-            prefer_dynamic: true,
-            .. config::basic_codegen_options()
-        },
+        cg: cg,
         test: as_test_harness,
         unstable_features: get_unstable_features_setting(),
-        ..config::basic_options().clone()
+        ..base
     };
 
     // Shuffle around a few input and output handles here. We're going to pass
@@ -192,30 +213,57 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
     let data = Arc::new(Mutex::new(Vec::new()));
     let emitter = diagnostic::EmitterWriter::new(box Sink(data.clone()), None);
     let old = io::set_panic(box Sink(data.clone()));
-    let _bomb = Bomb(data, old.unwrap_or(box io::stdout()));
-
-    // Compile the code
-    let codemap = CodeMap::new();
-    let diagnostic_handler = diagnostic::mk_handler(true, box emitter);
-    let span_diagnostic_handler =
-        diagnostic::mk_span_handler(diagnostic_handler, codemap);
-
-    let sess = session::build_session_(sessopts,
-                                       None,
-                                       span_diagnostic_handler);
-    rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+    let _bomb = Bomb(data.clone(), old.unwrap_or(box io::stdout()));
 
     let outdir = TempDir::new("rustdoctest").ok().expect("rustdoc needs a tempdir");
     let out = Some(outdir.path().to_path_buf());
-    let cfg = config::build_configuration(&sess);
-    let libdir = sess.target_filesearch(PathKind::All).get_lib_path();
-    let mut control = driver::CompileController::basic();
-    if no_run {
-        control.after_analysis.stop = Compilation::Stop;
-    }
-    driver::compile_input(sess, cfg, &input, &out, &None, None, control);
+    // A test which is supposed to fail to compile only needs to get as far
+    // as the errors, and neither does one which isn't going to be run.
+    let stop_after_analysis = info.no_run || info.compile_fail;
+
+    // Compile the code. Compilation errors unwind, which is caught here so
+    // that `compile_fail` tests can tell them apart from other failures.
+    let compiled = thread::catch_panic(move || {
+        let codemap = CodeMap::new();
+        let diagnostic_handler = diagnostic::mk_handler(true, box emitter);
+        let span_diagnostic_handler =
+            diagnostic::mk_span_handler(diagnostic_handler, codemap);
+
+        let sess = session::build_session_(sessopts,
+                                           None,
+                                           span_diagnostic_handler);
+        rustc_lint::register_builtins(&mut sess.lint_store.borrow_mut(), Some(&sess));
+
+        let cfg = config::build_configuration(&sess);
+        let libdir = sess.target_filesearch(PathKind::All).get_lib_path();
+        let mut control = driver::CompileController::basic();
+        if stop_after_analysis {
+            control.after_analysis.stop = Compilation::Stop;
+        }
+        driver::compile_input(sess, cfg, &input, &out, &None, None, control);
+        libdir
+    });
+
+    let libdir = match compiled {
+        Ok(..) if info.compile_fail => {
+            panic!("test compiled successfully, but it's marked `compile_fail`")
+        }
+        Ok(libdir) => libdir,
+        Err(ref e) if info.compile_fail && e.is::<diagnostic::FatalError>() => {
+            let output = String::from_utf8_lossy(&data.lock().unwrap()).into_owned();
+            let missing = info.error_codes.iter().filter(|code| {
+                !output.contains(&format!("[{}]", code))
+            }).collect::<Vec<_>>();
+            if !missing.is_empty() {
+                panic!("the test failed to compile, but without the expected \
+                        error codes {:?}", missing);
+            }
+            return
+        }
+        Err(..) => panic!("couldn't compile the test"),
+    };
 
-    if no_run { return }
+    if info.no_run { return }
 
     // Run the code!
     //
@@ -239,9 +287,9 @@ fn runtest(test: &str, cratename: &str, libs: SearchPaths,
                             " - maybe your tempdir is mounted with noexec?"
                         } else { "" }),
         Ok(out) => {
-            if should_panic && out.status.success() {
+            if info.should_panic && out.status.success() {
                 panic!("test executable succeeded when it should have failed");
-            } else if !should_panic && !out.status.success() {
+            } else if !info.should_panic && !out.status.success() {
                 panic!("test executable failed:\n{}\n{}",
                        str::from_utf8(&out.stdout).unwrap_or(""),
                        str::from_utf8(&out.stderr).unwrap_or(""));
@@ -344,9 +392,7 @@ impl Collector {
         }
     }
 
-    pub fn add_test(&mut self, test: String,
-                    should_panic: bool, no_run: bool, should_ignore: bool,
-                    as_test_harness: bool) {
+    pub fn add_test(&mut self, test: String, info: markdown::LangString) {
         let name = if self.use_headers {
             let s = self.current_header.as_ref().map(|s| &**s).unwrap_or("");
             format!("{}_{}", s, self.cnt)
@@ -358,6 +404,11 @@ impl Collector {
         let externs = self.externs.clone();
         let cratename = self.cratename.to_string();
         let inject_crate = self.inject_crate;
+        // `ignore-<target>` names a target, or any part of one, on which the
+        // test doesn't work. Tests are built for the host.
+        let should_ignore = info.ignore || info.ignore_targets.iter().any(|target| {
+            config::host_triple().contains(&target[..])
+        });
         debug!("Creating test {}: {}", name, test);
         self.tests.push(testing::TestDescAndFn {
             desc: testing::TestDesc {
@@ -370,9 +421,7 @@ impl Collector {
                         &cratename,
                         libs,
                         externs,
-                        should_panic,
-                        no_run,
-                        as_test_harness,
+                        &info,
                         inject_crate);
             }))
         });
@@ -127,6 +127,11 @@
 #![feature(std_misc)]
 #![feature(slice_patterns)]
 #![feature(debug_builders)]
+#![feature(rustc_private)]
+#![cfg_attr(not(stage0), feature(i128_type, i128))]
+#![cfg_attr(stage0, feature(panic_unwind))]
+#![cfg_attr(not(stage0), feature(needs_panic_runtime))]
+#![cfg_attr(not(stage0), needs_panic_runtime)]
 #![cfg_attr(test, feature(test, rustc_private, std_misc))]
 
 // Don't link to std. We are std.
@@ -152,6 +157,11 @@ extern crate collections as core_collections;
 extern crate alloc;
 extern crate unicode;
 extern crate libc;
+extern crate rustc_demangle;
+
+// The snapshot compiler doesn't know how to inject a panic runtime, so always
+// link the unwinding one when bootstrapping.
+#[cfg(stage0)] extern crate panic_unwind;
 
 #[macro_use] #[no_link] extern crate rustc_bitflags;
 
@@ -231,12 +241,16 @@ mod uint_macros;
 #[path = "num/i16.rs"]  pub mod i16;
 #[path = "num/i32.rs"]  pub mod i32;
 #[path = "num/i64.rs"]  pub mod i64;
+#[cfg(not(stage0))]
+#[path = "num/i128.rs"] pub mod i128;
 
 #[path = "num/usize.rs"] pub mod usize;
 #[path = "num/u8.rs"]   pub mod u8;
 #[path = "num/u16.rs"]  pub mod u16;
 #[path = "num/u32.rs"]  pub mod u32;
 #[path = "num/u64.rs"]  pub mod u64;
+#[cfg(not(stage0))]
+#[path = "num/u128.rs"] pub mod u128;
 
 #[path = "num/f32.rs"]   pub mod f32;
 #[path = "num/f64.rs"]   pub mod f64;
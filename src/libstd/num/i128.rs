@@ -0,0 +1,18 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Operations and constants for signed 128-bits integers (`i128` type)
+
+#![unstable(feature = "i128", reason = "recently added as an experimental type")]
+#![doc(primitive = "i128")]
+
+pub use core::i128::{BITS, BYTES, MIN, MAX};
+
+int_module! { i128 }
@@ -69,6 +69,13 @@ mod test {
         t!("_ZN12test$BP$test4foobE", "test*test::foob");
     }
 
+    #[test]
+    fn demangle_v1() {
+        t!("_RNCs_3std2io", "std::io");
+        t!("_RNMINNCs_5alloc3vec3VechE4push", "<alloc::vec::Vec<u8>>::push");
+        t!("_RINCs_3foo7genericRSlEh1a2b", "foo::generic<&[i32]>::h1a2b");
+    }
+
     #[test]
     fn demangle_windows() {
         t!("ZN4testE", "test");
@@ -56,6 +56,15 @@
 //! the unwind runtime.
 //!
 //! Currently Rust uses unwind runtime provided by libgcc.
+//!
+//! ## Panic runtimes
+//!
+//! Raising and catching a panic is delegated to a separate panic runtime
+//! crate. By default this is libpanic_unwind, which throws the panic as an
+//! exception as described above. Programs compiled with `-C panic=abort` link
+//! libpanic_abort instead, which aborts the process on panic. The personality
+//! routines below are used by both, as the standard library itself is compiled
+//! with landing pads.
 
 use prelude::v1::*;
 
@@ -66,18 +75,11 @@ use cmp;
 use panicking;
 use fmt;
 use intrinsics;
-use libc::c_void;
 use mem;
+use raw;
 use sync::atomic::{self, Ordering};
 use sys_common::mutex::{Mutex, MUTEX_INIT};
 
-use rt::libunwind as uw;
-
-struct Exception {
-    uwe: uw::_Unwind_Exception,
-    cause: Option<Box<Any + Send + 'static>>,
-}
-
 pub type Callback = fn(msg: &(Any + Send), file: &'static str, line: usize);
 
 // Variables used for invoking callbacks when a thread starts to unwind.
@@ -119,38 +121,48 @@ thread_local! { static PANICKING: Cell<bool> = Cell::new(false) }
 ///   run.
 pub unsafe fn try<F: FnOnce()>(f: F) -> Result<(), Box<Any + Send>> {
     let mut f = Some(f);
+    let mut data = 0;
+    let mut vtable = 0;
 
     let prev = PANICKING.with(|s| s.get());
     PANICKING.with(|s| s.set(false));
-    let ep = rust_try(try_fn::<F>, &mut f as *mut _ as *mut c_void);
+    let r = __rust_maybe_catch_panic(try_fn::<F>,
+                                     &mut f as *mut _ as *mut u8,
+                                     &mut data,
+                                     &mut vtable);
     PANICKING.with(|s| s.set(prev));
-    return if ep.is_null() {
+    return if r == 0 {
         Ok(())
     } else {
-        let my_ep = ep as *mut Exception;
-        rtdebug!("caught {}", (*my_ep).uwe.exception_class);
-        let cause = (*my_ep).cause.take();
-        uw::_Unwind_DeleteException(ep);
-        Err(cause.unwrap())
+        rtdebug!("caught panic");
+        let obj = raw::TraitObject {
+            data: data as *mut (),
+            vtable: vtable as *mut (),
+        };
+        Err(Box::from_raw(mem::transmute::<raw::TraitObject, *mut (Any + Send)>(obj)))
     };
 
-    extern fn try_fn<F: FnOnce()>(opt_closure: *mut c_void) {
+    extern fn try_fn<F: FnOnce()>(opt_closure: *mut u8) {
         let opt_closure = opt_closure as *mut Option<F>;
         unsafe { (*opt_closure).take().unwrap()(); }
     }
+}
 
-    #[link(name = "rustrt_native", kind = "static")]
-    #[cfg(not(test))]
-    extern {}
-
-    extern {
-        // Rust's try-catch
-        // When f(...) returns normally, the return value is null.
-        // When f(...) throws, the return value is a pointer to the caught
-        // exception object.
-        fn rust_try(f: extern fn(*mut c_void),
-                    data: *mut c_void) -> *mut uw::_Unwind_Exception;
-    }
+// The interface to the panic runtime. Exactly one crate providing these
+// symbols is linked into every program that uses the standard library, either
+// libpanic_unwind or libpanic_abort depending on the `-C panic` strategy.
+extern {
+    // Invokes `f(data)`. Returns 0 if it returned normally, or 1 if it
+    // panicked, in which case the payload is written out as the two halves
+    // of a `Box<Any + Send>` trait object.
+    fn __rust_maybe_catch_panic(f: extern fn(*mut u8),
+                                data: *mut u8,
+                                data_ptr: *mut usize,
+                                vtable_ptr: *mut usize) -> u32;
+
+    // Raises a panic carrying the given payload. Only returns, with an error
+    // code, if the panic could not be raised.
+    fn __rust_start_panic(data: usize, vtable: usize) -> u32;
 }
 
 /// Determines whether the current thread is unwinding because of panic.
@@ -166,33 +178,10 @@ fn rust_panic(cause: Box<Any + Send + 'static>) -> ! {
     rtdebug!("begin_unwind()");
 
     unsafe {
-        let exception: Box<_> = box Exception {
-            uwe: uw::_Unwind_Exception {
-                exception_class: rust_exception_class(),
-                exception_cleanup: exception_cleanup,
-                private: [0; uw::unwinder_private_data_size],
-            },
-            cause: Some(cause),
-        };
-        let exception_param = boxed::into_raw(exception) as *mut uw::_Unwind_Exception;
-        let error = uw::_Unwind_RaiseException(exception_param);
-        rtabort!("Could not unwind stack, error = {}", error as isize)
+        let obj = mem::transmute::<*mut (Any + Send), raw::TraitObject>(boxed::into_raw(cause));
+        let code = __rust_start_panic(obj.data as usize, obj.vtable as usize);
+        rtabort!("failed to initiate panic, error {}", code)
     }
-
-    extern fn exception_cleanup(_unwind_code: uw::_Unwind_Reason_Code,
-                                exception: *mut uw::_Unwind_Exception) {
-        rtdebug!("exception_cleanup()");
-        unsafe {
-            let _: Box<Exception> = Box::from_raw(exception as *mut Exception);
-        }
-    }
-}
-
-// Rust's exception class identifier.  This is used by personality routines to
-// determine whether the exception was thrown by their own runtime.
-fn rust_exception_class() -> uw::_Unwind_Exception_Class {
-    // M O Z \0  R U S T -- vendor, language
-    0x4d4f5a_00_52555354
 }
 
 // We could implement our personality routine in pure Rust, however exception
@@ -314,11 +314,11 @@ pub fn sleep(dur: Duration) {
 // Use dlsym to get the symbol value at runtime, both for
 // compatibility with older versions of glibc, and to avoid creating
 // dependencies on GLIBC_PRIVATE symbols.  Assumes that we've been
-// dynamically linked to libpthread but that is currently always the
-// case.  We previously used weak linkage (under the same assumption),
+// dynamically linked to libpthread, which is the case everywhere but
+// with musl.  We previously used weak linkage (under the same assumption),
 // but that caused Debian to detect an unnecessarily strict versioned
 // dependency on libc6 (#23628).
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(target_env = "musl")))]
 fn min_stack_size(attr: *const libc::pthread_attr_t) -> libc::size_t {
     use dynamic_lib::DynamicLibrary;
     use sync::{Once, ONCE_INIT};
@@ -344,7 +344,7 @@ fn min_stack_size(attr: *const libc::pthread_attr_t) -> libc::size_t {
 
 // No point in looking up __pthread_get_minstack() on non-glibc
 // platforms.
-#[cfg(not(target_os = "linux"))]
+#[cfg(not(all(target_os = "linux", not(target_env = "musl"))))]
 fn min_stack_size(_: *const libc::pthread_attr_t) -> libc::size_t {
     PTHREAD_STACK_MIN
 }
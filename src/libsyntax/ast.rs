@@ -909,7 +909,11 @@ pub enum Expr_ {
     ExprRepeat(P<Expr>, P<Expr>),
 
     /// No-op: used solely so we can pretty-print faithfully
-    ExprParen(P<Expr>)
+    ExprParen(P<Expr>),
+
+    /// Placeholder for an expression that failed to parse. The parser has
+    /// already reported an error for it.
+    ExprError,
 }
 
 /// The explicit Self type in a "qualified path". The actual
@@ -1209,6 +1213,7 @@ pub struct TypeField {
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct MethodSig {
     pub unsafety: Unsafety,
+    pub constness: Constness,
     pub abi: Abi,
     pub decl: P<FnDecl>,
     pub generics: Generics,
@@ -1230,6 +1235,7 @@ pub struct TraitItem {
 
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum TraitItem_ {
+    ConstTraitItem(P<Ty>, Option<P<Expr>>),
     MethodTraitItem(MethodSig, Option<P<Block>>),
     TypeTraitItem(TyParamBounds, Option<P<Ty>>),
 }
@@ -1246,6 +1252,7 @@ pub struct ImplItem {
 
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum ImplItem_ {
+    ConstImplItem(P<Ty>, P<Expr>),
     MethodImplItem(MethodSig, P<Block>),
     TypeImplItem(P<Ty>),
     MacImplItem(Mac),
@@ -1258,6 +1265,7 @@ pub enum IntTy {
     TyI16,
     TyI32,
     TyI64,
+    TyI128,
 }
 
 impl fmt::Debug for IntTy {
@@ -1277,6 +1285,7 @@ impl IntTy {
         match *self {
             TyIs | TyI8 => 2,
             TyI16 | TyI32 | TyI64  => 3,
+            TyI128 => 4,
         }
     }
 }
@@ -1288,6 +1297,7 @@ pub enum UintTy {
     TyU16,
     TyU32,
     TyU64,
+    TyU128,
 }
 
 impl UintTy {
@@ -1295,6 +1305,7 @@ impl UintTy {
         match *self {
             TyUs | TyU8 => 2,
             TyU16 | TyU32 | TyU64  => 3,
+            TyU128 => 4,
         }
     }
 }
@@ -1476,6 +1487,12 @@ impl fmt::Display for Unsafety {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum Constness {
+    Const,
+    NotConst,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash)]
 pub enum ImplPolarity {
     /// `impl Trait for Type`
@@ -1663,17 +1680,30 @@ pub struct PolyTraitRef {
     pub span: Span,
 }
 
-#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum Visibility {
     Public,
+    /// `pub(crate)`: visible anywhere in the current crate
+    PubCrate,
+    /// `pub(in path)`: visible within the named module, which must be an
+    /// ancestor of the item. `id` is the node the path is resolved under.
+    Restricted { path: P<Path>, id: NodeId },
     Inherited,
 }
 
 impl Visibility {
-    pub fn inherit_from(&self, parent_visibility: Visibility) -> Visibility {
+    pub fn inherit_from(&self, parent_visibility: &Visibility) -> Visibility {
         match self {
-            &Inherited => parent_visibility,
-            &Public => *self
+            &Inherited => parent_visibility.clone(),
+            _ => self.clone()
+        }
+    }
+
+    /// Whether this is `pub(crate)` or `pub(in path)`.
+    pub fn is_restricted(&self) -> bool {
+        match *self {
+            PubCrate | Restricted { .. } => true,
+            Public | Inherited => false,
         }
     }
 }
@@ -1697,7 +1727,7 @@ impl StructField_ {
 
 pub type StructField = Spanned<StructField_>;
 
-#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug, Copy)]
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub enum StructFieldKind {
     NamedField(Ident, Visibility),
     /// Element of a tuple-like struct
@@ -1753,7 +1783,7 @@ pub enum Item_ {
     /// A `const` item
     ItemConst(P<Ty>, P<Expr>),
     /// A function declaration
-    ItemFn(P<FnDecl>, Unsafety, Abi, Generics, P<Block>),
+    ItemFn(P<FnDecl>, Unsafety, Constness, Abi, Generics, P<Block>),
     /// A module
     ItemMod(Mod),
     /// An external module
@@ -17,6 +17,7 @@ use owned_slice::OwnedSlice;
 use parse::token;
 use print::pprust;
 use ptr::P;
+use util::int128::U128;
 use visit::Visitor;
 use visit;
 
@@ -128,7 +129,8 @@ pub fn int_ty_to_string(t: IntTy, val: Option<i64>) -> String {
         TyI8 => "i8",
         TyI16 => "i16",
         TyI32 => "i32",
-        TyI64 => "i64"
+        TyI64 => "i64",
+        TyI128 => "i128"
     };
 
     match val {
@@ -140,12 +142,13 @@ pub fn int_ty_to_string(t: IntTy, val: Option<i64>) -> String {
     }
 }
 
-pub fn int_ty_max(t: IntTy) -> u64 {
+pub fn int_ty_max(t: IntTy) -> U128 {
     match t {
-        TyI8 => 0x80,
-        TyI16 => 0x8000,
-        TyIs | TyI32 => 0x80000000, // actually ni about TyIs
-        TyI64 => 0x8000000000000000
+        TyI8 => U128::from_u64(0x80),
+        TyI16 => U128::from_u64(0x8000),
+        TyIs | TyI32 => U128::from_u64(0x80000000), // actually ni about TyIs
+        TyI64 => U128::from_u64(0x8000000000000000),
+        TyI128 => U128::from_parts(0x8000000000000000, 0)
     }
 }
 
@@ -157,7 +160,8 @@ pub fn uint_ty_to_string(t: UintTy, val: Option<u64>) -> String {
         TyU8 => "u8",
         TyU16 => "u16",
         TyU32 => "u32",
-        TyU64 => "u64"
+        TyU64 => "u64",
+        TyU128 => "u128"
     };
 
     match val {
@@ -166,12 +170,13 @@ pub fn uint_ty_to_string(t: UintTy, val: Option<u64>) -> String {
     }
 }
 
-pub fn uint_ty_max(t: UintTy) -> u64 {
+pub fn uint_ty_max(t: UintTy) -> U128 {
     match t {
-        TyU8 => 0xff,
-        TyU16 => 0xffff,
-        TyUs | TyU32 => 0xffffffff, // actually ni about TyUs
-        TyU64 => 0xffffffffffffffff
+        TyU8 => U128::from_u64(0xff),
+        TyU16 => U128::from_u64(0xffff),
+        TyUs | TyU32 => U128::from_u64(0xffffffff), // actually ni about TyUs
+        TyU64 => U128::from_u64(0xffffffffffffffff),
+        TyU128 => U128::max_value()
     }
 }
 
@@ -251,9 +256,9 @@ pub fn impl_pretty_name(trait_ref: &Option<TraitRef>, ty: Option<&Ty>) -> Ident
     token::gensym_ident(&pretty[..])
 }
 
-pub fn struct_field_visibility(field: ast::StructField) -> Visibility {
+pub fn struct_field_visibility(field: &ast::StructField) -> Visibility {
     match field.node.kind {
-        ast::NamedField(_, v) | ast::UnnamedField(v) => v
+        ast::NamedField(_, ref v) | ast::UnnamedField(ref v) => v.clone()
     }
 }
 
@@ -440,7 +445,7 @@ impl<'a, 'v, O: IdVisitingOperation> Visitor<'v> for IdVisitor<'a, O> {
         self.operation.visit_id(node_id);
 
         match function_kind {
-            visit::FkItemFn(_, generics, _, _) => {
+            visit::FkItemFn(_, generics, _, _, _) => {
                 self.visit_generics_helper(generics)
             }
             visit::FkMethod(_, sig) => {
@@ -471,6 +476,13 @@ impl<'a, 'v, O: IdVisitingOperation> Visitor<'v> for IdVisitor<'a, O> {
         visit::walk_struct_field(self, struct_field)
     }
 
+    fn visit_vis(&mut self, vis: &Visibility) {
+        if let Restricted { id, .. } = *vis {
+            self.operation.visit_id(id);
+        }
+        visit::walk_vis(self, vis)
+    }
+
     fn visit_struct_def(&mut self,
                         struct_def: &StructDef,
                         _: ast::Ident,
@@ -610,6 +610,7 @@ impl IntType {
             SignedInt(ast::TyI16) | UnsignedInt(ast::TyU16) |
             SignedInt(ast::TyI32) | UnsignedInt(ast::TyU32) |
             SignedInt(ast::TyI64) | UnsignedInt(ast::TyU64) => true,
+            SignedInt(ast::TyI128) | UnsignedInt(ast::TyU128) |
             SignedInt(ast::TyIs) | UnsignedInt(ast::TyUs) => false
         }
     }
@@ -24,6 +24,7 @@ pub use self::MacroFormat::*;
 use std::cell::RefCell;
 use std::num::ToPrimitive;
 use std::ops::{Add, Sub};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
 use libc::c_uint;
@@ -333,8 +334,16 @@ pub struct FileMap {
 
 impl Encodable for FileMap {
     fn encode<S: Encoder>(&self, s: &mut S) -> Result<(), S::Error> {
+        self.encode_as(&self.name, s)
+    }
+}
+
+impl FileMap {
+    /// Encodes this filemap under the given name rather than its own, so that
+    /// remapped paths are what ends up in crate metadata.
+    pub fn encode_as<S: Encoder>(&self, name: &str, s: &mut S) -> Result<(), S::Error> {
         s.emit_struct("FileMap", 5, |s| {
-            try! { s.emit_struct_field("name", 0, |s| self.name.encode(s)) };
+            try! { s.emit_struct_field("name", 0, |s| name.encode(s)) };
             try! { s.emit_struct_field("start_pos", 1, |s| self.start_pos.encode(s)) };
             try! { s.emit_struct_field("end_pos", 2, |s| self.end_pos.encode(s)) };
             try! { s.emit_struct_field("lines", 3, |s| {
@@ -513,17 +522,30 @@ impl FileMap {
 
 pub struct CodeMap {
     pub files: RefCell<Vec<Rc<FileMap>>>,
-    expansions: RefCell<Vec<ExpnInfo>>
+    expansions: RefCell<Vec<ExpnInfo>>,
+    path_mapping: FilePathMapping,
 }
 
 impl CodeMap {
     pub fn new() -> CodeMap {
+        CodeMap::with_file_path_mapping(FilePathMapping::empty())
+    }
+
+    pub fn with_file_path_mapping(path_mapping: FilePathMapping) -> CodeMap {
         CodeMap {
             files: RefCell::new(Vec::new()),
             expansions: RefCell::new(Vec::new()),
+            path_mapping: path_mapping,
         }
     }
 
+    /// The prefix mapping to apply to file names before they are written
+    /// into compiler output. File names in the codemap itself, and hence in
+    /// diagnostics, always refer to the real files.
+    pub fn path_mapping(&self) -> &FilePathMapping {
+        &self.path_mapping
+    }
+
     pub fn new_filemap(&self, filename: FileName, src: String) -> Rc<FileMap> {
         let mut files = self.files.borrow_mut();
         let start_pos = match files.last() {
@@ -899,6 +921,53 @@ pub struct MalformedCodemapPositions {
     end_pos: BytePos
 }
 
+// _____________________________________________________________________________
+// FilePathMapping
+//
+
+/// A list of `(from, to)` path prefix substitutions, as given by
+/// `--remap-path-prefix from=to`.
+#[derive(Clone)]
+pub struct FilePathMapping {
+    mapping: Vec<(PathBuf, PathBuf)>,
+}
+
+impl FilePathMapping {
+    pub fn empty() -> FilePathMapping {
+        FilePathMapping::new(Vec::new())
+    }
+
+    pub fn new(mapping: Vec<(String, String)>) -> FilePathMapping {
+        FilePathMapping {
+            mapping: mapping.into_iter()
+                            .map(|(from, to)| (PathBuf::from(from), PathBuf::from(to)))
+                            .collect()
+        }
+    }
+
+    /// Replaces the prefix of `path` by the last mapping whose `from`
+    /// prefix matches, so later mappings take precedence over earlier ones.
+    /// Prefixes only match whole components, so `/home/user` doesn't apply
+    /// to `/home/username`. Paths no mapping applies to are returned
+    /// unchanged.
+    pub fn map_prefix_path(&self, path: &Path) -> PathBuf {
+        for &(ref from, ref to) in self.mapping.iter().rev() {
+            if let Some(rest) = path.relative_from(from) {
+                if rest.components().next().is_none() {
+                    return to.clone();
+                }
+                return to.join(rest);
+            }
+        }
+        path.to_path_buf()
+    }
+
+    /// `map_prefix_path` for the file names stored in the codemap.
+    pub fn map_prefix(&self, path: &str) -> String {
+        self.map_prefix_path(Path::new(path)).to_string_lossy().into_owned()
+    }
+}
+
 
 // _____________________________________________________________________________
 // Tests
@@ -992,6 +1061,20 @@ mod test {
         assert_eq!(loc2.col, CharPos(0));
     }
 
+    #[test]
+    fn path_mapping() {
+        let mapping = FilePathMapping::new(vec![
+            ("/home/user".to_string(), "/src".to_string()),
+            ("/home/user/proj".to_string(), "proj".to_string()),
+        ]);
+        assert_eq!(mapping.map_prefix("/home/user/proj/lib.rs"), "proj/lib.rs");
+        assert_eq!(mapping.map_prefix("/home/user/other/lib.rs"), "/src/other/lib.rs");
+        assert_eq!(mapping.map_prefix("/tmp/lib.rs"), "/tmp/lib.rs");
+        assert_eq!(mapping.map_prefix("/home/user"), "/src");
+        assert_eq!(mapping.map_prefix("/home/username/lib.rs"), "/home/username/lib.rs");
+        assert_eq!(FilePathMapping::empty().map_prefix("lib.rs"), "lib.rs");
+    }
+
     fn init_code_map_mbc() -> CodeMap {
         let cm = CodeMap::new();
         // € is a three byte utf8 char.
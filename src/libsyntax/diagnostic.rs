@@ -142,6 +142,9 @@ impl SpanHandler {
 /// others log errors for later reporting.
 pub struct Handler {
     err_count: Cell<usize>,
+    // Errors that `abort_if_errors` lets compilation continue past; see
+    // `tolerate_errors`.
+    tolerated_err_count: Cell<usize>,
     emit: RefCell<Box<Emitter + Send>>,
     pub can_emit_warnings: bool
 }
@@ -164,7 +167,21 @@ impl Handler {
     pub fn has_errors(&self) -> bool {
         self.err_count.get() > 0
     }
+    /// Lets `abort_if_errors` continue past the errors reported so far, so
+    /// that later passes can report further errors. Used for syntax errors
+    /// the parser has recovered from.
+    pub fn tolerate_errors(&self) {
+        self.tolerated_err_count.set(self.err_count.get());
+    }
+    /// Aborts if any errors have been reported, including tolerated ones.
+    pub fn abort_if_any_errors(&self) {
+        self.tolerated_err_count.set(0);
+        self.abort_if_errors();
+    }
     pub fn abort_if_errors(&self) {
+        if self.err_count.get() <= self.tolerated_err_count.get() {
+            return;
+        }
         let s;
         match self.err_count.get() {
           0 => return,
@@ -230,6 +247,7 @@ pub fn default_handler(color_config: ColorConfig,
 pub fn mk_handler(can_emit_warnings: bool, e: Box<Emitter + Send>) -> Handler {
     Handler {
         err_count: Cell::new(0),
+        tolerated_err_count: Cell::new(0),
         emit: RefCell::new(e),
         can_emit_warnings: can_emit_warnings
     }
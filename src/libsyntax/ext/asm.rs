@@ -19,6 +19,7 @@ use codemap::Span;
 use ext::base;
 use ext::base::*;
 use feature_gate;
+use parse::parser::Parser;
 use parse::token::InternedString;
 use parse::token;
 use ptr::P;
@@ -58,6 +59,8 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     let mut p = cx.new_parser_from_tts(tts);
     let mut asm = InternedString::new("");
     let mut asm_str_style = None;
+    let mut asm_span = sp;
+    let mut names: Vec<(ast::Name, usize)> = Vec::new();
     let mut outputs = Vec::new();
     let mut inputs = Vec::new();
     let mut clobs = Vec::new();
@@ -76,7 +79,9 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                     cx.span_err(sp, "malformed inline assembly");
                     return DummyResult::expr(sp);
                 }
-                let (s, style) = match expr_to_string(cx, p.parse_expr(),
+                let expr = p.parse_expr();
+                asm_span = expr.span;
+                let (s, style) = match expr_to_string(cx, expr,
                                                    "inline assembly must be a string literal") {
                     Some((s, st)) => (s, st),
                     // let compilation continue
@@ -94,6 +99,9 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::Comma);
                     }
 
+                    let index = outputs.len();
+                    parse_operand_name(cx, &mut p, &mut names, index);
+
                     let (constraint, _str_style) = p.parse_str();
 
                     let span = p.last_span;
@@ -134,6 +142,9 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                         p.eat(&token::Comma);
                     }
 
+                    let index = outputs.len() + inputs.len();
+                    parse_operand_name(cx, &mut p, &mut names, index);
+
                     let (constraint, _str_style) = p.parse_str();
 
                     if constraint.starts_with("=") {
@@ -208,6 +219,12 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         }
     }
 
+    // Read+write outputs are also passed as inputs, after all the others, so
+    // they can be referred to as well.
+    let num_operands = outputs.len() + inputs.len() +
+                       outputs.iter().filter(|&&(_, _, is_rw)| is_rw).count();
+    let asm = expand_operand_refs(cx, asm_span, &asm, &names, num_operands);
+
     let expn_id = cx.codemap().record_expansion(codemap::ExpnInfo {
         call_site: sp,
         callee: codemap::NameAndSpan {
@@ -221,7 +238,7 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     MacEager::expr(P(ast::Expr {
         id: ast::DUMMY_NODE_ID,
         node: ast::ExprInlineAsm(ast::InlineAsm {
-            asm: token::intern_and_get_ident(&asm),
+            asm: token::intern_and_get_ident(&asm[..]),
             asm_str_style: asm_str_style.unwrap(),
             outputs: outputs,
             inputs: inputs,
@@ -234,3 +251,114 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         span: sp
     }))
 }
+
+/// Parses the optional `[name]` in front of an operand's constraint, which
+/// lets the template refer to the operand as `$name` or `${name}` instead
+/// of by its position.
+fn parse_operand_name(cx: &mut ExtCtxt, p: &mut Parser,
+                      names: &mut Vec<(ast::Name, usize)>, index: usize) {
+    if p.token != token::OpenDelim(token::Bracket) {
+        return
+    }
+    p.bump();
+    let name = p.parse_ident().name;
+    let span = p.last_span;
+    p.expect(&token::CloseDelim(token::Bracket));
+
+    if names.iter().any(|&(n, _)| n == name) {
+        cx.span_err(span, &format!("duplicate inline assembly operand name `{}`",
+                                   name.as_str()));
+    } else {
+        names.push((name, index));
+    }
+}
+
+/// Rewrites references to named operands in the template, `$name` and
+/// `${name:modifier}`, into the positional form LLVM understands, and
+/// checks positional references against the number of operands, which
+/// LLVM would otherwise only notice when emitting code.
+fn expand_operand_refs(cx: &mut ExtCtxt, sp: Span, asm: &str,
+                       names: &[(ast::Name, usize)], num_operands: usize) -> String {
+    fn is_ident_char(c: char) -> bool { c.is_alphanumeric() || c == '_' }
+
+    let check_index = |cx: &mut ExtCtxt, operand: &str| {
+        match operand.parse::<usize>() {
+            Ok(i) if i < num_operands => {}
+            _ => {
+                cx.span_err(sp, &format!("invalid reference to operand `${}`, the \
+                                          inline assembly has {} operand{}",
+                                         operand, num_operands,
+                                         if num_operands == 1 { "" } else { "s" }));
+            }
+        }
+    };
+    let lookup = |name: &str| {
+        names.iter().find(|&&(n, _)| n.as_str() == name).map(|&(_, i)| i)
+    };
+
+    let mut out = String::with_capacity(asm.len());
+    let mut rest = asm;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+
+        match rest.chars().next() {
+            // `$$` is a literal `$`
+            Some('$') => {
+                out.push_str("$$");
+                rest = &rest[1..];
+            }
+            Some(c) if c.is_digit(10) => {
+                let end = rest.find(|c: char| !c.is_digit(10)).unwrap_or(rest.len());
+                check_index(cx, &rest[..end]);
+                out.push('$');
+                out.push_str(&rest[..end]);
+                rest = &rest[end..];
+            }
+            Some('{') => {
+                let end = match rest.find('}') {
+                    Some(end) => end,
+                    None => {
+                        cx.span_err(sp, "unterminated operand reference in inline assembly");
+                        out.push('$');
+                        out.push_str(rest);
+                        break
+                    }
+                };
+                let (operand, modifier) = match rest[1..end].find(':') {
+                    Some(i) => (&rest[1..i + 1], &rest[i + 1..end]),
+                    None => (&rest[1..end], ""),
+                };
+                match lookup(operand) {
+                    Some(i) => out.push_str(&format!("${{{}{}}}", i, modifier)),
+                    None => {
+                        if operand.chars().all(|c| c.is_digit(10)) {
+                            check_index(cx, operand);
+                        } else {
+                            cx.span_err(sp, &format!("there is no inline assembly operand \
+                                                      named `{}`", operand));
+                        }
+                        out.push('$');
+                        out.push_str(&rest[..end + 1]);
+                    }
+                }
+                rest = &rest[end + 1..];
+            }
+            Some(c) if is_ident_char(c) => {
+                let end = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+                match lookup(&rest[..end]) {
+                    Some(i) => out.push_str(&format!("${}", i)),
+                    // Not one of ours, such as an AT&T immediate symbol.
+                    None => {
+                        out.push('$');
+                        out.push_str(&rest[..end]);
+                    }
+                }
+                rest = &rest[end..];
+            }
+            _ => out.push('$'),
+        }
+    }
+    out.push_str(rest);
+    out
+}
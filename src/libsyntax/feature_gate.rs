@@ -155,6 +155,25 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Status)] = &[
 
     // Allows use of unary negate on unsigned integers, e.g. -e for e: u8
     ("negate_unsigned", "1.0.0", Active),
+
+    // Allows the definition of `const fn` functions.
+    ("const_fn", "1.1.0", Active),
+
+    // Allows associated constants in traits and impls.
+    ("associated_consts", "1.1.0", Active),
+
+    // Allows the `i128` and `u128` primitive types and their literal suffixes.
+    ("i128_type", "1.1.0", Active),
+
+    // Allows `pub(crate)` and `pub(in path)` visibility restrictions.
+    ("pub_restricted", "1.1.0", Active),
+
+    // Allows a crate to declare itself as a panic runtime, or as needing one.
+    ("panic_runtime", "1.1.0", Active),
+    ("needs_panic_runtime", "1.1.0", Active),
+
+    // Allows a crate to declare that it needs the global allocator.
+    ("needs_allocator", "1.1.0", Active),
 ];
 // (changing above list without updating src/doc/reference.md makes @cmr sad)
 
@@ -229,6 +248,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
                                       is an experimental feature")),
     ("allocator", Gated("allocator",
                         "the `#[allocator]` attribute is an experimental feature")),
+    ("needs_allocator", Gated("needs_allocator",
+                              "the `#[needs_allocator]` attribute is an experimental \
+                               feature")),
     ("rustc_variance", Gated("rustc_attrs",
                              "the `#[rustc_variance]` attribute \
                               is an experimental feature")),
@@ -242,6 +264,13 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
     ("allow_internal_unstable", Gated("allow_internal_unstable",
                                       EXPLAIN_ALLOW_INTERNAL_UNSTABLE)),
 
+    ("panic_runtime", Gated("panic_runtime",
+                            "the `#[panic_runtime]` attribute is an experimental \
+                             feature")),
+    ("needs_panic_runtime", Gated("needs_panic_runtime",
+                                  "the `#[needs_panic_runtime]` attribute is an \
+                                   experimental feature")),
+
     ("fundamental", Gated("fundamental",
                           "the `#[fundamental]` attribute \
                            is an experimental feature")),
@@ -292,6 +321,9 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType)] = &[
     ("no_start", CrateLevel),
     ("no_main", CrateLevel),
     ("no_builtins", CrateLevel),
+    ("panic_runtime", CrateLevel),
+    ("needs_panic_runtime", CrateLevel),
+    ("needs_allocator", CrateLevel),
     ("recursion_limit", CrateLevel),
 ];
 
@@ -329,6 +361,8 @@ pub struct Features {
     pub simd_ffi: bool,
     pub unmarked_api: bool,
     pub negate_unsigned: bool,
+    pub const_fn: bool,
+    pub i128_type: bool,
     /// spans of #![feature] attrs for stable language features. for error reporting
     pub declared_stable_lang_features: Vec<Span>,
     /// #![feature] attrs for non-language (library) features
@@ -351,6 +385,8 @@ impl Features {
             simd_ffi: false,
             unmarked_api: false,
             negate_unsigned: false,
+            const_fn: false,
+            i128_type: false,
             declared_stable_lang_features: Vec::new(),
             declared_lib_features: Vec::new()
         }
@@ -495,6 +531,13 @@ impl<'a> PostExpansionVisitor<'a> {
             self.context.gate_feature(feature, span, explain)
         }
     }
+
+    fn gate_visibility(&self, vis: &ast::Visibility, span: Span) {
+        if vis.is_restricted() {
+            self.gate_feature("pub_restricted", span,
+                              "`pub(crate)` and `pub(in path)` are experimental");
+        }
+    }
 }
 
 impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
@@ -512,6 +555,7 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
     }
 
     fn visit_item(&mut self, i: &ast::Item) {
+        self.gate_visibility(&i.vis, i.span);
         match i.node {
             ast::ItemExternCrate(_) => {
                 if attr::contains_name(&i.attrs[..], "macro_reexport") {
@@ -587,6 +631,7 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
     }
 
     fn visit_foreign_item(&mut self, i: &ast::ForeignItem) {
+        self.gate_visibility(&i.vis, i.span);
         let links_to_llvm = match attr::first_attr_value_str_by_name(&i.attrs,
                                                                      "link_name") {
             Some(val) => val.starts_with("llvm."),
@@ -608,6 +653,17 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                                   "box expression syntax is experimental; \
                                    you can call `Box::new` instead.");
             }
+            ast::ExprLit(ref lit) => {
+                match lit.node {
+                    ast::LitInt(_, ast::SignedIntLit(ast::TyI128, _)) |
+                    ast::LitInt(_, ast::UnsignedIntLit(ast::TyU128)) => {
+                        self.gate_feature("i128_type",
+                                          e.span,
+                                          "128-bit integer types are experimental");
+                    }
+                    _ => {}
+                }
+            }
             _ => {}
         }
         visit::walk_expr(self, e);
@@ -644,21 +700,55 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
                 span: Span,
                 _node_id: NodeId) {
         match fn_kind {
-            visit::FkItemFn(_, _, _, abi) if abi == Abi::RustIntrinsic => {
+            visit::FkItemFn(_, _, _, _, abi) if abi == Abi::RustIntrinsic => {
                 self.gate_feature("intrinsics",
                                   span,
                                   "intrinsics are subject to change")
             }
-            visit::FkItemFn(_, _, _, abi) |
+            visit::FkItemFn(_, _, _, _, abi) |
             visit::FkMethod(_, &ast::MethodSig { abi, .. }) if abi == Abi::RustCall => {
                 self.gate_feature("unboxed_closures",
                                   span,
                                   "rust-call ABI is subject to change")
             }
+            visit::FkItemFn(_, _, _, ast::Constness::Const, _) |
+            visit::FkMethod(_, &ast::MethodSig { constness: ast::Constness::Const, .. }) => {
+                self.gate_feature("const_fn",
+                                  span,
+                                  "const fn is unstable")
+            }
             _ => {}
         }
         visit::walk_fn(self, fn_kind, fn_decl, block, span);
     }
+
+    fn visit_trait_item(&mut self, ti: &'v ast::TraitItem) {
+        if let ast::ConstTraitItem(..) = ti.node {
+            self.gate_feature("associated_consts",
+                              ti.span,
+                              "associated constants are experimental")
+        }
+        visit::walk_trait_item(self, ti);
+    }
+
+    fn visit_impl_item(&mut self, ii: &'v ast::ImplItem) {
+        self.gate_visibility(&ii.vis, ii.span);
+        if let ast::ConstImplItem(..) = ii.node {
+            self.gate_feature("associated_consts",
+                              ii.span,
+                              "associated constants are experimental")
+        }
+        visit::walk_impl_item(self, ii);
+    }
+
+    fn visit_struct_field(&mut self, sf: &'v ast::StructField) {
+        match sf.node.kind {
+            ast::NamedField(_, ref vis) | ast::UnnamedField(ref vis) => {
+                self.gate_visibility(vis, sf.span)
+            }
+        }
+        visit::walk_struct_field(self, sf);
+    }
 }
 
 fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler,
@@ -736,6 +826,8 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &SpanHandler,
         simd_ffi: cx.has_feature("simd_ffi"),
         unmarked_api: cx.has_feature("unmarked_api"),
         negate_unsigned: cx.has_feature("negate_unsigned"),
+        const_fn: cx.has_feature("const_fn"),
+        i128_type: cx.has_feature("i128_type"),
         declared_stable_lang_features: accepted_features,
         declared_lib_features: unknown_features
     }
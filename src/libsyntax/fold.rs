@@ -170,6 +170,10 @@ pub trait Folder : Sized {
         noop_fold_path_parameters(p, self)
     }
 
+    fn fold_vis(&mut self, vis: Visibility) -> Visibility {
+        noop_fold_vis(vis, self)
+    }
+
     fn fold_angle_bracketed_parameter_data(&mut self, p: AngleBracketedParameterData)
                                            -> AngleBracketedParameterData
     {
@@ -472,6 +476,16 @@ pub fn noop_fold_usize<T: Folder>(i: usize, _: &mut T) -> usize {
     i
 }
 
+pub fn noop_fold_vis<T: Folder>(vis: Visibility, fld: &mut T) -> Visibility {
+    match vis {
+        Restricted { path, id } => Restricted {
+            path: path.map(|path| fld.fold_path(path)),
+            id: fld.new_id(id),
+        },
+        _ => vis,
+    }
+}
+
 pub fn noop_fold_path<T: Folder>(Path {global, segments, span}: Path, fld: &mut T) -> Path {
     Path {
         global: global,
@@ -844,7 +858,10 @@ pub fn noop_fold_struct_field<T: Folder>(f: StructField, fld: &mut T) -> StructF
     Spanned {
         node: StructField_ {
             id: fld.new_id(id),
-            kind: kind,
+            kind: match kind {
+                NamedField(name, vis) => NamedField(name, fld.fold_vis(vis)),
+                UnnamedField(vis) => UnnamedField(fld.fold_vis(vis)),
+            },
             ty: fld.fold_ty(ty),
             attrs: fold_attrs(attrs, fld),
         },
@@ -907,10 +924,11 @@ pub fn noop_fold_item_underscore<T: Folder>(i: Item_, folder: &mut T) -> Item_ {
         ItemConst(t, e) => {
             ItemConst(folder.fold_ty(t), folder.fold_expr(e))
         }
-        ItemFn(decl, unsafety, abi, generics, body) => {
+        ItemFn(decl, unsafety, constness, abi, generics, body) => {
             ItemFn(
                 folder.fold_fn_decl(decl),
                 unsafety,
+                constness,
                 abi,
                 folder.fold_generics(generics),
                 folder.fold_block(body)
@@ -973,6 +991,10 @@ pub fn noop_fold_trait_item<T: Folder>(i: P<TraitItem>, folder: &mut T)
         ident: folder.fold_ident(ident),
         attrs: fold_attrs(attrs, folder),
         node: match node {
+            ConstTraitItem(ty, default) => {
+                ConstTraitItem(folder.fold_ty(ty),
+                               default.map(|x| folder.fold_expr(x)))
+            }
             MethodTraitItem(sig, body) => {
                 MethodTraitItem(noop_fold_method_sig(sig, folder),
                                 body.map(|x| folder.fold_block(x)))
@@ -992,8 +1014,11 @@ pub fn noop_fold_impl_item<T: Folder>(i: P<ImplItem>, folder: &mut T)
         id: folder.new_id(id),
         ident: folder.fold_ident(ident),
         attrs: fold_attrs(attrs, folder),
-        vis: vis,
+        vis: folder.fold_vis(vis),
         node: match node  {
+            ConstImplItem(ty, expr) => {
+                ConstImplItem(folder.fold_ty(ty), folder.fold_expr(expr))
+            }
             MethodImplItem(sig, body) => {
                 MethodImplItem(noop_fold_method_sig(sig, folder),
                                folder.fold_block(body))
@@ -1078,7 +1103,7 @@ pub fn noop_fold_item_simple<T: Folder>(Item {id, ident, attrs, node, vis, span}
         ident: folder.fold_ident(ident),
         attrs: fold_attrs(attrs, folder),
         node: node,
-        vis: vis,
+        vis: folder.fold_vis(vis),
         span: folder.new_span(span)
     }
 }
@@ -1096,7 +1121,7 @@ pub fn noop_fold_foreign_item<T: Folder>(ni: P<ForeignItem>, folder: &mut T) ->
                 ForeignItemStatic(folder.fold_ty(t), m)
             }
         },
-        vis: vis,
+        vis: folder.fold_vis(vis),
         span: folder.new_span(span)
     })
 }
@@ -1107,6 +1132,7 @@ pub fn noop_fold_method_sig<T: Folder>(sig: MethodSig, folder: &mut T) -> Method
         abi: sig.abi,
         explicit_self: folder.fold_explicit_self(sig.explicit_self),
         unsafety: sig.unsafety,
+        constness: sig.constness,
         decl: folder.fold_fn_decl(sig.decl)
     }
 }
@@ -1302,7 +1328,8 @@ pub fn noop_fold_expr<T: Folder>(Expr {id, node, span}: Expr, folder: &mut T) ->
                         fields.move_map(|x| folder.fold_field(x)),
                         maybe_expr.map(|x| folder.fold_expr(x)))
             },
-            ExprParen(ex) => ExprParen(folder.fold_expr(ex))
+            ExprParen(ex) => ExprParen(folder.fold_expr(ex)),
+            ExprError => ExprError,
         },
         span: folder.new_span(span)
     }
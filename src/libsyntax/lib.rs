@@ -36,6 +36,7 @@
 #![feature(staged_api)]
 #![feature(unicode)]
 #![feature(path_ext)]
+#![feature(path_relative_from)]
 #![feature(str_char)]
 #![feature(into_cow)]
 #![feature(slice_patterns)]
@@ -51,6 +52,7 @@ extern crate libc;
 extern crate serialize as rustc_serialize; // used by deriving
 
 pub mod util {
+    pub mod int128;
     pub mod interner;
     #[cfg(test)]
     pub mod parser_testing;
@@ -13,16 +13,17 @@ pub use self::PathParsingMode::*;
 use abi;
 use ast::BareFnTy;
 use ast::{RegionTyParamBound, TraitTyParamBound, TraitBoundModifier};
-use ast::{Public, Unsafety};
+use ast::{Public, PubCrate, Restricted, Unsafety, Constness};
 use ast::{Mod, BiAdd, Arg, Arm, Attribute, BindByRef, BindByValue};
 use ast::{BiBitAnd, BiBitOr, BiBitXor, BiRem, BiLt, BiGt, Block};
 use ast::{BlockCheckMode, CaptureByRef, CaptureByValue, CaptureClause};
+use ast::{ConstImplItem, ConstTraitItem};
 use ast::{Crate, CrateConfig, Decl, DeclItem};
 use ast::{DeclLocal, DefaultBlock, DefaultReturn};
 use ast::{UnDeref, BiDiv, EMPTY_CTXT, EnumDef, ExplicitSelf};
 use ast::{Expr, Expr_, ExprAddrOf, ExprMatch, ExprAgain};
 use ast::{ExprAssign, ExprAssignOp, ExprBinary, ExprBlock, ExprBox};
-use ast::{ExprBreak, ExprCall, ExprCast};
+use ast::{ExprBreak, ExprCall, ExprCast, ExprError};
 use ast::{ExprField, ExprTupField, ExprClosure, ExprIf, ExprIfLet, ExprIndex};
 use ast::{ExprLit, ExprLoop, ExprMac, ExprRange};
 use ast::{ExprMethodCall, ExprParen, ExprPath};
@@ -272,6 +273,11 @@ pub struct Parser<'a> {
     /// into modules, and sub-parsers have new values for this name.
     pub root_module_name: Option<String>,
     pub expected_tokens: Vec<TokenType>,
+    /// Set once a syntax error has been reported and parsing carried on past
+    /// it, and cleared when the parser gets back in sync at a statement or
+    /// item boundary. Errors raised in between are most likely fallout from
+    /// the first one, so they are not reported.
+    pub recovering: bool,
 }
 
 #[derive(PartialEq, Eq, Clone)]
@@ -334,6 +340,7 @@ impl<'a> Parser<'a> {
             owns_directory: true,
             root_module_name: None,
             expected_tokens: Vec::new(),
+            recovering: false,
         }
     }
 
@@ -383,6 +390,21 @@ impl<'a> Parser<'a> {
     pub fn expect_one_of(&mut self,
                          edible: &[token::Token],
                          inedible: &[token::Token]) {
+        if edible.contains(&self.token) {
+            self.bump();
+        } else if inedible.contains(&self.token) {
+            // leave it in the input
+        } else {
+            let msg = self.expected_one_of_message(edible, inedible);
+            self.fatal(&msg)
+        }
+    }
+
+    /// Build the "expected ..., found ..." message for the current token,
+    /// given the tokens that would have been accepted here.
+    fn expected_one_of_message(&self,
+                               edible: &[token::Token],
+                               inedible: &[token::Token]) -> String {
         fn tokens_to_string(tokens: &[TokenType]) -> String {
             let mut i = tokens.iter();
             // This might be a sign we need a connect method on Iterator.
@@ -400,36 +422,109 @@ impl<'a> Parser<'a> {
                 b
             })
         }
-        if edible.contains(&self.token) {
-            self.bump();
-        } else if inedible.contains(&self.token) {
-            // leave it in the input
+        let mut expected = edible.iter().map(|x| TokenType::Token(x.clone()))
+                                        .collect::<Vec<_>>();
+        expected.extend(inedible.iter().map(|x| TokenType::Token(x.clone())));
+        expected.push_all(&*self.expected_tokens);
+        expected.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
+        expected.dedup();
+        let expect = tokens_to_string(&expected[..]);
+        let actual = self.this_token_to_string();
+        if expected.len() > 1 {
+            format!("expected one of {}, found `{}`", expect, actual)
+        } else if expected.len() == 0 {
+            format!("unexpected token: `{}`", actual)
         } else {
-            let mut expected = edible.iter().map(|x| TokenType::Token(x.clone()))
-                                            .collect::<Vec<_>>();
-            expected.extend(inedible.iter().map(|x| TokenType::Token(x.clone())));
-            expected.push_all(&*self.expected_tokens);
-            expected.sort_by(|a, b| a.to_string().cmp(&b.to_string()));
-            expected.dedup();
-            let expect = tokens_to_string(&expected[..]);
-            let actual = self.this_token_to_string();
-            self.fatal(
-                &(if expected.len() > 1 {
-                    (format!("expected one of {}, found `{}`",
-                             expect,
-                             actual))
-                } else if expected.len() == 0 {
-                    (format!("unexpected token: `{}`",
-                             actual))
-                } else {
-                    (format!("expected {}, found `{}`",
-                             expect,
-                             actual))
-                })[..]
-            )
+            format!("expected {}, found `{}`", expect, actual)
         }
     }
 
+    /// Report a syntax error that the parser is about to recover from. Only
+    /// the first error since the parser was last in sync is reported.
+    pub fn recoverable_err(&mut self, sp: Span, m: &str) {
+        if !self.recovering {
+            self.span_err(sp, m);
+        }
+        self.recovering = true;
+    }
+
+    /// Skip ahead to the end of the current statement: just past the next
+    /// `;`, or up to the `}` closing the enclosing block. Delimited groups
+    /// are skipped as a whole. Only a `;` puts the parser back in sync; what
+    /// follows a `}` may still be part of the broken statement.
+    pub fn recover_stmt(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.token {
+                token::Eof => break,
+                token::CloseDelim(token::Brace) if depth == 0 => break,
+                token::Semi if depth == 0 => {
+                    self.bump();
+                    self.recovering = false;
+                    break;
+                }
+                token::OpenDelim(_) => depth += 1,
+                token::CloseDelim(_) => depth -= 1,
+                _ => {}
+            }
+            self.bump();
+        }
+    }
+
+    /// Skip ahead to the next token that can start an item, or to the `}`
+    /// closing the enclosing module. Always consumes at least one token
+    /// unless at the end of the input.
+    pub fn recover_item(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.token {
+                token::Eof => break,
+                token::OpenDelim(_) => depth += 1,
+                token::CloseDelim(_) => depth -= 1,
+                _ => {}
+            }
+            self.bump();
+            if depth <= 0 && self.token == token::CloseDelim(token::Brace) {
+                break;
+            }
+            if depth <= 0 && self.token_can_begin_item() {
+                self.recovering = false;
+                break;
+            }
+        }
+    }
+
+    /// Report that the input ended before the `}` closing a block or module,
+    /// and carry on as if it had been there. `open` is where the unclosed
+    /// block starts, if known. Enclosing blocks that are left unclosed by
+    /// the same missing `}` are not reported again.
+    fn recover_missing_brace(&mut self, open: Option<Span>) {
+        if !self.recovering {
+            let sp = self.span;
+            self.span_err(sp, "this file contains an un-closed delimiter");
+            if let Some(open) = open {
+                self.span_help(open, "did you mean to close this delimiter?");
+            }
+        }
+        self.recovering = true;
+    }
+
+    /// Is the current token one that can start an item (including its
+    /// attributes and visibility)?
+    fn token_can_begin_item(&self) -> bool {
+        self.token == token::Pound ||
+        [keywords::Pub, keywords::Fn, keywords::Struct, keywords::Enum,
+         keywords::Trait, keywords::Impl, keywords::Mod, keywords::Use,
+         keywords::Extern, keywords::Static, keywords::Const, keywords::Type,
+         keywords::Unsafe].iter().any(|&kw| self.token.is_keyword(kw))
+    }
+
+    /// Is the current token on a later line than the previous one?
+    fn token_starts_new_line(&self) -> bool {
+        let cm = &self.sess.span_diagnostic.cm;
+        cm.lookup_char_pos(self.last_span.hi).line < cm.lookup_char_pos(self.span.lo).line
+    }
+
     /// Check for erroneous `ident { }`; if matches, signal error and
     /// recover (without consuming any expected input token).  Returns
     /// true if and only if input was consumed for recovery.
@@ -470,6 +565,12 @@ impl<'a> Parser<'a> {
     /// Commit to parsing a complete statement `s`, which expects to be
     /// followed by some token from the set edible + inedible.  Check
     /// for recoverable input errors, discarding erroneous characters.
+    ///
+    /// If none of the expected tokens is found the error is reported and
+    /// parsing goes on: a token on a new line is taken to start the next
+    /// statement, as if the `;` had been written; otherwise the rest of the
+    /// statement is skipped. Further errors stay suppressed until a
+    /// statement has been terminated properly again.
     pub fn commit_stmt(&mut self, edible: &[token::Token], inedible: &[token::Token]) {
         if self.last_token
                .as_ref()
@@ -478,7 +579,22 @@ impl<'a> Parser<'a> {
             expected.push_all(&inedible);
             self.check_for_erroneous_unit_struct_expecting(&expected);
         }
-        self.expect_one_of(edible, inedible)
+        if edible.contains(&self.token) {
+            self.bump();
+            self.recovering = false;
+        } else if inedible.contains(&self.token) {
+            // leave it in the input
+            self.recovering = false;
+        } else if self.recovering {
+            self.recover_stmt();
+        } else {
+            let msg = self.expected_one_of_message(edible, inedible);
+            let sp = self.span;
+            self.recoverable_err(sp, &msg);
+            if !self.token_starts_new_line() {
+                self.recover_stmt();
+            }
+        }
     }
 
     pub fn commit_stmt_expecting(&mut self, edible: token::Token) {
@@ -1105,6 +1221,20 @@ impl<'a> Parser<'a> {
                 let TyParam {ident, bounds, default, ..} = p.parse_ty_param();
                 p.expect(&token::Semi);
                 (ident, TypeTraitItem(bounds, default))
+            } else if p.eat_keyword(keywords::Const) {
+                let ident = p.parse_ident();
+                p.expect(&token::Colon);
+                let ty = p.parse_ty_sum();
+                let default = if p.check(&token::Eq) {
+                    p.bump();
+                    let expr = p.parse_expr();
+                    p.commit_expr_expecting(&expr, token::Semi);
+                    Some(expr)
+                } else {
+                    p.expect(&token::Semi);
+                    None
+                };
+                (ident, ConstTraitItem(ty, default))
             } else {
                 let style = p.parse_unsafety();
                 let abi = if p.eat_keyword(keywords::Extern) {
@@ -1127,6 +1257,7 @@ impl<'a> Parser<'a> {
                 generics.where_clause = p.parse_where_clause();
                 let sig = ast::MethodSig {
                     unsafety: style,
+                    constness: ast::Constness::NotConst,
                     decl: d,
                     generics: generics,
                     abi: abi,
@@ -2165,11 +2296,23 @@ impl<'a> Parser<'a> {
 
                     hi = pth.span.hi;
                     ex = ExprPath(None, pth);
-                } else {
+                } else if self.token.is_lit() ||
+                          self.token.is_keyword(keywords::True) ||
+                          self.token.is_keyword(keywords::False) {
                     // other literal expression
                     let lit = self.parse_lit();
                     hi = lit.span.hi;
                     ex = ExprLit(P(lit));
+                } else {
+                    // Not the start of an expression. Report it and leave
+                    // a placeholder; the token is left for the enclosing
+                    // statement to recover from.
+                    let msg = format!("unexpected token: `{}`",
+                                      self.this_token_to_string());
+                    let sp = self.span;
+                    self.recoverable_err(sp, &msg);
+                    hi = lo;
+                    ex = ExprError;
                 }
             }
         }
@@ -3503,6 +3646,10 @@ impl<'a> Parser<'a> {
         let mut expr = None;
 
         while !self.eat(&token::CloseDelim(token::Brace)) {
+            if self.token == token::Eof {
+                self.recover_missing_brace(Some(mk_sp(lo, lo)));
+                break;
+            }
             let Spanned {node, span} = if let Some(s) = self.parse_stmt_() {
                 s
             } else {
@@ -4229,12 +4376,15 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse an item-position function declaration.
-    fn parse_item_fn(&mut self, unsafety: Unsafety, abi: abi::Abi) -> ItemInfo {
+    fn parse_item_fn(&mut self,
+                     unsafety: Unsafety,
+                     constness: Constness,
+                     abi: abi::Abi) -> ItemInfo {
         let (ident, mut generics) = self.parse_fn_header();
         let decl = self.parse_fn_decl(false);
         generics.where_clause = self.parse_where_clause();
         let (inner_attrs, body) = self.parse_inner_attrs_and_block();
-        (ident, ItemFn(decl, unsafety, abi, generics, body), Some(inner_attrs))
+        (ident, ItemFn(decl, unsafety, constness, abi, generics, body), Some(inner_attrs))
     }
 
     /// Parse an impl item.
@@ -4248,8 +4398,19 @@ impl<'a> Parser<'a> {
             let typ = self.parse_ty_sum();
             self.expect(&token::Semi);
             (name, TypeImplItem(typ))
+        } else if self.check_keyword(keywords::Const)
+            && !self.look_ahead(1, |t| t.is_keyword(keywords::Fn)
+                                       || t.is_keyword(keywords::Unsafe)) {
+            self.bump();
+            let name = self.parse_ident();
+            self.expect(&token::Colon);
+            let typ = self.parse_ty_sum();
+            self.expect(&token::Eq);
+            let expr = self.parse_expr();
+            self.commit_expr_expecting(&expr, token::Semi);
+            (name, ConstImplItem(typ, expr))
         } else {
-            let (name, inner_attrs, node) = self.parse_impl_method(vis);
+            let (name, inner_attrs, node) = self.parse_impl_method(&vis);
             attrs.extend(inner_attrs.into_iter());
             (name, node)
         };
@@ -4264,9 +4425,9 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn complain_if_pub_macro(&mut self, visa: Visibility, span: Span) {
-        match visa {
-            Public => {
+    fn complain_if_pub_macro(&mut self, visa: &Visibility, span: Span) {
+        match *visa {
+            Public | PubCrate | Restricted { .. } => {
                 self.span_err(span, "can't qualify macro invocation with `pub`");
                 self.fileline_help(span, "try adjusting the macro to put `pub` inside \
                                       the invocation");
@@ -4276,7 +4437,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Parse a method or a macro invocation in a trait impl.
-    fn parse_impl_method(&mut self, vis: Visibility)
+    fn parse_impl_method(&mut self, vis: &Visibility)
                          -> (Ident, Vec<ast::Attribute>, ast::ImplItem_) {
         // code copied from parse_macro_use_or_failure... abstraction!
         if !self.token.is_any_keyword()
@@ -4305,6 +4466,11 @@ impl<'a> Parser<'a> {
             }
             (token::special_idents::invalid, vec![], ast::MacImplItem(m))
         } else {
+            let constness = if self.eat_keyword(keywords::Const) {
+                Constness::Const
+            } else {
+                Constness::NotConst
+            };
             let unsafety = self.parse_unsafety();
             let abi = if self.eat_keyword(keywords::Extern) {
                 self.parse_opt_abi().unwrap_or(abi::C)
@@ -4324,6 +4490,7 @@ impl<'a> Parser<'a> {
                 abi: abi,
                 explicit_self: explicit_self,
                 unsafety: unsafety,
+                constness: constness,
                 decl: decl
              }, body))
         }
@@ -4606,33 +4773,67 @@ impl<'a> Parser<'a> {
 
         let attrs = self.parse_outer_attributes();
 
-        if self.eat_keyword(keywords::Pub) {
-            if !allow_pub {
-                let span = self.last_span;
-                self.span_err(span, "`pub` is not allowed here");
-            }
-            return self.parse_single_struct_field(Public, attrs);
+        let vis = self.parse_visibility();
+        if vis != Inherited && !allow_pub {
+            let span = self.last_span;
+            self.span_err(span, "`pub` is not allowed here");
         }
 
-        return self.parse_single_struct_field(Inherited, attrs);
+        return self.parse_single_struct_field(vis, attrs);
     }
 
-    /// Parse visibility: PUB, PRIV, or nothing
+    /// Parse visibility: `pub`, `pub(crate)`, `pub(in path)`, or nothing
     fn parse_visibility(&mut self) -> Visibility {
-        if self.eat_keyword(keywords::Pub) { Public }
-        else { Inherited }
+        if !self.eat_keyword(keywords::Pub) {
+            return Inherited
+        }
+
+        // `pub (u8, u8)` is a visibility followed by a tuple type in a tuple
+        // struct, so only `crate` and `in` introduce a restriction.
+        if self.check(&token::OpenDelim(token::Paren)) {
+            if self.look_ahead(1, |t| t.is_keyword(keywords::Crate)) &&
+               self.look_ahead(2, |t| *t == token::CloseDelim(token::Paren)) {
+                self.bump();
+                self.bump();
+                self.bump();
+                return PubCrate
+            }
+            if self.look_ahead(1, |t| t.is_keyword(keywords::In)) {
+                self.bump();
+                self.bump();
+                let path = self.parse_path(NoTypesAllowed);
+                self.expect(&token::CloseDelim(token::Paren));
+                return Restricted { path: P(path), id: ast::DUMMY_NODE_ID }
+            }
+        }
+
+        Public
     }
 
     /// Given a termination token, parse all of the items in a module
     fn parse_mod_items(&mut self, term: &token::Token, inner_lo: BytePos) -> Mod {
         let mut items = vec![];
-        while let Some(item) = self.parse_item() {
-            items.push(item);
-        }
+        loop {
+            while let Some(item) = self.parse_item() {
+                items.push(item);
+                self.recovering = false;
+            }
 
-        if !self.eat(term) {
+            if self.eat(term) {
+                break;
+            }
+            if self.token == token::Eof {
+                // `term` is the `}` closing an inline module.
+                self.recover_missing_brace(None);
+                break;
+            }
+
+            // Report whatever is in the way and skip ahead to the next thing
+            // that looks like an item.
             let token_str = self.this_token_to_string();
-            self.fatal(&format!("expected item, found `{}`", token_str))
+            let sp = self.span;
+            self.recoverable_err(sp, &format!("expected item, found `{}`", token_str));
+            self.recover_item();
         }
 
         ast::Mod {
@@ -5112,7 +5313,7 @@ impl<'a> Parser<'a> {
                 // EXTERN FUNCTION ITEM
                 let abi = opt_abi.unwrap_or(abi::C);
                 let (ident, item_, extra_attrs) =
-                    self.parse_item_fn(Unsafety::Normal, abi);
+                    self.parse_item_fn(Unsafety::Normal, Constness::NotConst, abi);
                 let last_span = self.last_span;
                 let item = self.mk_item(lo,
                                         last_span.hi,
@@ -5151,6 +5352,28 @@ impl<'a> Parser<'a> {
             return Some(item);
         }
         if self.eat_keyword(keywords::Const) {
+            if self.check_keyword(keywords::Fn)
+                || (self.check_keyword(keywords::Unsafe)
+                    && self.look_ahead(1, |t| t.is_keyword(keywords::Fn))) {
+                // CONST FUNCTION ITEM
+                let unsafety = if self.eat_keyword(keywords::Unsafe) {
+                    Unsafety::Unsafe
+                } else {
+                    Unsafety::Normal
+                };
+                self.bump();
+                let (ident, item_, extra_attrs) =
+                    self.parse_item_fn(unsafety, Constness::Const, abi::Rust);
+                let last_span = self.last_span;
+                let item = self.mk_item(lo,
+                                        last_span.hi,
+                                        ident,
+                                        item_,
+                                        visibility,
+                                        maybe_append(attrs, extra_attrs));
+                return Some(item);
+            }
+
             // CONST ITEM
             if self.eat_keyword(keywords::Mut) {
                 let last_span = self.last_span;
@@ -5204,7 +5427,7 @@ impl<'a> Parser<'a> {
             // FUNCTION ITEM
             self.bump();
             let (ident, item_, extra_attrs) =
-                self.parse_item_fn(Unsafety::Normal, abi::Rust);
+                self.parse_item_fn(Unsafety::Normal, Constness::NotConst, abi::Rust);
             let last_span = self.last_span;
             let item = self.mk_item(lo,
                                     last_span.hi,
@@ -5225,7 +5448,7 @@ impl<'a> Parser<'a> {
             };
             self.expect_keyword(keywords::Fn);
             let (ident, item_, extra_attrs) =
-                self.parse_item_fn(Unsafety::Unsafe, abi);
+                self.parse_item_fn(Unsafety::Unsafe, Constness::NotConst, abi);
             let last_span = self.last_span;
             let item = self.mk_item(lo,
                                     last_span.hi,
@@ -5353,7 +5576,7 @@ impl<'a> Parser<'a> {
             // MACRO INVOCATION ITEM
 
             let last_span = self.last_span;
-            self.complain_if_pub_macro(visibility, last_span);
+            self.complain_if_pub_macro(&visibility, last_span);
 
             // item macro.
             let pth = self.parse_path(NoTypesAllowed);
@@ -5402,9 +5625,9 @@ impl<'a> Parser<'a> {
         // FAILURE TO PARSE ITEM
         match visibility {
             Inherited => {}
-            Public => {
+            Public | PubCrate | Restricted { .. } => {
                 let last_span = self.last_span;
-                self.span_fatal(last_span, "unmatched visibility `pub`");
+                self.recoverable_err(last_span, "unmatched visibility `pub`");
             }
         }
 
@@ -384,13 +384,16 @@ pub fn ident_to_string(id: &ast::Ident) -> String {
     $to_string(|s| s.print_ident(*id))
 }
 
-pub fn fun_to_string(decl: &ast::FnDecl, unsafety: ast::Unsafety, name: ast::Ident,
-                  opt_explicit_self: Option<&ast::ExplicitSelf_>,
-                  generics: &ast::Generics) -> String {
+pub fn fun_to_string(decl: &ast::FnDecl,
+                     unsafety: ast::Unsafety,
+                     constness: ast::Constness,
+                     name: ast::Ident,
+                     opt_explicit_self: Option<&ast::ExplicitSelf_>,
+                     generics: &ast::Generics) -> String {
     $to_string(|s| {
         try!(s.head(""));
-        try!(s.print_fn(decl, unsafety, abi::Rust, Some(name),
-                        generics, opt_explicit_self, ast::Inherited));
+        try!(s.print_fn(decl, unsafety, constness, abi::Rust, Some(name),
+                        generics, opt_explicit_self, &ast::Inherited));
         try!(s.end()); // Close the head box
         s.end() // Close the outer box
     })
@@ -462,9 +465,11 @@ pub mod with_hygiene {
     thing_to_string_impls! { to_string_hyg }
 }
 
-pub fn visibility_qualified(vis: ast::Visibility, s: &str) -> String {
-    match vis {
+pub fn visibility_qualified(vis: &ast::Visibility, s: &str) -> String {
+    match *vis {
         ast::Public => format!("pub {}", s),
+        ast::PubCrate => format!("pub(crate) {}", s),
+        ast::Restricted { ref path, .. } => format!("pub(in {}) {}", path_to_string(path), s),
         ast::Inherited => s.to_string()
     }
 }
@@ -775,14 +780,15 @@ impl<'a> State<'a> {
             ast::ForeignItemFn(ref decl, ref generics) => {
                 try!(self.head(""));
                 try!(self.print_fn(&**decl, ast::Unsafety::Normal,
+                                   ast::Constness::NotConst,
                                    abi::Rust, Some(item.ident),
-                                   generics, None, item.vis));
+                                   generics, None, &item.vis));
                 try!(self.end()); // end head-ibox
                 try!(word(&mut self.s, ";"));
                 self.end() // end the outer fn box
             }
             ast::ForeignItemStatic(ref t, m) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                     "static")));
                 if m {
                     try!(self.word_space("mut"));
@@ -797,6 +803,26 @@ impl<'a> State<'a> {
         }
     }
 
+    fn print_associated_const(&mut self,
+                              ident: ast::Ident,
+                              ty: &ast::Ty,
+                              default: Option<&ast::Expr>,
+                              vis: &ast::Visibility)
+                              -> io::Result<()>
+    {
+        try!(word(&mut self.s, &visibility_qualified(vis, "")));
+        try!(self.word_space("const"));
+        try!(self.print_ident(ident));
+        try!(self.word_space(":"));
+        try!(self.print_type(ty));
+        if let Some(expr) = default {
+            try!(space(&mut self.s));
+            try!(self.word_space("="));
+            try!(self.print_expr(expr));
+        }
+        word(&mut self.s, ";")
+    }
+
     fn print_associated_type(&mut self,
                              ident: ast::Ident,
                              bounds: Option<&ast::TyParamBounds>,
@@ -824,7 +850,7 @@ impl<'a> State<'a> {
         try!(self.ann.pre(self, NodeItem(item)));
         match item.node {
             ast::ItemExternCrate(ref optional_path) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                      "extern crate")));
                 if let Some(p) = *optional_path {
                     let val = token::get_name(p);
@@ -843,7 +869,7 @@ impl<'a> State<'a> {
                 try!(self.end()); // end outer head-block
             }
             ast::ItemUse(ref vp) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                      "use")));
                 try!(self.print_view_path(&**vp));
                 try!(word(&mut self.s, ";"));
@@ -851,7 +877,7 @@ impl<'a> State<'a> {
                 try!(self.end()); // end outer head-block
             }
             ast::ItemStatic(ref ty, m, ref expr) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                     "static")));
                 if m == ast::MutMutable {
                     try!(self.word_space("mut"));
@@ -868,7 +894,7 @@ impl<'a> State<'a> {
                 try!(self.end()); // end the outer cbox
             }
             ast::ItemConst(ref ty, ref expr) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                     "const")));
                 try!(self.print_ident(item.ident));
                 try!(self.word_space(":"));
@@ -881,22 +907,23 @@ impl<'a> State<'a> {
                 try!(word(&mut self.s, ";"));
                 try!(self.end()); // end the outer cbox
             }
-            ast::ItemFn(ref decl, unsafety, abi, ref typarams, ref body) => {
+            ast::ItemFn(ref decl, unsafety, constness, abi, ref typarams, ref body) => {
                 try!(self.head(""));
                 try!(self.print_fn(
                     decl,
                     unsafety,
+                    constness,
                     abi,
                     Some(item.ident),
                     typarams,
                     None,
-                    item.vis
+                    &item.vis
                 ));
                 try!(word(&mut self.s, " "));
                 try!(self.print_block_with_attrs(&**body, &item.attrs));
             }
             ast::ItemMod(ref _mod) => {
-                try!(self.head(&visibility_qualified(item.vis,
+                try!(self.head(&visibility_qualified(&item.vis,
                                                     "mod")));
                 try!(self.print_ident(item.ident));
                 try!(self.nbsp());
@@ -914,7 +941,7 @@ impl<'a> State<'a> {
             ast::ItemTy(ref ty, ref params) => {
                 try!(self.ibox(indent_unit));
                 try!(self.ibox(0));
-                try!(self.word_nbsp(&visibility_qualified(item.vis, "type")));
+                try!(self.word_nbsp(&visibility_qualified(&item.vis, "type")));
                 try!(self.print_ident(item.ident));
                 try!(self.print_generics(params));
                 try!(self.end()); // end the inner ibox
@@ -932,17 +959,17 @@ impl<'a> State<'a> {
                     params,
                     item.ident,
                     item.span,
-                    item.vis
+                    &item.vis
                 ));
             }
             ast::ItemStruct(ref struct_def, ref generics) => {
-                try!(self.head(&visibility_qualified(item.vis,"struct")));
+                try!(self.head(&visibility_qualified(&item.vis,"struct")));
                 try!(self.print_struct(&**struct_def, generics, item.ident, item.span));
             }
 
             ast::ItemDefaultImpl(unsafety, ref trait_ref) => {
                 try!(self.head(""));
-                try!(self.print_visibility(item.vis));
+                try!(self.print_visibility(&item.vis));
                 try!(self.print_unsafety(unsafety));
                 try!(self.word_nbsp("impl"));
                 try!(self.print_trait_ref(trait_ref));
@@ -959,7 +986,7 @@ impl<'a> State<'a> {
                           ref ty,
                           ref impl_items) => {
                 try!(self.head(""));
-                try!(self.print_visibility(item.vis));
+                try!(self.print_visibility(&item.vis));
                 try!(self.print_unsafety(unsafety));
                 try!(self.word_nbsp("impl"));
 
@@ -997,7 +1024,7 @@ impl<'a> State<'a> {
             }
             ast::ItemTrait(unsafety, ref generics, ref bounds, ref trait_items) => {
                 try!(self.head(""));
-                try!(self.print_visibility(item.vis));
+                try!(self.print_visibility(&item.vis));
                 try!(self.print_unsafety(unsafety));
                 try!(self.word_nbsp("trait"));
                 try!(self.print_ident(item.ident));
@@ -1024,7 +1051,7 @@ impl<'a> State<'a> {
             // I think it's reasonable to hide the context here:
             ast::ItemMac(codemap::Spanned { node: ast::MacInvocTT(ref pth, ref tts, _),
                                             ..}) => {
-                try!(self.print_visibility(item.vis));
+                try!(self.print_visibility(&item.vis));
                 try!(self.print_path(pth, false, 0));
                 try!(word(&mut self.s, "! "));
                 try!(self.print_ident(item.ident));
@@ -1067,7 +1094,7 @@ impl<'a> State<'a> {
     pub fn print_enum_def(&mut self, enum_definition: &ast::EnumDef,
                           generics: &ast::Generics, ident: ast::Ident,
                           span: codemap::Span,
-                          visibility: ast::Visibility) -> io::Result<()> {
+                          visibility: &ast::Visibility) -> io::Result<()> {
         try!(self.head(&visibility_qualified(visibility, "enum")));
         try!(self.print_ident(ident));
         try!(self.print_generics(generics));
@@ -1093,7 +1120,7 @@ impl<'a> State<'a> {
         self.bclose(span)
     }
 
-    pub fn print_visibility(&mut self, vis: ast::Visibility) -> io::Result<()> {
+    pub fn print_visibility(&mut self, vis: &ast::Visibility) -> io::Result<()> {
         match vis {
             ast::Public => self.word_nbsp("pub"),
             ast::Inherited => Ok(())
@@ -1115,7 +1142,7 @@ impl<'a> State<'a> {
                     |s, field| {
                         match field.node.kind {
                             ast::NamedField(..) => panic!("unexpected named field"),
-                            ast::UnnamedField(vis) => {
+                            ast::UnnamedField(ref vis) => {
                                 try!(s.print_visibility(vis));
                                 try!(s.maybe_print_comment(field.span.lo));
                                 s.print_type(&*field.node.ty)
@@ -1138,7 +1165,7 @@ impl<'a> State<'a> {
             for field in &struct_def.fields {
                 match field.node.kind {
                     ast::UnnamedField(..) => panic!("unexpected unnamed field"),
-                    ast::NamedField(ident, visibility) => {
+                    ast::NamedField(ident, ref visibility) => {
                         try!(self.hardbreak_if_not_bol());
                         try!(self.maybe_print_comment(field.span.lo));
                         try!(self.print_outer_attributes(&field.node.attrs));
@@ -1221,7 +1248,7 @@ impl<'a> State<'a> {
     }
 
     pub fn print_variant(&mut self, v: &ast::Variant) -> io::Result<()> {
-        try!(self.print_visibility(v.node.vis));
+        try!(self.print_visibility(&v.node.vis));
         match v.node.kind {
             ast::TupleVariantKind(ref args) => {
                 try!(self.print_ident(v.node.name));
@@ -1252,10 +1279,11 @@ impl<'a> State<'a> {
     pub fn print_method_sig(&mut self,
                             ident: ast::Ident,
                             m: &ast::MethodSig,
-                            vis: ast::Visibility)
+                            vis: &ast::Visibility)
                             -> io::Result<()> {
         self.print_fn(&m.decl,
                       m.unsafety,
+                      m.constness,
                       m.abi,
                       Some(ident),
                       &m.generics,
@@ -1270,11 +1298,16 @@ impl<'a> State<'a> {
         try!(self.maybe_print_comment(ti.span.lo));
         try!(self.print_outer_attributes(&ti.attrs));
         match ti.node {
+            ast::ConstTraitItem(ref ty, ref default) => {
+                try!(self.print_associated_const(ti.ident, &ty,
+                                                 default.as_ref().map(|expr| &**expr),
+                                                 &ast::Inherited));
+            }
             ast::MethodTraitItem(ref sig, ref body) => {
                 if body.is_some() {
                     try!(self.head(""));
                 }
-                try!(self.print_method_sig(ti.ident, sig, ast::Inherited));
+                try!(self.print_method_sig(ti.ident, sig, &ast::Inherited));
                 if let Some(ref body) = *body {
                     try!(self.nbsp());
                     try!(self.print_block_with_attrs(body, &ti.attrs));
@@ -1296,9 +1329,12 @@ impl<'a> State<'a> {
         try!(self.maybe_print_comment(ii.span.lo));
         try!(self.print_outer_attributes(&ii.attrs));
         match ii.node {
+            ast::ConstImplItem(ref ty, ref expr) => {
+                try!(self.print_associated_const(ii.ident, &ty, Some(&expr), &ii.vis));
+            }
             ast::MethodImplItem(ref sig, ref body) => {
                 try!(self.head(""));
-                try!(self.print_method_sig(ii.ident, sig, ii.vis));
+                try!(self.print_method_sig(ii.ident, sig, &ii.vis));
                 try!(self.nbsp());
                 try!(self.print_block_with_attrs(body, &ii.attrs));
             }
@@ -1970,6 +2006,11 @@ impl<'a> State<'a> {
                 try!(self.print_expr(&**e));
                 try!(self.pclose());
             }
+            ast::ExprError => {
+                try!(self.popen());
+                try!(word(&mut self.s, "/*ERROR*/"));
+                try!(self.pclose());
+            }
         }
         try!(self.ann.post(self, NodeExpr(expr)));
         self.end()
@@ -2344,12 +2385,13 @@ impl<'a> State<'a> {
     pub fn print_fn(&mut self,
                     decl: &ast::FnDecl,
                     unsafety: ast::Unsafety,
+                    constness: ast::Constness,
                     abi: abi::Abi,
                     name: Option<ast::Ident>,
                     generics: &ast::Generics,
                     opt_explicit_self: Option<&ast::ExplicitSelf_>,
-                    vis: ast::Visibility) -> io::Result<()> {
-        try!(self.print_fn_header_info(unsafety, abi, vis));
+                    vis: &ast::Visibility) -> io::Result<()> {
+        try!(self.print_fn_header_info(unsafety, constness, abi, vis));
 
         if let Some(name) = name {
             try!(self.nbsp());
@@ -2735,9 +2777,9 @@ impl<'a> State<'a> {
                 predicates: Vec::new(),
             },
         };
-        try!(self.print_fn(decl, unsafety, abi, name,
+        try!(self.print_fn(decl, unsafety, ast::Constness::NotConst, abi, name,
                            &generics, opt_explicit_self,
-                           ast::Inherited));
+                           &ast::Inherited));
         self.end()
     }
 
@@ -2985,9 +3027,16 @@ impl<'a> State<'a> {
 
     pub fn print_fn_header_info(&mut self,
                                 unsafety: ast::Unsafety,
+                                constness: ast::Constness,
                                 abi: abi::Abi,
-                                vis: ast::Visibility) -> io::Result<()> {
+                                vis: &ast::Visibility) -> io::Result<()> {
         try!(word(&mut self.s, &visibility_qualified(vis, "")));
+
+        match constness {
+            ast::Constness::NotConst => {}
+            ast::Constness::Const => try!(self.word_nbsp("const"))
+        }
+
         try!(self.print_unsafety(unsafety));
 
         if abi != abi::Rust {
@@ -3027,7 +3076,9 @@ mod test {
             variadic: false
         };
         let generics = ast_util::empty_generics();
-        assert_eq!(fun_to_string(&decl, ast::Unsafety::Normal, abba_ident,
+        assert_eq!(fun_to_string(&decl, ast::Unsafety::Normal,
+                                 ast::Constness::NotConst,
+                                 abba_ident,
                                None, &generics),
                    "fn abba()");
     }
@@ -0,0 +1,483 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Two's complement 128-bit integers.
+//!
+//! The snapshot compiler that builds rustc has no `i128` or `u128`, so the
+//! compiler holds values of those types (e.g. during constant evaluation)
+//! in these two-word structs instead. They behave like the primitive
+//! integers: the `overflowing_*` operations wrap and report overflow, and
+//! `ToPrimitive` gives the checked narrowing conversions.
+
+use std::fmt;
+use std::i64;
+use std::num::ToPrimitive;
+use std::num::wrapping::OverflowingOps;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
+
+const TWO_64: f64 = 18446744073709551616.0;
+
+/// An unsigned 128-bit integer.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct U128 {
+    // The field order makes the derived `Ord` compare numerically.
+    hi: u64,
+    lo: u64,
+}
+
+/// A signed 128-bit integer.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct I128 {
+    // Comparing the signed high word first and then the unsigned low
+    // word orders two's complement values numerically.
+    hi: i64,
+    lo: u64,
+}
+
+impl U128 {
+    pub fn from_u64(v: u64) -> U128 {
+        U128 { hi: 0, lo: v }
+    }
+
+    /// Builds a value from its high and low 64 bits.
+    pub fn from_parts(hi: u64, lo: u64) -> U128 {
+        U128 { hi: hi, lo: lo }
+    }
+
+    /// Converts like an `as` cast from `f64`, truncating toward zero and
+    /// saturating at the bounds of the type.
+    pub fn from_f64(f: f64) -> U128 {
+        if !(f >= 1.0) {
+            // Also catches NaN.
+            U128::from_u64(0)
+        } else if f >= TWO_64 * TWO_64 {
+            U128::max_value()
+        } else {
+            let hi = (f / TWO_64) as u64;
+            U128 { hi: hi, lo: (f - hi as f64 * TWO_64) as u64 }
+        }
+    }
+
+    pub fn min_value() -> U128 {
+        U128::from_u64(0)
+    }
+
+    pub fn max_value() -> U128 {
+        U128 { hi: !0, lo: !0 }
+    }
+
+    /// The value's 64-bit words, least significant first.
+    pub fn words(self) -> [u64; 2] {
+        [self.lo, self.hi]
+    }
+
+    /// The low 64 bits, like an `as u64` cast.
+    pub fn as_u64(self) -> u64 {
+        self.lo
+    }
+
+    /// The same bits read as a signed value, like an `as i128` cast.
+    pub fn as_i128(self) -> I128 {
+        I128 { hi: self.hi as i64, lo: self.lo }
+    }
+
+    /// The nearest `f64`, like an `as f64` cast.
+    pub fn as_f64(self) -> f64 {
+        self.hi as f64 * TWO_64 + self.lo as f64
+    }
+
+    pub fn to_u128(&self) -> Option<U128> {
+        Some(*self)
+    }
+
+    pub fn to_i128(&self) -> Option<I128> {
+        if self.hi >> 63 == 0 { Some(self.as_i128()) } else { None }
+    }
+
+    pub fn wrapping_neg(self) -> U128 {
+        (!self).overflowing_add(U128::from_u64(1)).0
+    }
+
+    fn shl(self, n: u32) -> U128 {
+        match n {
+            0 => self,
+            1...63 => U128 { hi: self.hi << n | self.lo >> (64 - n), lo: self.lo << n },
+            _ => U128 { hi: self.lo << (n - 64), lo: 0 },
+        }
+    }
+
+    fn shr(self, n: u32) -> U128 {
+        match n {
+            0 => self,
+            1...63 => U128 { hi: self.hi >> n, lo: self.lo >> n | self.hi << (64 - n) },
+            _ => U128 { hi: 0, lo: self.hi >> (n - 64) },
+        }
+    }
+
+    /// Long division, one bit at a time. `d` must not be zero.
+    fn div_rem(self, d: U128) -> (U128, U128) {
+        let (mut q, mut r) = (U128::from_u64(0), U128::from_u64(0));
+        for i in (0..128).rev() {
+            r = r.shl(1) | (self.shr(i) & U128::from_u64(1));
+            if r >= d {
+                r = r.overflowing_sub(d).0;
+                q = q | U128::from_u64(1).shl(i);
+            }
+        }
+        (q, r)
+    }
+
+    fn to_decimal(self) -> String {
+        const TEN_19: u64 = 10_000_000_000_000_000_000;
+        let ten_19 = U128::from_u64(TEN_19);
+        if self.hi == 0 {
+            return format!("{}", self.lo);
+        }
+        // 2^128 has 39 digits, so at most three 19-digit chunks.
+        let (q, low) = self.div_rem(ten_19);
+        if q.hi == 0 {
+            format!("{}{:019}", q.lo, low.lo)
+        } else {
+            let (top, mid) = q.div_rem(ten_19);
+            format!("{}{:019}{:019}", top.lo, mid.lo, low.lo)
+        }
+    }
+}
+
+/// Multiplies two 64-bit values into a full 128-bit product.
+fn mul_u64(a: u64, b: u64) -> U128 {
+    let (a1, a0) = (a >> 32, a & 0xffff_ffff);
+    let (b1, b0) = (b >> 32, b & 0xffff_ffff);
+    let (p00, p01, p10, p11) = (a0 * b0, a0 * b1, a1 * b0, a1 * b1);
+    let mid = (p00 >> 32) + (p01 & 0xffff_ffff) + (p10 & 0xffff_ffff);
+    U128 {
+        hi: p11 + (p01 >> 32) + (p10 >> 32) + (mid >> 32),
+        lo: (p00 & 0xffff_ffff) | mid << 32,
+    }
+}
+
+impl OverflowingOps for U128 {
+    fn overflowing_add(self, rhs: U128) -> (U128, bool) {
+        let (lo, carry) = self.lo.overflowing_add(rhs.lo);
+        let (hi, o1) = self.hi.overflowing_add(rhs.hi);
+        let (hi, o2) = hi.overflowing_add(carry as u64);
+        (U128 { hi: hi, lo: lo }, o1 || o2)
+    }
+
+    fn overflowing_sub(self, rhs: U128) -> (U128, bool) {
+        let (lo, borrow) = self.lo.overflowing_sub(rhs.lo);
+        let (hi, o1) = self.hi.overflowing_sub(rhs.hi);
+        let (hi, o2) = hi.overflowing_sub(borrow as u64);
+        (U128 { hi: hi, lo: lo }, o1 || o2)
+    }
+
+    fn overflowing_mul(self, rhs: U128) -> (U128, bool) {
+        let low = mul_u64(self.lo, rhs.lo);
+        let (c1, o1) = self.hi.overflowing_mul(rhs.lo);
+        let (c2, o2) = self.lo.overflowing_mul(rhs.hi);
+        let (cross, o3) = c1.overflowing_add(c2);
+        let (hi, o4) = low.hi.overflowing_add(cross);
+        let oflo = (self.hi != 0 && rhs.hi != 0) || o1 || o2 || o3 || o4;
+        (U128 { hi: hi, lo: low.lo }, oflo)
+    }
+
+    fn overflowing_div(self, rhs: U128) -> (U128, bool) {
+        if rhs == U128::from_u64(0) {
+            panic!("attempted to divide by zero");
+        }
+        (self.div_rem(rhs).0, false)
+    }
+
+    fn overflowing_rem(self, rhs: U128) -> (U128, bool) {
+        if rhs == U128::from_u64(0) {
+            panic!("attempted remainder with a divisor of zero");
+        }
+        (self.div_rem(rhs).1, false)
+    }
+
+    fn overflowing_shl(self, rhs: u32) -> (U128, bool) {
+        (self.shl(rhs & 127), rhs > 127)
+    }
+
+    fn overflowing_shr(self, rhs: u32) -> (U128, bool) {
+        (self.shr(rhs & 127), rhs > 127)
+    }
+}
+
+impl I128 {
+    pub fn from_i64(v: i64) -> I128 {
+        I128 { hi: v >> 63, lo: v as u64 }
+    }
+
+    /// Converts like an `as` cast from `f64`, truncating toward zero and
+    /// saturating at the bounds of the type.
+    pub fn from_f64(f: f64) -> I128 {
+        if f < 0.0 {
+            if -f >= TWO_64 * TWO_64 / 2.0 {
+                I128::min_value()
+            } else {
+                U128::from_f64(-f).wrapping_neg().as_i128()
+            }
+        } else if f >= TWO_64 * TWO_64 / 2.0 {
+            I128::max_value()
+        } else {
+            U128::from_f64(f).as_i128()
+        }
+    }
+
+    pub fn min_value() -> I128 {
+        I128 { hi: i64::MIN, lo: 0 }
+    }
+
+    pub fn max_value() -> I128 {
+        I128 { hi: i64::MAX, lo: !0 }
+    }
+
+    pub fn is_negative(self) -> bool {
+        self.hi < 0
+    }
+
+    /// The low 64 bits, like an `as u64` cast.
+    pub fn as_u64(self) -> u64 {
+        self.lo
+    }
+
+    /// The same bits read as an unsigned value, like an `as u128` cast.
+    pub fn as_u128(self) -> U128 {
+        U128 { hi: self.hi as u64, lo: self.lo }
+    }
+
+    /// The nearest `f64`, like an `as f64` cast.
+    pub fn as_f64(self) -> f64 {
+        if self.is_negative() {
+            -self.unsigned_abs().as_f64()
+        } else {
+            self.as_u128().as_f64()
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<U128> {
+        if self.is_negative() { None } else { Some(self.as_u128()) }
+    }
+
+    pub fn to_i128(&self) -> Option<I128> {
+        Some(*self)
+    }
+
+    pub fn wrapping_neg(self) -> I128 {
+        self.as_u128().wrapping_neg().as_i128()
+    }
+
+    /// The magnitude, which always fits in a `U128`.
+    fn unsigned_abs(self) -> U128 {
+        if self.is_negative() { self.as_u128().wrapping_neg() } else { self.as_u128() }
+    }
+
+    /// Gives `magnitude` the sign requested, wrapping like `as i128`.
+    fn with_sign(magnitude: U128, negative: bool) -> I128 {
+        if negative { magnitude.wrapping_neg().as_i128() } else { magnitude.as_i128() }
+    }
+}
+
+impl OverflowingOps for I128 {
+    fn overflowing_add(self, rhs: I128) -> (I128, bool) {
+        let r = self.as_u128().overflowing_add(rhs.as_u128()).0.as_i128();
+        let oflo = self.is_negative() == rhs.is_negative() &&
+                   r.is_negative() != self.is_negative();
+        (r, oflo)
+    }
+
+    fn overflowing_sub(self, rhs: I128) -> (I128, bool) {
+        let r = self.as_u128().overflowing_sub(rhs.as_u128()).0.as_i128();
+        let oflo = self.is_negative() != rhs.is_negative() &&
+                   r.is_negative() != self.is_negative();
+        (r, oflo)
+    }
+
+    fn overflowing_mul(self, rhs: I128) -> (I128, bool) {
+        // The low 128 bits of a product do not depend on the signedness.
+        let r = self.as_u128().overflowing_mul(rhs.as_u128()).0.as_i128();
+        let negative = self.is_negative() != rhs.is_negative();
+        let (magnitude, oflo) = self.unsigned_abs().overflowing_mul(rhs.unsigned_abs());
+        let limit = I128::max_value().as_u128().overflowing_add(
+            U128::from_u64(negative as u64)).0;
+        (r, oflo || magnitude > limit)
+    }
+
+    fn overflowing_div(self, rhs: I128) -> (I128, bool) {
+        if rhs == I128::from_i64(-1) && self == I128::min_value() {
+            return (self, true);
+        }
+        let (q, _) = self.unsigned_abs().overflowing_div(rhs.unsigned_abs());
+        (I128::with_sign(q, self.is_negative() != rhs.is_negative()), false)
+    }
+
+    fn overflowing_rem(self, rhs: I128) -> (I128, bool) {
+        if rhs == I128::from_i64(-1) && self == I128::min_value() {
+            return (I128::from_i64(0), true);
+        }
+        let (r, _) = self.unsigned_abs().overflowing_rem(rhs.unsigned_abs());
+        (I128::with_sign(r, self.is_negative()), false)
+    }
+
+    fn overflowing_shl(self, rhs: u32) -> (I128, bool) {
+        (self.as_u128().shl(rhs & 127).as_i128(), rhs > 127)
+    }
+
+    fn overflowing_shr(self, rhs: u32) -> (I128, bool) {
+        let n = rhs & 127;
+        let r = match n {
+            0 => self,
+            1...63 => I128 {
+                hi: self.hi >> n,
+                lo: self.lo >> n | (self.hi as u64) << (64 - n),
+            },
+            _ => I128 { hi: self.hi >> 63, lo: (self.hi >> (n - 64)) as u64 },
+        };
+        (r, rhs > 127)
+    }
+}
+
+impl ToPrimitive for U128 {
+    fn to_i64(&self) -> Option<i64> {
+        if self.hi == 0 { self.lo.to_i64() } else { None }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        if self.hi == 0 { Some(self.lo) } else { None }
+    }
+}
+
+impl ToPrimitive for I128 {
+    fn to_i64(&self) -> Option<i64> {
+        // Fits if the high word is just the sign extension of the low one.
+        if self.hi == (self.lo as i64) >> 63 { Some(self.lo as i64) } else { None }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        if self.hi == 0 { Some(self.lo) } else { None }
+    }
+}
+
+macro_rules! bit_ops {
+    ($($t:ident)*) => ($(
+        impl BitAnd for $t {
+            type Output = $t;
+            fn bitand(self, rhs: $t) -> $t { $t { hi: self.hi & rhs.hi, lo: self.lo & rhs.lo } }
+        }
+
+        impl BitOr for $t {
+            type Output = $t;
+            fn bitor(self, rhs: $t) -> $t { $t { hi: self.hi | rhs.hi, lo: self.lo | rhs.lo } }
+        }
+
+        impl BitXor for $t {
+            type Output = $t;
+            fn bitxor(self, rhs: $t) -> $t { $t { hi: self.hi ^ rhs.hi, lo: self.lo ^ rhs.lo } }
+        }
+
+        impl Not for $t {
+            type Output = $t;
+            fn not(self) -> $t { $t { hi: !self.hi, lo: !self.lo } }
+        }
+    )*)
+}
+
+bit_ops! { U128 I128 }
+
+impl fmt::Display for U128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_decimal())
+    }
+}
+
+impl fmt::Display for I128 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "", &self.unsigned_abs().to_decimal())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::num::ToPrimitive;
+    use std::num::wrapping::OverflowingOps;
+
+    #[test]
+    fn test_unsigned_arith() {
+        let max = U128::max_value();
+        let one = U128::from_u64(1);
+        assert_eq!(max.overflowing_add(one), (U128::from_u64(0), true));
+        assert_eq!(U128::from_u64(!0).overflowing_add(one), (U128::from_parts(1, 0), false));
+        assert_eq!(U128::from_u64(0).overflowing_sub(one), (max, true));
+
+        let big = U128::from_u64(1 << 63);
+        assert_eq!(big.overflowing_mul(U128::from_u64(4)), (U128::from_parts(2, 0), false));
+        assert_eq!(U128::from_parts(1, 0).overflowing_mul(U128::from_parts(1, 0)).1, true);
+        assert_eq!(max.overflowing_mul(max), (one, true));
+
+        let x = U128::from_parts(0x1234, 0x5678);
+        let (q, _) = x.overflowing_div(U128::from_u64(10));
+        let (r, _) = x.overflowing_rem(U128::from_u64(10));
+        assert_eq!(q.overflowing_mul(U128::from_u64(10)).0.overflowing_add(r).0, x);
+    }
+
+    #[test]
+    fn test_signed_arith() {
+        let min = I128::min_value();
+        let max = I128::max_value();
+        let one = I128::from_i64(1);
+        let neg_one = I128::from_i64(-1);
+        assert_eq!(max.overflowing_add(one), (min, true));
+        assert_eq!(min.overflowing_sub(one), (max, true));
+        assert_eq!(neg_one.overflowing_add(neg_one), (I128::from_i64(-2), false));
+
+        assert_eq!(min.overflowing_mul(one), (min, false));
+        assert_eq!(min.overflowing_mul(neg_one), (min, true));
+        assert_eq!(I128::from_i64(-7).overflowing_mul(I128::from_i64(6)),
+                   (I128::from_i64(-42), false));
+
+        assert_eq!(min.overflowing_div(neg_one), (min, true));
+        assert_eq!(I128::from_i64(-7).overflowing_div(I128::from_i64(2)),
+                   (I128::from_i64(-3), false));
+        assert_eq!(I128::from_i64(-7).overflowing_rem(I128::from_i64(2)),
+                   (I128::from_i64(-1), false));
+    }
+
+    #[test]
+    fn test_shifts() {
+        let one = U128::from_u64(1);
+        assert_eq!(one.overflowing_shl(100).0.overflowing_shr(100), (one, false));
+        assert_eq!(one.overflowing_shl(128), (one, true));
+        assert_eq!(I128::min_value().overflowing_shr(127), (I128::from_i64(-1), false));
+        assert_eq!(I128::from_i64(-4).overflowing_shr(1), (I128::from_i64(-2), false));
+    }
+
+    #[test]
+    fn test_conversions() {
+        const TWO_65: f64 = 36893488147419103232.0;
+        assert_eq!(I128::from_i64(-1).to_i64(), Some(-1));
+        assert_eq!(I128::from_i64(-1).to_u64(), None);
+        assert_eq!(I128::from_i64(-1).as_u128(), U128::max_value());
+        assert_eq!(U128::from_parts(1, 0).to_u64(), None);
+        assert_eq!(U128::max_value().to_i128(), None);
+        assert_eq!(I128::from_f64(-2.5), I128::from_i64(-2));
+        assert_eq!(U128::from_f64(TWO_65), U128::from_parts(2, 0));
+        assert_eq!(U128::from_parts(2, 0).as_f64(), TWO_65);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(U128::max_value().to_string(), "340282366920938463463374607431768211455");
+        assert_eq!(I128::min_value().to_string(), "-170141183460469231731687303715884105728");
+        assert_eq!(I128::from_i64(-42).to_string(), "-42");
+        assert_eq!(format!("{:>5}", U128::from_u64(7)), "    7");
+    }
+}
@@ -12,11 +12,16 @@
 
 #include "rustllvm.h"
 
+#include "llvm/ADT/DenseMap.h"
+#include "llvm/ADT/SmallString.h"
+#include "llvm/IR/MDBuilder.h"
+#include "llvm/ProfileData/InstrProfReader.h"
 #include "llvm/Support/CBindingWrapping.h"
 #include "llvm/Support/FileSystem.h"
 #include "llvm/Support/Host.h"
 #include "llvm/Target/TargetLibraryInfo.h"
 #include "llvm/Transforms/IPO/PassManagerBuilder.h"
+#include "llvm/Transforms/Utils/ModuleUtils.h"
 
 #include "llvm-c/Transforms/PassManagerBuilder.h"
 
@@ -296,3 +301,296 @@ LLVMRustMarkAllFunctionsNounwind(LLVMModuleRef M) {
         }
     }
 }
+
+typedef void (*RustBitcodeCallback)(void *, const char *, size_t);
+
+// Serializes M so it can be linked into a module of another context.
+extern "C" void
+LLVMRustWriteBitcodeToCallback(LLVMModuleRef M,
+                               RustBitcodeCallback Callback,
+                               void *Payload) {
+    SmallString<0> Buffer;
+    raw_svector_ostream OS(Buffer);
+    WriteBitcodeToFile(unwrap(M), OS);
+    OS.flush();
+    Callback(Payload, Buffer.data(), Buffer.size());
+}
+
+// Turns the definition of a function or global variable into a declaration,
+// so that it refers to the definition in another module.
+extern "C" void
+LLVMRustDropDefinition(LLVMValueRef V) {
+    GlobalValue *GV = unwrap<GlobalValue>(V);
+    if (Function *F = dyn_cast<Function>(GV)) {
+        F->deleteBody();
+    } else if (GlobalVariable *G = dyn_cast<GlobalVariable>(GV)) {
+        G->setInitializer(NULL);
+    }
+    GV->setLinkage(GlobalValue::ExternalLinkage);
+}
+
+// Profile-guided optimization
+//
+// Every function defined in the module gets a counter for the number of
+// times it's called, and one for each edge leaving a block through a
+// conditional branch or a switch. The counters and the records describing
+// them use the layout of the instrumentation clang emits, so that the
+// profiling runtime from compiler-rt writes them out and `llvm-profdata`
+// merges them. When the profile is used the counts become branch weights,
+// and the call counts mark functions hot or cold.
+
+typedef std::vector<std::pair<TerminatorInst *, unsigned> > ProfiledEdges;
+
+static bool
+IsProfiled(Function &F) {
+    return !F.isDeclaration() && !F.hasAvailableExternallyLinkage();
+}
+
+static void
+GetProfiledEdges(Function &F, ProfiledEdges &Edges) {
+    for (Function::iterator BB = F.begin(), E = F.end(); BB != E; ++BB) {
+        TerminatorInst *TI = BB->getTerminator();
+        if (!isa<BranchInst>(TI) && !isa<SwitchInst>(TI))
+            continue;
+        if (TI->getNumSuccessors() < 2)
+            continue;
+        for (unsigned i = 0; i < TI->getNumSuccessors(); ++i)
+            Edges.push_back(std::make_pair(TI, i));
+    }
+}
+
+// Hashes the shape of F's control flow graph, which is what the counters
+// depend on, so that a profile of different code can be told apart.
+static uint64_t
+ProfileHash(Function &F) {
+    DenseMap<BasicBlock *, uint64_t> Index;
+    uint64_t Next = 0;
+    for (Function::iterator BB = F.begin(), E = F.end(); BB != E; ++BB)
+        Index[BB] = Next++;
+
+    // FNV-1a
+    uint64_t Hash = 14695981039346656037ULL;
+    auto Mix = [&](uint64_t V) {
+        Hash = (Hash ^ V) * 1099511628211ULL;
+    };
+    Mix(F.size());
+    for (Function::iterator BB = F.begin(), E = F.end(); BB != E; ++BB) {
+        TerminatorInst *TI = BB->getTerminator();
+        Mix(TI->getOpcode());
+        Mix(TI->getNumSuccessors());
+        for (unsigned i = 0; i < TI->getNumSuccessors(); ++i)
+            Mix(Index[TI->getSuccessor(i)]);
+    }
+    return Hash;
+}
+
+static void
+IncrementCounter(IRBuilder<> &B, GlobalVariable *Counters, uint64_t Idx) {
+    Value *Addr = B.CreateConstInBoundsGEP2_64(Counters, 0, Idx);
+    Value *Count = B.CreateLoad(Addr, "prof.count");
+    B.CreateStore(B.CreateAdd(Count, B.getInt64(1)), Addr);
+}
+
+static void
+AppendToUsed(Module &M, ArrayRef<GlobalValue *> Values) {
+    Type *Int8PtrTy = Type::getInt8PtrTy(M.getContext());
+    SmallVector<Constant *, 16> Init;
+    if (GlobalVariable *Used = M.getGlobalVariable("llvm.used")) {
+        ConstantArray *CA = cast<ConstantArray>(Used->getInitializer());
+        for (unsigned i = 0; i < CA->getNumOperands(); ++i)
+            Init.push_back(CA->getOperand(i));
+        Used->eraseFromParent();
+    }
+    for (unsigned i = 0; i < Values.size(); ++i)
+        Init.push_back(ConstantExpr::getBitCast(Values[i], Int8PtrTy));
+
+    ArrayType *ATy = ArrayType::get(Int8PtrTy, Init.size());
+    GlobalVariable *Used = new GlobalVariable(M, ATy, false,
+                                              GlobalValue::AppendingLinkage,
+                                              ConstantArray::get(ATy, Init),
+                                              "llvm.used");
+    Used->setSection("llvm.metadata");
+}
+
+// Instruments M so that the program it ends up in writes its profile to
+// `Path` when it exits.
+extern "C" void
+LLVMRustInstrumentForProfiling(LLVMModuleRef MR, const char *Path) {
+    Module &M = *unwrap(MR);
+    LLVMContext &Ctx = M.getContext();
+    bool IsDarwin = Triple(M.getTargetTriple()).isOSDarwin();
+    const char *DataSection = IsDarwin ? "__DATA,__llvm_prf_data"
+                                       : "__llvm_prf_data";
+    const char *NamesSection = IsDarwin ? "__DATA,__llvm_prf_names"
+                                        : "__llvm_prf_names";
+    const char *CountersSection = IsDarwin ? "__DATA,__llvm_prf_cnts"
+                                           : "__llvm_prf_cnts";
+
+    Type *Int8PtrTy = Type::getInt8PtrTy(Ctx);
+    Type *Int32Ty = Type::getInt32Ty(Ctx);
+    Type *Int64Ty = Type::getInt64Ty(Ctx);
+    Type *DataTypes[] = {
+        Int32Ty,                       // NameSize
+        Int32Ty,                       // NumCounters
+        Int64Ty,                       // FuncHash
+        Int8PtrTy,                     // Name
+        Type::getInt64PtrTy(Ctx),      // Counters
+    };
+    StructType *DataTy = StructType::get(Ctx, DataTypes);
+
+    std::vector<Function *> Functions;
+    for (Module::iterator F = M.begin(), E = M.end(); F != E; ++F)
+        if (IsProfiled(*F))
+            Functions.push_back(F);
+    if (Functions.empty())
+        return;
+
+    std::vector<GlobalValue *> Records;
+    for (unsigned i = 0; i < Functions.size(); ++i) {
+        Function &F = *Functions[i];
+        StringRef Name = F.getName();
+        uint64_t Hash = ProfileHash(F);
+        ProfiledEdges Edges;
+        GetProfiledEdges(F, Edges);
+
+        ArrayType *CountersTy = ArrayType::get(Int64Ty, Edges.size() + 1);
+        GlobalVariable *Counters =
+            new GlobalVariable(M, CountersTy, false,
+                               GlobalValue::InternalLinkage,
+                               Constant::getNullValue(CountersTy),
+                               "__llvm_profile_counters_" + Name);
+        Counters->setSection(CountersSection);
+        Counters->setAlignment(8);
+
+        Constant *NameInit = ConstantDataArray::getString(Ctx, Name, false);
+        GlobalVariable *NameVar =
+            new GlobalVariable(M, NameInit->getType(), true,
+                               GlobalValue::InternalLinkage, NameInit,
+                               "__llvm_profile_name_" + Name);
+        NameVar->setSection(NamesSection);
+
+        Constant *DataInit[] = {
+            ConstantInt::get(Int32Ty, Name.size()),
+            ConstantInt::get(Int32Ty, Edges.size() + 1),
+            ConstantInt::get(Int64Ty, Hash),
+            ConstantExpr::getBitCast(NameVar, Int8PtrTy),
+            ConstantExpr::getBitCast(Counters, Type::getInt64PtrTy(Ctx)),
+        };
+        GlobalVariable *Data =
+            new GlobalVariable(M, DataTy, true, GlobalValue::InternalLinkage,
+                               ConstantStruct::get(DataTy, DataInit),
+                               "__llvm_profile_data_" + Name);
+        Data->setSection(DataSection);
+        Data->setAlignment(8);
+        Records.push_back(Data);
+
+        IRBuilder<> Entry(F.getEntryBlock().getFirstInsertionPt());
+        IncrementCounter(Entry, Counters, 0);
+
+        // Count each edge in a block of its own on the way to the successor.
+        for (unsigned j = 0; j < Edges.size(); ++j) {
+            TerminatorInst *TI = Edges[j].first;
+            BasicBlock *Src = TI->getParent();
+            BasicBlock *Dst = TI->getSuccessor(Edges[j].second);
+            BasicBlock *Edge = BasicBlock::Create(Ctx, "prof.edge", &F, Dst);
+            IRBuilder<> B(Edge);
+            IncrementCounter(B, Counters, j + 1);
+            B.CreateBr(Dst);
+            TI->setSuccessor(Edges[j].second, Edge);
+
+            // A switch may have several edges to the same block, each with
+            // an incoming value of its own.
+            for (BasicBlock::iterator I = Dst->begin(); isa<PHINode>(I); ++I) {
+                PHINode *PN = cast<PHINode>(I);
+                PN->setIncomingBlock(PN->getBasicBlockIndex(Src), Edge);
+            }
+        }
+    }
+    AppendToUsed(M, Records);
+
+    // Tell the runtime where to write the profile, and on platforms where
+    // it can't find the records through their section, about the records.
+    FunctionType *RuntimeFnTy = FunctionType::get(Type::getVoidTy(Ctx),
+                                                  Int8PtrTy, false);
+    Constant *SetFilename =
+        M.getOrInsertFunction("__llvm_profile_set_filename", RuntimeFnTy);
+    Constant *Register =
+        M.getOrInsertFunction("__llvm_profile_register_function", RuntimeFnTy);
+    Function *Init =
+        Function::Create(FunctionType::get(Type::getVoidTy(Ctx), false),
+                         GlobalValue::InternalLinkage,
+                         "__llvm_profile_init", &M);
+    Init->addFnAttr(Attribute::NoUnwind);
+    IRBuilder<> B(BasicBlock::Create(Ctx, "", Init));
+    B.CreateCall(SetFilename, B.CreateGlobalStringPtr(Path));
+    if (!IsDarwin) {
+        for (unsigned i = 0; i < Records.size(); ++i)
+            B.CreateCall(Register,
+                         ConstantExpr::getBitCast(Records[i], Int8PtrTy));
+    }
+    B.CreateRetVoid();
+    appendToGlobalCtors(M, Init, 0);
+}
+
+typedef void (*RustProfileMismatchCallback)(void *, const char *);
+
+// Applies the merged profile data in `Path` to M. Functions whose profile
+// doesn't match their code are passed to Callback and left alone.
+extern "C" bool
+LLVMRustApplyProfile(LLVMModuleRef MR,
+                     const char *Path,
+                     RustProfileMismatchCallback Callback,
+                     void *Payload) {
+    std::unique_ptr<IndexedInstrProfReader> Reader;
+    if (std::error_code EC = IndexedInstrProfReader::create(Path, Reader)) {
+        LLVMRustSetLastError(EC.message().c_str());
+        return false;
+    }
+    uint64_t MaxCount = Reader->getMaximumFunctionCount();
+
+    Module &M = *unwrap(MR);
+    MDBuilder MDB(M.getContext());
+    for (Module::iterator F = M.begin(), E = M.end(); F != E; ++F) {
+        if (!IsProfiled(*F))
+            continue;
+        ProfiledEdges Edges;
+        GetProfiledEdges(*F, Edges);
+
+        std::vector<uint64_t> Counts;
+        std::error_code EC =
+            Reader->getFunctionCounts(F->getName(), ProfileHash(*F), Counts);
+        // Code that never ran isn't in the profile.
+        if (EC == instrprof_error::unknown_function)
+            continue;
+        if (EC || Counts.size() != Edges.size() + 1) {
+            Callback(Payload, F->getName().str().c_str());
+            continue;
+        }
+
+        // The same thresholds as clang.
+        if (MaxCount > 0) {
+            if (Counts[0] >= (uint64_t)(0.3 * (double)MaxCount))
+                F->addFnAttr(Attribute::InlineHint);
+            else if (Counts[0] <= (uint64_t)(0.01 * (double)MaxCount))
+                F->addFnAttr(Attribute::Cold);
+        }
+
+        // Weights are 32 bits wide, so scale the counts down to fit. Adding
+        // one keeps edges that were never taken possible.
+        for (unsigned i = 0; i < Edges.size(); ) {
+            TerminatorInst *TI = Edges[i].first;
+            unsigned N = TI->getNumSuccessors();
+            uint64_t Max = 0;
+            for (unsigned j = 0; j < N; ++j)
+                Max = std::max(Max, Counts[i + j + 1]);
+            uint64_t Scale = Max / UINT32_MAX + 1;
+            SmallVector<uint32_t, 4> Weights;
+            for (unsigned j = 0; j < N; ++j)
+                Weights.push_back(Counts[i + j + 1] / Scale + 1);
+            TI->setMetadata(LLVMContext::MD_prof,
+                            MDB.createBranchWeights(Weights));
+            i += N;
+        }
+    }
+    return true;
+}
@@ -11,6 +11,7 @@
 #include "rustllvm.h"
 #include "llvm/Object/Archive.h"
 #include "llvm/Object/ObjectFile.h"
+#include "llvm/Object/SymbolicFile.h"
 #include "llvm/IR/DiagnosticInfo.h"
 #include "llvm/IR/DiagnosticPrinter.h"
 
@@ -72,6 +73,20 @@ extern "C" LLVMValueRef LLVMRustConstInt(LLVMTypeRef IntTy,
   return LLVMConstInt(IntTy, N, SignExtend);
 }
 
+extern "C" LLVMBool LLVMRustConstInt128Get(LLVMValueRef CV,
+                                           LLVMBool SignExtend,
+                                           uint64_t *High,
+                                           uint64_t *Low) {
+  ConstantInt *C = unwrap<ConstantInt>(CV);
+  if (C->getBitWidth() > 128)
+    return false;
+  APInt AP = SignExtend ? C->getValue().sextOrSelf(128)
+                        : C->getValue().zextOrSelf(128);
+  *Low = AP.getLoBits(64).getZExtValue();
+  *High = AP.getHiBits(64).getZExtValue();
+  return true;
+}
+
 extern "C" void LLVMRustPrintPassTimings() {
   raw_fd_ostream OS (2, false); // stderr.
   TimerGroup::printAll(OS);
@@ -199,6 +214,11 @@ extern "C" LLVMValueRef LLVMInlineAsm(LLVMTypeRef Ty,
                                IsAlignStack, (InlineAsm::AsmDialect) Dialect));
 }
 
+extern "C" bool LLVMRustInlineAsmVerify(LLVMTypeRef Ty,
+                                        char *Constraints) {
+    return InlineAsm::Verify(unwrap<FunctionType>(Ty), Constraints);
+}
+
 typedef DIBuilder* DIBuilderRef;
 
 #if LLVM_VERSION_MINOR >= 6
@@ -228,6 +248,26 @@ extern "C" void LLVMRustAddModuleFlag(LLVMModuleRef M,
     unwrap(M)->addModuleFlag(Module::Warning, name, value);
 }
 
+// Tells the GCOV profiling pass where to put the notes and data files of all
+// compile units in the module: `Path` with its extension replaced by `gcno` or
+// `gcda`.
+extern "C" void LLVMRustAddGCOVMetadata(LLVMModuleRef M, const char *Path) {
+    Module *Mod = unwrap(M);
+    NamedMDNode *CUs = Mod->getNamedMetadata("llvm.dbg.cu");
+    if (!CUs)
+        return;
+    LLVMContext &Context = Mod->getContext();
+    NamedMDNode *GCov = Mod->getOrInsertNamedMetadata("llvm.gcov");
+    for (unsigned i = 0; i < CUs->getNumOperands(); i++) {
+#if LLVM_VERSION_MINOR >= 6
+        Metadata *Ops[] = { MDString::get(Context, Path), CUs->getOperand(i) };
+#else
+        Value *Ops[] = { MDString::get(Context, Path), CUs->getOperand(i) };
+#endif
+        GCov->addOperand(MDNode::get(Context, Ops));
+    }
+}
+
 extern "C" DIBuilderRef LLVMDIBuilderCreate(LLVMModuleRef M) {
     return new DIBuilder(*unwrap(M));
 }
@@ -792,6 +832,48 @@ LLVMRustDestroyArchive(Archive *ar) {
     delete ar;
 }
 
+// Writes the name of every global symbol defined by the object file in
+// `data` to `str`, each followed by a NUL byte. This is what goes into the
+// symbol table of a static archive. Returns false if the buffer isn't an
+// object file that LLVM understands, for example a compressed bytecode file
+// or the metadata of an rlib.
+extern "C" bool
+LLVMRustGetObjectSymbols(const char *data, size_t len, RustStringRef str) {
+#if LLVM_VERSION_MINOR >= 6
+    MemoryBufferRef buf(StringRef(data, len), "");
+    ErrorOr<std::unique_ptr<SymbolicFile>> obj_or =
+        SymbolicFile::createSymbolicFile(buf, sys::fs::file_magic::unknown,
+                                         nullptr);
+    if (!obj_or)
+        return false;
+    SymbolicFile *obj = obj_or.get().get();
+#else
+    std::unique_ptr<MemoryBuffer> buf(
+        MemoryBuffer::getMemBuffer(StringRef(data, len), "", false));
+    ErrorOr<SymbolicFile *> obj_or =
+        SymbolicFile::createSymbolicFile(buf, sys::fs::file_magic::unknown,
+                                         nullptr);
+    if (!obj_or)
+        return false;
+    std::unique_ptr<SymbolicFile> owned(obj_or.get());
+    SymbolicFile *obj = owned.get();
+#endif
+
+    raw_rust_string_ostream os(str);
+    for (auto sym = obj->symbol_begin(), end = obj->symbol_end();
+         sym != end; ++sym) {
+        uint32_t flags = sym->getFlags();
+        if (!(flags & SymbolRef::SF_Global))
+            continue;
+        if (flags & (SymbolRef::SF_Undefined | SymbolRef::SF_FormatSpecific))
+            continue;
+        if (sym->printName(os))
+            return false;
+        os << '\0';
+    }
+    return true;
+}
+
 extern "C" void
 LLVMRustSetDLLExportStorageClass(LLVMValueRef Value) {
     GlobalValue *V = unwrap<GlobalValue>(Value);
@@ -900,6 +982,32 @@ extern "C" void LLVMWriteDebugLocToString(
     unwrap(dl)->print(*unwrap(C), os);
 }
 
+// Splits a debug location into the file, line and column it points at, if
+// it points anywhere.
+extern "C" bool LLVMRustUnpackDebugLoc(
+    LLVMContextRef C,
+    LLVMDebugLocRef dl,
+    RustStringRef file_out,
+    unsigned *line_out,
+    unsigned *col_out)
+{
+    const DebugLoc *loc = unwrap(dl);
+#if LLVM_VERSION_MINOR >= 7
+    if (!*loc)
+        return false;
+    StringRef file = cast<DIScope>(loc->getScope())->getFilename();
+#else
+    if (loc->isUnknown())
+        return false;
+    StringRef file = DIScope(loc->getScope(*unwrap(C))).getFilename();
+#endif
+    raw_rust_string_ostream os(file_out);
+    os << file;
+    *line_out = loc->getLine();
+    *col_out = loc->getCol();
+    return true;
+}
+
 DEFINE_SIMPLE_CONVERSION_FUNCTIONS(SMDiagnostic, LLVMSMDiagnosticRef)
 
 extern "C" void LLVMSetInlineAsmDiagnosticHandler(
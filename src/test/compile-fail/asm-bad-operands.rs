@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// ignore-x86
+// ignore-arm
+// ignore-aarch64
+// ignore-mips
+// ignore-mipsel
+// ignore-powerpc
+
+#![feature(asm)]
+
+struct Pair(u32, u32);
+
+fn main() {
+    let x: u32;
+    let y: u64;
+    let z: u32;
+    let w: u32;
+    let v: u32;
+    let n = 7_u32;
+    unsafe {
+        asm!("mov $1, $0" : "=r"(x) : "z"(5_u32));
+        //~^ ERROR unknown operand constraint `z` for x86_64
+        asm!("mov $1, $0" : "={rax}"(y) : "{foo}"(5_u64));
+        //~^ ERROR unknown register `foo` for x86_64
+        asm!("movd $1, $0" : "=y"(z) : "r"(5_u32));
+        //~^ ERROR the constraint `y` takes values of 64 to 64 bits on x86_64, but `u32` has 32 bits
+        asm!("" :: "r"(Pair(1, 2)));
+        //~^ ERROR cannot pass a value of type `Pair` in a register with the constraint `r`
+        asm!("" :: "r"(&[1_u8, 2][..]));
+        //~^ ERROR cannot pass a value of type `&[u8]` in a register with the constraint `r`
+        asm!("add $1, $0" : "=r"(w) : "1"(5_u32));
+        //~^ ERROR input operand is tied to output 1, but there is only 1 output
+        asm!("add $1, $0" : "=r"(v) : "0"(5_u64));
+        //~^ ERROR input operand of 64 bits is tied to output 0 of 32 bits
+        asm!("add $1, $0" : "=i"(v) : "r"(5_u32));
+        //~^ ERROR output operand constraint `=i` only allows immediates
+        asm!("mov $1, $0" : "=r"(v) : "i"(n));
+        //~^ ERROR the operand for the constraint `i` must be a constant
+    }
+    println!("{} {} {} {} {}", x, y, z, w, v);
+}
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86",
+          target_arch = "x86_64",
+          target_arch = "arm",
+          target_arch = "aarch64"))]
+pub fn main() {
+    let x: usize;
+    let y: usize;
+    unsafe {
+        asm!("mov $src, $dst" : [dst] "=r"(x) : [src] "r"(5_usize), [src] "r"(6_usize));
+        //~^ ERROR duplicate inline assembly operand name `src`
+        asm!("mov ${source}, $dst" : [dst] "=r"(y) : [src] "r"(5_usize));
+        //~^ ERROR there is no inline assembly operand named `source`
+        asm!("mov $2, $0" : "=r"(y) : "r"(5_usize));
+        //~^ ERROR invalid reference to operand `$2`, the inline assembly has 2 operands
+    }
+    println!("{} {}", x, y);
+}
+
+#[cfg(not(any(target_arch = "x86",
+              target_arch = "x86_64",
+              target_arch = "arm",
+              target_arch = "aarch64")))]
+pub fn main() {}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z nll
+
+// Check that non-lexical lifetimes still reject uses of borrowed data
+// while the borrowed reference may be used later, including around
+// loops, through copies of the reference and when it is returned.
+
+fn main() {
+    let mut x = 1;
+    let r = &mut x;
+    x += 1; //~ ERROR cannot assign to `x` because it is borrowed
+    *r += 1;
+
+    let mut y = 1;
+    let p = &mut y;
+    loop {
+        *p += 1;
+        y += 1; //~ ERROR cannot assign to `y` because it is borrowed
+    }
+}
+
+fn copies() {
+    let mut z = 1;
+    let a = &z;
+    let b = a;
+    z = 2; //~ ERROR cannot assign to `z` because it is borrowed
+    println!("{}", *b);
+}
+
+fn returned(v: &mut Vec<u32>) -> &mut u32 {
+    let last = v.last_mut().unwrap();
+    v.push(1); //~ ERROR cannot borrow `*v` as mutable more than once at a time
+    last
+}
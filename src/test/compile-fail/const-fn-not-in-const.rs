@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that calls to plain fns are still rejected in constants even
+// when const fns are enabled.
+
+#![feature(const_fn)]
+
+const fn ok() -> u32 { 1 }
+fn not_ok() -> u32 { 2 }
+
+const A: u32 = ok();
+const B: u32 = not_ok(); //~ ERROR function calls in constants are limited to constant functions
+
+fn main() {}
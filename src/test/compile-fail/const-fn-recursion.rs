@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a const fn that calls itself without end is reported instead of
+// overflowing the compiler's stack.
+
+#![feature(const_fn)]
+
+const fn forever(x: usize) -> usize {
+    forever(x) //~ ERROR reached the recursion limit while evaluating a constant function call
+}
+
+fn main() {
+    let _: [u8; forever(1)]; //~ NOTE for array length here
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test use of associated constants without feature gate.
+
+trait Foo {
+    const BAR: u32; //~ ERROR associated constants are experimental
+}
+
+struct Baz;
+
+impl Foo for Baz {
+    const BAR: u32 = 3; //~ ERROR associated constants are experimental
+}
+
+fn main() { }
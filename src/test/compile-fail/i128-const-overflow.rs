@@ -0,0 +1,47 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(i128_type, i128)]
+
+// The overflow checks on 128-bit constants use all 128 bits.
+
+use std::fmt;
+use std::{i128, u128};
+
+const VALS_I128: (i128, i128, i128, i128, i128) =
+    (-i128::MIN,
+     //~^ ERROR attempted to negate with overflow
+     i128::MIN - 1,
+     //~^ ERROR attempted to sub with overflow
+     i128::MAX + 1,
+     //~^ ERROR attempted to add with overflow
+     i128::MIN * 2,
+     //~^ ERROR attempted to mul with overflow
+     i128::MAX / 2 + 1,
+     );
+
+const VALS_U128: (u128, u128, u128, u128) =
+    (u128::MIN - 1,
+     //~^ ERROR attempted to sub with overflow
+     u128::MAX + 1,
+     //~^ ERROR attempted to add with overflow
+     (1 << 64) * (1 << 64),
+     //~^ ERROR attempted to mul with overflow
+     (1 << 64) * (1 << 63),
+     );
+
+fn main() {
+    foo(VALS_I128);
+    foo(VALS_U128);
+}
+
+fn foo<T:fmt::Debug>(x: T) {
+    println!("{:?}", x);
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+mod outer {
+    pub mod inner {
+        pub(in outer) fn helper() {}
+        pub(in outer) use self::deep::twice;
+        pub(crate) const LIMIT: u32 = 3;
+
+        pub mod deep {
+            pub fn twice() {}
+        }
+    }
+}
+
+use outer::inner::*;
+
+fn main() {
+    let _ = LIMIT;
+    helper(); //~ ERROR unresolved name `helper`
+    twice(); //~ ERROR unresolved name `twice`
+}
@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+mod outer {
+    pub mod inner {
+        pub(in outer) fn helper() {}
+    }
+
+    mod sibling {
+        use outer::inner::helper;
+
+        pub fn f() { helper() }
+    }
+}
+
+mod other {
+    use outer::inner::helper; //~ ERROR function `helper` is private
+}
+
+fn main() {}
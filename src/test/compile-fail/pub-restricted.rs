@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+mod outer {
+    pub mod inner {
+        pub(in outer) fn helper() {}
+
+        pub struct Config {
+            pub(crate) verbose: bool,
+            pub(in outer) level: u32,
+        }
+    }
+
+    pub fn use_helper() -> u32 {
+        inner::helper();
+        let c = inner::Config { verbose: true, level: 1 };
+        c.level
+    }
+}
+
+pub enum E {
+    pub(crate) A, //~ ERROR enum variants cannot have restricted visibility
+}
+
+fn main() {
+    outer::use_helper();
+    outer::inner::helper(); //~ ERROR function `helper` is private
+
+    let c = outer::inner::Config { verbose: true, level: 0 };
+    //~^ ERROR field `level` of struct `outer::inner::Config` is private
+    let _ = c.verbose;
+    let _ = c.level; //~ ERROR field `level` of struct `outer::inner::Config` is private
+}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Unresolved names come with suggestions for imports that would bring a
+// public item of that name into scope, from this crate or from std.
+
+mod foo {
+    pub struct Bar;
+
+    pub mod baz {
+        pub fn qux() {}
+    }
+}
+
+fn main() {
+    let _: HashMap<u8, u8>;
+    //~^ ERROR use of undeclared type name `HashMap`
+    //~| HELP possible candidates: use std::collections::HashMap;
+
+    let _: Bar;
+    //~^ ERROR use of undeclared type name `Bar`
+    //~| HELP possible candidates: use foo::Bar;
+
+    qux();
+    //~^ ERROR unresolved name `qux`
+    //~| HELP possible candidates: use foo::baz::qux;
+}
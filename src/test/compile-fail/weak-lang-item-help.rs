@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+// aux-build:weak-lang-items.rs
+// error-pattern: help: `#![no_std]` programs have to define what happens on a panic with `#[lang = "panic_fmt"]
+// error-pattern: file: &'static str, line: usize) -> !`
+// error-pattern: help: `#![no_std]` programs have to define the personality routine
+// error-pattern: all their dependencies are built with `-C panic=abort`
+
+#![feature(no_std)]
+#![no_std]
+
+extern crate core;
+extern crate weak_lang_items;
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A missing `}` at the end of the file is reported once, and the code
+// before it is still checked for errors.
+
+fn f() {
+    let x = 1 + ; //~ ERROR unexpected token: `;`
+}
+
+fn g() { //~ HELP did you mean to close this delimiter?
+    let y = 2;
+
+fn main() {
+    let z = ; //~ ERROR unexpected token: `;`
+} //~ ERROR this file contains an un-closed delimiter
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Independent syntax errors in statements and items are all reported
+// rather than only the first one.
+
+fn f() {
+    let x = 1 + ; //~ ERROR unexpected token: `;`
+    let y = x x; //~ ERROR expected one of
+}
+
+fn g() -> u8 {
+    let a = 1
+    let b = 2; //~ ERROR expected one of
+    a + b
+}
+
+struct S { a: u8 } where //~ ERROR expected item, found `where`
+
+fn main() {
+    f();
+    let z = ; //~ ERROR unexpected token: `;`
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type = "lib"]
+
+pub fn foo(n: i32, s: String) -> i32 {
+    let v = vec![n];
+    if n > 0 {
+        return v[0];
+    }
+    let w = vec![n];
+    match Some(s) {
+        Some(t) => t.len() as i32 + w.len() as i32,
+        None => v.len() as i32,
+    }
+}
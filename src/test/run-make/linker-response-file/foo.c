@@ -0,0 +1,2 @@
+// ignore-license
+int foo() { return 3; }
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#[inline]
+fn helper(x: u32) -> u32 {
+    x.wrapping_mul(3).wrapping_add(1)
+}
+
+#[inline(never)]
+pub fn hot(xs: &[u32]) -> u32 {
+    xs.iter().fold(0, |acc, &x| acc ^ helper(x))
+}
+
+#[inline(never)]
+pub fn cold(x: u32) -> u32 {
+    helper(x)
+}
+
+fn main() {
+    let xs = [1, 2, 3];
+    println!("{} {}", hot(&xs), cold(4));
+}
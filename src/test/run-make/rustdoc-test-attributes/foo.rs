@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![crate_type = "lib"]
+
+/// Moving out of a vector leaves nothing behind to use.
+///
+/// ```compile_fail,E0382
+/// let v = vec![1, 2, 3];
+/// let w = v;
+/// println!("{:?} {:?}", v, w);
+/// ```
+///
+/// Tests can be built with extra flags.
+///
+/// ```rustc-flag=--cfg=flagged
+/// assert!(cfg!(flagged));
+/// ```
+///
+/// ```ignore-x86,ignore-arm,ignore-aarch64,ignore-mips,ignore-powerpc
+/// panic!("not ignored");
+/// ```
+pub fn foo() {}
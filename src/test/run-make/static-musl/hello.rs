@@ -0,0 +1,19 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::thread;
+
+fn main() {
+    assert!(cfg!(target_env = "musl"));
+    // Threads and unwinding work without libgcc_s and libpthread.so
+    let res = thread::spawn(|| -> () { panic!("expected") }).join();
+    assert!(res.is_err());
+    println!("hello from musl");
+}
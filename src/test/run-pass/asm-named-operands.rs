@@ -0,0 +1,46 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn add(a: u32, b: u32) -> u32 {
+    let sum: u32;
+    unsafe {
+        asm!("add ${rhs:k}, $sum" : [sum] "=r"(sum) : "0"(a), [rhs] "r"(b));
+    }
+    sum
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn sub(a: u32, b: u32) -> u32 {
+    let diff: u32;
+    unsafe {
+        asm!("sub $diff, $rhs" : [diff] "=r"(diff) : "0"(a), [rhs] "r"(b) : : "intel");
+    }
+    diff
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    assert_eq!(add(3, 14159), 14162);
+    assert_eq!(sub(14162, 3), 14159);
+
+    // `$$` is still a literal dollar sign
+    let mut x = 5_u32;
+    unsafe {
+        asm!("shl $$2, $x" : [x] "+r"(x) :: "cc");
+    }
+    assert_eq!(x, 20);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Thin references and boxes can be passed in registers, as the pointers
+// they are.
+
+#![feature(asm)]
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn load(p: &u32) -> u32 {
+    let v: u32;
+    unsafe {
+        asm!("mov ($1), $0" : "=r"(v) : "r"(p));
+    }
+    v
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn store(p: &mut u32, v: u32) {
+    unsafe {
+        asm!("mov $1, ($0)" : : "r"(p), "r"(v) : "memory");
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn load_box(b: Box<u32>) -> u32 {
+    let v: u32;
+    unsafe {
+        asm!("mov ($1), $0" : "=r"(v) : "r"(b));
+    }
+    v
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn main() {
+    let mut x = 5_u32;
+    // What test::black_box does to keep a value alive.
+    unsafe {
+        asm!("" : : "r"(&x));
+    }
+    assert_eq!(load(&x), 5);
+    store(&mut x, 7);
+    assert_eq!(x, 7);
+    assert_eq!(load_box(Box::new(9)), 9);
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+pub fn main() {}
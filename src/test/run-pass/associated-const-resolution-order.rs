@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Test that a trait constant is resolved to the impl's value in generic code,
+// and falls back to the trait's default when the impl omits it.
+
+#![feature(associated_consts)]
+
+trait Foo {
+    const ID: i32 = 2;
+}
+
+struct A;
+struct B;
+
+impl Foo for A {
+    const ID: i32 = 3;
+}
+
+impl Foo for B {}
+
+fn id<T: Foo>() -> i32 {
+    T::ID
+}
+
+fn main() {
+    assert_eq!(3, id::<A>());
+    assert_eq!(2, id::<B>());
+}
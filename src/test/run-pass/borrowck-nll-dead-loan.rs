@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z nll
+
+// Check that with non-lexical lifetimes a loan ends at the last use
+// of the reference it created, rather than at the end of its scope.
+
+fn cond() -> bool { true }
+
+fn main() {
+    let mut x = 1;
+    let r = &mut x;
+    *r += 1;
+    x += 1;
+    assert_eq!(x, 3);
+
+    let mut v = vec![1, 2, 3];
+    let w = &mut v;
+    w.push(4);
+    v.push(5);
+    assert_eq!(v, [1, 2, 3, 4, 5]);
+
+    let mut y = 0;
+    let p = &mut y;
+    if cond() {
+        *p = 1;
+    } else {
+        y = 2;
+    }
+    assert_eq!(y, 1);
+}
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z nll
+
+// Check that with non-lexical lifetimes a borrow that is returned on
+// some paths doesn't restrict the borrowed data on the others.
+
+fn last_or_push(v: &mut Vec<u32>) -> &mut u32 {
+    if let Some(last) = v.last_mut() {
+        if *last > 0 {
+            return last;
+        }
+    }
+    v.push(1);
+    v.last_mut().unwrap()
+}
+
+fn main() {
+    let mut v = vec![];
+    *last_or_push(&mut v) += 1;
+    assert_eq!(v, [2]);
+    *last_or_push(&mut v) += 1;
+    assert_eq!(v, [3]);
+
+    let mut w = vec![0];
+    *last_or_push(&mut w) += 1;
+    assert_eq!(w, [0, 2]);
+}
@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z nll
+
+// Check that with non-lexical lifetimes a map can be inserted into when
+// looking up the key found nothing, whether or not the entry that was
+// found is returned.
+
+use std::collections::HashMap;
+
+fn get_or_insert(map: &mut HashMap<u32, String>, key: u32) -> &mut String {
+    match map.get_mut(&key) {
+        Some(value) => return value,
+        None => {}
+    }
+    map.insert(key, String::new());
+    map.get_mut(&key).unwrap()
+}
+
+fn count(map: &mut HashMap<u32, u32>, key: u32) {
+    match map.get_mut(&key) {
+        Some(n) => *n += 1,
+        None => {
+            map.insert(key, 1);
+        }
+    }
+}
+
+fn main() {
+    let mut names = HashMap::new();
+    get_or_insert(&mut names, 1).push_str("one");
+    get_or_insert(&mut names, 1).push_str("!");
+    assert_eq!(names[&1], "one!");
+
+    let mut counts = HashMap::new();
+    count(&mut counts, 7);
+    count(&mut counts, 7);
+    count(&mut counts, 8);
+    assert_eq!(counts[&7], 2);
+    assert_eq!(counts[&8], 1);
+}
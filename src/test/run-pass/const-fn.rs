@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A very basic test of const fn functionality.
+
+#![feature(const_fn)]
+
+struct Wrapper(u32);
+
+impl Wrapper {
+    const fn new(x: u32) -> Wrapper { Wrapper(x) }
+}
+
+const fn add(x: u32, y: u32) -> u32 {
+    x + y
+}
+
+const fn sub(x: u32, y: u32) -> u32 {
+    x - y
+}
+
+const unsafe fn div(x: u32, y: u32) -> u32 {
+    x / y
+}
+
+const fn twice(x: u32) -> u32 {
+    add(x, x)
+}
+
+const SUM: u32 = add(44, 22);
+const DIFF: u32 = sub(44, 22);
+const DIV: u32 = unsafe { div(44, 22) };
+const TWICE: u32 = twice(SUM);
+
+static WRAPPED: Wrapper = Wrapper::new(7);
+
+fn main() {
+    assert_eq!(SUM, 66);
+    assert!(SUM != 88);
+
+    assert_eq!(DIFF, 22);
+    assert_eq!(DIV, 2);
+    assert_eq!(TWICE, 132);
+    assert_eq!(WRAPPED.0, 7);
+
+    let _: [&'static str; sub(100, 99) as usize] = ["hi"];
+
+    // const fns can still be called at runtime.
+    let x = 5;
+    assert_eq!(add(x, 1), 6);
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(i128_type)]
+
+// The constant evaluator works on all 128 bits, so constants above
+// 2^64 can be used in patterns.
+
+const X: u128 = 1 << 100;
+const MAX: u128 = !0;
+const NEG: i128 = -(1 << 120) / 3;
+
+fn b<T>(t: T) -> T { t }
+
+fn classify(x: u128) -> u32 {
+    match x {
+        0...X => 0,
+        MAX => 2,
+        _ => 1,
+    }
+}
+
+fn main() {
+    assert_eq!(classify(b(X)), 0);
+    assert_eq!(classify(b(X + 1)), 1);
+    assert_eq!(classify(b(0u128.wrapping_sub(1))), 2);
+
+    match b(NEG) {
+        NEG => {}
+        _ => panic!(),
+    }
+
+    assert_eq!(X >> 100, 1);
+    assert_eq!(NEG * 3 + (1 << 120), 1);
+}
@@ -0,0 +1,69 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(i128_type, i128, core)]
+
+use std::i128;
+use std::num::NumCast;
+
+fn b<T>(t: T) -> T { t }
+
+// Literals are limited to 64 bits, so wider values are built from halves.
+fn parts(hi: u64, lo: u64) -> i128 {
+    ((hi as i128) << 64) | lo as i128
+}
+
+fn main() {
+    let x: i128 = b(-1);
+    assert_eq!(0, !x);
+    let y: i128 = b(-2);
+    assert_eq!(!1, y);
+    let z: i128 = b(0xABCD_EF);
+    assert_eq!(z * z, 0x734C_C2F2_A521);
+    assert_eq!(z * z * z * z, parts(0x33EE_0E2A, 0x54E2_59DA_A0E7_8E41));
+    assert_eq!(-z * -z * -z * -z, parts(0x33EE_0E2A, 0x54E2_59DA_A0E7_8E41));
+    assert_eq!(-z + -z + -z + -z, -0x2AF3_7BC);
+
+    let k = b(-parts(0x1234_5678_9ABC_DEFF, 0xEDCB_A987_6543_2100));
+    assert_eq!(k + k, -parts(0x2468_ACF1_3579_BDFF, 0xDB97_530E_CA86_4200));
+    assert_eq!(0, k - k);
+    assert_eq!(k / 42, -parts(0x6E_F5DE_4CD3_BC2A, 0xAA3B_B4CC_5DD6_EE80));
+    assert_eq!(k % 1000, -640);
+    assert_eq!(-k, k / -1);
+    assert_eq!(k >> 65, -0x91A_2B3C_4D5E_6F80);
+    assert_eq!(k << 65, parts(0x2468_ACF1_3579_BE00, 0));
+    assert!(k < z);
+    assert!(y > k);
+    assert!(y < x);
+
+    assert_eq!(k as i64, 0x1234_5678_9ABC_DF00);
+    assert_eq!(z as f64, 11259375.0);
+    assert_eq!(k as f64, -2.4197857203266735e37);
+
+    assert_eq!(format!("{}", k), "-24197857203266735157317685914930192640");
+    assert_eq!(format!("{}", i128::MIN), "-170141183460469231731687303715884105728");
+    assert_eq!("-24197857203266735157317685914930192640".parse::<i128>(), Ok(k));
+    assert!("170141183460469231731687303715884105728".parse::<i128>().is_err());
+
+    assert_eq!(i128::MAX.checked_add(1), None);
+    assert_eq!(i128::MIN.checked_sub(1), None);
+    assert_eq!(i128::MIN.checked_div(-1), None);
+    assert_eq!(k.checked_mul(2), Some(k + k));
+    assert_eq!(i128::MAX.wrapping_add(1), i128::MIN);
+    assert_eq!(i128::MIN.overflowing_mul(-1), (i128::MIN, true));
+    assert_eq!(i128::MIN.abs(), i128::MIN);
+
+    // Casts of values outside of 64 bits don't go through 64 bits.
+    assert_eq!(<i128 as NumCast>::from(i128::MIN), Some(i128::MIN));
+    assert_eq!(<i128 as NumCast>::from(i128::MAX), Some(i128::MAX));
+    assert_eq!(<u128 as NumCast>::from(i128::MAX), Some((i128::MAX) as u128));
+    assert_eq!(<u128 as NumCast>::from(i128::MIN), None);
+    assert_eq!(<i64 as NumCast>::from(i128::MIN), None);
+}
@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that `-C lto=parallel` keeps a single copy of each static and of each
+// private function when they are used from several partitions.
+
+// compile-flags: -C lto=parallel -C codegen-units=4 -O
+// no-prefer-dynamic
+
+static mut COUNTER: usize = 0;
+static TABLE: [usize; 4] = [1, 2, 3, 4];
+
+fn bump(by: usize) -> usize {
+    unsafe {
+        COUNTER += by;
+        COUNTER
+    }
+}
+
+mod a {
+    pub fn run() -> usize { super::bump(super::TABLE[0]) }
+}
+
+mod b {
+    pub fn run() -> usize { super::bump(super::TABLE[1]) }
+}
+
+mod c {
+    pub fn run() -> usize { super::bump(super::TABLE[2]) }
+}
+
+fn main() {
+    assert_eq!(a::run(), 1);
+    assert_eq!(b::run(), 3);
+    assert_eq!(c::run(), 6);
+    assert_eq!(bump(TABLE[3]), 10);
+}
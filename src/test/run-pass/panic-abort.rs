@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags:-C panic=abort
+// no-prefer-dynamic
+
+use std::env;
+use std::process::{self, Command};
+
+// Destructors are never run on panic with the abort strategy.
+struct Bomb;
+
+impl Drop for Bomb {
+    fn drop(&mut self) {
+        process::exit(0);
+    }
+}
+
+fn main() {
+    let mut args = env::args();
+    let me = args.next().unwrap();
+    if args.next().is_some() {
+        let _bomb = Bomb;
+        panic!("try to catch me");
+    }
+
+    let status = Command::new(&me).arg("child").status().unwrap();
+    assert!(!status.success());
+}
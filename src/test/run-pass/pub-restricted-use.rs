@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+mod outer {
+    pub mod inner {
+        pub(in outer) fn helper() -> u32 { 1 }
+        pub(crate) const LIMIT: u32 = 3;
+        pub(crate) use self::deep::twice;
+
+        pub mod deep {
+            pub fn twice(x: u32) -> u32 { x * 2 }
+        }
+    }
+
+    mod single {
+        use outer::inner::helper;
+
+        pub fn one() -> u32 { helper() }
+    }
+
+    mod glob {
+        use super::inner::*;
+
+        pub fn total() -> u32 { helper() + LIMIT + twice(1) }
+    }
+
+    pub fn total() -> u32 { single::one() + glob::total() }
+}
+
+use outer::inner::*;
+
+fn main() {
+    assert_eq!(outer::total(), 7);
+    assert_eq!(LIMIT, 3);
+    assert_eq!(twice(2), 4);
+}
@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+
+mod outer {
+    pub mod inner {
+        pub(crate) struct Counter {
+            pub(crate) count: u32,
+            pub(in outer) step: u32,
+        }
+
+        impl Counter {
+            pub(crate) fn new() -> Counter {
+                Counter { count: 0, step: 2 }
+            }
+
+            pub(in outer) fn bump(&mut self) {
+                self.count += self.step;
+            }
+        }
+    }
+
+    pub fn bump_twice(c: &mut inner::Counter) {
+        c.bump();
+        c.bump();
+    }
+}
+
+fn main() {
+    let mut c = outer::inner::Counter::new();
+    outer::bump_twice(&mut c);
+    assert_eq!(c.count, 4);
+}
@@ -0,0 +1,61 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(i128_type, i128, core)]
+
+use std::num::NumCast;
+use std::u128;
+
+fn b<T>(t: T) -> T { t }
+
+// Literals are limited to 64 bits, so wider values are built from halves.
+fn parts(hi: u64, lo: u64) -> u128 {
+    ((hi as u128) << 64) | lo as u128
+}
+
+fn main() {
+    let x: u128 = b(0xFFFF_FFFF_FFFF_FFFF);
+    assert_eq!(x + 1, parts(1, 0));
+    assert_eq!(x * x, parts(0xFFFF_FFFF_FFFF_FFFE, 1));
+
+    let u = b(parts(0xFEDC_BA98_7654_3210, 0x0123_4567_89AB_CDEF));
+    assert_eq!(u / 1000, parts(0x41_3EA6_8D6E_2A08, 0xB852_3615_DD10_CFD2));
+    assert_eq!(u % 1000, 415);
+    assert_eq!(u >> 3, parts(0x1FDB_9753_0ECA_8642, 0x24_68AC_F135_79BD));
+    assert_eq!(u << 7, parts(0x6E5D_4C3B_2A19_0800, 0x91A2_B3C4_D5E6_F780));
+    assert_eq!(u.wrapping_mul(3), parts(0xFC96_2FC9_62FC_9630, 0x369_D036_9D03_69CD));
+    assert_eq!(u.rotate_left(64), parts(0x0123_4567_89AB_CDEF, 0xFEDC_BA98_7654_3210));
+    assert_eq!(u.swap_bytes(), parts(0xEFCD_AB89_6745_2301, 0x1032_5476_98BA_DCFE));
+    assert_eq!(u.count_ones(), 64);
+    assert_eq!(u.leading_zeros(), 0);
+    assert_eq!((u >> 100).leading_zeros(), 100);
+    assert_eq!(u as u64, 0x0123_4567_89AB_CDEF);
+    assert_eq!(u as f64, 3.387700008457343e38);
+
+    assert_eq!(format!("{}", u), "338770000845734292516042252062085074415");
+    assert_eq!(format!("{:x}", u), "fedcba98765432100123456789abcdef");
+    assert_eq!(format!("{}", u128::MAX), "340282366920938463463374607431768211455");
+    assert_eq!("338770000845734292516042252062085074415".parse::<u128>(), Ok(u));
+    assert_eq!(u128::from_str_radix("fedcba98765432100123456789abcdef", 16), Ok(u));
+    assert!("340282366920938463463374607431768211456".parse::<u128>().is_err());
+
+    assert_eq!(u128::MAX.checked_add(1), None);
+    assert_eq!(0u128.checked_sub(1), None);
+    assert_eq!(u.checked_mul(2), None);
+    assert_eq!(u128::MAX.wrapping_add(1), 0);
+    assert_eq!(0u128.wrapping_sub(1), u128::MAX);
+
+    // Casts of values above `u64::MAX` don't go through 64 bits.
+    assert_eq!(<u128 as NumCast>::from(u128::MAX), Some(u128::MAX));
+    assert_eq!(<u128 as NumCast>::from(parts(1, 0)), Some(parts(1, 0)));
+    assert_eq!(<i128 as NumCast>::from(parts(1, 0)), Some(1i128 << 64));
+    assert_eq!(<i128 as NumCast>::from(u128::MAX), None);
+    assert_eq!(<u64 as NumCast>::from(parts(1, 0)), None);
+}